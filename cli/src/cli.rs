@@ -4,6 +4,8 @@
 
 use clap::{Parser, Subcommand};
 
+use crate::commands::release_commit::FluxTarget;
+
 #[derive(Parser)]
 #[command(
     name = "forge",
@@ -143,6 +145,11 @@ pub enum Commands {
         /// Attic cache name (for build step)
         #[arg(long, env = "ATTIC_CACHE_NAME", default_value = "cache")]
         cache_name: String,
+
+        /// Submit the rendered manifests with dryRun=All before the GitOps
+        /// commit, failing on admission, immutable-field or quota rejections
+        #[arg(long, env = "FORGE_SERVER_DRY_RUN")]
+        server_dry_run: bool,
     },
 
     /// Monitor Kubernetes rollout status
@@ -155,7 +162,8 @@ pub enum Commands {
         #[arg(short, long, required = true)]
         name: String,
 
-        /// Refresh interval in seconds
+        /// Progress re-render interval in seconds (pod and workload
+        /// changes are picked up immediately via watches)
         #[arg(long, default_value = "3")]
         interval: u64,
 
@@ -168,6 +176,47 @@ pub enum Commands {
         rollback: bool,
     },
 
+    /// Full-screen release dashboard for one product environment
+    /// (rollout progress, pods, Flux, migrations, failing-pod logs)
+    Watch {
+        /// Product name (e.g., "myapp"). Auto-discovered from deploy.yaml if omitted.
+        #[arg(long)]
+        product: Option<String>,
+
+        /// Git repository root path
+        #[arg(long, required = true)]
+        repo_root: String,
+
+        /// Environment to watch
+        #[arg(long, default_value = "staging")]
+        env: String,
+
+        /// Refresh interval in seconds
+        #[arg(long, default_value = "3")]
+        interval: u64,
+    },
+
+    /// Compare a product environment's rendered kustomizations with the
+    /// live cluster objects and report field-level drift (exits non-zero
+    /// on drift)
+    Drift {
+        /// Product name (e.g., "myapp"). Auto-discovered from deploy.yaml if omitted.
+        #[arg(long)]
+        product: Option<String>,
+
+        /// Git repository root path
+        #[arg(long, required = true)]
+        repo_root: String,
+
+        /// Environment to check
+        #[arg(long, required = true)]
+        env: String,
+
+        /// Output format (text, json)
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+
     /// Comprehensive release workflow with full testing
     ComprehensiveRelease {
         /// Service name (auth, cart, order, etc.)
@@ -491,6 +540,12 @@ pub enum Commands {
         /// without deploying to any environment.
         #[arg(long)]
         build_only: bool,
+
+        /// Submit each service's rendered manifests with dryRun=All before
+        /// its GitOps commit, failing on admission, immutable-field or
+        /// quota rejections
+        #[arg(long, env = "FORGE_SERVER_DRY_RUN")]
+        server_dry_run: bool,
     },
 
     /// Run Rust unit tests
@@ -549,6 +604,12 @@ pub enum Commands {
         namespace: String,
     },
 
+    /// Suspend or resume GitOps for a product environment
+    Flux {
+        #[command(subcommand)]
+        command: FluxCommands,
+    },
+
     /// Run database migrations for a service
     RunMigrations {
         /// Service name
@@ -579,6 +640,61 @@ pub enum Commands {
         product: String,
     },
 
+    /// Classify changes to a service's extracted subgraph schema against the
+    /// deployed one and fail on unacknowledged breaking changes
+    SubgraphCheck {
+        /// Service name
+        #[arg(long, required = true)]
+        service: String,
+
+        /// Read the deployed subgraph schema from this git ref instead of
+        /// the router's supergraph file
+        #[arg(long, conflicts_with = "configmap")]
+        base_ref: Option<String>,
+
+        /// Read the deployed schema from this router ConfigMap instead of
+        /// the router's supergraph file
+        #[arg(long, requires = "namespace")]
+        configmap: Option<String>,
+
+        /// Namespace of the router ConfigMap
+        #[arg(long)]
+        namespace: Option<String>,
+
+        /// Report breaking changes without failing
+        #[arg(long)]
+        allow_breaking: bool,
+    },
+
+    /// Validate client operations (persisted queries and frontend
+    /// documents) against a supergraph and report which would break
+    OperationCheck {
+        /// Service name (locates the product and its federation config)
+        #[arg(long, required = true)]
+        service: String,
+
+        /// Supergraph to validate against (default: the federation
+        /// directory's supergraph.graphql)
+        #[arg(long)]
+        supergraph: Option<String>,
+
+        /// Persisted-query manifest (overrides federation.operations.manifest)
+        #[arg(long)]
+        manifest: Option<String>,
+
+        /// Directory to collect GraphQL documents from (overrides dirs.web)
+        #[arg(long)]
+        documents: Option<String>,
+
+        /// Write the persisted-query manifest of the checked operations here
+        #[arg(long)]
+        output: Option<String>,
+
+        /// Report broken operations without failing
+        #[arg(long)]
+        allow_broken: bool,
+    },
+
     /// Verify web build (NO hardcoded URLs) and prepare runtime env.js
     WebBuildVerify {
         /// Path to dist directory
@@ -666,6 +782,15 @@ pub enum Commands {
         /// GHCR token (defaults to GHCR_TOKEN env var)
         #[arg(long)]
         token: Option<String>,
+
+        /// Wait until Flux has applied the pushed commit (or a later one on top of it)
+        #[arg(long)]
+        wait_for_flux: bool,
+
+        /// Flux Kustomization to wait on, as NAMESPACE/NAME[@CONTEXT]; repeat
+        /// once per cluster (default: flux-system/flux-system on the current context)
+        #[arg(long = "flux-target", requires = "wait_for_flux")]
+        flux_targets: Vec<FluxTarget>,
     },
 
     /// Regenerate Cargo.lock and Cargo.nix for workspace
@@ -716,18 +841,22 @@ pub enum Commands {
         /// Specific test suite to run (by name). If not specified, runs all suites
         #[arg(long)]
         suite: Option<String>,
+
+        /// Write per-test results as JUnit XML to this path
+        #[arg(long)]
+        junit: Option<String>,
     },
 
     /// Show deployed version/image/tag for a service
     /// Queries Kubernetes for current deployment state
     Status {
-        /// Service name
-        #[arg(long, required = true)]
-        service: String,
+        /// Service name (required unless --all)
+        #[arg(long, required_unless_present = "all")]
+        service: Option<String>,
 
-        /// Service directory path (for loading deploy.yaml)
-        #[arg(long, required = true)]
-        service_dir: String,
+        /// Service directory path (for loading deploy.yaml; required unless --all)
+        #[arg(long, required_unless_present = "all")]
+        service_dir: Option<String>,
 
         /// Git repository root path
         #[arg(long, required = true)]
@@ -736,6 +865,11 @@ pub enum Commands {
         /// Output format (text, json)
         #[arg(long, default_value = "text")]
         format: String,
+
+        /// Show every service of every product across all environments
+        /// as a service × environment matrix
+        #[arg(long, conflicts_with_all = ["service", "service_dir"])]
+        all: bool,
     },
 
     /// Run tests (unit and/or integration) for a service
@@ -760,6 +894,21 @@ pub enum Commands {
         /// Test type to run: unit, integration, or all (default: all)
         #[arg(default_value = "all")]
         test_type: String,
+
+        /// Write per-test results as JUnit XML to this path
+        #[arg(long)]
+        junit: Option<String>,
+
+        /// Run only this worker's share of the tests, as i/n (e.g. 2/4)
+        #[arg(long)]
+        shard: Option<String>,
+
+        /// Test duration history used to balance shards: a JSON file
+        /// (updated after the run when given here) or a directory of
+        /// previous JUnit reports
+        /// (default: deploy/reports/test-durations.json in the product)
+        #[arg(long)]
+        durations: Option<String>,
     },
 
     /// Start local development environment for a Rust service
@@ -913,9 +1062,19 @@ pub enum Commands {
         #[arg(long)]
         skip_frontend: bool,
 
-        /// Skip migration gates (G6-G7)
+        /// Skip migration gates (G6-G8f)
         #[arg(long)]
         skip_migrations: bool,
+
+        /// Write per-test results as JUnit XML to this path
+        #[arg(long)]
+        junit: Option<String>,
+    },
+
+    /// Database migration tooling (lint)
+    Migrations {
+        #[command(subcommand)]
+        command: MigrationsCommands,
     },
 
     /// Scaffold a new SeaORM migration with manifest entry
@@ -975,6 +1134,12 @@ pub enum Commands {
         skip_entities: bool,
     },
 
+    /// Search index tooling (reindex)
+    Search {
+        #[command(subcommand)]
+        command: SearchCommands,
+    },
+
     /// Validate ReBAC configuration
     /// Checks that Redis Tuple Store relations match SeaORM entity definitions
     RebacValidate {
@@ -1097,6 +1262,21 @@ pub enum Commands {
         /// Path for JSON test report (default: test-report.json in web directory)
         #[arg(long)]
         report_path: Option<String>,
+
+        /// Write per-test results as JUnit XML to this path
+        #[arg(long)]
+        junit: Option<String>,
+
+        /// Run only this worker's share of the tests, as i/n (e.g. 2/4)
+        #[arg(long)]
+        shard: Option<String>,
+
+        /// Test duration history used to balance shards: a JSON file
+        /// (updated after the run when given here) or a directory of
+        /// previous JUnit reports
+        /// (default: deploy/reports/test-durations.json)
+        #[arg(long)]
+        durations: Option<String>,
     },
 
     /// Run unit tests only (backend + frontend)
@@ -1121,6 +1301,10 @@ pub enum Commands {
         /// Path for JSON test report (default: test-report.json in web directory)
         #[arg(long)]
         report_path: Option<String>,
+
+        /// Write per-test results as JUnit XML to this path
+        #[arg(long)]
+        junit: Option<String>,
     },
 
     /// Run integration tests only
@@ -1133,6 +1317,10 @@ pub enum Commands {
         /// Filter pattern for test names
         #[arg(long)]
         filter: Option<String>,
+
+        /// Write per-test results as JUnit XML to this path
+        #[arg(long)]
+        junit: Option<String>,
     },
 
     /// Clean up orphaned testcontainers and E2E images
@@ -1161,6 +1349,10 @@ pub enum Commands {
         /// Force rebuild images even if they exist
         #[arg(long)]
         force_rebuild: bool,
+
+        /// Write per-test results as JUnit XML to this path
+        #[arg(long)]
+        junit: Option<String>,
     },
 
     /// Release kenshi operator: push image and update K8s manifests for all clusters
@@ -1189,6 +1381,15 @@ pub enum Commands {
         /// GHCR token (defaults to GHCR_TOKEN env var)
         #[arg(long)]
         token: Option<String>,
+
+        /// Wait until Flux has applied the pushed commit (or a later one on top of it)
+        #[arg(long)]
+        wait_for_flux: bool,
+
+        /// Flux Kustomization to wait on, as NAMESPACE/NAME[@CONTEXT]; repeat
+        /// once per cluster (default: flux-system/flux-system on the current context)
+        #[arg(long = "flux-target", requires = "wait_for_flux")]
+        flux_targets: Vec<FluxTarget>,
     },
 
     /// Release kenshi-agent: push image and update K8s manifests for all clusters
@@ -1225,6 +1426,15 @@ pub enum Commands {
         /// GHCR token (defaults to GHCR_TOKEN env var)
         #[arg(long)]
         token: Option<String>,
+
+        /// Wait until Flux has applied the pushed commit (or a later one on top of it)
+        #[arg(long)]
+        wait_for_flux: bool,
+
+        /// Flux Kustomization to wait on, as NAMESPACE/NAME[@CONTEXT]; repeat
+        /// once per cluster (default: flux-system/flux-system on the current context)
+        #[arg(long = "flux-target", requires = "wait_for_flux")]
+        flux_targets: Vec<FluxTarget>,
     },
 
     /// Seed test profiles into an environment
@@ -1296,6 +1506,10 @@ pub enum Commands {
         /// Number of test threads
         #[arg(long, default_value = "4")]
         threads: u32,
+
+        /// Write per-test results as JUnit XML to this path
+        #[arg(long)]
+        junit: Option<String>,
     },
 
     /// Run tests with coverage (cargo-tarpaulin)
@@ -1634,6 +1848,186 @@ pub enum GemCommands {
     },
 }
 
+/// Database migration subcommands
+#[derive(Subcommand)]
+pub enum MigrationsCommands {
+    /// Lint SQLx and SeaORM migrations for lock-heavy and destructive DDL
+    /// (the G8c pre-release gate), using the prerelease config in deploy.yaml
+    Lint {
+        /// Product root directory (repo root for standalone products, pkgs/products/{name} in monorepo)
+        #[arg(long, default_value = ".")]
+        working_dir: String,
+    },
+
+    /// Rehearse new migrations against throwaway Postgres, Databend and
+    /// Elasticsearch containers: build the previous release's schema, apply
+    /// the migrations added since, and report per-migration timings,
+    /// rollback-compatibility warnings and mapping changes needing a reindex
+    Rehearse {
+        /// Product root directory (repo root for standalone products, pkgs/products/{name} in monorepo)
+        #[arg(long, default_value = ".")]
+        working_dir: String,
+
+        /// Git ref of the previous release (defaults to the latest vX.Y.Z tag)
+        #[arg(long)]
+        from_ref: Option<String>,
+
+        /// Schema-only dump (pg_dump --schema-only) to restore as the baseline
+        /// instead of replaying the previous release's migrations
+        #[arg(long)]
+        schema_dump: Option<String>,
+
+        /// Postgres image for the rehearsal container
+        #[arg(long, default_value = "postgres:16-alpine")]
+        image: String,
+
+        /// Databend image, used when Databend migrations were added
+        #[arg(long, default_value = "datafuselabs/databend")]
+        databend_image: String,
+
+        /// Elasticsearch image, used when Elasticsearch migrations were added
+        #[arg(
+            long,
+            default_value = "docker.elastic.co/elasticsearch/elasticsearch:8.15.3"
+        )]
+        elasticsearch_image: String,
+
+        /// Keep the container running after the rehearsal for inspection
+        #[arg(long)]
+        keep: bool,
+    },
+
+    /// Show every Shinka DatabaseMigration of a product environment: expected
+    /// vs applied tag, phase, last error, job durations, and which
+    /// migration-manifest.yaml migrations are applied, pending or failed
+    Status {
+        /// Product name (e.g., "myapp"). Auto-discovered from deploy.yaml if omitted.
+        #[arg(long)]
+        product: Option<String>,
+
+        /// Git repository root path
+        #[arg(long, default_value = ".")]
+        repo_root: String,
+
+        /// Environment to inspect
+        #[arg(long, required = true)]
+        env: String,
+    },
+
+    /// Run a data_only migration's backfill in batches against the primary
+    /// database, resuming from the cursor recorded in forge_backfill_progress
+    Backfill {
+        /// Product name (e.g., "myapp"). Auto-discovered from deploy.yaml if omitted.
+        #[arg(long)]
+        product: Option<String>,
+
+        /// Git repository root path
+        #[arg(long, default_value = ".")]
+        repo_root: String,
+
+        /// Environment to backfill
+        #[arg(long, required = true)]
+        env: String,
+
+        /// Data migration name as declared in migration-manifest.yaml
+        #[arg(long, required = true)]
+        migration: String,
+
+        /// Rows per batch (default: the manifest's batch_size, else 1000)
+        #[arg(long)]
+        batch_size: Option<u64>,
+
+        /// Pause between batches, e.g. "2s" (default: the manifest's sleep, else none)
+        #[arg(long)]
+        sleep: Option<String>,
+
+        /// Stop after this long (e.g. "30m"); a later run resumes where it stopped
+        #[arg(long)]
+        max_duration: Option<String>,
+
+        /// Discard recorded progress and start from the first row
+        #[arg(long)]
+        restart: bool,
+    },
+}
+
+/// Search index subcommands
+#[derive(Subcommand)]
+pub enum SearchCommands {
+    /// Reindex an alias into a new versioned index built from its index
+    /// template, verify document counts, swap the alias atomically and keep
+    /// the old index for a rollback window
+    Reindex {
+        /// Alias the search service reads and writes through
+        #[arg(long, required = true)]
+        alias: String,
+
+        /// Elasticsearch URL
+        #[arg(
+            long,
+            env = "ELASTICSEARCH_URL",
+            default_value = "http://localhost:9200"
+        )]
+        url: String,
+
+        /// Index template to build the new index from (default: the alias name)
+        #[arg(long)]
+        template: Option<String>,
+
+        /// How long to keep a retired index for rollback, e.g. "168h"
+        #[arg(long, default_value = "168h")]
+        retain: String,
+
+        /// Give up (and cancel the reindex task) after this long, e.g. "1h"
+        #[arg(long, default_value = "1h")]
+        timeout: String,
+    },
+}
+
+/// Flux GitOps subcommands
+#[derive(Subcommand)]
+pub enum FluxCommands {
+    /// Suspend every Flux Kustomization and HelmRelease of a product
+    /// environment, recording who suspended it and why. Release commands
+    /// refuse a suspended product.
+    Suspend {
+        /// Product name (e.g., "myapp"). Auto-discovered from deploy.yaml if omitted.
+        #[arg(long)]
+        product: Option<String>,
+
+        /// Git repository root path
+        #[arg(long, default_value = ".")]
+        repo_root: String,
+
+        /// Environment to freeze
+        #[arg(long, required = true)]
+        env: String,
+
+        /// Why GitOps is frozen (e.g., the incident reference)
+        #[arg(long, required = true)]
+        reason: String,
+
+        /// Who is suspending (defaults to $USER)
+        #[arg(long)]
+        by: Option<String>,
+    },
+
+    /// Resume a suspended product environment and clear the suspension record
+    Resume {
+        /// Product name (e.g., "myapp"). Auto-discovered from deploy.yaml if omitted.
+        #[arg(long)]
+        product: Option<String>,
+
+        /// Git repository root path
+        #[arg(long, default_value = ".")]
+        repo_root: String,
+
+        /// Environment to resume
+        #[arg(long, required = true)]
+        env: String,
+    },
+}
+
 /// Helm chart subcommands
 #[derive(Subcommand)]
 pub enum HelmCommands {
@@ -1685,9 +2079,27 @@ pub enum HelmCommands {
         #[arg(long)]
         commit: bool,
 
-        /// Watch FluxCD reconciliation after deploy
+        /// Follow the HelmRelease after the push until it is Ready, rolled
+        /// back or uninstalled (requires --commit)
         #[arg(long)]
         watch: bool,
+
+        /// Render the chart with the old and new values and print the
+        /// resource-level diff of the upgrade
+        #[arg(long)]
+        diff: bool,
+
+        /// Local chart directory to render instead of the HelmRelease's chart
+        #[arg(long)]
+        chart_dir: Option<String>,
+
+        /// OCI registry serving charts the HelmRelease takes from a HelmRepository
+        #[arg(long, default_value = "oci://ghcr.io/pleme-io/charts")]
+        registry: String,
+
+        /// How long --watch waits for the upgrade (e.g. 15m)
+        #[arg(long, default_value = "15m")]
+        timeout: String,
     },
 
     /// Full chart lifecycle: lint, package, push
@@ -739,6 +739,7 @@ pub async fn execute(
             true, // skip_build
             cache_url,
             cache_name,
+            crate::server_dry_run::enabled(),
         )
         .await?;
 
@@ -0,0 +1,342 @@
+//! Databend Migration Validation and Rehearsal
+//!
+//! The analytics service applies plain `.sql` migrations to Databend
+//! (ClickHouse-dialect SQL) with `RUN_MODE=MIGRATE`. This module gives that
+//! chain the same gates as the Postgres one:
+//!
+//! - Idempotency: `CREATE` without `IF NOT EXISTS` / `OR REPLACE`, `DROP`
+//!   without `IF EXISTS`, `ADD COLUMN` without `IF NOT EXISTS`
+//! - Manifest consistency: a `migration-manifest.yaml` next to the files,
+//!   checked like the SeaORM manifest
+//! - Rollback compatibility: drops, renames and column type changes the
+//!   previous release's queries may not survive (warnings only)
+//!
+//! A file opts out of a rule with `-- forge-lint: allow(idempotency)` or
+//! `allow(rollback)`. Rehearsal applies the chain to a throwaway
+//! `datafuselabs/databend` container through the HTTP query API.
+
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use regex::Regex;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+use std::time::{Duration, Instant};
+use tokio::fs;
+
+use super::migration_lint::{allowed_rules, statements, Statement};
+use super::migration_rehearsal::{migration_files, Container, MigrationKind};
+use super::migration_validation::{check_manifest, MigrationIssue, StoreValidationResult};
+
+/// User and password of the rehearsal container
+const USER: &str = "databend";
+const PASSWORD: &str = "databend";
+
+/// How long to wait for the rehearsal container to answer queries
+const READY_TIMEOUT: Duration = Duration::from_secs(90);
+
+static DROP_OBJECT: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"^DROP (TABLE|DATABASE|VIEW|MATERIALIZED VIEW|DICTIONARY)\b(?: IF EXISTS)? ([\w.`]+)",
+    )
+    .unwrap()
+});
+static DROP_COLUMN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"^ALTER TABLE(?: IF EXISTS)? ([\w.`]+) .*\bDROP (?:COLUMN )?(?:IF EXISTS )?([\w`]+)",
+    )
+    .unwrap()
+});
+static RENAME: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(?:ALTER TABLE(?: IF EXISTS)? ([\w.`]+) .*\bRENAME\b|RENAME TABLE ([\w.`]+))")
+        .unwrap()
+});
+static TYPE_CHANGE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^ALTER TABLE(?: IF EXISTS)? ([\w.`]+) .*\b(?:MODIFY COLUMN|ALTER COLUMN [\w`]+ (?:SET DATA )?TYPE)\b")
+        .unwrap()
+});
+
+// =============================================================================
+// Validation
+// =============================================================================
+
+/// Idempotency issue for one statement, if any
+fn statement_idempotency(file: &Path, statement: &Statement) -> Option<MigrationIssue> {
+    let upper = statement.text.to_uppercase();
+    let violation = |suggestion: &str| MigrationIssue::IdempotencyViolation {
+        file: file.to_path_buf(),
+        line_number: statement.line_number,
+        statement: statement.text.clone(),
+        suggestion: suggestion.to_string(),
+    };
+
+    if upper.starts_with("CREATE ")
+        && !upper.contains(" IF NOT EXISTS ")
+        && !upper.starts_with("CREATE OR REPLACE ")
+    {
+        return Some(violation(
+            "Use CREATE ... IF NOT EXISTS or CREATE OR REPLACE",
+        ));
+    }
+    if upper.starts_with("DROP ") && !upper.contains(" IF EXISTS ") {
+        return Some(MigrationIssue::UnsafeDrop {
+            file: file.to_path_buf(),
+            line_number: statement.line_number,
+            statement: statement.text.clone(),
+            suggestion: "Use DROP ... IF EXISTS".to_string(),
+        });
+    }
+    if upper.starts_with("ALTER TABLE ")
+        && upper.contains(" ADD COLUMN ")
+        && !upper.contains(" ADD COLUMN IF NOT EXISTS ")
+    {
+        return Some(violation(
+            "Use ADD COLUMN IF NOT EXISTS, or allow(idempotency) when the column check happens in the migrator",
+        ));
+    }
+    None
+}
+
+/// Rollback warning for one statement, if any
+fn statement_rollback_warning(file: &Path, statement: &Statement) -> Option<String> {
+    let upper = statement.text.to_uppercase();
+    let what = if let Some(c) = DROP_OBJECT.captures(&upper) {
+        format!("drops {} {}", c[1].to_lowercase(), c[2].to_lowercase())
+    } else if let Some(c) = DROP_COLUMN.captures(&upper) {
+        format!(
+            "drops column {}.{}",
+            c[1].to_lowercase(),
+            c[2].to_lowercase()
+        )
+    } else if let Some(c) = RENAME.captures(&upper) {
+        let table = c.get(1).or_else(|| c.get(2)).map_or("", |m| m.as_str());
+        format!("renames in {}", table.to_lowercase())
+    } else if let Some(c) = TYPE_CHANGE.captures(&upper) {
+        format!("changes a column type in {}", c[1].to_lowercase())
+    } else {
+        return None;
+    };
+    Some(format!(
+        "{}:{}: {} — the previous release's queries may break after a rollback",
+        file.display(),
+        statement.line_number,
+        what
+    ))
+}
+
+/// Check one migration file
+fn check_file(file: &Path, content: &str) -> (Vec<MigrationIssue>, Vec<String>) {
+    let allowed = allowed_rules(content);
+    let mut issues = Vec::new();
+    let mut warnings = Vec::new();
+    for statement in statements(file, content) {
+        if !allowed.contains("idempotency") {
+            issues.extend(statement_idempotency(file, &statement));
+        }
+        if !allowed.contains("rollback") {
+            warnings.extend(statement_rollback_warning(file, &statement));
+        }
+    }
+    (issues, warnings)
+}
+
+/// Rollback warnings for the given migration files
+pub(super) async fn rollback_warnings(files: &[PathBuf]) -> Result<Vec<String>> {
+    let mut warnings = Vec::new();
+    for file in files {
+        let content = fs::read_to_string(file)
+            .await
+            .with_context(|| format!("Failed to read: {}", file.display()))?;
+        warnings.extend(check_file(file, &content).1);
+    }
+    Ok(warnings)
+}
+
+/// Validate the Databend migration chain in `dir`
+pub async fn validate_databend_migrations(dir: &Path) -> Result<StoreValidationResult> {
+    println!(
+        "{}",
+        format!("Validating Databend migrations in {}...", dir.display()).bold()
+    );
+
+    let files = migration_files(dir, MigrationKind::Databend).await?;
+    let mut result = StoreValidationResult {
+        files_checked: files.len(),
+        ..Default::default()
+    };
+    if files.is_empty() {
+        println!("   No migration files found");
+        return Ok(result);
+    }
+    println!("   Found {} migration files", files.len());
+
+    for file in &files {
+        let content = fs::read_to_string(file)
+            .await
+            .with_context(|| format!("Failed to read: {}", file.display()))?;
+        let (issues, warnings) = check_file(file, &content);
+        result.issues.extend(issues);
+        result.rollback_warnings.extend(warnings);
+    }
+    result.issues.extend(
+        check_manifest(dir, &files, "sql", None, false)
+            .await?
+            .issues,
+    );
+
+    if result.issues.is_empty() {
+        println!("   {} All migrations valid", "✅".green());
+    } else {
+        println!("   {} Found {} issues", "❌".red(), result.issues.len());
+        for issue in &result.issues {
+            println!("\n   {}", issue.format());
+        }
+    }
+    for warning in &result.rollback_warnings {
+        println!("   {} {}", "⚠️".yellow(), warning);
+    }
+    Ok(result)
+}
+
+// =============================================================================
+// Rehearsal
+// =============================================================================
+
+#[derive(Debug, Deserialize)]
+struct QueryResponse {
+    #[serde(default)]
+    error: Option<QueryError>,
+    #[serde(default)]
+    next_uri: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct QueryError {
+    message: String,
+}
+
+/// A throwaway Databend container
+pub(super) struct RehearsalDatabend {
+    container: Container,
+    client: reqwest::Client,
+}
+
+impl RehearsalDatabend {
+    pub(super) async fn start(image: &str, keep: bool) -> Result<Self> {
+        let env = [
+            format!("QUERY_DEFAULT_USER={}", USER),
+            format!("QUERY_DEFAULT_PASSWORD={}", PASSWORD),
+        ];
+        let container = Container::start(image, 8000, &env, keep, |port| {
+            format!("databend://{}:{}@127.0.0.1:{}", USER, PASSWORD, port)
+        })
+        .await?;
+        let databend = Self {
+            container,
+            client: reqwest::Client::new(),
+        };
+
+        let start = Instant::now();
+        while let Err(e) = databend.query("SELECT 1").await {
+            if start.elapsed() >= READY_TIMEOUT {
+                return Err(e.context(format!(
+                    "Databend container {} not ready after {}s",
+                    databend.container.id,
+                    READY_TIMEOUT.as_secs()
+                )));
+            }
+            tokio::time::sleep(Duration::from_secs(1)).await;
+        }
+        Ok(databend)
+    }
+
+    /// Run one statement through the HTTP query API, following `next_uri`
+    /// pages until the query finishes.
+    async fn query(&self, sql: &str) -> Result<()> {
+        let base = format!("http://127.0.0.1:{}", self.container.port);
+        let mut request = self
+            .client
+            .post(format!("{}/v1/query", base))
+            .json(&serde_json::json!({ "sql": sql }));
+        loop {
+            let response: QueryResponse = request
+                .basic_auth(USER, Some(PASSWORD))
+                .send()
+                .await
+                .context("Databend query request failed")?
+                .error_for_status()?
+                .json()
+                .await
+                .context("Failed to parse Databend query response")?;
+            if let Some(error) = response.error {
+                bail!("{}", error.message);
+            }
+            match response.next_uri {
+                Some(uri) => request = self.client.get(format!("{}{}", base, uri)),
+                None => return Ok(()),
+            }
+        }
+    }
+
+    /// Apply one migration file statement by statement
+    pub(super) async fn apply(&self, file: &Path, op: &str) -> Result<()> {
+        let content = fs::read_to_string(file)
+            .await
+            .with_context(|| format!("Failed to read: {}", file.display()))?;
+        for statement in statements(file, &content) {
+            self.query(&statement.text)
+                .await
+                .with_context(|| format!("{} failed at line {}", op, statement.line_number))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_file_flags_non_idempotent_ddl() {
+        let content = "\
+CREATE TABLE events (id UInt64, ts Timestamp);
+CREATE TABLE IF NOT EXISTS sessions (id UInt64);
+CREATE OR REPLACE VIEW daily AS SELECT 1;
+DROP TABLE legacy_events;
+ALTER TABLE events ADD COLUMN source String;
+";
+        let (issues, _) = check_file(Path::new("001_events.sql"), content);
+        assert_eq!(issues.len(), 3);
+        assert!(matches!(
+            &issues[0],
+            MigrationIssue::IdempotencyViolation { line_number: 1, .. }
+        ));
+        assert!(matches!(
+            &issues[1],
+            MigrationIssue::UnsafeDrop { line_number: 4, .. }
+        ));
+        assert!(matches!(
+            &issues[2],
+            MigrationIssue::IdempotencyViolation { line_number: 5, .. }
+        ));
+    }
+
+    #[test]
+    fn test_check_file_warns_on_rollback_hazards() {
+        let content = "\
+-- forge-lint: allow(idempotency)
+DROP TABLE IF EXISTS legacy_events;
+ALTER TABLE events DROP COLUMN source;
+ALTER TABLE events RENAME COLUMN ts TO created_at;
+ALTER TABLE events MODIFY COLUMN id UInt32;
+ALTER TABLE events ADD COLUMN region String;
+";
+        let (issues, warnings) = check_file(Path::new("002_events.sql"), content);
+        assert!(issues.is_empty());
+        assert_eq!(warnings.len(), 4);
+        assert!(warnings[0].contains("drops table legacy_events"));
+        assert!(warnings[1].contains("drops column events.source"));
+        assert!(warnings[2].contains("renames in events"));
+        assert!(warnings[3].contains("changes a column type in events"));
+    }
+}
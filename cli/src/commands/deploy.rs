@@ -4,7 +4,10 @@ use indicatif::{ProgressBar, ProgressStyle};
 use std::path::Path;
 use tracing::{info, warn};
 
-use crate::{cloudflare, commands, config::DeployConfig, flux_reconcile, git};
+use crate::{
+    cloudflare, commands, config::DeployConfig, flux_reconcile, git, manifest_schema,
+    server_dry_run,
+};
 
 pub async fn execute(
     manifest: String,
@@ -17,6 +20,7 @@ pub async fn execute(
     skip_build: bool,
     cache_url: String,
     cache_name: String,
+    server_dry_run: bool,
 ) -> Result<()> {
     println!();
     println!(
@@ -44,6 +48,9 @@ pub async fn execute(
     info!("🚀 Deployment: {}", name);
     println!();
 
+    // Refuse to deploy into a namespace frozen with `forge flux suspend`
+    commands::flux_suspend::ensure_namespace_not_suspended(&namespace).await?;
+
     // Step 1: Build (unless skipped)
     if !skip_build {
         info!("━━━ Step 1/3: Build ━━━");
@@ -116,12 +123,37 @@ pub async fn execute(
     info!("   New tag: {}", tag);
     println!();
 
-    // Update kustomization.yaml's images[].newTag
-    git::update_manifest(kustomization_path, &old_tag, &tag).await?;
+    // Snapshot the ConfigMap too, so a failed validation or dry run leaves
+    // the working tree as it was
+    let configmap_path = git::configmap_path(kustomization_path);
+    let configmap_content = match &configmap_path {
+        Some(path) if path.exists() => Some(
+            tokio::fs::read_to_string(path)
+                .await
+                .context("Failed to read ConfigMap file")?,
+        ),
+        _ => None,
+    };
 
-    // Update ConfigMap with GIT_SHA
-    info!("📝 Updating ConfigMap with GIT_SHA...");
-    git::update_configmap_git_sha(kustomization_path, &tag).await?;
+    let prepared = prepare_overlay(
+        kustomization_path,
+        &tag,
+        &old_tag,
+        &namespace,
+        server_dry_run,
+    )
+    .await;
+    if let Err(e) = prepared {
+        tokio::fs::write(kustomization_path, &kustomization_content)
+            .await
+            .context("Failed to restore kustomization.yaml")?;
+        if let (Some(path), Some(content)) = (&configmap_path, &configmap_content) {
+            tokio::fs::write(path, content)
+                .await
+                .context("Failed to restore ConfigMap file")?;
+        }
+        return Err(e);
+    }
 
     // Commit and push
     info!("📤 Committing to Git...");
@@ -232,3 +264,42 @@ pub async fn execute(
 
     Ok(())
 }
+
+/// Apply the new tag and GIT_SHA to the overlay, then render and validate
+/// it (and dry-run it against the apiserver when asked) before anything
+/// reaches git.
+async fn prepare_overlay(
+    kustomization_path: &Path,
+    tag: &str,
+    old_tag: &str,
+    namespace: &str,
+    server_dry_run: bool,
+) -> Result<()> {
+    // Update kustomization.yaml's images[].newTag
+    git::update_manifest(kustomization_path, old_tag, tag).await?;
+
+    // Update ConfigMap with GIT_SHA
+    info!("📝 Updating ConfigMap with GIT_SHA...");
+    git::update_configmap_git_sha(kustomization_path, tag).await?;
+
+    info!("🔍 Validating rendered manifests...");
+    if let Some(validation) = manifest_schema::validate_overlay(kustomization_path)? {
+        info!(
+            "✅ Overlay renders ({} objects, {} schema-validated)",
+            validation.objects, validation.validated
+        );
+    }
+
+    if server_dry_run {
+        info!("🧪 Server-side dry run (dryRun=All)...");
+        if let Some(outcome) =
+            server_dry_run::dry_run_overlay(kustomization_path, namespace).await?
+        {
+            info!(
+                "✅ Apiserver accepted {} objects ({} SOPS-encrypted skipped)",
+                outcome.submitted, outcome.skipped
+            );
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,752 @@
+//! Live-vs-git drift detection (`forge drift`)
+//!
+//! For every service in a product's `release.services`, renders the
+//! service's kustomization for one environment (the same path
+//! [`DeployConfig::k8s_manifest_path_for_env`] hands to the GitOps commit)
+//! with the in-process renderer in [`crate::kustomize`] and compares each rendered object with its live counterpart:
+//!
+//! - only fields the rendered manifest declares are compared, so defaults
+//!   and fields the apiserver or controllers add never count as drift
+//! - `status` and server-managed metadata (`uid`, `resourceVersion`,
+//!   `managedFields`, the last-applied annotation, ...) are ignored even
+//!   when a manifest happens to declare them
+//! - lists of named entries (containers, env vars, ports, volumes) are
+//!   matched by `name`, and named entries present only on the live object
+//!   are reported — a `kubectl set env` or an injected container shows up
+//! - a rendered object that does not exist in the cluster is drift
+//! - Secret `data` values are never printed, only the keys that differ;
+//!   SOPS-encrypted objects (a top-level `sops` key) are skipped, since git
+//!   only holds their ciphertext
+//!
+//! Live objects are read through the kube dynamic API with kinds resolved
+//! by discovery. Any drift, or any service that cannot be checked, makes
+//! the command exit non-zero so it can gate CI.
+
+use anyhow::{bail, Result};
+use colored::Colorize;
+use kube::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+
+use super::fleet_status;
+use super::status::OutputFormat;
+use crate::config::{self, DeployConfig, ProductServiceConfig};
+use crate::error::KubernetesError;
+use crate::k8s;
+use crate::kustomize;
+use crate::repo;
+
+/// Metadata fields owned by the apiserver.
+const SERVER_METADATA_FIELDS: &[&str] = &[
+    "uid",
+    "resourceVersion",
+    "generation",
+    "creationTimestamp",
+    "deletionTimestamp",
+    "deletionGracePeriodSeconds",
+    "managedFields",
+    "selfLink",
+    "ownerReferences",
+    "finalizers",
+];
+
+/// Printed in place of Secret values.
+const REDACTED: &str = "<redacted>";
+
+/// Annotations written by kubectl and controllers rather than by git.
+const SERVER_ANNOTATIONS: &[&str] = &[
+    "kubectl.kubernetes.io/last-applied-configuration",
+    "deployment.kubernetes.io/revision",
+];
+
+// ============================================================================
+// Data Structures
+// ============================================================================
+
+/// Drift of one product environment.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DriftReport {
+    pub product: String,
+    pub environment: String,
+    pub services: Vec<ServiceDrift>,
+}
+
+impl DriftReport {
+    /// Objects that differ from git (including missing ones).
+    pub fn drifted_objects(&self) -> usize {
+        self.services
+            .iter()
+            .flat_map(|s| &s.objects)
+            .filter(|o| o.is_drifted())
+            .count()
+    }
+
+    /// Services whose manifests or live objects could not be read.
+    pub fn failed_services(&self) -> usize {
+        self.services.iter().filter(|s| s.error.is_some()).count()
+    }
+}
+
+/// Drift of one service's rendered kustomization.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServiceDrift {
+    pub service: String,
+    /// Kustomization that was rendered (empty when it could not be resolved).
+    pub kustomization: String,
+    pub objects: Vec<ObjectDrift>,
+    /// Set when the service could not be checked at all.
+    pub error: Option<String>,
+}
+
+/// Comparison of one rendered object with the cluster.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ObjectDrift {
+    pub api_version: String,
+    pub kind: String,
+    pub namespace: Option<String>,
+    pub name: String,
+    /// The object does not exist in the cluster.
+    pub missing: bool,
+    pub differences: Vec<FieldDiff>,
+    /// Set when the live object could not be read; the object is then
+    /// neither clean nor drifted.
+    pub error: Option<String>,
+}
+
+impl ObjectDrift {
+    pub fn is_drifted(&self) -> bool {
+        self.missing || !self.differences.is_empty()
+    }
+
+    /// `Kind/namespace/name`, or `Kind/name` for cluster-scoped objects.
+    pub fn display_name(&self) -> String {
+        match &self.namespace {
+            Some(ns) => format!("{}/{}/{}", self.kind, ns, self.name),
+            None => format!("{}/{}", self.kind, self.name),
+        }
+    }
+}
+
+/// One differing field. `desired` is `None` for a named list entry that
+/// exists only on the live object; `live` is `None` for a declared field
+/// the live object lacks.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldDiff {
+    pub path: String,
+    pub desired: Option<Value>,
+    pub live: Option<Value>,
+}
+
+// ============================================================================
+// Execute Command
+// ============================================================================
+
+/// Execute `forge drift`
+pub async fn execute(
+    product: &str,
+    repo_root: &str,
+    env: &str,
+    format: OutputFormat,
+) -> Result<()> {
+    let product_config = DeployConfig::load_product_config(product, repo_root)?;
+    let services = product_config
+        .release
+        .as_ref()
+        .map(|r| r.services.clone())
+        .unwrap_or_default();
+    if services.is_empty() {
+        bail!(
+            "Product '{}' has no services in release.services\n  \
+             Drift detection compares each release service's kustomization with the cluster.",
+            product
+        );
+    }
+
+    let product_dir = config::resolve_product_dir(Path::new(repo_root), product);
+    let client = k8s::create_client().await?;
+    let mut kinds = k8s::KindCache::default();
+
+    let mut report = DriftReport {
+        product: product.to_string(),
+        environment: env.to_string(),
+        services: Vec::new(),
+    };
+
+    for svc in &services {
+        let namespace = fleet_status::service_namespace(&product_config, &svc.path, repo_root, env);
+        report.services.push(
+            check_service(
+                &client,
+                &mut kinds,
+                repo_root,
+                &product_dir,
+                svc,
+                env,
+                &namespace,
+            )
+            .await,
+        );
+    }
+
+    match format {
+        OutputFormat::Text => print_text_report(&report),
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+    }
+
+    let drifted = report.drifted_objects();
+    let failed = report.failed_services();
+    if drifted > 0 || failed > 0 {
+        bail!(
+            "{} object(s) drifted from git and {} service(s) could not be checked in {}-{}",
+            drifted,
+            failed,
+            product,
+            env
+        );
+    }
+
+    Ok(())
+}
+
+/// Render one service's kustomization and compare every object in it.
+async fn check_service(
+    client: &Client,
+    kinds: &mut k8s::KindCache,
+    repo_root: &str,
+    product_dir: &Path,
+    svc: &ProductServiceConfig,
+    env: &str,
+    namespace: &str,
+) -> ServiceDrift {
+    let mut drift = ServiceDrift {
+        service: svc.name.clone(),
+        kustomization: String::new(),
+        objects: Vec::new(),
+        error: None,
+    };
+
+    let rendered = kustomization_path(repo_root, product_dir, svc, env).and_then(|path| {
+        drift.kustomization = path.display().to_string();
+        Ok(kustomize::render(&path)?)
+    });
+    let manifests = match rendered {
+        Ok(m) => m,
+        Err(e) => {
+            drift.error = Some(format!("{e:#}"));
+            return drift;
+        }
+    };
+
+    for desired in manifests {
+        if desired.get("sops").is_some() {
+            continue;
+        }
+        drift
+            .objects
+            .push(check_object(client, kinds, &desired, namespace).await);
+    }
+    drift
+}
+
+/// Kustomization path for a release service, resolved through the
+/// service's own deploy.yaml exactly as `deploy` resolves it.
+fn kustomization_path(
+    repo_root: &str,
+    product_dir: &Path,
+    svc: &ProductServiceConfig,
+    env: &str,
+) -> Result<PathBuf> {
+    repo::activate_root_flake(repo_root, product_dir.join(&svc.path))?;
+    let deploy_config = DeployConfig::load_for_service(&svc.name)?;
+    deploy_config.k8s_manifest_path_for_env(env)
+}
+
+// ============================================================================
+// Live Objects
+// ============================================================================
+
+/// Compare one rendered object with its live counterpart.
+async fn check_object(
+    client: &Client,
+    kinds: &mut k8s::KindCache,
+    desired: &Value,
+    default_namespace: &str,
+) -> ObjectDrift {
+    let field = |path: &str| desired.pointer(path).and_then(Value::as_str).unwrap_or("");
+    let mut drift = ObjectDrift {
+        api_version: field("/apiVersion").to_string(),
+        kind: field("/kind").to_string(),
+        namespace: None,
+        name: field("/metadata/name").to_string(),
+        missing: false,
+        differences: Vec::new(),
+        error: None,
+    };
+
+    if drift.api_version.is_empty() || drift.kind.is_empty() || drift.name.is_empty() {
+        drift.error = Some("rendered object lacks apiVersion, kind or metadata.name".to_string());
+        return drift;
+    }
+
+    let api = match k8s::object_api(client, kinds, desired, default_namespace).await {
+        Ok((api, namespace)) => {
+            drift.namespace = namespace;
+            api
+        }
+        Err(e) => {
+            drift.error = Some(format!("{e:#}"));
+            return drift;
+        }
+    };
+
+    match api.get_opt(&drift.name).await {
+        Ok(Some(live)) => match serde_json::to_value(&live) {
+            Ok(live) => drift.differences = diff_object(desired, &live),
+            Err(e) => drift.error = Some(format!("Failed to serialize live object: {e}")),
+        },
+        Ok(None) => drift.missing = true,
+        Err(e) => {
+            let op = format!("get {} {}", drift.kind, drift.name);
+            let namespace = drift.namespace.clone().unwrap_or_default();
+            drift.error = Some(KubernetesError::api_failed(op, namespace, e).to_string());
+        }
+    }
+    drift
+}
+
+// ============================================================================
+// Diffing
+// ============================================================================
+
+/// Field-level differences between a rendered object and its live
+/// counterpart, ignoring `status` and server-managed metadata. Secret
+/// values are redacted.
+pub fn diff_object(desired: &Value, live: &Value) -> Vec<FieldDiff> {
+    let mut desired = strip_server_fields(desired);
+    let live = strip_server_fields(live);
+    let is_secret = desired.get("kind").and_then(Value::as_str) == Some("Secret");
+    if is_secret {
+        fold_string_data(&mut desired);
+    }
+    let mut diffs = Vec::new();
+    diff_value("", &desired, &live, &mut diffs);
+    if is_secret {
+        for diff in &mut diffs {
+            if diff.path == ".data" || diff.path.starts_with(".data.") {
+                diff.desired = diff.desired.as_ref().map(redact);
+                diff.live = diff.live.as_ref().map(redact);
+            }
+        }
+    }
+    diffs
+}
+
+/// Move a Secret's `stringData` into `data`, base64-encoded, as the
+/// apiserver stores it.
+fn fold_string_data(secret: &mut Value) {
+    use base64::Engine as _;
+    let Some(map) = secret.as_object_mut() else {
+        return;
+    };
+    let Some(Value::Object(string_data)) = map.remove("stringData") else {
+        return;
+    };
+    let data = map
+        .entry("data")
+        .or_insert_with(|| Value::Object(Default::default()));
+    if let Value::Object(data) = data {
+        for (key, value) in string_data {
+            let text = value.as_str().map(str::to_string).unwrap_or_default();
+            let encoded = base64::engine::general_purpose::STANDARD.encode(text);
+            data.insert(key, Value::String(encoded));
+        }
+    }
+}
+
+/// A Secret value, or a map of them, with only its keys left.
+fn redact(value: &Value) -> Value {
+    match value {
+        Value::Object(map) => Value::Object(
+            map.keys()
+                .map(|key| (key.clone(), Value::String(REDACTED.to_string())))
+                .collect(),
+        ),
+        _ => Value::String(REDACTED.to_string()),
+    }
+}
+
+/// Copy of `object` without the fields git does not own.
+fn strip_server_fields(object: &Value) -> Value {
+    let mut object = object.clone();
+    if let Some(map) = object.as_object_mut() {
+        map.remove("status");
+        if let Some(Value::Object(metadata)) = map.get_mut("metadata") {
+            for field in SERVER_METADATA_FIELDS {
+                metadata.remove(*field);
+            }
+            if let Some(Value::Object(annotations)) = metadata.get_mut("annotations") {
+                for annotation in SERVER_ANNOTATIONS {
+                    annotations.remove(*annotation);
+                }
+            }
+        }
+    }
+    object
+}
+
+fn diff_value(path: &str, desired: &Value, live: &Value, diffs: &mut Vec<FieldDiff>) {
+    match (desired, live) {
+        (Value::Object(want), Value::Object(have)) => {
+            for (key, want_value) in want {
+                let child = format!("{path}.{key}");
+                match have.get(key) {
+                    Some(have_value) => diff_value(&child, want_value, have_value, diffs),
+                    None if want_value.is_null() => {}
+                    None => diffs.push(FieldDiff {
+                        path: child,
+                        desired: Some(want_value.clone()),
+                        live: None,
+                    }),
+                }
+            }
+        }
+        (Value::Array(want), Value::Array(have)) if is_named_list(want) => {
+            diff_named_list(path, want, have, diffs);
+        }
+        (Value::Array(want), Value::Array(have)) if want.len() == have.len() => {
+            for (index, (w, h)) in want.iter().zip(have).enumerate() {
+                diff_value(&format!("{path}[{index}]"), w, h, diffs);
+            }
+        }
+        _ if scalars_equal(desired, live) => {}
+        _ => diffs.push(FieldDiff {
+            path: path.to_string(),
+            desired: Some(desired.clone()),
+            live: Some(live.clone()),
+        }),
+    }
+}
+
+/// A non-empty list whose entries are all objects carrying a string `name`.
+fn is_named_list(items: &[Value]) -> bool {
+    !items.is_empty() && items.iter().all(|item| entry_name(item).is_some())
+}
+
+fn entry_name(item: &Value) -> Option<&str> {
+    item.get("name").and_then(Value::as_str)
+}
+
+fn diff_named_list(path: &str, want: &[Value], have: &[Value], diffs: &mut Vec<FieldDiff>) {
+    for want_item in want {
+        let name = entry_name(want_item).unwrap_or_default();
+        let child = format!("{path}[{name}]");
+        match have.iter().find(|h| entry_name(h) == Some(name)) {
+            Some(have_item) => diff_value(&child, want_item, have_item, diffs),
+            None => diffs.push(FieldDiff {
+                path: child,
+                desired: Some(want_item.clone()),
+                live: None,
+            }),
+        }
+    }
+    for have_item in have {
+        let Some(name) = entry_name(have_item) else {
+            continue;
+        };
+        if !want.iter().any(|w| entry_name(w) == Some(name)) {
+            diffs.push(FieldDiff {
+                path: format!("{path}[{name}]"),
+                desired: None,
+                live: Some(have_item.clone()),
+            });
+        }
+    }
+}
+
+/// Equality that tolerates the apiserver's canonicalisation: numbers
+/// written as strings (`"8080"` vs `8080`) and resource quantities
+/// (`"1000m"` vs `"1"`, `"1024Mi"` vs `"1Gi"`).
+fn scalars_equal(desired: &Value, live: &Value) -> bool {
+    if desired == live {
+        return true;
+    }
+    let text = |v: &Value| match v {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    };
+    match (text(desired), text(live)) {
+        (Some(a), Some(b)) => match (parse_quantity(&a), parse_quantity(&b)) {
+            (Some(x), Some(y)) => (x - y).abs() <= f64::EPSILON * x.abs().max(1.0),
+            _ => a == b,
+        },
+        _ => false,
+    }
+}
+
+/// Parse a Kubernetes resource quantity into its base-unit value.
+fn parse_quantity(quantity: &str) -> Option<f64> {
+    const SUFFIXES: &[(&str, f64)] = &[
+        ("Ki", 1024.0),
+        ("Mi", 1024.0 * 1024.0),
+        ("Gi", 1024.0 * 1024.0 * 1024.0),
+        ("Ti", 1024.0 * 1024.0 * 1024.0 * 1024.0),
+        ("m", 0.001),
+        ("k", 1e3),
+        ("M", 1e6),
+        ("G", 1e9),
+        ("T", 1e12),
+    ];
+    let quantity = quantity.trim();
+    for (suffix, factor) in SUFFIXES {
+        if let Some(number) = quantity.strip_suffix(suffix) {
+            return number.parse::<f64>().ok().map(|n| n * factor);
+        }
+    }
+    quantity.parse::<f64>().ok()
+}
+
+// ============================================================================
+// Output
+// ============================================================================
+
+fn print_text_report(report: &DriftReport) {
+    println!();
+    println!(
+        "{} {}-{}",
+        "Drift".bright_cyan().bold(),
+        report.product,
+        report.environment
+    );
+
+    for service in &report.services {
+        println!();
+        println!("{}", service.service.bright_white().bold());
+        if !service.kustomization.is_empty() {
+            println!("  {}", service.kustomization.dimmed());
+        }
+        if let Some(err) = &service.error {
+            println!("  {} {}", "✗".red(), err);
+            continue;
+        }
+        for object in &service.objects {
+            for line in render_object(object) {
+                println!("{line}");
+            }
+        }
+    }
+
+    println!();
+    println!(
+        "{} drifted object(s), {} service(s) not checked",
+        report.drifted_objects(),
+        report.failed_services()
+    );
+}
+
+/// Text lines for one object: a status line, then one line per field.
+pub fn render_object(object: &ObjectDrift) -> Vec<String> {
+    let name = object.display_name();
+    if let Some(err) = &object.error {
+        return vec![format!("  {} {}: {}", "⚠".yellow(), name, err)];
+    }
+    if object.missing {
+        return vec![format!("  {} {} (missing from cluster)", "✗".red(), name)];
+    }
+    if object.differences.is_empty() {
+        return vec![format!("  {} {}", "✓".green(), name)];
+    }
+
+    let show = |v: &Option<Value>| match v {
+        Some(v) => v.to_string(),
+        None => "<absent>".to_string(),
+    };
+    let mut lines = vec![format!("  {} {}", "~".yellow(), name)];
+    for diff in &object.differences {
+        lines.push(format!(
+            "      {}: git {} → live {}",
+            diff.path,
+            show(&diff.desired),
+            show(&diff.live)
+        ));
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn deployment(image: &str, env: Value) -> Value {
+        json!({
+            "apiVersion": "apps/v1",
+            "kind": "Deployment",
+            "metadata": {"name": "cart", "namespace": "shop-staging", "labels": {"app": "cart"}},
+            "spec": {
+                "replicas": 2,
+                "template": {"spec": {"containers": [{
+                    "name": "cart",
+                    "image": image,
+                    "env": env,
+                    "resources": {"requests": {"cpu": "500m", "memory": "1Gi"}}
+                }]}}
+            }
+        })
+    }
+
+    #[test]
+    fn diff_ignores_status_server_metadata_and_defaulted_fields() {
+        let desired = deployment("cart:abc", json!([{"name": "RUST_LOG", "value": "info"}]));
+        let mut live = deployment("cart:abc", json!([{"name": "RUST_LOG", "value": "info"}]));
+        live["status"] = json!({"readyReplicas": 2});
+        live["metadata"]["uid"] = json!("1234");
+        live["metadata"]["resourceVersion"] = json!("99");
+        live["metadata"]["managedFields"] = json!([{"manager": "kustomize-controller"}]);
+        live["metadata"]["annotations"] = json!({"deployment.kubernetes.io/revision": "7"});
+        live["spec"]["progressDeadlineSeconds"] = json!(600);
+        live["spec"]["template"]["spec"]["containers"][0]["imagePullPolicy"] = json!("Always");
+        live["spec"]["template"]["spec"]["containers"][0]["resources"]["requests"]["cpu"] =
+            json!("0.5");
+        live["spec"]["template"]["spec"]["containers"][0]["resources"]["requests"]["memory"] =
+            json!("1024Mi");
+
+        assert!(diff_object(&desired, &live).is_empty());
+    }
+
+    #[test]
+    fn diff_matches_named_lists_by_name_and_reports_scalar_paths() {
+        let desired = deployment(
+            "cart:abc",
+            json!([{"name": "A", "value": "1"}, {"name": "B", "value": "2"}]),
+        );
+        let live = deployment(
+            "cart:old",
+            json!([{"name": "B", "value": "2"}, {"name": "A", "value": "9"}]),
+        );
+
+        let diffs = diff_object(&desired, &live);
+        let paths: Vec<&str> = diffs.iter().map(|d| d.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                ".spec.template.spec.containers[cart].env[A].value",
+                ".spec.template.spec.containers[cart].image",
+            ]
+        );
+        assert_eq!(diffs[1].desired, Some(json!("cart:abc")));
+        assert_eq!(diffs[1].live, Some(json!("cart:old")));
+    }
+
+    #[test]
+    fn diff_reports_live_only_named_entries_and_missing_fields() {
+        let desired = deployment("cart:abc", json!([{"name": "A", "value": "1"}]));
+        let mut live = deployment(
+            "cart:abc",
+            json!([{"name": "A", "value": "1"}, {"name": "DEBUG", "value": "true"}]),
+        );
+        live["metadata"]["labels"] = json!({});
+
+        let diffs = diff_object(&desired, &live);
+        assert_eq!(
+            diffs,
+            vec![
+                FieldDiff {
+                    path: ".metadata.labels.app".into(),
+                    desired: Some(json!("cart")),
+                    live: None,
+                },
+                FieldDiff {
+                    path: ".spec.template.spec.containers[cart].env[DEBUG]".into(),
+                    desired: None,
+                    live: Some(json!({"name": "DEBUG", "value": "true"})),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn diff_compares_unnamed_lists_whole_when_lengths_differ() {
+        let desired = json!({"spec": {"args": ["--port", "8080"]}});
+        let live = json!({"spec": {"args": ["--port", "8080", "--debug"]}});
+
+        let diffs = diff_object(&desired, &live);
+        assert_eq!(diffs.len(), 1);
+        assert_eq!(diffs[0].path, ".spec.args");
+    }
+
+    #[test]
+    fn diff_redacts_secret_values_and_folds_string_data() {
+        let desired = json!({
+            "apiVersion": "v1",
+            "kind": "Secret",
+            "metadata": {"name": "cart-secrets"},
+            "data": {"API_KEY": "bmV3", "TOKEN": "c2FtZQ=="},
+            "stringData": {"PASSWORD": "hunter2"}
+        });
+        let live = json!({
+            "apiVersion": "v1",
+            "kind": "Secret",
+            "metadata": {"name": "cart-secrets"},
+            "data": {"API_KEY": "b2xk", "TOKEN": "c2FtZQ==", "PASSWORD": "aHVudGVyMg=="}
+        });
+        assert_eq!(
+            diff_object(&desired, &live),
+            vec![FieldDiff {
+                path: ".data.API_KEY".into(),
+                desired: Some(json!("<redacted>")),
+                live: Some(json!("<redacted>")),
+            }]
+        );
+
+        let empty =
+            json!({"apiVersion": "v1", "kind": "Secret", "metadata": {"name": "cart-secrets"}});
+        let diffs = diff_object(&desired, &empty);
+        assert_eq!(diffs.len(), 1);
+        assert_eq!(
+            diffs[0].desired,
+            Some(json!({"API_KEY": "<redacted>", "PASSWORD": "<redacted>", "TOKEN": "<redacted>"}))
+        );
+    }
+
+    #[test]
+    fn render_object_marks_missing_and_drifted_objects() {
+        let mut object = ObjectDrift {
+            api_version: "apps/v1".into(),
+            kind: "Deployment".into(),
+            namespace: Some("shop-staging".into()),
+            name: "cart".into(),
+            missing: true,
+            differences: Vec::new(),
+            error: None,
+        };
+        // Colours are process-global; strip them rather than turning them off
+        let ansi = regex::Regex::new(r"\x1b\[[0-9;]*m").unwrap();
+        let render = |object: &ObjectDrift| -> Vec<String> {
+            render_object(object)
+                .iter()
+                .map(|l| ansi.replace_all(l, "").into_owned())
+                .collect()
+        };
+        assert_eq!(
+            render(&object),
+            vec!["  ✗ Deployment/shop-staging/cart (missing from cluster)"]
+        );
+
+        object.missing = false;
+        object.differences.push(FieldDiff {
+            path: ".spec.replicas".into(),
+            desired: Some(json!(2)),
+            live: Some(json!(5)),
+        });
+        assert_eq!(
+            render(&object),
+            vec![
+                "  ~ Deployment/shop-staging/cart".to_string(),
+                "      .spec.replicas: git 2 → live 5".to_string(),
+            ]
+        );
+        assert!(object.is_drifted());
+    }
+}
@@ -7,10 +7,14 @@
 //! - E2E: auto-builds and loads images if missing
 
 use anyhow::{bail, Context, Result};
+use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::thread;
 use std::time::{Duration, Instant};
 
+use super::test_quarantine::{self, Quarantine};
+use super::test_reports::TestReport;
+use super::test_sharding::{self, Shard, ShardPlan, TestDurations};
 use crate::repo::get_tool_path;
 use crate::retry::{run_inherited_status_sync, RetryPolicy};
 use crate::ui;
@@ -231,6 +235,20 @@ pub enum TestLevel {
 /// Run the full testing pyramid
 ///
 /// Executes tests in order: Unit → Integration → E2E (fast feedback first)
+///
+/// With `junit`, every level's results are merged into one JUnit XML file
+/// at that path, written before returning whether or not tests passed.
+///
+/// With `shard` (`i/n`), only this worker's share of the test units runs:
+/// the backend unit tests, each integration and E2E test target and the
+/// individual vitest files are partitioned by their durations in
+/// `durations` (default: the product's `deploy/reports/test-durations.json`).
+/// This run's results are folded back into the history only when
+/// `durations` is given explicitly.
+///
+/// Tests in the product's `quarantine.yaml` do not fail a level, and an
+/// expired quarantine entry fails the run.
+#[allow(clippy::too_many_arguments)]
 pub fn run_test_pyramid(
     repo_root: Option<String>,
     skip_unit: bool,
@@ -240,6 +258,9 @@ pub fn run_test_pyramid(
     fail_fast: bool,
     report: bool,
     report_path: Option<String>,
+    junit: Option<String>,
+    shard: Option<String>,
+    durations: Option<String>,
 ) -> Result<()> {
     let repo_root = resolve_repo_root(repo_root)?;
     // TODO: derive product paths from deploy.yaml config
@@ -251,15 +272,78 @@ pub fn run_test_pyramid(
     println!("Test order: Unit → Integration → E2E (fast feedback first)");
     println!();
 
+    let durations_path = durations
+        .as_deref()
+        .map(PathBuf::from)
+        .unwrap_or_else(|| test_sharding::default_durations_path(Path::new(&repo_root)));
+    let mut history = TestDurations::load(&durations_path)?;
+    let quarantine = Quarantine::load(Path::new(&repo_root))?;
+    let (integration_targets, e2e_targets) = backend_test_targets(&backend_dir);
+    let plan = match shard.as_deref().map(Shard::parse).transpose()? {
+        Some(shard) => {
+            let mut units = Vec::new();
+            if !skip_unit {
+                units.push(BACKEND_UNIT_SUITE.to_string());
+                units.extend(
+                    test_sharding::find_vitest_files(Path::new(&web_dir))
+                        .into_iter()
+                        .map(|file| format!("{}/{}", FRONTEND_UNIT_SUITE, file)),
+                );
+            }
+            if !skip_integration {
+                units.extend(
+                    integration_targets
+                        .iter()
+                        .map(|target| format!("{}/{}", BACKEND_INTEGRATION_SUITE, target)),
+                );
+            }
+            if !skip_e2e {
+                units.extend(
+                    e2e_targets
+                        .iter()
+                        .map(|target| format!("{}/{}", E2E_SUITE, target)),
+                );
+            }
+            let plan = ShardPlan::new(shard, &units, &history);
+            plan.print_summary();
+            println!();
+            Some(plan)
+        }
+        None => None,
+    };
+    let in_shard = |unit: &str| plan.as_ref().is_none_or(|plan| plan.contains(unit));
+    let vitest_files = plan
+        .as_ref()
+        .map(|plan| plan.files_under(FRONTEND_UNIT_SUITE))
+        .unwrap_or_default();
+    let skip_backend_unit = skip_unit || !in_shard(BACKEND_UNIT_SUITE);
+    let skip_frontend_unit = skip_unit || (plan.is_some() && vitest_files.is_empty());
+    let shard_targets = |suite: &str, targets: Vec<String>| match &plan {
+        Some(plan) => plan.files_under(suite),
+        None => targets,
+    };
+    let integration_targets = shard_targets(BACKEND_INTEGRATION_SUITE, integration_targets);
+    let e2e_targets = shard_targets(E2E_SUITE, e2e_targets);
+    let skip_integration = skip_integration || integration_targets.is_empty();
+    let skip_e2e = skip_e2e || e2e_targets.is_empty();
+
     let mut all_passed = true;
+    let mut results = TestReport::default();
+    let collect = junit.is_some() || plan.is_some() || !quarantine.is_empty();
 
     // Phase 1: Backend Unit Tests
-    if !skip_unit {
+    if !skip_backend_unit {
         ui::print_header("Phase 1: Backend Unit Tests");
-        let result = run_backend_unit_tests(&backend_dir, filter.as_deref());
+        let result = run_backend_unit_tests(
+            &backend_dir,
+            filter.as_deref(),
+            &quarantine,
+            collect.then_some(&mut results),
+        );
         if result.is_err() {
             all_passed = false;
             if fail_fast {
+                write_junit(&results, junit.as_deref(), "test-pyramid")?;
                 return result;
             }
             ui::print_error("Backend unit tests failed");
@@ -270,13 +354,22 @@ pub fn run_test_pyramid(
     }
 
     // Phase 2: Frontend Unit Tests
-    if !skip_unit {
+    if !skip_frontend_unit {
         ui::print_header("Phase 2: Frontend Unit Tests");
-        let result =
-            run_frontend_unit_tests(&web_dir, filter.as_deref(), report, report_path.as_deref());
+        let before = results.cases.len();
+        let result = run_frontend_unit_tests(
+            &web_dir,
+            filter.as_deref(),
+            &vitest_files,
+            report,
+            report_path.as_deref(),
+            collect.then_some(&mut results),
+        );
+        let result = quarantine.settle(result, &results.cases[before..]);
         if result.is_err() {
             all_passed = false;
             if fail_fast {
+                write_junit(&results, junit.as_deref(), "test-pyramid")?;
                 return result;
             }
             ui::print_error("Frontend unit tests failed");
@@ -294,10 +387,17 @@ pub fn run_test_pyramid(
         if let Err(e) = verify_docker() {
             ui::print_warning(&format!("Skipping integration tests: {}", e));
         } else {
-            let result = run_backend_integration_tests(&backend_dir, filter.as_deref());
+            let result = run_backend_integration_tests(
+                &backend_dir,
+                Some(&integration_targets),
+                filter.as_deref(),
+                &quarantine,
+                collect.then_some(&mut results),
+            );
             if result.is_err() {
                 all_passed = false;
                 if fail_fast {
+                    write_junit(&results, junit.as_deref(), "test-pyramid")?;
                     return result;
                 }
                 ui::print_error("Backend integration tests failed");
@@ -322,10 +422,18 @@ pub fn run_test_pyramid(
                 ui::print_warning(&format!("Failed to prepare E2E images: {}", e));
                 ui::print_info("Skipping E2E tests");
             } else {
-                let result = run_e2e_tests(Some(repo_root.clone()), true, filter.clone());
+                let result = run_e2e_tests(
+                    Some(repo_root.clone()),
+                    true,
+                    filter.clone(),
+                    Some(&e2e_targets),
+                    &quarantine,
+                    collect.then_some(&mut results),
+                );
                 if result.is_err() {
                     all_passed = false;
                     if fail_fast {
+                        write_junit(&results, junit.as_deref(), "test-pyramid")?;
                         return result;
                     }
                     ui::print_error("E2E tests failed");
@@ -334,10 +442,18 @@ pub fn run_test_pyramid(
                 }
             }
         } else {
-            let result = run_e2e_tests(Some(repo_root.clone()), true, filter.clone());
+            let result = run_e2e_tests(
+                Some(repo_root.clone()),
+                true,
+                filter.clone(),
+                Some(&e2e_targets),
+                &quarantine,
+                collect.then_some(&mut results),
+            );
             if result.is_err() {
                 all_passed = false;
                 if fail_fast {
+                    write_junit(&results, junit.as_deref(), "test-pyramid")?;
                     return result;
                 }
                 ui::print_error("E2E tests failed");
@@ -351,29 +467,143 @@ pub fn run_test_pyramid(
     // Summary
     println!();
     ui::print_header("Test Pyramid Summary");
+    let today = test_quarantine::today();
+    if collect {
+        quarantine.apply(&mut results, today);
+        results.print_summary();
+        write_junit(&results, junit.as_deref(), "test-pyramid")?;
+        if durations.is_some() {
+            history.record(&results);
+            history.save(&durations_path)?;
+        }
+    }
     if all_passed {
         ui::print_success("All test levels passed!");
     } else {
         bail!("Some tests failed");
     }
 
-    Ok(())
+    quarantine.check_expiry(today)
+}
+
+/// Report suite (and shard unit) names of the pyramid's levels
+const BACKEND_UNIT_SUITE: &str = "backend-unit";
+const FRONTEND_UNIT_SUITE: &str = "frontend-unit";
+const BACKEND_INTEGRATION_SUITE: &str = "backend-integration";
+const E2E_SUITE: &str = "e2e";
+
+/// Backend test targets run when the `tests/` directory has none of a level
+const INTEGRATION_TARGET: &str = "integration_tests";
+const E2E_TARGET: &str = "e2e_tests";
+
+/// Cargo test targets of the backend's integration and E2E levels: its
+/// `tests/` targets, the `e2e*` ones being E2E.
+fn backend_test_targets(backend_dir: &str) -> (Vec<String>, Vec<String>) {
+    let (e2e, integration): (Vec<String>, Vec<String>) =
+        test_sharding::find_rust_test_targets(Path::new(backend_dir))
+            .into_iter()
+            .partition(|target| target.starts_with("e2e"));
+    let or_default = |targets: Vec<String>, default: &str| {
+        if targets.is_empty() {
+            vec![default.to_string()]
+        } else {
+            targets
+        }
+    };
+    (
+        or_default(integration, INTEGRATION_TARGET),
+        or_default(e2e, E2E_TARGET),
+    )
+}
+
+/// `cargo test` arguments selecting `targets` with the integration feature
+fn target_args(targets: &[String]) -> Vec<String> {
+    let mut args = vec!["test".to_string()];
+    for target in targets {
+        args.push("--test".to_string());
+        args.push(target.clone());
+    }
+    args.extend(["--features".to_string(), "integration-tests".to_string()]);
+    args
+}
+
+/// Write collected results to the `--junit` path, if one was given
+fn write_junit(results: &TestReport, junit: Option<&str>, name: &str) -> Result<()> {
+    match junit {
+        Some(path) => results.write_junit(Path::new(path), name),
+        None => Ok(()),
+    }
+}
+
+/// Run the quarantined tests of a cargo test step on their own, with
+/// `--no-fail-fast`, after the blocking run skipped them. Their failures do
+/// not fail the step. `cmd` is the step's `cargo test` command up to `--`.
+fn run_quarantined_cargo_tests(
+    mut cmd: Command,
+    libtest_args: &[&str],
+    suite: &str,
+    op: &str,
+    quarantine: &Quarantine,
+    results: Option<&mut TestReport>,
+) -> Result<()> {
+    let names = quarantine.libtest_names(suite);
+    if names.is_empty() {
+        return Ok(());
+    }
+    ui::print_info(&format!(
+        "Running {} quarantined test(s) (non-blocking)",
+        names.len()
+    ));
+    cmd.arg("--no-fail-fast")
+        .arg("--")
+        .args(libtest_args)
+        .args(&names);
+
+    let mut scratch = TestReport::default();
+    let results = results.unwrap_or(&mut scratch);
+    let before = results.cases.len();
+    let outcome = results.record_tests(cmd.output(), suite, &format!("{} (quarantined)", op));
+    quarantine.settle_quarantined_run(outcome, &results.cases[before..])
 }
 
 /// Run backend unit tests
-fn run_backend_unit_tests(backend_dir: &str, filter: Option<&str>) -> Result<()> {
+///
+/// With `results`, the output is captured and parsed into per-test results.
+/// Quarantined tests are skipped, then run on their own without blocking.
+fn run_backend_unit_tests(
+    backend_dir: &str,
+    filter: Option<&str>,
+    quarantine: &Quarantine,
+    mut results: Option<&mut TestReport>,
+) -> Result<()> {
     ui::print_info("Running cargo test --lib");
 
     let cargo = cargo_bin();
-    let mut cmd = Command::new(&cargo);
-    cmd.current_dir(backend_dir).arg("test").arg("--lib");
-
-    if let Some(f) = filter {
-        cmd.arg(f);
-    }
+    let cargo_test = || {
+        let mut cmd = Command::new(&cargo);
+        cmd.current_dir(backend_dir).arg("test").arg("--lib");
+        cmd
+    };
+    let mut cmd = cargo_test();
+    cmd.arg("--")
+        .args(filter)
+        .args(quarantine.libtest_skip_args(BACKEND_UNIT_SUITE));
 
     let start = Instant::now();
-    let outcome = run_inherited_status_sync(cmd, "Backend unit tests");
+    let outcome = match results.as_deref_mut() {
+        Some(results) => {
+            results.record_output(cmd.output(), BACKEND_UNIT_SUITE, "Backend unit tests")
+        }
+        None => run_inherited_status_sync(cmd, "Backend unit tests"),
+    };
+    let quarantined = run_quarantined_cargo_tests(
+        cargo_test(),
+        &[],
+        BACKEND_UNIT_SUITE,
+        "Backend unit tests",
+        quarantine,
+        results,
+    );
     let elapsed = start.elapsed();
 
     ui::print_info(&format!(
@@ -381,15 +611,63 @@ fn run_backend_unit_tests(backend_dir: &str, filter: Option<&str>) -> Result<()>
         elapsed.as_secs_f64()
     ));
 
-    outcome
+    outcome.and(quarantined)
 }
 
 /// Run frontend unit tests
+///
+/// With `results`, vitest also writes a JUnit report to a scratch file that
+/// is merged into `results` afterwards. Non-empty `files` restricts the run
+/// to those test files (relative to `web_dir`).
 fn run_frontend_unit_tests(
     web_dir: &str,
     filter: Option<&str>,
+    files: &[String],
     report: bool,
     report_path: Option<&str>,
+    results: Option<&mut TestReport>,
+) -> Result<()> {
+    let junit_dir = match results {
+        Some(_) => Some(
+            tempfile::Builder::new()
+                .prefix("forge-vitest-junit-")
+                .tempdir()
+                .context("create vitest JUnit scratch tempdir")?,
+        ),
+        None => None,
+    };
+    let junit_file = junit_dir.as_ref().map(|dir| dir.path().join("junit.xml"));
+    let start = Instant::now();
+    let outcome = run_frontend_unit_tests_with(
+        web_dir,
+        filter,
+        files,
+        report,
+        report_path,
+        junit_file.as_deref(),
+    );
+
+    if let (Some(results), Some(junit_file)) = (results, junit_file) {
+        if !results.merge_junit_file(&junit_file, FRONTEND_UNIT_SUITE)? {
+            results.record_step(
+                FRONTEND_UNIT_SUITE,
+                "Frontend unit tests",
+                outcome.is_ok(),
+                start.elapsed().as_secs_f64(),
+                outcome.as_ref().err().map(|e| format!("{:#}", e)),
+            );
+        }
+    }
+    outcome
+}
+
+fn run_frontend_unit_tests_with(
+    web_dir: &str,
+    filter: Option<&str>,
+    files: &[String],
+    report: bool,
+    report_path: Option<&str>,
+    junit_file: Option<&Path>,
 ) -> Result<()> {
     if report {
         // Determine report path
@@ -413,11 +691,16 @@ fn run_frontend_unit_tests(
             .arg("--run")
             .arg("--reporter=default")
             .arg("--reporter=json")
-            .arg(format!("--outputFile={}", output_file));
+            .arg(format!("--outputFile.json={}", output_file));
+        if let Some(junit_file) = junit_file {
+            cmd.arg("--reporter=junit")
+                .arg(format!("--outputFile.junit={}", junit_file.display()));
+        }
 
         if let Some(f) = filter {
             cmd.arg(f);
         }
+        cmd.args(files);
 
         let start = Instant::now();
         let outcome = run_inherited_status_sync(cmd, "Frontend unit tests");
@@ -441,10 +724,16 @@ fn run_frontend_unit_tests(
             .arg("test")
             .arg("--")
             .arg("--run");
+        if let Some(junit_file) = junit_file {
+            cmd.arg("--reporter=default")
+                .arg("--reporter=junit")
+                .arg(format!("--outputFile.junit={}", junit_file.display()));
+        }
 
         if let Some(f) = filter {
             cmd.arg(f);
         }
+        cmd.args(files);
 
         let start = Instant::now();
         let outcome = run_inherited_status_sync(cmd, "Frontend unit tests");
@@ -462,26 +751,59 @@ fn run_frontend_unit_tests(
 }
 
 /// Run backend integration tests
-fn run_backend_integration_tests(backend_dir: &str, filter: Option<&str>) -> Result<()> {
-    let mut args = vec![
-        "test",
-        "--test",
-        "integration_tests",
-        "--features",
-        "integration-tests",
-    ];
-    if let Some(f) = filter {
-        args.push("--");
-        args.push(f);
+///
+/// Runs `targets`, or every integration test target when `None`.
+/// With `results`, the output is captured and parsed into per-test results.
+/// Quarantined tests are skipped, then run on their own without blocking.
+fn run_backend_integration_tests(
+    backend_dir: &str,
+    targets: Option<&[String]>,
+    filter: Option<&str>,
+    quarantine: &Quarantine,
+    mut results: Option<&mut TestReport>,
+) -> Result<()> {
+    let args = match targets {
+        Some(targets) => target_args(targets),
+        None => target_args(&backend_test_targets(backend_dir).0),
+    };
+    let mut test_args: Vec<String> = filter.into_iter().map(String::from).collect();
+    test_args.extend(quarantine.libtest_skip_args(BACKEND_INTEGRATION_SUITE));
+
+    if test_args.is_empty() {
+        ui::print_info(&format!("Running cargo {}", args.join(" ")));
+    } else {
+        ui::print_info(&format!(
+            "Running cargo {} -- {}",
+            args.join(" "),
+            test_args.join(" ")
+        ));
     }
 
-    ui::print_info(&format!("Running cargo {}", args.join(" ")));
-
     let start = Instant::now();
     let cargo = cargo_bin();
-    let mut cmd = Command::new(&cargo);
-    cmd.current_dir(backend_dir).args(&args);
-    let outcome = run_inherited_status_sync(cmd, "Backend integration tests");
+    let cargo_test = || {
+        let mut cmd = Command::new(&cargo);
+        cmd.current_dir(backend_dir).args(&args);
+        cmd
+    };
+    let mut cmd = cargo_test();
+    cmd.arg("--").args(&test_args);
+    let outcome = match results.as_deref_mut() {
+        Some(results) => results.record_output(
+            cmd.output(),
+            BACKEND_INTEGRATION_SUITE,
+            "Backend integration tests",
+        ),
+        None => run_inherited_status_sync(cmd, "Backend integration tests"),
+    };
+    let quarantined = run_quarantined_cargo_tests(
+        cargo_test(),
+        &[],
+        BACKEND_INTEGRATION_SUITE,
+        "Backend integration tests",
+        quarantine,
+        results,
+    );
     let elapsed = start.elapsed();
 
     ui::print_info(&format!(
@@ -489,7 +811,7 @@ fn run_backend_integration_tests(backend_dir: &str, filter: Option<&str>) -> Res
         elapsed.as_secs_f64()
     ));
 
-    outcome
+    outcome.and(quarantined)
 }
 
 /// Prepare E2E test images by building them via Nix and loading into Docker
@@ -550,10 +872,17 @@ pub fn prepare_e2e_images(
 }
 
 /// Run E2E tests with full-stack testcontainers
+///
+/// Runs `targets`, or every E2E test target when `None`.
+/// With `results`, the output is captured and parsed into per-test results.
+/// Quarantined tests are skipped, then run on their own without blocking.
 pub fn run_e2e_tests(
     repo_root: Option<String>,
     headless: bool,
     filter: Option<String>,
+    targets: Option<&[String]>,
+    quarantine: &Quarantine,
+    mut results: Option<&mut TestReport>,
 ) -> Result<()> {
     let repo_root = resolve_repo_root(repo_root)?;
 
@@ -581,23 +910,20 @@ pub fn run_e2e_tests(
     cleanup_testcontainers()?;
 
     // Build the cargo command
-    let mut args = vec![
-        "test",
-        "--test",
-        "e2e_tests",
-        "--features",
-        "integration-tests",
-        "--",
-        "--include-ignored",
-    ];
-    let filter_owned;
-    if let Some(f) = &filter {
-        filter_owned = f.clone();
-        args.push(&filter_owned);
-    }
+    let args = match targets {
+        Some(targets) => target_args(targets),
+        None => target_args(&backend_test_targets(&backend_dir).1),
+    };
+    let mut test_args = vec!["--include-ignored".to_string()];
+    test_args.extend(filter);
+    test_args.extend(quarantine.libtest_skip_args(E2E_SUITE));
 
     ui::print_info("Running E2E tests");
-    println!("  Command: cargo {}", args.join(" "));
+    println!(
+        "  Command: cargo {} -- {}",
+        args.join(" "),
+        test_args.join(" ")
+    );
     println!("  Dir:     {}", backend_dir);
     println!("  Headless: {}", headless);
     println!();
@@ -605,17 +931,34 @@ pub fn run_e2e_tests(
     let start = Instant::now();
 
     let cargo = cargo_bin();
-    let mut cmd = Command::new(&cargo);
-    cmd.current_dir(&backend_dir).args(&args);
+    let cargo_test = || {
+        let mut cmd = Command::new(&cargo);
+        cmd.current_dir(&backend_dir).args(&args);
 
-    // Set headless mode
-    if headless {
-        cmd.env("E2E_HEADLESS", "1");
-    } else {
-        cmd.env_remove("E2E_HEADLESS");
-    }
-
-    let outcome = run_inherited_status_sync(cmd, "E2E tests");
+        // Set headless mode
+        if headless {
+            cmd.env("E2E_HEADLESS", "1");
+        } else {
+            cmd.env_remove("E2E_HEADLESS");
+        }
+        cmd
+    };
+    let mut cmd = cargo_test();
+    cmd.arg("--").args(&test_args);
+
+    let outcome = match results.as_deref_mut() {
+        Some(results) => results.record_output(cmd.output(), E2E_SUITE, "E2E tests"),
+        None => run_inherited_status_sync(cmd, "E2E tests"),
+    };
+    let quarantined = run_quarantined_cargo_tests(
+        cargo_test(),
+        &["--include-ignored"],
+        E2E_SUITE,
+        "E2E tests",
+        quarantine,
+        results,
+    );
+    let outcome = outcome.and(quarantined);
     let elapsed = start.elapsed();
 
     // Post-cleanup: remove containers regardless of pass/fail
@@ -1001,12 +1344,16 @@ fn print_failure_diagnostics() {
 
 /// Run unit tests only (backend + frontend)
 /// No external dependencies required.
+///
+/// With `junit`, backend and frontend results are merged into one JUnit XML
+/// file at that path. Quarantined backend failures do not fail the run.
 pub fn run_unit_tests(
     repo_root: Option<String>,
     filter: Option<String>,
     skip_frontend: bool,
     report: bool,
     report_path: Option<String>,
+    junit: Option<String>,
 ) -> Result<()> {
     let repo_root = resolve_repo_root(repo_root)?;
     // TODO: derive product paths from deploy.yaml config
@@ -1016,16 +1363,38 @@ pub fn run_unit_tests(
     ui::print_header("Unit Tests");
     println!();
 
+    let quarantine = Quarantine::load(Path::new(&repo_root))?;
+    let today = test_quarantine::today();
+    let mut results = TestReport::default();
+    let collect = junit.is_some() || !quarantine.is_empty();
+
     // Backend unit tests
     ui::print_info("Running backend unit tests");
-    run_backend_unit_tests(&backend_dir, filter.as_deref())?;
+    let backend = run_backend_unit_tests(
+        &backend_dir,
+        filter.as_deref(),
+        &quarantine,
+        collect.then_some(&mut results),
+    );
+    if backend.is_err() {
+        quarantine.apply(&mut results, today);
+        write_junit(&results, junit.as_deref(), "test-unit")?;
+    }
+    backend?;
     ui::print_success("Backend unit tests passed");
 
     // Frontend unit tests
     if !skip_frontend {
         println!();
         ui::print_info("Running frontend unit tests");
-        match run_frontend_unit_tests(&web_dir, filter.as_deref(), report, report_path.as_deref()) {
+        match run_frontend_unit_tests(
+            &web_dir,
+            filter.as_deref(),
+            &[],
+            report,
+            report_path.as_deref(),
+            collect.then_some(&mut results),
+        ) {
             Ok(_) => ui::print_success("Frontend unit tests passed"),
             Err(e) => {
                 ui::print_warning(&format!("Frontend unit tests skipped: {}", e));
@@ -1033,14 +1402,27 @@ pub fn run_unit_tests(
         }
     }
 
+    if collect {
+        quarantine.apply(&mut results, today);
+        println!();
+        results.print_summary();
+        write_junit(&results, junit.as_deref(), "test-unit")?;
+    }
+
     println!();
     ui::print_success("Unit tests complete!");
-    Ok(())
+    quarantine.check_expiry(today)
 }
 
 /// Run integration tests only
 /// Auto-starts Docker on macOS if not running.
-pub fn run_integration_tests(repo_root: Option<String>, filter: Option<String>) -> Result<()> {
+///
+/// With `junit`, per-test results are written as JUnit XML to that path.
+pub fn run_integration_tests(
+    repo_root: Option<String>,
+    filter: Option<String>,
+    junit: Option<String>,
+) -> Result<()> {
     let repo_root = resolve_repo_root(repo_root)?;
     // TODO: derive product paths from deploy.yaml config
     let backend_dir = format!("{}/services/rust/backend", repo_root);
@@ -1053,20 +1435,40 @@ pub fn run_integration_tests(repo_root: Option<String>, filter: Option<String>)
 
     // Run integration tests
     ui::print_info("Running backend integration tests");
-    run_backend_integration_tests(&backend_dir, filter.as_deref())?;
+    let quarantine = Quarantine::load(Path::new(&repo_root))?;
+    let today = test_quarantine::today();
+    let mut results = TestReport::default();
+    let collect = junit.is_some() || !quarantine.is_empty();
+    let outcome = run_backend_integration_tests(
+        &backend_dir,
+        None,
+        filter.as_deref(),
+        &quarantine,
+        collect.then_some(&mut results),
+    );
+    if collect {
+        quarantine.apply(&mut results, today);
+        println!();
+        results.print_summary();
+        write_junit(&results, junit.as_deref(), "test-integration")?;
+    }
+    outcome?;
 
     println!();
     ui::print_success("Integration tests complete!");
-    Ok(())
+    quarantine.check_expiry(today)
 }
 
 /// Run E2E tests with smart image preparation
 /// Auto-builds and loads images if missing.
+///
+/// With `junit`, per-test results are written as JUnit XML to that path.
 pub fn run_e2e_tests_smart(
     repo_root: Option<String>,
     headless: bool,
     filter: Option<String>,
     force_rebuild: bool,
+    junit: Option<String>,
 ) -> Result<()> {
     let repo_root = resolve_repo_root(repo_root)?;
 
@@ -1100,9 +1502,26 @@ pub fn run_e2e_tests_smart(
     }
 
     // Run E2E tests
-    run_e2e_tests(Some(repo_root), headless, filter)?;
-
-    Ok(())
+    let quarantine = Quarantine::load(Path::new(&repo_root))?;
+    let today = test_quarantine::today();
+    let mut results = TestReport::default();
+    let collect = junit.is_some() || !quarantine.is_empty();
+    let outcome = run_e2e_tests(
+        Some(repo_root),
+        headless,
+        filter,
+        None,
+        &quarantine,
+        collect.then_some(&mut results),
+    );
+    if collect {
+        quarantine.apply(&mut results, today);
+        println!();
+        results.print_summary();
+        write_junit(&results, junit.as_deref(), "test-e2e")?;
+    }
+    outcome?;
+    quarantine.check_expiry(today)
 }
 
 /// Ensure Docker daemon is running, auto-starting on macOS if needed
@@ -2193,3 +2612,34 @@ mod e2e_status_spawn_routing_tests {
         );
     }
 }
+
+#[cfg(test)]
+mod backend_test_target_tests {
+    use super::*;
+
+    #[test]
+    fn test_backend_test_targets_split_e2e_and_fall_back_to_defaults() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let dir = tmp.path().to_str().unwrap();
+        assert_eq!(
+            backend_test_targets(dir),
+            (
+                vec!["integration_tests".to_string()],
+                vec!["e2e_tests".to_string()]
+            )
+        );
+
+        let tests = tmp.path().join("tests");
+        std::fs::create_dir_all(tests.join("integration_tests")).unwrap();
+        std::fs::write(tests.join("integration_tests/main.rs"), "").unwrap();
+        std::fs::write(tests.join("billing.rs"), "").unwrap();
+        std::fs::write(tests.join("e2e_tests.rs"), "").unwrap();
+        let (integration, e2e) = backend_test_targets(dir);
+        assert_eq!(integration, ["billing", "integration_tests"]);
+        assert_eq!(e2e, ["e2e_tests"]);
+        assert_eq!(
+            target_args(&integration).join(" "),
+            "test --test billing --test integration_tests --features integration-tests"
+        );
+    }
+}
@@ -0,0 +1,610 @@
+//! Elasticsearch Migration Validation and Rehearsal
+//!
+//! The search service applies Elasticsearch migrations with
+//! `RUN_MODE=migrate_elasticsearch`: `.json` files, one REST request each,
+//! applied in file-name order:
+//!
+//! ```json
+//! { "method": "PUT", "path": "/_index_template/products", "body": { ... } }
+//! ```
+//!
+//! Validation replays the chain, tracking every index's and index template's
+//! mappings and settings, and flags:
+//!
+//! - Idempotency: `PUT /<index>` (fails once the index exists) and `POST`
+//!   requests other than `/_aliases`
+//! - Reindexing: a change to an existing field's type, analyzer or other
+//!   index-time attribute, or to a static setting (shards, analysis), which
+//!   existing documents cannot take in place
+//! - Manifest consistency, as for the SQL chains
+//! - Rollback compatibility: deletes and reindex-requiring changes (warnings)
+//!
+//! JSON has no comments, so a file opts out of a rule with a string such as
+//! `"_comment": "forge-lint: allow(idempotency)"`. Rehearsal sends the chain
+//! to a throwaway single-node Elasticsearch container.
+
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use tokio::fs;
+
+use super::migration_lint::allowed_rules;
+use super::migration_rehearsal::{migration_files, Container, MigrationKind};
+use super::migration_validation::{check_manifest, MigrationIssue, StoreValidationResult};
+
+/// How long to wait for the rehearsal cluster to turn yellow
+const READY_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Field attributes fixed at index time; changing one needs a reindex.
+const REINDEX_ATTRIBUTES: &[&str] = &[
+    "type",
+    "analyzer",
+    "normalizer",
+    "index",
+    "index_options",
+    "doc_values",
+    "store",
+    "format",
+    "similarity",
+    "term_vector",
+    "dims",
+    "element_type",
+];
+
+/// Index settings that can only be set when an index is created.
+const STATIC_SETTINGS: &[&str] = &[
+    "number_of_shards",
+    "number_of_routing_shards",
+    "codec",
+    "analysis",
+    "sort",
+];
+
+// =============================================================================
+// Data Structures
+// =============================================================================
+
+/// One migration file: a single REST request
+#[derive(Debug, Clone, Deserialize)]
+pub(super) struct EsRequest {
+    #[serde(default = "default_method")]
+    pub(super) method: String,
+    pub(super) path: String,
+    #[serde(default)]
+    pub(super) body: Option<Value>,
+}
+
+fn default_method() -> String {
+    "PUT".to_string()
+}
+
+/// What a request path addresses
+#[derive(Debug, PartialEq)]
+enum Target<'a> {
+    /// `/_index_template/<name>` or legacy `/_template/<name>`
+    Template(&'a str),
+    /// `/<index>`
+    Index(&'a str),
+    /// `/<index>/_mapping`
+    Mapping(&'a str),
+    /// `/<index>/_settings`
+    Settings(&'a str),
+    Other,
+}
+
+fn target(path: &str) -> Target<'_> {
+    let path = path.split('?').next().unwrap_or_default().trim_matches('/');
+    let segments: Vec<&str> = path.split('/').collect();
+    match segments.as_slice() {
+        ["_index_template" | "_template", name] => Target::Template(name),
+        [index] if !index.is_empty() && !index.starts_with('_') => Target::Index(index),
+        [index, "_mapping"] if !index.starts_with('_') => Target::Mapping(index),
+        [index, "_settings"] if !index.starts_with('_') => Target::Settings(index),
+        _ => Target::Other,
+    }
+}
+
+/// Mappings and settings of one index or template, flattened to dotted paths
+#[derive(Debug, Default, Clone)]
+struct Schema {
+    fields: BTreeMap<String, Map<String, Value>>,
+    settings: BTreeMap<String, Value>,
+}
+
+/// Findings for one migration file
+#[derive(Debug, Default)]
+pub(super) struct FileFindings {
+    pub(super) file: PathBuf,
+    pub(super) issues: Vec<MigrationIssue>,
+    pub(super) rollback_warnings: Vec<String>,
+}
+
+// =============================================================================
+// Schema Tracking
+// =============================================================================
+
+/// Flatten `properties` (and multi-field `fields`) into `parent.child` paths.
+/// A field without a type is an object.
+fn flatten_properties(
+    properties: &Value,
+    prefix: &str,
+    out: &mut BTreeMap<String, Map<String, Value>>,
+) {
+    let Some(properties) = properties.as_object() else {
+        return;
+    };
+    for (name, definition) in properties {
+        let path = if prefix.is_empty() {
+            name.clone()
+        } else {
+            format!("{}.{}", prefix, name)
+        };
+        let mut attributes = definition.as_object().cloned().unwrap_or_default();
+        let children = attributes.remove("properties");
+        let multi_fields = attributes.remove("fields");
+        attributes
+            .entry("type")
+            .or_insert_with(|| Value::String("object".to_string()));
+        out.insert(path.clone(), attributes);
+        for nested in children.iter().chain(multi_fields.iter()) {
+            flatten_properties(nested, &path, out);
+        }
+    }
+}
+
+/// Flatten settings to dotted keys without the `index.` prefix, so
+/// `{"index": {"number_of_shards": 3}}` and `{"number_of_shards": 3}` agree.
+fn flatten_settings(settings: &Value, prefix: &str, out: &mut BTreeMap<String, Value>) {
+    let Some(settings) = settings.as_object() else {
+        return;
+    };
+    for (key, value) in settings {
+        let path = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{}.{}", prefix, key)
+        };
+        match value {
+            Value::Object(_) => flatten_settings(value, &path, out),
+            _ => {
+                let key = path.strip_prefix("index.").unwrap_or(&path).to_string();
+                out.insert(key, value.clone());
+            }
+        }
+    }
+}
+
+/// Schema declared by an index-creation or template body. Composable
+/// templates nest it under `template`.
+fn schema_of(body: &Value) -> Schema {
+    let body = body.get("template").unwrap_or(body);
+    let mut schema = Schema::default();
+    if let Some(properties) = body.pointer("/mappings/properties") {
+        flatten_properties(properties, "", &mut schema.fields);
+    }
+    if let Some(settings) = body.get("settings") {
+        flatten_settings(settings, "", &mut schema.settings);
+    }
+    schema
+}
+
+/// Describe every change from `old` to `new` existing documents cannot take
+/// in place.
+fn reindex_changes(old: &Schema, new: &Schema) -> Vec<String> {
+    let mut changes = Vec::new();
+    for (path, new_attributes) in &new.fields {
+        let Some(old_attributes) = old.fields.get(path) else {
+            continue;
+        };
+        for attribute in REINDEX_ATTRIBUTES {
+            let (before, after) = (
+                old_attributes.get(*attribute),
+                new_attributes.get(*attribute),
+            );
+            if before != after {
+                changes.push(format!(
+                    "{} {}: {} → {}",
+                    path,
+                    attribute,
+                    before.map_or("unset".to_string(), Value::to_string),
+                    after.map_or("unset".to_string(), Value::to_string)
+                ));
+            }
+        }
+    }
+    for (key, value) in &new.settings {
+        let is_static = STATIC_SETTINGS
+            .iter()
+            .any(|s| key == s || key.starts_with(&format!("{}.", s)));
+        if is_static && old.settings.get(key).is_some_and(|old| old != value) {
+            changes.push(format!(
+                "static setting {}: {} → {}",
+                key, old.settings[key], value
+            ));
+        }
+    }
+    changes
+}
+
+// =============================================================================
+// Validation
+// =============================================================================
+
+/// Replay the chain in order, returning each file's findings.
+pub(super) fn analyze(migrations: &[(PathBuf, String)]) -> Result<Vec<FileFindings>> {
+    let mut templates: BTreeMap<String, Schema> = BTreeMap::new();
+    let mut indices: BTreeMap<String, Schema> = BTreeMap::new();
+    let mut all_findings = Vec::new();
+
+    for (file, content) in migrations {
+        let request: EsRequest = serde_json::from_str(content).with_context(|| {
+            format!("Failed to parse Elasticsearch migration {}", file.display())
+        })?;
+        let method = request.method.to_uppercase();
+        let body = request.body.clone().unwrap_or(Value::Null);
+        let allowed = allowed_rules(content);
+        let mut findings = FileFindings {
+            file: file.clone(),
+            ..Default::default()
+        };
+        let statement = format!("{} {}", method, request.path);
+        let mut reindex = |index: String, changes: Vec<String>, suggestion: &str| {
+            for change in changes {
+                findings.rollback_warnings.push(format!(
+                    "{}: {} changes {} — the previous release may query a mapping the cluster no longer has",
+                    file.display(),
+                    index,
+                    change
+                ));
+                findings.issues.push(MigrationIssue::ReindexRequired {
+                    file: file.clone(),
+                    index: index.clone(),
+                    reason: change,
+                    suggestion: suggestion.to_string(),
+                });
+            }
+        };
+
+        match (method.as_str(), target(&request.path)) {
+            ("PUT", Target::Template(name)) => {
+                let new = schema_of(&body);
+                if let Some(old) = templates.get(name) {
+                    reindex(
+                        format!("index template {}", name),
+                        reindex_changes(old, &new),
+                        "Existing indices keep the old mapping; roll over to a new index and reindex into it, or add a new field instead",
+                    );
+                }
+                templates.insert(name.to_string(), new);
+            }
+            ("PUT", Target::Index(name)) => {
+                if !allowed.contains("idempotency") {
+                    findings.issues.push(MigrationIssue::IdempotencyViolation {
+                        file: file.clone(),
+                        line_number: 1,
+                        statement: statement.clone(),
+                        suggestion: "Index creation fails once the index exists; create indices through an index template and an alias".to_string(),
+                    });
+                }
+                indices.insert(name.to_string(), schema_of(&body));
+            }
+            ("PUT", Target::Mapping(name)) => {
+                let mut update = Schema::default();
+                if let Some(properties) = body.get("properties") {
+                    flatten_properties(properties, "", &mut update.fields);
+                }
+                let index = indices.entry(name.to_string()).or_default();
+                let changes = reindex_changes(index, &update);
+                index.fields.extend(update.fields);
+                reindex(
+                    format!("index {}", name),
+                    changes,
+                    "Elasticsearch rejects in-place mapping changes; create a new index with the new mapping, reindex, then swap the alias",
+                );
+            }
+            ("PUT", Target::Settings(name)) => {
+                let mut update = Schema::default();
+                flatten_settings(&body, "", &mut update.settings);
+                let index = indices.entry(name.to_string()).or_default();
+                let changes = reindex_changes(index, &update);
+                index.settings.extend(update.settings);
+                reindex(
+                    format!("index {}", name),
+                    changes,
+                    "Static settings only apply at index creation; create a new index with the settings, reindex, then swap the alias",
+                );
+            }
+            ("DELETE", Target::Index(name)) => {
+                if !allowed.contains("rollback") {
+                    findings.rollback_warnings.push(format!(
+                        "{}: deletes index {} — the previous release cannot query it after a rollback",
+                        file.display(),
+                        name
+                    ));
+                }
+                indices.remove(name);
+            }
+            ("DELETE", Target::Template(name)) => {
+                if !allowed.contains("rollback") {
+                    findings.rollback_warnings.push(format!(
+                        "{}: deletes index template {} — indices the previous release creates get dynamic mappings",
+                        file.display(),
+                        name
+                    ));
+                }
+                templates.remove(name);
+            }
+            ("POST", _)
+                if !request.path.trim_end_matches('/').ends_with("/_aliases")
+                    && !allowed.contains("idempotency") =>
+            {
+                findings.issues.push(MigrationIssue::IdempotencyViolation {
+                    file: file.clone(),
+                    line_number: 1,
+                    statement: statement.clone(),
+                    suggestion: "POST requests repeat their effect when re-run; use PUT with an explicit id or name".to_string(),
+                });
+            }
+            _ => {}
+        }
+        all_findings.push(findings);
+    }
+    Ok(all_findings)
+}
+
+/// Read the chain's files with their contents, in order
+pub(super) async fn read_chain(files: &[PathBuf]) -> Result<Vec<(PathBuf, String)>> {
+    let mut migrations = Vec::new();
+    for file in files {
+        let content = fs::read_to_string(file)
+            .await
+            .with_context(|| format!("Failed to read: {}", file.display()))?;
+        migrations.push((file.clone(), content));
+    }
+    Ok(migrations)
+}
+
+/// Validate the Elasticsearch migration chain in `dir`
+pub async fn validate_elasticsearch_migrations(dir: &Path) -> Result<StoreValidationResult> {
+    println!(
+        "{}",
+        format!(
+            "Validating Elasticsearch migrations in {}...",
+            dir.display()
+        )
+        .bold()
+    );
+
+    let files = migration_files(dir, MigrationKind::Elasticsearch).await?;
+    let mut result = StoreValidationResult {
+        files_checked: files.len(),
+        ..Default::default()
+    };
+    if files.is_empty() {
+        println!("   No migration files found");
+        return Ok(result);
+    }
+    println!("   Found {} migration files", files.len());
+
+    for findings in analyze(&read_chain(&files).await?)? {
+        result.issues.extend(findings.issues);
+        result.rollback_warnings.extend(findings.rollback_warnings);
+    }
+    result.issues.extend(
+        check_manifest(dir, &files, "json", None, false)
+            .await?
+            .issues,
+    );
+
+    if result.issues.is_empty() {
+        println!("   {} All migrations valid", "✅".green());
+    } else {
+        println!("   {} Found {} issues", "❌".red(), result.issues.len());
+        for issue in &result.issues {
+            println!("\n   {}", issue.format());
+        }
+    }
+    for warning in &result.rollback_warnings {
+        println!("   {} {}", "⚠️".yellow(), warning);
+    }
+    Ok(result)
+}
+
+// =============================================================================
+// Rehearsal
+// =============================================================================
+
+/// A throwaway single-node Elasticsearch container
+pub(super) struct RehearsalElasticsearch {
+    container: Container,
+    client: reqwest::Client,
+}
+
+impl RehearsalElasticsearch {
+    pub(super) async fn start(image: &str, keep: bool) -> Result<Self> {
+        let env = [
+            "discovery.type=single-node".to_string(),
+            "xpack.security.enabled=false".to_string(),
+            "ES_JAVA_OPTS=-Xms512m -Xmx512m".to_string(),
+        ];
+        let container = Container::start(image, 9200, &env, keep, |port| {
+            format!("http://127.0.0.1:{}", port)
+        })
+        .await?;
+        let elasticsearch = Self {
+            container,
+            client: reqwest::Client::new(),
+        };
+
+        let health = format!(
+            "{}/_cluster/health?wait_for_status=yellow&timeout=1s",
+            elasticsearch.base_url()
+        );
+        let start = Instant::now();
+        loop {
+            let ready = elasticsearch
+                .client
+                .get(&health)
+                .send()
+                .await
+                .is_ok_and(|r| r.status().is_success());
+            if ready {
+                return Ok(elasticsearch);
+            }
+            if start.elapsed() >= READY_TIMEOUT {
+                bail!(
+                    "Elasticsearch container {} not ready after {}s",
+                    elasticsearch.container.id,
+                    READY_TIMEOUT.as_secs()
+                );
+            }
+            tokio::time::sleep(Duration::from_secs(1)).await;
+        }
+    }
+
+    pub(super) fn base_url(&self) -> String {
+        format!("http://127.0.0.1:{}", self.container.port)
+    }
+
+    /// Send one migration file's request
+    pub(super) async fn apply(&self, file: &Path, op: &str) -> Result<()> {
+        let content = fs::read_to_string(file)
+            .await
+            .with_context(|| format!("Failed to read: {}", file.display()))?;
+        let request: EsRequest = serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", file.display()))?;
+        let method = reqwest::Method::from_bytes(request.method.to_uppercase().as_bytes())
+            .with_context(|| {
+                format!("Invalid method '{}' in {}", request.method, file.display())
+            })?;
+        let url = format!(
+            "{}/{}",
+            self.base_url(),
+            request.path.trim_start_matches('/')
+        );
+        let mut builder = self.client.request(method, url);
+        if let Some(ref body) = request.body {
+            builder = builder.json(body);
+        }
+        let response = builder
+            .send()
+            .await
+            .with_context(|| format!("{} request failed", op))?;
+        let status = response.status();
+        if !status.is_success() {
+            let text = response.text().await.unwrap_or_default();
+            bail!("{} failed ({}): {}", op, status, text.trim());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chain(files: &[(&str, &str)]) -> Vec<(PathBuf, String)> {
+        files
+            .iter()
+            .map(|(name, content)| (PathBuf::from(name), content.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_target_classifies_paths() {
+        assert_eq!(
+            target("/_index_template/products"),
+            Target::Template("products")
+        );
+        assert_eq!(target("/products"), Target::Index("products"));
+        assert_eq!(target("products/_mapping"), Target::Mapping("products"));
+        assert_eq!(
+            target("/products/_settings?preserve_existing=true"),
+            Target::Settings("products")
+        );
+        assert_eq!(target("/_aliases"), Target::Other);
+    }
+
+    #[test]
+    fn test_analyze_detects_mapping_changes_requiring_reindex() {
+        let migrations = chain(&[
+            (
+                "001_products.json",
+                r#"{"path": "/products", "body": {"settings": {"index": {"number_of_shards": 1}},
+                    "mappings": {"properties": {"title": {"type": "text", "fields": {"raw": {"type": "keyword"}}},
+                    "price": {"type": "integer"}}}}}"#,
+            ),
+            (
+                "002_products_fields.json",
+                r#"{"path": "/products/_mapping", "body": {"properties": {
+                    "price": {"type": "scaled_float", "scaling_factor": 100},
+                    "brand": {"type": "keyword"}}}}"#,
+            ),
+            (
+                "003_products_shards.json",
+                r#"{"path": "/products/_settings", "body": {"number_of_shards": 3, "refresh_interval": "5s"}}"#,
+            ),
+        ]);
+        let findings = analyze(&migrations).unwrap();
+
+        assert!(matches!(
+            findings[0].issues.as_slice(),
+            [MigrationIssue::IdempotencyViolation { .. }]
+        ));
+        let reasons: Vec<&str> = findings[1..]
+            .iter()
+            .flat_map(|f| &f.issues)
+            .filter_map(|issue| match issue {
+                MigrationIssue::ReindexRequired { reason, .. } => Some(reason.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            reasons,
+            vec![
+                r#"price type: "integer" → "scaled_float""#,
+                "static setting number_of_shards: 1 → 3",
+            ]
+        );
+        assert_eq!(findings[1].rollback_warnings.len(), 1);
+    }
+
+    #[test]
+    fn test_analyze_template_changes_and_idempotent_requests() {
+        let migrations = chain(&[
+            (
+                "001_logs_template.json",
+                r#"{"method": "put", "path": "/_index_template/logs", "body": {"index_patterns": ["logs-*"],
+                    "template": {"mappings": {"properties": {"message": {"type": "text"}}}}}}"#,
+            ),
+            (
+                "002_logs_template.json",
+                r#"{"path": "/_index_template/logs", "body": {"index_patterns": ["logs-*"],
+                    "template": {"mappings": {"properties": {"message": {"type": "text", "analyzer": "english"},
+                    "level": {"type": "keyword"}}}}}}"#,
+            ),
+            (
+                "003_logs_alias.json",
+                r#"{"method": "POST", "path": "/_aliases", "body": {"actions": []}}"#,
+            ),
+            (
+                "004_reindex.json",
+                r#"{"_comment": "forge-lint: allow(idempotency)", "method": "POST", "path": "/_reindex", "body": {}}"#,
+            ),
+        ]);
+        let findings = analyze(&migrations).unwrap();
+
+        assert!(findings[0].issues.is_empty());
+        assert!(matches!(
+            findings[1].issues.as_slice(),
+            [MigrationIssue::ReindexRequired { index, reason, .. }]
+                if index == "index template logs" && reason.contains("message analyzer")
+        ));
+        assert!(findings[2].issues.is_empty());
+        assert!(findings[3].issues.is_empty());
+    }
+}
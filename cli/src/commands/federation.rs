@@ -5,8 +5,10 @@
 //! ## Architecture
 //!
 //! - **Schema Extraction**: Extract GraphQL schemas from Rust services
-//! - **Supergraph Composition**: Compose schemas using Apollo Rover
+//! - **Change Detection**: Block breaking subgraph changes before composition
+//! - **Supergraph Composition**: Compose schemas natively or using Apollo Rover
 //! - **Verification**: Pre and post-composition validation
+//! - **Operation Checks**: Validate client operations against the new supergraph
 //! - **GitOps**: Commit and push supergraph changes
 
 use anyhow::{anyhow, bail, Context, Result};
@@ -18,7 +20,7 @@ use tokio::process::Command;
 
 use crate::retry::run_inherited_status;
 
-use crate::config::DeployConfig;
+use crate::config::{Composer, DeployConfig};
 use crate::path_builder::PathBuilder;
 
 /// Resolve the `rover-fhs` binary path via `ROVER_FHS_BIN`, falling back to
@@ -66,6 +68,9 @@ fn rover_fhs_bin() -> String {
 /// - Validates schema content (size, expected types)
 /// - Provides detailed error messages
 ///
+/// The extracted schema is then diffed against the deployed supergraph, and
+/// unacknowledged breaking changes fail the release before composition.
+///
 /// # Configuration
 /// Schema extraction is configured in deploy.yaml:
 /// ```yaml
@@ -80,6 +85,7 @@ pub async fn extract_schema(_service: String, deploy_config: &DeployConfig) -> R
 
     // Use new schema validation module
     use crate::commands::schema_validation::extract_and_validate_schema;
+    use crate::commands::subgraph_changes::{check_subgraph_changes, Baseline};
 
     let result = extract_and_validate_schema(deploy_config).await?;
 
@@ -90,6 +96,17 @@ pub async fn extract_schema(_service: String, deploy_config: &DeployConfig) -> R
             println!("   Path: {}", extraction_result.schema_path.display());
             println!("   Size: {} bytes", extraction_result.schema_size);
             println!("   Types: {}", extraction_result.type_count);
+            println!();
+
+            // The router's supergraph is only rewritten by a federation
+            // update, so it still holds what is deployed even when the new
+            // subgraph file was committed earlier on this branch.
+            let baseline = Baseline::Supergraph {
+                path: deploy_config
+                    .supergraph_router_path()
+                    .context("Failed to compute supergraph router path from config")?,
+            };
+            check_subgraph_changes(deploy_config, &baseline, false).await?;
             Ok(())
         }
         None => {
@@ -99,9 +116,102 @@ pub async fn extract_schema(_service: String, deploy_config: &DeployConfig) -> R
     }
 }
 
+/// Compose the supergraph in-process
+async fn compose_native(subgraphs: &[(String, String, PathBuf)]) -> Result<Vec<u8>> {
+    use crate::commands::supergraph_compose::{compose, Subgraph};
+
+    println!(
+        "🔨 Composing supergraph schema from {} service(s)...",
+        subgraphs.len()
+    );
+    let mut inputs = Vec::new();
+    for (name, url, path) in subgraphs {
+        let sdl = tokio::fs::read_to_string(path)
+            .await
+            .with_context(|| format!("Failed to read subgraph schema: {}", path.display()))?;
+        inputs.push(Subgraph {
+            name: name.clone(),
+            url: url.clone(),
+            sdl,
+        });
+    }
+    let composition = compose(&inputs)?;
+    for hint in &composition.hints {
+        println!("   {} {}", "hint:".yellow(), hint);
+    }
+    Ok(composition.supergraph.into_bytes())
+}
+
+/// Compose the supergraph with `rover supergraph compose`
+async fn compose_with_rover(subgraphs: &[(String, String, PathBuf)]) -> Result<Vec<u8>> {
+    // Generate supergraph config YAML from subgraph schemas
+    println!("🔨 Generating supergraph configuration...");
+
+    // Use exact Federation version to avoid Rover warnings and ensure deterministic composition
+    // This should match the version Hive Router expects (Federation v2 compatible)
+    let mut supergraph_config = String::from("federation_version: =2.11.3\nsubgraphs:\n");
+    for (service_name, routing_url, _) in subgraphs {
+        // Each subgraph entry in the config
+        supergraph_config.push_str(&format!(
+            "  {}:\n    routing_url: {}\n    schema:\n      file: ./subgraphs/{}.graphql\n",
+            service_name, routing_url, service_name
+        ));
+    }
+
+    // Write temporary supergraph config
+    let config_path = PathBuf::from("supergraph-config.yaml");
+    tokio::fs::write(&config_path, &supergraph_config)
+        .await
+        .context("Failed to write supergraph config")?;
+
+    println!(
+        "📋 Supergraph config generated with {} service(s)",
+        subgraphs.len()
+    );
+
+    // Run rover supergraph compose via rover-fhs (FHS environment for dynamic linking)
+    // Rover downloads pre-built supergraph binaries that need dynamic linking (not available in pure Nix)
+    println!("🔨 Composing supergraph schema with Rover...");
+    let rover = rover_fhs_bin();
+    let output = Command::new(&rover)
+        .args(&[
+            "supergraph",
+            "compose",
+            "--config",
+            "supergraph-config.yaml",
+            "--elv2-license",
+            "accept", // Accept ELv2 license for Federation spec
+        ])
+        .env("TMPDIR", "/tmp") // Ensure temp directory is accessible in FHS env
+        .output()
+        .await
+        .context("Failed to run rover-fhs")?;
+
+    // Clean up temporary config
+    let _ = tokio::fs::remove_file(&config_path).await;
+
+    // Check stderr first - Rover may output errors even on status 0
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if !stderr.is_empty() {
+        eprintln!("Rover stderr: {}", stderr);
+    }
+
+    if !output.status.success() {
+        bail!("Rover composition failed:\n{}", stderr);
+    }
+
+    // Check if we got valid output
+    if output.stdout.is_empty() {
+        bail!("Rover produced empty output. stderr:\n{}", stderr);
+    }
+
+    Ok(output.stdout)
+}
+
 /// Update GraphQL Federation supergraph for Hive Router
 ///
-/// Composes supergraph schema from all subgraph .graphql files using Rover.
+/// Composes supergraph schema from all subgraph .graphql files, natively or
+/// with Rover depending on the `composer` federation setting.
 /// This replaces the TypeScript federation-updater.ts script with native Rust.
 pub async fn update_federation(
     service: String,
@@ -144,7 +254,12 @@ pub async fn update_federation(
     println!("🔍 Running pre-composition validation...");
     let subgraphs_dir = PathBuf::from("subgraphs");
 
-    let pre_check = run_pre_composition_checks(&subgraphs_dir).await?;
+    let federation = deploy_config
+        .service
+        .federation
+        .as_ref()
+        .unwrap_or(&deploy_config.global.federation);
+    let pre_check = run_pre_composition_checks(&subgraphs_dir, federation.composer).await?;
 
     // Print all check results
     for check in &pre_check.checks {
@@ -162,14 +277,8 @@ pub async fn update_federation(
     println!("✅ {}", "Pre-composition validation passed".green());
     println!();
 
-    // Generate supergraph config YAML from subgraph schemas
-    println!("🔨 Generating supergraph configuration...");
-
-    // Collect all .graphql files
-    // Use exact Federation version to avoid Rover warnings and ensure deterministic composition
-    // This should match the version Hive Router expects (Federation v2 compatible)
-    let mut supergraph_config = String::from("federation_version: =2.11.3\nsubgraphs:\n");
-
+    // Collect all .graphql files with their routing URLs
+    let mut subgraphs = Vec::new();
     for entry in std::fs::read_dir(&subgraphs_dir)? {
         let entry = entry?;
         let path = entry.path();
@@ -177,11 +286,6 @@ pub async fn update_federation(
         if path.extension().and_then(|s| s.to_str()) == Some("graphql") {
             if let Some(service_name) = path.file_stem().and_then(|s| s.to_str()) {
                 // Build routing URL using config pattern (supports {service}, {product}, {environment}, {port}, {protocol})
-                let federation = deploy_config
-                    .service
-                    .federation
-                    .as_ref()
-                    .unwrap_or(&deploy_config.global.federation);
                 let routing_url = federation
                     .routing_url_pattern
                     .replace("{protocol}", &federation.protocol)
@@ -189,63 +293,20 @@ pub async fn update_federation(
                     .replace("{product}", &deploy_config.product.name)
                     .replace("{environment}", &deploy_config.product.environment)
                     .replace("{port}", &federation.port.to_string());
-
-                // Each subgraph entry in the config
-                supergraph_config.push_str(&format!(
-                    "  {}:\n    routing_url: {}\n    schema:\n      file: ./subgraphs/{}.graphql\n",
-                    service_name, routing_url, service_name
-                ));
+                subgraphs.push((service_name.to_string(), routing_url, path.clone()));
             }
         }
     }
+    subgraphs.sort();
 
-    // Write temporary supergraph config
-    let config_path = PathBuf::from("supergraph-config.yaml");
-    tokio::fs::write(&config_path, &supergraph_config)
-        .await
-        .context("Failed to write supergraph config")?;
-
-    println!(
-        "📋 Supergraph config generated with {} service(s)",
-        supergraph_config.matches("routing_url").count()
-    );
-
-    // Run rover supergraph compose via rover-fhs (FHS environment for dynamic linking)
-    // Rover downloads pre-built supergraph binaries that need dynamic linking (not available in pure Nix)
-    println!("🔨 Composing supergraph schema with Rover...");
-    let rover = rover_fhs_bin();
-    let output = Command::new(&rover)
-        .args(&[
-            "supergraph",
-            "compose",
-            "--config",
-            "supergraph-config.yaml",
-            "--elv2-license",
-            "accept", // Accept ELv2 license for Federation spec
-        ])
-        .env("TMPDIR", "/tmp") // Ensure temp directory is accessible in FHS env
-        .output()
-        .await
-        .context("Failed to run rover-fhs")?;
-
-    // Check stderr first - Rover may output errors even on status 0
-    let stderr = String::from_utf8_lossy(&output.stderr);
-    if !stderr.is_empty() {
-        eprintln!("Rover stderr: {}", stderr);
-    }
-
-    if !output.status.success() {
-        bail!("Rover composition failed:\n{}", stderr);
-    }
-
-    // Check if we got valid output
-    if output.stdout.is_empty() {
-        bail!("Rover produced empty output. stderr:\n{}", stderr);
-    }
+    let supergraph = match federation.composer {
+        Composer::Native => compose_native(&subgraphs).await?,
+        Composer::Rover => compose_with_rover(&subgraphs).await?,
+    };
 
     // Write composed supergraph schema
     let supergraph_path = PathBuf::from("supergraph.graphql");
-    tokio::fs::write(&supergraph_path, &output.stdout)
+    tokio::fs::write(&supergraph_path, &supergraph)
         .await
         .context("Failed to write supergraph schema")?;
 
@@ -281,6 +342,23 @@ pub async fn update_federation(
     println!("   Services included: {}", post_check.service_count);
     println!();
 
+    // CLIENT OPERATION CHECKS
+    // Validate the frontend's operations against the new supergraph and
+    // publish the persisted-query manifest alongside it
+    use crate::commands::operation_checks::{
+        check_client_operations, OperationSources, MANIFEST_FILE,
+    };
+
+    let sources = OperationSources::from_config(deploy_config)?;
+    check_client_operations(
+        &sources,
+        &supergraph_path,
+        Some(federation_dir.join(MANIFEST_FILE).as_path()),
+        false,
+    )
+    .await?;
+    println!();
+
     // Get git SHA for metadata tracking — routed through the
     // canonical `git::get_short_sha_async` primitive so a non-zero
     // exit at git surfaces as `Err` instead of silently producing an
@@ -295,15 +373,20 @@ pub async fn update_federation(
     use crate::commands::supergraph_verification::SupergraphMetadata;
     println!("🔐 Generating supergraph verification metadata...");
 
-    let metadata =
-        SupergraphMetadata::generate(&federation_dir, service.clone(), git_sha.clone()).await?;
+    let metadata = SupergraphMetadata::generate(
+        &federation_dir,
+        service.clone(),
+        git_sha.clone(),
+        federation.composer,
+    )
+    .await?;
 
     metadata.save(&federation_dir).await?;
 
     println!("✅ Metadata saved:");
     println!("   Hash: {}", &metadata.supergraph_hash[..16]);
     println!("   Services: {}", metadata.services.len());
-    println!("   Rover: {}", metadata.rover_version);
+    println!("   Composer: {}", metadata.rover_version);
 
     // Copy supergraph.graphql to hive-router kustomization directory for FluxCD
     // FluxCD/kustomize cannot load files from outside the kustomization directory
@@ -458,9 +541,6 @@ pub async fn update_federation(
         );
     }
 
-    // Clean up temporary config
-    let _ = tokio::fs::remove_file(&config_path).await;
-
     // Change to repo root for git operations (CRITICAL: paths are relative to repo root)
     env::set_current_dir(&repo_root)?;
 
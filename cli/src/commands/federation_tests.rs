@@ -25,11 +25,23 @@
 //! 2. GraphQL schema is extracted and federation is updated
 //! 3. Federation tests run against the updated Hive Router
 //! 4. Deployment succeeds only if tests pass (or tests disabled)
+//!
+//! ## Test Reports
+//!
+//! The job runs with `TEST_REPORT_FORMAT=junit`, and the federation-tests
+//! image prints its JUnit (or JSON) report to stdout between
+//! `REPORT_BEGIN_MARKER` and `REPORT_END_MARKER`. After the job finishes
+//! the report is read back from the pod logs, summarized per test, and
+//! stored as a local artifact under `target/reports/federation-tests/<service>/`
+//! in the product directory (or `report_dir`). Nothing is committed; CI
+//! keeps the directory between runs by caching or uploading it. The last
+//! `report_history` stored runs are kept, and a test that both passed and
+//! failed across them is reported as flaky.
 
 use anyhow::{bail, Context, Result};
 use colored::Colorize;
 use std::collections::BTreeMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Stdio;
 
 use k8s_openapi::api::batch::v1::{Job, JobSpec};
@@ -42,10 +54,17 @@ use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
 
 use std::time::Duration;
 
+use super::test_reports::{detect_flaky, FlakyTest, TestReport};
 use crate::config::DeployConfig;
 use crate::infrastructure::kubectl::kubectl_command_async;
 use crate::retry::RetryPolicy;
 
+/// Line the federation-tests image prints before its test report
+const REPORT_BEGIN_MARKER: &str = "=== FORGE TEST REPORT BEGIN ===";
+
+/// Line the federation-tests image prints after its test report
+const REPORT_END_MARKER: &str = "=== FORGE TEST REPORT END ===";
+
 /// The typed exponential-backoff policy for [`wait_for_job_completion`]
 /// federation-test k8s-Job status-poll cadence — `initial_backoff` 5s ×
 /// `factor` 2 capped at `max_backoff` 30s. Consumes the pre-existing
@@ -339,6 +358,67 @@ pub async fn run_federation_tests(
     // file`'s docstring for the two structural defects the RAII lift
     // closes over the pre-lift trailing-`remove_file` shape.
 
+    // Read the structured report back from the pod logs, summarize it and
+    // keep it with the previous runs for flaky-test detection
+    let report = match collect_job_report(&job_name, namespace).await {
+        Ok(Some(report)) => Some(report),
+        Ok(None) => {
+            println!(
+                "   ⚠️  {}",
+                "Job logs contain no structured test report".yellow()
+            );
+            None
+        }
+        Err(e) => {
+            eprintln!("   ⚠️  Could not collect test report: {:#}", e);
+            None
+        }
+    };
+    if let Some(report) = &report {
+        println!("   📊 {}", "Test results:".bold());
+        report.print_summary();
+
+        let run = FederationTestRun {
+            job_name: job_name.clone(),
+            service: service_name.to_string(),
+            suite: test_suite.to_string(),
+            environment: environment.to_string(),
+            git_sha: git_sha.to_string(),
+            finished_at: chrono::Utc::now().to_rfc3339(),
+            passed: wait_result.is_ok() && job_succeeded,
+            report: report.clone(),
+            flaky: Vec::new(),
+        };
+        let tests_config = &deploy_config.service.federation_tests;
+        let history = tests_config.report_history;
+        match record_test_run(
+            run,
+            product,
+            tests_config.report_dir.as_deref(),
+            &timestamp.to_string(),
+            history,
+        ) {
+            Ok(flaky) if !flaky.is_empty() => {
+                println!(
+                    "   ⚠️  {}",
+                    format!("Flaky tests (last {} runs):", history)
+                        .yellow()
+                        .bold()
+                );
+                for test in &flaky {
+                    println!(
+                        "      {} (failed {}, passed {})",
+                        test.id.yellow(),
+                        test.failures,
+                        test.passes
+                    );
+                }
+            }
+            Ok(_) => {}
+            Err(e) => eprintln!("   ⚠️  Could not store test report: {:#}", e),
+        }
+    }
+
     // Handle results
     match (wait_result, job_succeeded) {
         (Ok(()), true) => {
@@ -347,10 +427,21 @@ pub async fn run_federation_tests(
         }
         (wait_result, _) => {
             // Job timed out or failed - fetch logs for debugging
-            let failure_reason = match wait_result {
+            let mut failure_reason = match wait_result {
                 Err(e) => format!("Timeout: {}", e),
                 Ok(()) => "Job failed".to_string(),
             };
+            if let Some(report) = &report {
+                let failed: Vec<String> = report.failures().map(|c| c.id()).collect();
+                if !failed.is_empty() {
+                    failure_reason = format!(
+                        "{} ({} failed: {})",
+                        failure_reason,
+                        failed.len(),
+                        failed.join(", ")
+                    );
+                }
+            }
 
             println!(
                 "   ❌ {}",
@@ -588,6 +679,11 @@ fn create_federation_test_job(
             value: Some("info".to_string()),
             ..Default::default()
         },
+        EnvVar {
+            name: "TEST_REPORT_FORMAT".to_string(),
+            value: Some("junit".to_string()),
+            ..Default::default()
+        },
     ];
 
     // Build resource requirements
@@ -736,6 +832,159 @@ async fn wait_for_job_completion(
     }
 }
 
+/// One federation test run as stored under the product's report directory
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct FederationTestRun {
+    pub job_name: String,
+    pub service: String,
+    pub suite: String,
+    pub environment: String,
+    pub git_sha: String,
+    /// RFC 3339 timestamp of when the job finished
+    pub finished_at: String,
+    pub passed: bool,
+    pub report: TestReport,
+    /// Flaky tests across this run and the stored history before it
+    #[serde(default)]
+    pub flaky: Vec<FlakyTest>,
+}
+
+/// Read the job's logs and extract the test report printed between the markers
+async fn collect_job_report(job_name: &str, namespace: &str) -> Result<Option<TestReport>> {
+    let output = crate::retry::classify_capture_anyhow(
+        kubectl_command_async()
+            .args([
+                "logs",
+                "-n",
+                namespace,
+                &format!("job/{}", job_name),
+                "--tail=-1",
+            ])
+            .output()
+            .await,
+        "kubectl logs federation test job",
+    )?;
+
+    let logs = String::from_utf8_lossy(&output.stdout);
+    extract_report(&logs).map(TestReport::parse).transpose()
+}
+
+/// Text of the last report block in `logs`, without the marker lines
+fn extract_report(logs: &str) -> Option<&str> {
+    let begin = logs.rfind(REPORT_BEGIN_MARKER)?;
+    let body = &logs[begin + REPORT_BEGIN_MARKER.len()..];
+    let end = body.find(REPORT_END_MARKER)?;
+    Some(body[..end].trim())
+}
+
+/// Directory holding a service's stored federation test runs
+///
+/// `report_dir` (relative to the product directory) replaces the default
+/// `target/reports/federation-tests`.
+fn federation_reports_dir(
+    product_dir: &Path,
+    report_dir: Option<&str>,
+    service_name: &str,
+) -> PathBuf {
+    match report_dir {
+        Some(dir) => product_dir.join(dir),
+        None => product_dir
+            .join("target")
+            .join("reports")
+            .join("federation-tests"),
+    }
+    .join(service_name)
+}
+
+/// Load the most recent `limit` stored runs, oldest first
+///
+/// Files are named `<unix-timestamp>-<git-sha>.json`, so name order is run
+/// order. Files that fail to parse are skipped.
+fn load_test_runs(dir: &Path, limit: usize) -> Result<Vec<FederationTestRun>> {
+    let mut runs = Vec::new();
+    for path in stored_run_files(dir)?.into_iter().rev().take(limit) {
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        match serde_json::from_str(&content) {
+            Ok(run) => runs.push(run),
+            Err(e) => eprintln!("   ⚠️  Skipping {}: {}", path.display(), e),
+        }
+    }
+    runs.reverse();
+    Ok(runs)
+}
+
+/// Stored run files in `dir`, sorted by name
+fn stored_run_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut files: Vec<PathBuf> = std::fs::read_dir(dir)
+        .with_context(|| format!("Failed to read {}", dir.display()))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+/// Write `run` as `<file_stem>.json`, then delete the oldest runs beyond `keep`
+fn store_test_run(
+    dir: &Path,
+    file_stem: &str,
+    run: &FederationTestRun,
+    keep: usize,
+) -> Result<PathBuf> {
+    std::fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    let path = dir.join(format!("{}.json", file_stem));
+    let json = serde_json::to_string_pretty(run).context("Failed to serialize test run")?;
+    std::fs::write(&path, json + "\n")
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+
+    let files = stored_run_files(dir)?;
+    for stale in files.iter().take(files.len().saturating_sub(keep)) {
+        std::fs::remove_file(stale)
+            .with_context(|| format!("Failed to remove {}", stale.display()))?;
+    }
+    Ok(path)
+}
+
+/// Detect flaky tests against the stored history, then store `run`
+///
+/// Returns the flaky tests across `run` and up to `history - 1` earlier runs.
+/// With `history` 0 nothing is stored and only retries within `run` count.
+fn record_test_run(
+    mut run: FederationTestRun,
+    product: &str,
+    report_dir: Option<&str>,
+    timestamp: &str,
+    history: usize,
+) -> Result<Vec<FlakyTest>> {
+    let repo_root = crate::git::get_repo_root()?;
+    let product_dir = crate::config::resolve_product_dir(&repo_root, product);
+    let dir = federation_reports_dir(&product_dir, report_dir, &run.service);
+
+    let previous = load_test_runs(&dir, history.saturating_sub(1))?;
+    run.flaky = detect_flaky(
+        previous
+            .iter()
+            .map(|r| &r.report)
+            .chain(std::iter::once(&run.report)),
+    );
+    if history == 0 {
+        return Ok(run.flaky);
+    }
+
+    let path = store_test_run(
+        &dir,
+        &format!("{}-{}", timestamp, run.git_sha),
+        &run,
+        history,
+    )?;
+    println!("   📝 Stored test report: {}", path.display());
+    Ok(run.flaky)
+}
+
 /// Check if job succeeded
 async fn check_job_success(job_name: &str, namespace: &str) -> Result<bool> {
     let output = kubectl_command_async()
@@ -1310,4 +1559,71 @@ mod tests {
              the RAII lift closes. Offending: {stale_cleanup:#?}",
         );
     }
+
+    #[test]
+    fn test_extract_report_takes_the_last_marked_block() {
+        let logs = format!(
+            "starting\n{b}\n<testsuite name=\"a\"/>\n{e}\nretrying\n{b}\n<testsuites/>\n{e}\ndone\n",
+            b = REPORT_BEGIN_MARKER,
+            e = REPORT_END_MARKER
+        );
+        assert_eq!(extract_report(&logs), Some("<testsuites/>"));
+        assert_eq!(extract_report("no report here"), None);
+        assert_eq!(
+            extract_report(&format!("{}\n<testsuites>", REPORT_BEGIN_MARKER)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_federation_reports_dir_defaults_to_an_untracked_artifact_dir() {
+        let product = Path::new("/repo/products/shop");
+        assert_eq!(
+            federation_reports_dir(product, None, "auth"),
+            PathBuf::from("/repo/products/shop/target/reports/federation-tests/auth")
+        );
+        assert_eq!(
+            federation_reports_dir(product, Some("ci-artifacts/federation"), "auth"),
+            PathBuf::from("/repo/products/shop/ci-artifacts/federation/auth")
+        );
+    }
+
+    #[test]
+    fn test_store_test_run_prunes_to_history_and_loads_oldest_first() {
+        let dir = tempfile::tempdir().unwrap();
+        let run = |sha: &str| FederationTestRun {
+            job_name: format!("myapp-cart-federation-tests-{}", sha),
+            service: "cart".to_string(),
+            suite: "cart".to_string(),
+            environment: "staging".to_string(),
+            git_sha: sha.to_string(),
+            finished_at: "2026-01-01T00:00:00Z".to_string(),
+            passed: true,
+            report: TestReport::default(),
+            flaky: Vec::new(),
+        };
+
+        for (timestamp, sha) in [
+            (1700000001, "aaa"),
+            (1700000002, "bbb"),
+            (1700000003, "ccc"),
+        ] {
+            store_test_run(dir.path(), &format!("{}-{}", timestamp, sha), &run(sha), 2).unwrap();
+        }
+
+        let stored = stored_run_files(dir.path()).unwrap();
+        assert_eq!(stored.len(), 2);
+        let shas: Vec<String> = load_test_runs(dir.path(), 10)
+            .unwrap()
+            .into_iter()
+            .map(|r| r.git_sha)
+            .collect();
+        assert_eq!(shas, vec!["bbb", "ccc"]);
+
+        let latest = load_test_runs(dir.path(), 1).unwrap();
+        assert_eq!(latest[0].git_sha, "ccc");
+        assert!(load_test_runs(&dir.path().join("missing"), 5)
+            .unwrap()
+            .is_empty());
+    }
 }
@@ -0,0 +1,802 @@
+//! Fleet-wide status (`forge status --all`)
+//!
+//! Walks every product in the repository, every service listed in each
+//! product's `release.services`, and every active environment of those
+//! services, then renders a service × environment matrix per product:
+//!
+//! - deployed image tag (live Deployment) vs the tag the environment's own
+//!   overlay deploys (its rendered Deployment, the same kustomization the
+//!   GitOps commit updates), so environments promoted at different times
+//!   are each compared with what git holds for them
+//! - replica readiness and total container restarts
+//! - Flux readiness of the `{product}-{environment}` kustomization, from a
+//!   single `flux get kustomizations --all-namespaces` listing
+//!
+//! Kubernetes queries go to the current kubeconfig context through the
+//! typed kube client, reusing the fetch helpers in [`super::status`]. A
+//! cell whose namespace or Deployment cannot be read records the failure
+//! instead of aborting the whole matrix; `--format json` emits the same
+//! records for dashboards.
+
+use anyhow::{bail, Result};
+use colored::Colorize;
+use kube::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::Path;
+use tokio::task::JoinSet;
+
+use super::status::{self, OutputFormat};
+use crate::config::{self, DeployConfig, ProductConfig, ProductServiceConfig};
+use crate::error::KubernetesError;
+use crate::flux_get::{self, KustomizationRow};
+use crate::k8s;
+use crate::kustomize;
+
+// ============================================================================
+// Data Structures
+// ============================================================================
+
+/// Status of every product in the repository.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FleetStatus {
+    pub products: Vec<ProductFleetStatus>,
+    /// Set when the Flux listing failed; per-environment Flux cells are then empty.
+    pub flux_error: Option<String>,
+}
+
+/// One product's service × environment matrix.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProductFleetStatus {
+    pub product: String,
+    /// Union of the services' active environments, in first-seen order.
+    pub environments: Vec<String>,
+    pub services: Vec<ServiceFleetRow>,
+    pub flux: Vec<FluxCell>,
+    /// Set when the product's configuration could not be loaded.
+    pub error: Option<String>,
+}
+
+/// One service row of the matrix. Only environments the service is
+/// active in carry a cell.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServiceFleetRow {
+    pub service: String,
+    pub cells: Vec<FleetCell>,
+}
+
+/// Live state of one service in one environment.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FleetCell {
+    pub environment: String,
+    pub namespace: String,
+    pub deployment: String,
+    pub expected_tag: Option<String>,
+    pub deployed_tag: Option<String>,
+    /// `false` when the Deployment does not exist in the namespace.
+    pub found: bool,
+    pub ready_replicas: i32,
+    pub desired_replicas: i32,
+    pub restarts: i32,
+    /// Set when the apiserver query failed (RBAC, transport, ...).
+    pub error: Option<String>,
+    pub state: CellState,
+}
+
+/// Flux readiness of a product's `{product}-{environment}` kustomization.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FluxCell {
+    pub environment: String,
+    pub kustomization: String,
+    /// `None` when the kustomization is not present in the listing.
+    pub ready: Option<bool>,
+    pub revision: Option<String>,
+    pub message: Option<String>,
+}
+
+/// Classification of a [`FleetCell`], worst first.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CellState {
+    Error,
+    Missing,
+    Degraded,
+    Drifted,
+    #[default]
+    Healthy,
+}
+
+impl FleetCell {
+    /// Classify the cell: an API failure wins, then a missing Deployment,
+    /// then unready replicas, then a deployed tag that differs from the
+    /// expected one.
+    pub fn classify(&self) -> CellState {
+        if self.error.is_some() {
+            CellState::Error
+        } else if !self.found {
+            CellState::Missing
+        } else if self.ready_replicas < self.desired_replicas {
+            CellState::Degraded
+        } else if self.expected_tag.is_some() && self.deployed_tag != self.expected_tag {
+            CellState::Drifted
+        } else {
+            CellState::Healthy
+        }
+    }
+
+    /// Plain-text summary rendered inside a matrix cell.
+    pub fn summary(&self) -> String {
+        match self.state {
+            CellState::Error => "✗ error".to_string(),
+            CellState::Missing => "∅ missing".to_string(),
+            _ => {
+                let icon = match self.state {
+                    CellState::Healthy => "✓",
+                    CellState::Drifted => "≠",
+                    _ => "⚠",
+                };
+                let mut summary = format!(
+                    "{} {} {}/{}",
+                    icon,
+                    self.deployed_tag.as_deref().unwrap_or("?"),
+                    self.ready_replicas,
+                    self.desired_replicas
+                );
+                if self.restarts > 0 {
+                    summary.push_str(&format!(" ↻{}", self.restarts));
+                }
+                if self.state == CellState::Drifted {
+                    if let Some(expected) = &self.expected_tag {
+                        summary.push_str(&format!(" (want {expected})"));
+                    }
+                }
+                summary
+            }
+        }
+    }
+}
+
+impl FluxCell {
+    /// Plain-text summary rendered in the matrix's Flux row.
+    pub fn summary(&self) -> String {
+        match self.ready {
+            Some(true) => format!("✓ {}", self.revision.as_deref().unwrap_or("")),
+            Some(false) => format!("✗ {}", self.message.as_deref().unwrap_or("not ready")),
+            None => "- absent".to_string(),
+        }
+        .trim_end()
+        .to_string()
+    }
+}
+
+/// Where to look for one service in one environment. Owned so it can be
+/// moved into a spawned fetch task.
+#[derive(Debug, Clone)]
+struct CellTarget {
+    product_index: usize,
+    service_index: usize,
+    environment: String,
+    namespace: String,
+    deployment: String,
+    expected_tag: Option<String>,
+}
+
+// ============================================================================
+// Execute Command
+// ============================================================================
+
+/// Execute `forge status --all`
+pub async fn execute(repo_root: &str, format: OutputFormat) -> Result<()> {
+    let products = discover_products(Path::new(repo_root));
+    if products.is_empty() {
+        bail!(
+            "No products found under {}\n  \
+             Expected a root deploy.yaml with `name:` or pkgs/products/*/deploy.yaml",
+            repo_root
+        );
+    }
+
+    let (mut fleet, targets) = plan_fleet(repo_root, &products);
+
+    let client = k8s::create_client().await?;
+    let (cells, flux) = tokio::join!(
+        fetch_cells(&client, targets),
+        flux_get::list_kustomizations_all_namespaces()
+    );
+
+    for (target, cell) in cells {
+        fleet.products[target.product_index].services[target.service_index]
+            .cells
+            .push(cell);
+    }
+
+    match flux {
+        Ok(rows) => {
+            for product in &mut fleet.products {
+                product.flux = flux_cells(&product.product, &product.environments, &rows);
+            }
+        }
+        Err(e) => fleet.flux_error = Some(e.to_string()),
+    }
+
+    match format {
+        OutputFormat::Text => print_text_fleet(&fleet),
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&fleet)?),
+    }
+
+    Ok(())
+}
+
+// ============================================================================
+// Discovery
+// ============================================================================
+
+/// Every product in the repository: the standalone product named by the
+/// root `deploy.yaml` (via [`config::auto_discover_product`]) plus every
+/// `pkgs/products/*` directory carrying a `deploy.yaml`. Sorted, deduplicated.
+pub fn discover_products(repo_root: &Path) -> Vec<String> {
+    let mut products = Vec::new();
+
+    if let Ok(product) = config::auto_discover_product(&repo_root.to_string_lossy()) {
+        products.push(product);
+    }
+
+    if let Ok(entries) = std::fs::read_dir(repo_root.join("pkgs/products")) {
+        for entry in entries.flatten() {
+            if entry.path().join("deploy.yaml").is_file() {
+                if let Some(name) = entry.file_name().to_str() {
+                    products.push(name.to_string());
+                }
+            }
+        }
+    }
+
+    products.sort();
+    products.dedup();
+    products
+}
+
+/// Build the empty matrix skeleton and the list of cells to fetch, from
+/// configuration alone. A product whose config cannot be loaded keeps an
+/// `error` and contributes no targets.
+fn plan_fleet(repo_root: &str, products: &[String]) -> (FleetStatus, Vec<CellTarget>) {
+    let mut fleet = FleetStatus {
+        products: Vec::new(),
+        flux_error: None,
+    };
+    let mut targets = Vec::new();
+
+    for product in products {
+        let product_index = fleet.products.len();
+        let mut status = ProductFleetStatus {
+            product: product.clone(),
+            environments: Vec::new(),
+            services: Vec::new(),
+            flux: Vec::new(),
+            error: None,
+        };
+
+        let product_config = match DeployConfig::load_product_config(product, repo_root) {
+            Ok(c) => c,
+            Err(e) => {
+                status.error = Some(format!("{e:#}"));
+                fleet.products.push(status);
+                continue;
+            }
+        };
+        let services = product_config
+            .release
+            .as_ref()
+            .map(|r| r.services.clone())
+            .unwrap_or_default();
+        if services.is_empty() {
+            status.error = Some("no services configured in release.services".to_string());
+        }
+
+        let product_dir = config::resolve_product_dir(Path::new(repo_root), product);
+        for svc in &services {
+            let service_index = status.services.len();
+            status.services.push(ServiceFleetRow {
+                service: svc.name.clone(),
+                cells: Vec::new(),
+            });
+
+            let release = DeployConfig::load_service_release_config(product, &svc.path, repo_root)
+                .unwrap_or_default();
+            let service_config =
+                DeployConfig::load_for_service_dir(&svc.name, product_dir.join(&svc.path)).ok();
+            let deployment = service_deployment_name(&product_dir, svc);
+
+            for env in release.get_environments("all") {
+                if !status.environments.contains(&env) {
+                    status.environments.push(env.clone());
+                }
+                let expected_tag = service_config
+                    .as_ref()
+                    .and_then(|c| overlay_tag(c, &deployment, &env));
+                targets.push(CellTarget {
+                    product_index,
+                    service_index,
+                    namespace: service_namespace(&product_config, &svc.path, repo_root, &env),
+                    environment: env,
+                    deployment: deployment.clone(),
+                    expected_tag,
+                });
+            }
+        }
+
+        fleet.products.push(status);
+    }
+
+    (fleet, targets)
+}
+
+/// Image tag the environment's overlay deploys for `deployment`, or `None`
+/// when the overlay cannot be rendered.
+fn overlay_tag(service_config: &DeployConfig, deployment: &str, env: &str) -> Option<String> {
+    let path = service_config.k8s_manifest_path_for_env(env).ok()?;
+    rendered_deployment_tag(&kustomize::render(&path).ok()?, deployment)
+}
+
+/// Tag of the first container image of the rendered Deployment `name`.
+fn rendered_deployment_tag(objects: &[Value], name: &str) -> Option<String> {
+    objects
+        .iter()
+        .find(|o| {
+            o.get("kind").and_then(Value::as_str) == Some("Deployment")
+                && o.pointer("/metadata/name").and_then(Value::as_str) == Some(name)
+        })?
+        .pointer("/spec/template/spec/containers/0/image")
+        .and_then(Value::as_str)
+        .and_then(crate::oci_manifest::image_tag)
+        .map(str::to_string)
+}
+
+/// Deployment name for a product service: the release health check's
+/// `deployment`, else whatever the service's deploy.yaml declares.
+pub fn service_deployment_name(product_dir: &Path, svc: &ProductServiceConfig) -> String {
+    match &svc.health_check {
+        Some(hc) => hc.deployment.clone(),
+        None => {
+            let service_dir = product_dir.join(&svc.path);
+            let deploy_yaml =
+                config::resolve_deploy_yaml_path(product_dir, &svc.name, &service_dir);
+            status::configured_deployment_name(&deploy_yaml, &svc.name)
+        }
+    }
+}
+
+/// Namespace for a service in `env`: the service's `environments.{env}.namespace`,
+/// else the product convention [`ProductConfig::namespace_for_env`].
+pub fn service_namespace(
+    product_config: &ProductConfig,
+    service_path: &str,
+    repo_root: &str,
+    env: &str,
+) -> String {
+    DeployConfig::load_service_namespace(&product_config.name, service_path, repo_root, env)
+        .unwrap_or_else(|_| product_config.namespace_for_env(env))
+}
+
+// ============================================================================
+// Data Fetching
+// ============================================================================
+
+/// Fetch every cell concurrently, returning them in `targets` order.
+async fn fetch_cells(client: &Client, targets: Vec<CellTarget>) -> Vec<(CellTarget, FleetCell)> {
+    let mut tasks = JoinSet::new();
+    for (index, target) in targets.iter().cloned().enumerate() {
+        let client = client.clone();
+        tasks.spawn(async move { (index, fetch_cell(&client, &target).await) });
+    }
+
+    let mut cells: Vec<Option<FleetCell>> = vec![None; targets.len()];
+    while let Some(joined) = tasks.join_next().await {
+        if let Ok((index, cell)) = joined {
+            cells[index] = Some(cell);
+        }
+    }
+
+    targets
+        .into_iter()
+        .zip(cells)
+        .map(|(target, cell)| {
+            let cell = cell
+                .unwrap_or_else(|| cell_from(&target, Some("status task panicked".to_string())));
+            (target, cell)
+        })
+        .collect()
+}
+
+async fn fetch_cell(client: &Client, target: &CellTarget) -> FleetCell {
+    let (deployment, pods) = tokio::join!(
+        status::fetch_deployment(client, &target.namespace, &target.deployment),
+        status::fetch_pods(client, &target.namespace, &target.deployment),
+    );
+
+    match (deployment, pods) {
+        (Err(KubernetesError::DeploymentNotFound { .. }), _) => cell_from(target, None),
+        (Err(e), _) | (_, Err(e)) => cell_from(target, Some(e.to_string())),
+        (Ok(info), Ok(pods)) => {
+            let (pods, _) = status::extract_pod_and_container_info(&pods);
+            let mut cell = cell_from(target, None);
+            cell.found = true;
+            cell.deployed_tag = info.tag;
+            cell.ready_replicas = info.replicas.ready;
+            cell.desired_replicas = info.replicas.desired;
+            cell.restarts = pods.iter().map(|p| p.restarts).sum();
+            cell.state = cell.classify();
+            cell
+        }
+    }
+}
+
+fn cell_from(target: &CellTarget, error: Option<String>) -> FleetCell {
+    let mut cell = FleetCell {
+        environment: target.environment.clone(),
+        namespace: target.namespace.clone(),
+        deployment: target.deployment.clone(),
+        expected_tag: target.expected_tag.clone(),
+        error,
+        ..FleetCell::default()
+    };
+    cell.state = cell.classify();
+    cell
+}
+
+/// Match each environment's `{product}-{environment}` kustomization in a
+/// `flux get kustomizations --all-namespaces` listing.
+pub fn flux_cells(
+    product: &str,
+    environments: &[String],
+    rows: &[KustomizationRow],
+) -> Vec<FluxCell> {
+    environments
+        .iter()
+        .map(|env| {
+            let kustomization = format!("{product}-{env}");
+            let row = rows.iter().find(|r| r.name == kustomization);
+            FluxCell {
+                environment: env.clone(),
+                ready: row.map(KustomizationRow::is_ready),
+                revision: row.map(|r| r.revision.clone()),
+                message: row.map(|r| r.message.clone()).filter(|m| !m.is_empty()),
+                kustomization,
+            }
+        })
+        .collect()
+}
+
+// ============================================================================
+// Output
+// ============================================================================
+
+fn print_text_fleet(fleet: &FleetStatus) {
+    println!();
+    println!("{}", "Fleet Status".bright_cyan().bold());
+    if let Some(err) = &fleet.flux_error {
+        println!("  {} Flux readiness unavailable: {}", "⚠".yellow(), err);
+    }
+
+    for product in &fleet.products {
+        println!();
+        println!("{}", product.product.bright_white().bold());
+        if let Some(err) = &product.error {
+            println!("  {} {}", "✗".red(), err);
+        }
+        if product.services.is_empty() {
+            continue;
+        }
+        for line in render_matrix(product) {
+            println!("{line}");
+        }
+    }
+    println!();
+}
+
+/// Plain-text cell and the state it is colored by (`None` for an
+/// environment the row has no cell in).
+type MatrixCell = (String, Option<CellState>);
+
+/// Render one product's matrix as text lines (header, one row per service,
+/// then a Flux row). Cells are padded on their plain text before being
+/// colored so ANSI escapes do not skew the column widths.
+pub fn render_matrix(product: &ProductFleetStatus) -> Vec<String> {
+    let mut rows: Vec<(String, Vec<MatrixCell>)> = Vec::new();
+
+    for svc in &product.services {
+        let cells = product
+            .environments
+            .iter()
+            .map(
+                |env| match svc.cells.iter().find(|c| &c.environment == env) {
+                    Some(cell) => (cell.summary(), Some(cell.state)),
+                    None => ("-".to_string(), None),
+                },
+            )
+            .collect();
+        rows.push((svc.service.clone(), cells));
+    }
+    if !product.flux.is_empty() {
+        let cells = product
+            .environments
+            .iter()
+            .map(
+                |env| match product.flux.iter().find(|f| &f.environment == env) {
+                    Some(flux) => {
+                        let state = match flux.ready {
+                            Some(true) => CellState::Healthy,
+                            Some(false) => CellState::Degraded,
+                            None => CellState::Missing,
+                        };
+                        (flux.summary(), Some(state))
+                    }
+                    None => ("-".to_string(), None),
+                },
+            )
+            .collect();
+        rows.push(("flux".to_string(), cells));
+    }
+
+    let name_width = rows
+        .iter()
+        .map(|(name, _)| name.chars().count())
+        .chain(std::iter::once("SERVICE".len()))
+        .max()
+        .unwrap_or(0);
+    let widths: Vec<usize> = product
+        .environments
+        .iter()
+        .enumerate()
+        .map(|(i, env)| {
+            rows.iter()
+                .map(|(_, cells)| cells[i].0.chars().count())
+                .chain(std::iter::once(env.chars().count()))
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    let mut lines = Vec::new();
+    let mut header = format!("  {:<name_width$}", "SERVICE");
+    for (env, width) in product.environments.iter().zip(&widths) {
+        header.push_str(&format!("  {:<width$}", env.to_uppercase()));
+    }
+    lines.push(header.trim_end().bold().to_string());
+
+    for (name, cells) in rows {
+        let mut line = format!("  {:<name_width$}", name);
+        for ((text, state), width) in cells.iter().zip(&widths) {
+            let padded = format!("{:<width$}", text);
+            let colored = match state {
+                Some(CellState::Healthy) => padded.green(),
+                Some(CellState::Drifted) => padded.yellow(),
+                Some(CellState::Degraded) | Some(CellState::Missing) => padded.bright_yellow(),
+                Some(CellState::Error) => padded.red(),
+                None => padded.dimmed(),
+            };
+            line.push_str(&format!("  {colored}"));
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn healthy_cell() -> FleetCell {
+        FleetCell {
+            environment: "staging".to_string(),
+            namespace: "acme-staging".to_string(),
+            deployment: "api".to_string(),
+            expected_tag: Some("amd64-abc1234".to_string()),
+            deployed_tag: Some("amd64-abc1234".to_string()),
+            found: true,
+            ready_replicas: 2,
+            desired_replicas: 2,
+            ..FleetCell::default()
+        }
+    }
+
+    fn row(name: &str, ready: &str, revision: &str, message: &str) -> KustomizationRow {
+        KustomizationRow {
+            namespace: "flux-system".to_string(),
+            name: name.to_string(),
+            revision: revision.to_string(),
+            suspended: "False".to_string(),
+            ready: ready.to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    #[test]
+    fn discover_products_merges_root_product_and_pkgs_products() {
+        let root = tempfile::tempdir().unwrap();
+        std::fs::write(root.path().join("deploy.yaml"), "name: zeta\n").unwrap();
+        for product in ["acme", "beta"] {
+            let dir = root.path().join("pkgs/products").join(product);
+            std::fs::create_dir_all(&dir).unwrap();
+            std::fs::write(dir.join("deploy.yaml"), format!("name: {product}\n")).unwrap();
+        }
+        // A product directory without deploy.yaml is not a product.
+        std::fs::create_dir_all(root.path().join("pkgs/products/scratch")).unwrap();
+        // The standalone product may also live under pkgs/products; no duplicate.
+        let dup = root.path().join("pkgs/products/zeta");
+        std::fs::create_dir_all(&dup).unwrap();
+        std::fs::write(dup.join("deploy.yaml"), "name: zeta\n").unwrap();
+
+        assert_eq!(discover_products(root.path()), vec!["acme", "beta", "zeta"]);
+    }
+
+    #[test]
+    fn discover_products_is_empty_without_any_deploy_yaml() {
+        let root = tempfile::tempdir().unwrap();
+        assert!(discover_products(root.path()).is_empty());
+    }
+
+    #[test]
+    fn classify_orders_error_missing_degraded_drifted_healthy() {
+        let cell = healthy_cell();
+        assert_eq!(cell.classify(), CellState::Healthy);
+
+        let drifted = FleetCell {
+            deployed_tag: Some("amd64-0000000".to_string()),
+            ..healthy_cell()
+        };
+        assert_eq!(drifted.classify(), CellState::Drifted);
+
+        let degraded = FleetCell {
+            ready_replicas: 1,
+            ..drifted.clone()
+        };
+        assert_eq!(degraded.classify(), CellState::Degraded);
+
+        let missing = FleetCell {
+            found: false,
+            ..degraded.clone()
+        };
+        assert_eq!(missing.classify(), CellState::Missing);
+
+        let error = FleetCell {
+            error: Some("forbidden".to_string()),
+            ..missing
+        };
+        assert_eq!(error.classify(), CellState::Error);
+    }
+
+    #[test]
+    fn rendered_deployment_tag_reads_the_named_deployment() {
+        let objects = vec![
+            serde_json::json!({
+                "kind": "Service",
+                "metadata": {"name": "acme-api"}
+            }),
+            serde_json::json!({
+                "kind": "Deployment",
+                "metadata": {"name": "acme-worker"},
+                "spec": {"template": {"spec": {"containers": [
+                    {"image": "registry:5000/acme-worker:amd64-old"}
+                ]}}}
+            }),
+            serde_json::json!({
+                "kind": "Deployment",
+                "metadata": {"name": "acme-api"},
+                "spec": {"template": {"spec": {"containers": [
+                    {"image": "registry:5000/acme-api:amd64-abc1234"},
+                    {"image": "envoy:v1"}
+                ]}}}
+            }),
+        ];
+
+        assert_eq!(
+            rendered_deployment_tag(&objects, "acme-api").as_deref(),
+            Some("amd64-abc1234")
+        );
+        assert_eq!(rendered_deployment_tag(&objects, "acme-web"), None);
+    }
+
+    #[test]
+    fn classify_without_expected_tag_never_reports_drift() {
+        let cell = FleetCell {
+            expected_tag: None,
+            deployed_tag: Some("anything".to_string()),
+            ..healthy_cell()
+        };
+        assert_eq!(cell.classify(), CellState::Healthy);
+    }
+
+    #[test]
+    fn summary_shows_tag_readiness_restarts_and_wanted_tag() {
+        let mut cell = FleetCell {
+            deployed_tag: Some("amd64-0000000".to_string()),
+            restarts: 3,
+            ..healthy_cell()
+        };
+        cell.state = cell.classify();
+        assert_eq!(
+            cell.summary(),
+            "≠ amd64-0000000 2/2 ↻3 (want amd64-abc1234)"
+        );
+
+        let mut healthy = healthy_cell();
+        healthy.state = healthy.classify();
+        assert_eq!(healthy.summary(), "✓ amd64-abc1234 2/2");
+    }
+
+    #[test]
+    fn flux_cells_match_product_environment_kustomization() {
+        let rows = vec![
+            row("acme-staging", "True", "main@sha1:abc", "Applied revision"),
+            row(
+                "acme-staging-init",
+                "False",
+                "main@sha1:abc",
+                "init failing",
+            ),
+            row(
+                "acme-production",
+                "False",
+                "main@sha1:def",
+                "health check failed",
+            ),
+        ];
+        let envs = vec![
+            "staging".to_string(),
+            "production".to_string(),
+            "dev".to_string(),
+        ];
+
+        let cells = flux_cells("acme", &envs, &rows);
+
+        assert_eq!(cells[0].kustomization, "acme-staging");
+        assert_eq!(cells[0].ready, Some(true));
+        assert_eq!(cells[0].summary(), "✓ main@sha1:abc");
+        assert_eq!(cells[1].ready, Some(false));
+        assert_eq!(cells[1].summary(), "✗ health check failed");
+        assert_eq!(cells[2].ready, None);
+        assert_eq!(cells[2].summary(), "- absent");
+    }
+
+    #[test]
+    fn render_matrix_pads_columns_and_marks_inactive_environments() {
+        let mut staging = healthy_cell();
+        staging.state = staging.classify();
+        let product = ProductFleetStatus {
+            product: "acme".to_string(),
+            environments: vec!["staging".to_string(), "production".to_string()],
+            services: vec![
+                ServiceFleetRow {
+                    service: "api".to_string(),
+                    cells: vec![staging],
+                },
+                ServiceFleetRow {
+                    service: "web".to_string(),
+                    cells: Vec::new(),
+                },
+            ],
+            flux: Vec::new(),
+            error: None,
+        };
+
+        // Colours are process-global; strip them rather than turning them off
+        let ansi = regex::Regex::new(r"\x1b\[[0-9;]*m").unwrap();
+        let lines: Vec<String> = render_matrix(&product)
+            .iter()
+            .map(|l| ansi.replace_all(l, "").into_owned())
+            .collect();
+
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("  SERVICE  STAGING"));
+        assert!(lines[1].starts_with("  api      ✓ amd64-abc1234 2/2  -"));
+        assert!(lines[2].starts_with("  web      -"));
+        // Columns are aligned on characters, not bytes (the icons are multi-byte).
+        let char_col =
+            |line: &str, needle: &str| line[..line.find(needle).unwrap()].chars().count();
+        assert_eq!(
+            char_col(&lines[0], "PRODUCTION"),
+            char_col(&lines[1], "  -") + 2
+        );
+    }
+}
@@ -109,15 +109,13 @@ pub async fn health_check(context: &str) -> Result<()> {
     );
 
     // Route through the canonical `flux_get::list_kustomizations_all_namespaces`
-    // primitive so this site honors `FLUX_BIN` (via `get_tool_path("flux")`)
-    // and — on failure — surfaces the typed `(exit_code, stderr)` record. The
-    // outer `.context("Failed to run flux get kustomizations")?` wraps the
-    // typed error, so a telemetry consumer can still recover the typed
-    // variant across the anyhow boundary via
-    // `err.downcast_ref::<FluxGetKustomizationsError>()`.
+    // primitive, which reads Kustomization objects through the Kubernetes
+    // API. The outer `.context(...)` wraps the typed error, so a telemetry
+    // consumer can still recover the typed variant across the anyhow
+    // boundary via `err.downcast_ref::<FluxGetKustomizationsError>()`.
     let rows = list_kustomizations_all_namespaces()
         .await
-        .context("Failed to run flux get kustomizations")?;
+        .context("Failed to list Flux kustomizations")?;
 
     let (ready_count, failures) = partition_ready(&rows);
     let total = rows.len();
@@ -281,15 +279,9 @@ fn partition_ready(rows: &[KustomizationRow]) -> (usize, Vec<String>) {
 /// Err((ready_count, total_count, failures)) if any unhealthy
 async fn check_health_status() -> Result<(usize, usize), (usize, usize, Vec<String>)> {
     // Route through the canonical `flux_get::list_kustomizations_all_namespaces`
-    // primitive so this site honors `FLUX_BIN` (via `get_tool_path("flux")`)
-    // and — on failure — collapses the typed `(exit_code, stderr)` record
-    // into the pre-lift `(0, 0, vec![message])` tuple by rendering the
-    // typed error via `Display`. That preserves the pre-lift caller
-    // contract bit-for-bit while gaining the operator-visible exit code
-    // and stderr in the collapsed failure line: pre-lift both spawn-
-    // failure and op-failure surfaced as bare "Failed to run flux command"
-    // / "Failed to get FluxCD status" strings that dropped the stderr and
-    // the exit code.
+    // primitive and — on failure — collapse the typed error into the
+    // `(0, 0, vec![message])` tuple by rendering it via `Display`, which
+    // keeps the apiserver status and message in the operator-visible line.
     let rows = list_kustomizations_all_namespaces()
         .await
         .map_err(|e: FluxGetKustomizationsError| (0usize, 0usize, vec![e.to_string()]))?;
@@ -343,13 +335,10 @@ async fn reconcile_source() -> Result<()> {
     println!("   🔄 Reconciling git source...");
 
     // Route through the canonical `flux_reconcile::reconcile_source_git`
-    // primitive so this site honors `FLUX_BIN` (via
-    // `get_tool_path("flux")`) and — on failure — surfaces the typed
-    // `(source_name, namespace, exit_code, stderr)` record. Pre-lift
-    // `run_inherited_status` streamed flux's live progress to the
-    // operator; post-lift the primitive captures stderr and embeds it
-    // in the failure message, which is what the outer anyhow context
-    // ultimately needs.
+    // primitive, which annotates the GitRepository and waits for the new
+    // artifact; on failure it surfaces the typed
+    // `(source_name, namespace, source)` record with the controller's
+    // reason.
     crate::flux_reconcile::reconcile_source_git("flux-system", "flux-system")
         .await
         .context("Failed to reconcile FluxCD git source")?;
@@ -393,19 +382,12 @@ async fn reconcile_product_chain(namespace: &str) -> Result<()> {
         };
 
         // Route through the canonical `flux_get::get_kustomization_scoped`
-        // primitive so this site honors `FLUX_BIN` (via
-        // `get_tool_path("flux")`), reads the ready-boundary through
+        // primitive, reading the ready-boundary through
         // `KustomizationRow::is_ready` (one-oracle for the `== "True"`
-        // comparison this site pre-lift duplicated against the
-        // `--all-namespaces` sibling), and carries the typed
-        // `FluxGetKustomizationError` at the API boundary. Pre-lift the
-        // site fused spawn-failure and non-zero-exit into a single
-        // `_ => continue` arm; the primitive splits them structurally
-        // — `Ok(None)` for "kustomization doesn't exist" (silently
-        // skip, matching pre-lift), `Err(_)` for spawn-failure — and
-        // we preserve the pre-lift silent-skip on both by collapsing
-        // them at the `let-else` here so this commit's blast radius
-        // stays a pure refactor.
+        // comparison shared with the `--all-namespaces` sibling). The
+        // primitive splits "kustomization doesn't exist" (`Ok(None)`)
+        // from a failed lookup (`Err(_)`); both are skipped here, since
+        // a phase the chain cannot see is not one it can reconcile.
         let Ok(Some(row)) = get_kustomization_scoped(&ks_name, "flux-system").await else {
             continue;
         };
@@ -419,30 +401,25 @@ async fn reconcile_product_chain(namespace: &str) -> Result<()> {
         let phase_label = if phase.is_empty() { "app" } else { phase };
         println!("      ⏳ Reconciling {}...", phase_label);
 
-        // Route through the canonical `flux_reconcile` primitive so this
-        // site honors `FLUX_BIN` (via `get_tool_path("flux")`) and yields
-        // the typed `(kustomization, namespace, with_source, exit_code,
-        // stderr)` failure record on non-zero exit — best-effort warn
-        // preserved by matching on the typed variant.
+        // Route through the canonical `flux_reconcile` primitive: an
+        // unreachable cluster is fatal, while a reconcile that did not
+        // become ready is a best-effort warn carrying the controller's
+        // reason.
         match crate::flux_reconcile::reconcile_kustomization(&ks_name, "flux-system", false).await {
             Ok(()) => println!("      ✓ {}", phase_label.green()),
-            Err(e @ crate::flux_reconcile::FluxReconcileError::SpawnFailed { .. }) => {
+            Err(e @ crate::flux_reconcile::FluxReconcileError::ClientFailed { .. }) => {
                 return Err(anyhow::Error::new(e)
                     .context(format!("Failed to reconcile kustomization {}", ks_name)));
             }
-            Err(crate::flux_reconcile::FluxReconcileError::Failed {
-                exit_code, stderr, ..
-            }) => {
+            Err(crate::flux_reconcile::FluxReconcileError::Failed { source, .. }) => {
                 // Non-fatal: the kustomization might have a dependency not yet
                 // ready. The verify_deployment_image step will catch this
-                // downstream. Surface exit code + trimmed stderr so the operator
-                // has a real hint instead of a bare "returned non-zero".
+                // downstream. Surface the controller's reason so the operator
+                // has a real hint.
                 println!(
-                    "      ⚠ {} (reconcile returned non-zero, may need dependency; \
-                     exit={:?}): {}",
+                    "      ⚠ {} (reconcile did not become ready, may need dependency): {}",
                     phase_label.yellow(),
-                    exit_code,
-                    stderr.trim()
+                    source
                 );
             }
         }
@@ -456,12 +433,10 @@ async fn reconcile_product_chain(namespace: &str) -> Result<()> {
 async fn reconcile_kustomization() -> Result<()> {
     println!("   🔄 Reconciling kustomization...");
 
-    // Route through the canonical `flux_reconcile` primitive so this site honors
-    // `FLUX_BIN` (via `get_tool_path("flux")`) and — on failure — surfaces the
-    // typed `(kustomization, namespace, with_source, exit_code, stderr)` record.
-    // Pre-lift `run_inherited_status` streamed flux's live progress to the
-    // operator; post-lift the primitive captures stderr and embeds it in the
-    // failure message, which is what the outer anyhow context ultimately needs.
+    // Route through the canonical `flux_reconcile` primitive, which requests
+    // the reconcile via annotation and waits for the controller's outcome;
+    // on failure it surfaces the typed `(kustomization, namespace,
+    // with_source, source)` record.
     crate::flux_reconcile::reconcile_kustomization("flux-system", "flux-system", false)
         .await
         .context("Failed to reconcile root FluxCD kustomization")?;
@@ -927,10 +902,8 @@ pub async fn gather_deployment_diagnostics(namespace: &str, deployment_name: &st
     }
 
     // 8. Flux kustomization status for namespace — route through the
-    //    canonical `flux_get::list_kustomizations_in_namespace` primitive
-    //    (the last raw `Command::new("flux")` stanza in forge, redeemed
-    //    here) so this site honors `FLUX_BIN` (via `get_tool_path("flux")`),
-    //    reads `is_ready` through the one-oracle boundary
+    //    canonical `flux_get::list_kustomizations_in_namespace` primitive,
+    //    read `is_ready` through the one-oracle boundary
     //    `KustomizationRow` owns, and yields typed
     //    `FluxGetKustomizationsError` on failure. Best-effort diagnostic:
     //    if the primitive errors, we silently omit this subsection —
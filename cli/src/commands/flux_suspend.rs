@@ -0,0 +1,419 @@
+//! GitOps freeze for a product environment (`forge flux suspend|resume`)
+//!
+//! Suspends every Flux `Kustomization` and `HelmRelease` belonging to a
+//! product environment so an incident can be worked without Flux
+//! reverting hand-applied changes, and without editing YAML. An object
+//! belongs to the product when it lives in the product namespace
+//! ([`ProductConfig::namespace_for_env`]), or when its name is the
+//! namespace or `{product}-{environment}` (the naming the deploy
+//! `flux_commands` reconcile) optionally followed by a `-{phase}` suffix
+//! (`acme-staging-secrets`, `acme-staging-migrations`, ...).
+//!
+//! Who suspended the product, why and when are recorded as annotations on
+//! each suspended object, so `resume`, `product-release` and anyone reading
+//! the cluster see the same record. Release commands refuse to deploy to a
+//! suspended product: `product-release` and `orchestrate-release` per
+//! environment ([`ensure_not_suspended`]), `deploy` per namespace
+//! ([`ensure_namespace_not_suspended`]) and `helm deploy` per HelmRelease
+//! ([`ensure_release_not_suspended`], skipped with a warning when no
+//! cluster is configured, since a git-only deploy needs none).
+
+use anyhow::{bail, Context, Result};
+use chrono::{SecondsFormat, Utc};
+use colored::Colorize;
+use kube::Client;
+use serde_json::{json, Value};
+
+use crate::config::{DeployConfig, ProductConfig};
+use crate::error::KubernetesError;
+use crate::flux_api::{self, FluxKind, FluxObject};
+use crate::k8s;
+
+/// Namespace the root and per-product Flux Kustomizations live in.
+const FLUX_NAMESPACE: &str = "flux-system";
+
+pub const SUSPENDED_BY_ANNOTATION: &str = "forge.pleme.io/suspended-by";
+pub const SUSPENDED_REASON_ANNOTATION: &str = "forge.pleme.io/suspended-reason";
+pub const SUSPENDED_AT_ANNOTATION: &str = "forge.pleme.io/suspended-at";
+
+// ============================================================================
+// Data Structures
+// ============================================================================
+
+/// Who suspended an object and why, as recorded in its annotations.
+/// Objects suspended outside forge (`flux suspend`, a YAML edit) have no
+/// record.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Suspension {
+    pub object: String,
+    pub by: Option<String>,
+    pub reason: Option<String>,
+    pub at: Option<String>,
+}
+
+impl Suspension {
+    pub fn from_object(object: &FluxObject) -> Self {
+        let annotation = |key: &str| object.annotations.get(key).cloned();
+        Suspension {
+            object: object.display_name(),
+            by: annotation(SUSPENDED_BY_ANNOTATION),
+            reason: annotation(SUSPENDED_REASON_ANNOTATION),
+            at: annotation(SUSPENDED_AT_ANNOTATION),
+        }
+    }
+
+    /// `Kind/ns/name (by alice at <time>: <reason>)`
+    pub fn describe(&self) -> String {
+        match (&self.by, &self.reason) {
+            (None, None) => format!("{} (suspended outside forge)", self.object),
+            (by, reason) => format!(
+                "{} (by {} at {}: {})",
+                self.object,
+                by.as_deref().unwrap_or("unknown"),
+                self.at.as_deref().unwrap_or("unknown time"),
+                reason.as_deref().unwrap_or("no reason given")
+            ),
+        }
+    }
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Suspend every Flux object of `product` in `env`, recording `by` and
+/// `reason` on each.
+pub async fn suspend(
+    product: &str,
+    repo_root: &str,
+    env: &str,
+    reason: &str,
+    by: Option<String>,
+) -> Result<()> {
+    let product_config = DeployConfig::load_product_config(product, repo_root)?;
+    let by = by
+        .or_else(|| std::env::var("USER").ok())
+        .unwrap_or_else(|| "unknown".to_string());
+
+    let client = k8s::create_client().await?;
+    let objects = product_objects(&client, &product_config, env).await?;
+    if objects.is_empty() {
+        bail!(
+            "No Flux Kustomizations or HelmReleases found for {} ({})",
+            product,
+            env
+        );
+    }
+
+    println!(
+        "{} Suspending GitOps for {} ({})",
+        "⏸".bold(),
+        product.cyan().bold(),
+        env
+    );
+    let annotations = json!({
+        SUSPENDED_BY_ANNOTATION: by,
+        SUSPENDED_REASON_ANNOTATION: reason,
+        SUSPENDED_AT_ANNOTATION: Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true),
+    });
+    for object in &objects {
+        flux_api::set_suspended(
+            &client,
+            object.kind,
+            &object.name,
+            &object.namespace,
+            true,
+            annotations.clone(),
+        )
+        .await?;
+        println!("   ✓ {}", object.display_name());
+    }
+    println!(
+        "   {} objects suspended by {}: {}",
+        objects.len(),
+        by.cyan(),
+        reason
+    );
+    println!(
+        "   Release commands will refuse {} ({}) until `forge flux resume`.",
+        product, env
+    );
+    Ok(())
+}
+
+/// Resume every Flux object of `product` in `env` and clear the
+/// suspension record.
+pub async fn resume(product: &str, repo_root: &str, env: &str) -> Result<()> {
+    let product_config = DeployConfig::load_product_config(product, repo_root)?;
+    let client = k8s::create_client().await?;
+    let objects = product_objects(&client, &product_config, env).await?;
+
+    println!(
+        "{} Resuming GitOps for {} ({})",
+        "▶".bold(),
+        product.cyan().bold(),
+        env
+    );
+    let cleared = json!({
+        SUSPENDED_BY_ANNOTATION: Value::Null,
+        SUSPENDED_REASON_ANNOTATION: Value::Null,
+        SUSPENDED_AT_ANNOTATION: Value::Null,
+    });
+    let mut resumed = 0;
+    for object in objects.iter().filter(|o| o.suspended) {
+        flux_api::set_suspended(
+            &client,
+            object.kind,
+            &object.name,
+            &object.namespace,
+            false,
+            cleared.clone(),
+        )
+        .await?;
+        println!("   ✓ {}", Suspension::from_object(object).describe());
+        resumed += 1;
+    }
+    if resumed == 0 {
+        println!("   Nothing was suspended.");
+    } else {
+        println!("   {} objects resumed", resumed);
+    }
+    Ok(())
+}
+
+/// Fail when any Flux object of the product environment is suspended,
+/// naming who suspended it and why.
+pub async fn ensure_not_suspended(product_config: &ProductConfig, env: &str) -> Result<()> {
+    let namespace = product_config.namespace_for_env(env);
+    let prefixes = product_prefixes(product_config, env);
+    ensure_scope_not_suspended(
+        &k8s::create_client().await?,
+        &format!("{} ({})", product_config.name, env),
+        &namespace,
+        |o| belongs_to_product(o, &namespace, &prefixes),
+        &format!(
+            "Run `forge flux resume --product {} --env {}` once the freeze is over.",
+            product_config.name, env
+        ),
+    )
+    .await
+}
+
+/// [`ensure_not_suspended`] for release paths that only know the target
+/// namespace: the Flux objects in it or named after it.
+pub async fn ensure_namespace_not_suspended(namespace: &str) -> Result<()> {
+    let prefixes = [namespace.to_string()];
+    ensure_scope_not_suspended(
+        &k8s::create_client().await?,
+        &format!("Namespace {}", namespace),
+        namespace,
+        |o| belongs_to_product(o, namespace, &prefixes),
+        "Run `forge flux resume` for its product once the freeze is over.",
+    )
+    .await
+}
+
+/// [`ensure_not_suspended`] for a service deployed through its own
+/// HelmRelease `name` in `namespace`: the Flux objects named after it in
+/// that namespace and `flux-system`. Without a configured cluster there is
+/// nothing to check, so it only warns.
+pub async fn ensure_release_not_suspended(name: &str, namespace: &str) -> Result<()> {
+    let client = match k8s::create_client().await {
+        Ok(client) => client,
+        Err(e) => {
+            println!(
+                "   {} No cluster configured; not checking whether HelmRelease {}/{} is suspended: {:#}",
+                "⚠️".yellow(),
+                namespace,
+                name,
+                e
+            );
+            return Ok(());
+        }
+    };
+    let prefixes = [name.to_string()];
+    ensure_scope_not_suspended(
+        &client,
+        &format!("HelmRelease {}/{}", namespace, name),
+        namespace,
+        |o| named_after(o, &prefixes),
+        "Run `forge flux resume` for its product once the freeze is over.",
+    )
+    .await
+}
+
+async fn ensure_scope_not_suspended(
+    client: &Client,
+    target: &str,
+    namespace: &str,
+    belongs: impl Fn(&FluxObject) -> bool,
+    resume_hint: &str,
+) -> Result<()> {
+    let objects = scoped_objects(client, namespace, belongs)
+        .await
+        .with_context(|| format!("Failed to check whether {} is suspended", target))?;
+    let suspended: Vec<String> = objects
+        .iter()
+        .filter(|o| o.suspended)
+        .map(|o| Suspension::from_object(o).describe())
+        .collect();
+    if !suspended.is_empty() {
+        bail!(
+            "{} is suspended; refusing to release:\n  {}\n  {}",
+            target,
+            suspended.join("\n  "),
+            resume_hint
+        );
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Object Selection
+// ============================================================================
+
+/// Names a product environment's Flux objects are prefixed with.
+pub fn product_prefixes(product_config: &ProductConfig, env: &str) -> Vec<String> {
+    let mut prefixes = vec![product_config.namespace_for_env(env)];
+    let flux_name = format!("{}-{}", product_config.name, env);
+    if !prefixes.contains(&flux_name) {
+        prefixes.push(flux_name);
+    }
+    prefixes
+}
+
+/// Whether `object` belongs to the product environment.
+pub fn belongs_to_product(object: &FluxObject, namespace: &str, prefixes: &[String]) -> bool {
+    object.namespace == namespace || named_after(object, prefixes)
+}
+
+/// Whether `object` is named one of `prefixes`, optionally followed by a
+/// `-{phase}` suffix.
+pub fn named_after(object: &FluxObject, prefixes: &[String]) -> bool {
+    prefixes.iter().any(|prefix| {
+        object.name == *prefix
+            || object
+                .name
+                .strip_prefix(prefix.as_str())
+                .is_some_and(|rest| rest.starts_with('-'))
+    })
+}
+
+async fn product_objects(
+    client: &Client,
+    product_config: &ProductConfig,
+    env: &str,
+) -> Result<Vec<FluxObject>> {
+    let namespace = product_config.namespace_for_env(env);
+    let prefixes = product_prefixes(product_config, env);
+    scoped_objects(client, &namespace, |o| {
+        belongs_to_product(o, &namespace, &prefixes)
+    })
+    .await
+}
+
+/// Flux objects in `flux-system` and `namespace` that `belongs` selects.
+async fn scoped_objects(
+    client: &Client,
+    namespace: &str,
+    belongs: impl Fn(&FluxObject) -> bool,
+) -> Result<Vec<FluxObject>> {
+    let scopes = if namespace == FLUX_NAMESPACE {
+        vec![namespace]
+    } else {
+        vec![FLUX_NAMESPACE, namespace]
+    };
+
+    let mut objects = Vec::new();
+    for kind in [FluxKind::Kustomization, FluxKind::HelmRelease] {
+        for scope in &scopes {
+            let found = match flux_api::list(client, kind, Some(scope)).await {
+                Ok(found) => found,
+                // HelmRelease CRD not installed.
+                Err(KubernetesError::ApiFailed {
+                    code: Some(404), ..
+                }) if kind == FluxKind::HelmRelease => Vec::new(),
+                Err(e) => return Err(e.into()),
+            };
+            objects.extend(found.into_iter().filter(|o| belongs(o)));
+        }
+    }
+    Ok(objects)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kube::api::DynamicObject;
+
+    fn object(namespace: &str, name: &str, annotations: Value) -> FluxObject {
+        let dynamic: DynamicObject = serde_json::from_value(json!({
+            "apiVersion": "kustomize.toolkit.fluxcd.io/v1",
+            "kind": "Kustomization",
+            "metadata": {"name": name, "namespace": namespace, "annotations": annotations},
+            "spec": {"suspend": true}
+        }))
+        .unwrap();
+        FluxObject::from_dynamic(FluxKind::Kustomization, &dynamic).unwrap()
+    }
+
+    #[test]
+    fn product_objects_match_namespace_and_phase_names() {
+        let prefixes = vec![
+            "acme-production".to_string(),
+            "acme-production-a".to_string(),
+        ];
+        let belongs = |namespace: &str, name: &str| {
+            belongs_to_product(
+                &object(namespace, name, json!({})),
+                "acme-production",
+                &prefixes,
+            )
+        };
+
+        assert!(belongs("flux-system", "acme-production"));
+        assert!(belongs("flux-system", "acme-production-secrets"));
+        assert!(belongs("flux-system", "acme-production-a"));
+        assert!(belongs("acme-production", "cart"));
+        assert!(!belongs("flux-system", "acme-productionx"));
+        assert!(!belongs("flux-system", "acme-staging"));
+        assert!(!belongs("flux-system", "flux-system"));
+    }
+
+    #[test]
+    fn release_objects_match_by_name_only() {
+        let prefixes = vec!["cart".to_string()];
+        let named = |namespace: &str, name: &str| {
+            named_after(&object(namespace, name, json!({})), &prefixes)
+        };
+
+        assert!(named("commerce", "cart"));
+        assert!(named("flux-system", "cart-migrations"));
+        assert!(!named("commerce", "checkout"));
+        assert!(!named("commerce", "cartography"));
+    }
+
+    #[test]
+    fn suspension_record_is_read_from_annotations() {
+        let recorded = Suspension::from_object(&object(
+            "flux-system",
+            "acme-staging",
+            json!({
+                SUSPENDED_BY_ANNOTATION: "alice",
+                SUSPENDED_REASON_ANNOTATION: "INC-42 database failover",
+                SUSPENDED_AT_ANNOTATION: "2026-10-18T09:00:00Z"
+            }),
+        ));
+        assert_eq!(
+            recorded.describe(),
+            "Kustomization/flux-system/acme-staging \
+             (by alice at 2026-10-18T09:00:00Z: INC-42 database failover)"
+        );
+
+        let foreign = Suspension::from_object(&object("flux-system", "acme-staging", json!({})));
+        assert_eq!(
+            foreign.describe(),
+            "Kustomization/flux-system/acme-staging (suspended outside forge)"
+        );
+    }
+}
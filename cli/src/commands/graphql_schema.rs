@@ -0,0 +1,724 @@
+//! GraphQL Schema Model
+//!
+//! A flattened view of a GraphQL SDL document for the federation tooling:
+//! every type with its fields, arguments, enum values, union members and
+//! directives, with `extend type` definitions merged into their base type.
+//! Parsing is done by `async-graphql-parser`; this module only normalizes
+//! its AST into something that is easy to diff and query.
+//!
+//! A subgraph can also be recovered from a composed supergraph through the
+//! `@join__*` directives composition leaves on every type, field, enum
+//! value and union member, which is how the schema a subgraph is currently
+//! served with is read back from the router's ConfigMap.
+
+use anyhow::{Context, Result};
+use async_graphql_parser::types::{
+    ConstDirective, FieldDefinition, InputValueDefinition, TypeKind, TypeSystemDefinition,
+};
+use async_graphql_parser::Positioned;
+use async_graphql_value::ConstValue;
+use std::collections::BTreeMap;
+
+pub use async_graphql_parser::types::{BaseType, Type};
+
+/// Kind of a named type
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    Scalar,
+    Object,
+    Interface,
+    Union,
+    Enum,
+    InputObject,
+}
+
+impl Kind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Kind::Scalar => "scalar",
+            Kind::Object => "type",
+            Kind::Interface => "interface",
+            Kind::Union => "union",
+            Kind::Enum => "enum",
+            Kind::InputObject => "input",
+        }
+    }
+}
+
+/// An applied directive, e.g. `@key(fields: "id")`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Directive {
+    pub name: String,
+    pub arguments: Vec<(String, ConstValue)>,
+}
+
+impl Directive {
+    pub fn argument(&self, name: &str) -> Option<&ConstValue> {
+        self.arguments
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, value)| value)
+    }
+
+    /// String or enum argument as plain text
+    pub fn text_argument(&self, name: &str) -> Option<&str> {
+        match self.argument(name)? {
+            ConstValue::String(s) => Some(s),
+            ConstValue::Enum(e) => Some(e.as_str()),
+            _ => None,
+        }
+    }
+}
+
+/// An argument or input field
+#[derive(Debug, Clone, PartialEq)]
+pub struct InputValue {
+    pub name: String,
+    pub description: Option<String>,
+    pub ty: Type,
+    pub default: Option<ConstValue>,
+    pub directives: Vec<Directive>,
+}
+
+/// A field of an object or interface type
+#[derive(Debug, Clone, PartialEq)]
+pub struct Field {
+    pub name: String,
+    pub description: Option<String>,
+    pub ty: Type,
+    pub args: Vec<InputValue>,
+    pub directives: Vec<Directive>,
+}
+
+impl Field {
+    pub fn arg(&self, name: &str) -> Option<&InputValue> {
+        self.args.iter().find(|a| a.name == name)
+    }
+}
+
+/// An enum value
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnumValue {
+    pub name: String,
+    pub description: Option<String>,
+    pub directives: Vec<Directive>,
+}
+
+/// A named type with its extensions merged in
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeDef {
+    pub name: String,
+    pub kind: Kind,
+    pub description: Option<String>,
+    /// Only ever defined with `extend` (a federation 1 entity extension)
+    pub extension: bool,
+    pub directives: Vec<Directive>,
+    pub implements: Vec<String>,
+    pub fields: Vec<Field>,
+    pub input_fields: Vec<InputValue>,
+    pub members: Vec<String>,
+    pub values: Vec<EnumValue>,
+}
+
+impl TypeDef {
+    pub fn new(name: &str, kind: Kind) -> Self {
+        Self {
+            name: name.to_string(),
+            kind,
+            description: None,
+            extension: false,
+            directives: Vec::new(),
+            implements: Vec::new(),
+            fields: Vec::new(),
+            input_fields: Vec::new(),
+            members: Vec::new(),
+            values: Vec::new(),
+        }
+    }
+
+    pub fn field(&self, name: &str) -> Option<&Field> {
+        self.fields.iter().find(|f| f.name == name)
+    }
+
+    pub fn input_field(&self, name: &str) -> Option<&InputValue> {
+        self.input_fields.iter().find(|f| f.name == name)
+    }
+
+    pub fn directives_named<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Directive> {
+        self.directives.iter().filter(move |d| d.name == name)
+    }
+}
+
+/// A parsed SDL document
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Schema {
+    pub types: BTreeMap<String, TypeDef>,
+    pub schema_directives: Vec<Directive>,
+    /// Directive definitions, printed verbatim after the schema definition
+    /// (not filled in by [`Schema::parse`])
+    pub directive_definitions: Vec<String>,
+    pub query: Option<String>,
+    pub mutation: Option<String>,
+    pub subscription: Option<String>,
+}
+
+fn directives(list: &[Positioned<ConstDirective>]) -> Vec<Directive> {
+    list.iter()
+        .map(|d| Directive {
+            name: d.node.name.node.to_string(),
+            arguments: d
+                .node
+                .arguments
+                .iter()
+                .map(|(name, value)| (name.node.to_string(), value.node.clone()))
+                .collect(),
+        })
+        .collect()
+}
+
+fn input_value(value: &Positioned<InputValueDefinition>) -> InputValue {
+    InputValue {
+        name: value.node.name.node.to_string(),
+        description: value.node.description.as_ref().map(|d| d.node.clone()),
+        ty: value.node.ty.node.clone(),
+        default: value.node.default_value.as_ref().map(|v| v.node.clone()),
+        directives: directives(&value.node.directives),
+    }
+}
+
+fn field(field: &Positioned<FieldDefinition>) -> Field {
+    Field {
+        name: field.node.name.node.to_string(),
+        description: field.node.description.as_ref().map(|d| d.node.clone()),
+        ty: field.node.ty.node.clone(),
+        args: field.node.arguments.iter().map(input_value).collect(),
+        directives: directives(&field.node.directives),
+    }
+}
+
+/// Name of the innermost named type, e.g. `Product` for `[Product!]!`
+pub fn named_type(ty: &Type) -> &str {
+    match &ty.base {
+        BaseType::Named(name) => name.as_str(),
+        BaseType::List(inner) => named_type(inner),
+    }
+}
+
+/// Whether a schema coordinate's type is a built-in or federation type that
+/// is not part of a subgraph's own API.
+pub fn is_builtin_type(name: &str) -> bool {
+    name.starts_with("__")
+        || name.starts_with("join__")
+        || name.starts_with("link__")
+        || name.starts_with("federation__")
+        || matches!(name, "_Service" | "_Entity" | "_Any" | "_FieldSet")
+}
+
+impl Schema {
+    /// Parse an SDL document
+    pub fn parse(sdl: &str) -> Result<Self> {
+        let document =
+            async_graphql_parser::parse_schema(sdl).context("Failed to parse GraphQL SDL")?;
+        let mut schema = Schema::default();
+
+        for definition in &document.definitions {
+            match definition {
+                TypeSystemDefinition::Schema(def) => {
+                    let def = &def.node;
+                    schema.schema_directives.extend(directives(&def.directives));
+                    let name = |n: &Option<Positioned<async_graphql_value::Name>>| {
+                        n.as_ref().map(|n| n.node.to_string())
+                    };
+                    schema.query = name(&def.query).or(schema.query);
+                    schema.mutation = name(&def.mutation).or(schema.mutation);
+                    schema.subscription = name(&def.subscription).or(schema.subscription);
+                }
+                TypeSystemDefinition::Type(def) => {
+                    let def = &def.node;
+                    let kind = match &def.kind {
+                        TypeKind::Scalar => Kind::Scalar,
+                        TypeKind::Object(_) => Kind::Object,
+                        TypeKind::Interface(_) => Kind::Interface,
+                        TypeKind::Union(_) => Kind::Union,
+                        TypeKind::Enum(_) => Kind::Enum,
+                        TypeKind::InputObject(_) => Kind::InputObject,
+                    };
+                    let name = def.name.node.to_string();
+                    let ty = schema.types.entry(name.clone()).or_insert_with(|| {
+                        let mut ty = TypeDef::new(&name, kind);
+                        ty.extension = true;
+                        ty
+                    });
+                    ty.extension &= def.extend;
+                    if ty.description.is_none() {
+                        ty.description = def.description.as_ref().map(|d| d.node.clone());
+                    }
+                    ty.directives.extend(directives(&def.directives));
+                    match &def.kind {
+                        TypeKind::Scalar => {}
+                        TypeKind::Object(object) => {
+                            ty.implements
+                                .extend(object.implements.iter().map(|i| i.node.to_string()));
+                            ty.fields.extend(object.fields.iter().map(field));
+                        }
+                        TypeKind::Interface(interface) => {
+                            ty.implements
+                                .extend(interface.implements.iter().map(|i| i.node.to_string()));
+                            ty.fields.extend(interface.fields.iter().map(field));
+                        }
+                        TypeKind::Union(union) => {
+                            ty.members
+                                .extend(union.members.iter().map(|m| m.node.to_string()));
+                        }
+                        TypeKind::Enum(e) => {
+                            ty.values.extend(e.values.iter().map(|v| EnumValue {
+                                name: v.node.value.node.to_string(),
+                                description: v.node.description.as_ref().map(|d| d.node.clone()),
+                                directives: directives(&v.node.directives),
+                            }));
+                        }
+                        TypeKind::InputObject(input) => {
+                            ty.input_fields.extend(input.fields.iter().map(input_value));
+                        }
+                    }
+                }
+                TypeSystemDefinition::Directive(_) => {}
+            }
+        }
+
+        Ok(schema)
+    }
+
+    /// Name of the query root type
+    pub fn query_type(&self) -> &str {
+        self.query.as_deref().unwrap_or("Query")
+    }
+
+    /// Name of the mutation root type, if the schema has one
+    pub fn mutation_type(&self) -> Option<&str> {
+        self.mutation
+            .as_deref()
+            .or_else(|| self.types.contains_key("Mutation").then_some("Mutation"))
+    }
+
+    /// Name of the subscription root type, if the schema has one
+    pub fn subscription_type(&self) -> Option<&str> {
+        self.subscription.as_deref().or_else(|| {
+            self.types
+                .contains_key("Subscription")
+                .then_some("Subscription")
+        })
+    }
+
+    /// The `join__Graph` enum value of the subgraph named `name` in a
+    /// composed supergraph
+    fn join_graph(&self, name: &str) -> Option<String> {
+        self.types
+            .get("join__Graph")?
+            .values
+            .iter()
+            .find_map(|value| {
+                value
+                    .directives
+                    .iter()
+                    .find(|d| d.name == "join__graph")
+                    .filter(|d| d.text_argument("name") == Some(name))
+                    .map(|_| value.name.clone())
+            })
+    }
+
+    /// The part of a composed supergraph served by subgraph `name`, read
+    /// from the `@join__*` directives. Returns `None` when the supergraph
+    /// has no such subgraph.
+    pub fn subgraph(&self, name: &str) -> Option<Schema> {
+        let graph = self.join_graph(name)?;
+        let in_graph = |directives: &[Directive], directive: &str| {
+            let mut tagged = directives.iter().filter(|d| d.name == directive).peekable();
+            tagged.peek().is_none()
+                || tagged.any(|d| d.text_argument("graph") == Some(graph.as_str()))
+        };
+
+        let mut subgraph = Schema {
+            query: self.query.clone(),
+            mutation: self.mutation.clone(),
+            subscription: self.subscription.clone(),
+            ..Default::default()
+        };
+        for ty in self.types.values() {
+            if is_builtin_type(&ty.name) {
+                continue;
+            }
+            // Built-in scalars carry no join__type; everything composed does.
+            let join_types: Vec<&Directive> = ty.directives_named("join__type").collect();
+            if join_types.is_empty()
+                || !join_types
+                    .iter()
+                    .any(|d| d.text_argument("graph") == Some(graph.as_str()))
+            {
+                continue;
+            }
+
+            let mut own = TypeDef::new(&ty.name, ty.kind);
+            own.description = ty.description.clone();
+            own.directives = ty
+                .directives
+                .iter()
+                .filter(|d| !d.name.starts_with("join__"))
+                .cloned()
+                .collect();
+            own.fields = ty
+                .fields
+                .iter()
+                .filter(|f| in_graph(&f.directives, "join__field"))
+                .cloned()
+                .collect();
+            own.input_fields = ty
+                .input_fields
+                .iter()
+                .filter(|f| in_graph(&f.directives, "join__field"))
+                .cloned()
+                .collect();
+            own.values = ty
+                .values
+                .iter()
+                .filter(|v| in_graph(&v.directives, "join__enumValue"))
+                .cloned()
+                .collect();
+            own.members = ty
+                .directives_named("join__unionMember")
+                .filter(|d| d.text_argument("graph") == Some(graph.as_str()))
+                .filter_map(|d| d.text_argument("member").map(str::to_string))
+                .collect();
+            if ty.kind == Kind::Union && ty.directives_named("join__unionMember").next().is_none() {
+                own.members = ty.members.clone();
+            }
+            let implements: Vec<String> = ty
+                .directives_named("join__implements")
+                .filter(|d| d.text_argument("graph") == Some(graph.as_str()))
+                .filter_map(|d| d.text_argument("interface").map(str::to_string))
+                .collect();
+            own.implements = if ty.directives_named("join__implements").next().is_some() {
+                implements
+            } else {
+                ty.implements.clone()
+            };
+            subgraph.types.insert(own.name.clone(), own);
+        }
+        Some(subgraph)
+    }
+}
+
+// =============================================================================
+// Printing
+// =============================================================================
+
+fn print_description(out: &mut String, description: &Option<String>, indent: &str) {
+    let Some(description) = description else {
+        return;
+    };
+    let escaped = description.replace("\"\"\"", "\\\"\"\"");
+    if escaped.contains('\n') || escaped.ends_with('"') {
+        out.push_str(&format!("{indent}\"\"\"\n"));
+        for line in escaped.lines() {
+            out.push_str(&format!("{indent}{line}\n"));
+        }
+        out.push_str(&format!("{indent}\"\"\"\n"));
+    } else {
+        out.push_str(&format!("{indent}\"\"\"{escaped}\"\"\"\n"));
+    }
+}
+
+impl std::fmt::Display for Directive {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "@{}", self.name)?;
+        if !self.arguments.is_empty() {
+            let arguments: Vec<String> = self
+                .arguments
+                .iter()
+                .map(|(name, value)| format!("{}: {}", name, value))
+                .collect();
+            write!(f, "({})", arguments.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+fn inline_directives(directives: &[Directive]) -> String {
+    directives.iter().map(|d| format!(" {}", d)).collect()
+}
+
+/// Type-level directives, one per line
+fn block_directives(directives: &[Directive]) -> String {
+    directives.iter().map(|d| format!("\n  {}", d)).collect()
+}
+
+impl std::fmt::Display for InputValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.name, self.ty)?;
+        if let Some(default) = &self.default {
+            write!(f, " = {}", default)?;
+        }
+        f.write_str(&inline_directives(&self.directives))
+    }
+}
+
+fn print_arguments(out: &mut String, args: &[InputValue]) {
+    if args.is_empty() {
+        return;
+    }
+    if args.iter().all(|a| a.description.is_none()) {
+        let args: Vec<String> = args.iter().map(InputValue::to_string).collect();
+        out.push_str(&format!("({})", args.join(", ")));
+        return;
+    }
+    out.push_str("(\n");
+    for arg in args {
+        print_description(out, &arg.description, "    ");
+        out.push_str(&format!("    {}\n", arg));
+    }
+    out.push_str("  )");
+}
+
+impl TypeDef {
+    fn print(&self, out: &mut String) {
+        print_description(out, &self.description, "");
+        out.push_str(&format!("{} {}", self.kind.as_str(), self.name));
+        if !self.implements.is_empty() {
+            out.push_str(&format!(" implements {}", self.implements.join(" & ")));
+        }
+        out.push_str(&block_directives(&self.directives));
+        let separator = if self.directives.is_empty() {
+            " "
+        } else {
+            "\n"
+        };
+        match self.kind {
+            Kind::Scalar => out.push('\n'),
+            Kind::Union => out.push_str(&format!(
+                "{}= {}\n",
+                if self.directives.is_empty() {
+                    " "
+                } else {
+                    "\n "
+                },
+                self.members.join(" | ")
+            )),
+            Kind::Enum => {
+                out.push_str(&format!("{}{{\n", separator));
+                for value in &self.values {
+                    print_description(out, &value.description, "  ");
+                    out.push_str(&format!(
+                        "  {}{}\n",
+                        value.name,
+                        inline_directives(&value.directives)
+                    ));
+                }
+                out.push_str("}\n");
+            }
+            Kind::InputObject => {
+                out.push_str(&format!("{}{{\n", separator));
+                for field in &self.input_fields {
+                    print_description(out, &field.description, "  ");
+                    out.push_str(&format!("  {}\n", field));
+                }
+                out.push_str("}\n");
+            }
+            Kind::Object | Kind::Interface => {
+                out.push_str(&format!("{}{{\n", separator));
+                for field in &self.fields {
+                    print_description(out, &field.description, "  ");
+                    out.push_str(&format!("  {}", field.name));
+                    print_arguments(out, &field.args);
+                    out.push_str(&format!(
+                        ": {}{}\n",
+                        field.ty,
+                        inline_directives(&field.directives)
+                    ));
+                }
+                out.push_str("}\n");
+            }
+        }
+    }
+}
+
+impl Schema {
+    /// Print the schema as SDL: the schema definition, directive
+    /// definitions, then every type in name order.
+    pub fn to_sdl(&self) -> String {
+        let mut out = String::new();
+        let roots: Vec<(&str, &str)> = [
+            ("query", Some(self.query_type())),
+            ("mutation", self.mutation_type()),
+            ("subscription", self.subscription_type()),
+        ]
+        .into_iter()
+        .filter_map(|(op, ty)| Some((op, ty?)))
+        .filter(|(_, ty)| self.types.contains_key(*ty))
+        .collect();
+        out.push_str("schema");
+        out.push_str(&block_directives(&self.schema_directives));
+        out.push_str(if self.schema_directives.is_empty() {
+            " {\n"
+        } else {
+            "\n{\n"
+        });
+        for (op, ty) in roots {
+            out.push_str(&format!("  {}: {}\n", op, ty));
+        }
+        out.push_str("}\n");
+
+        for definition in &self.directive_definitions {
+            out.push('\n');
+            out.push_str(definition.trim_end());
+            out.push('\n');
+        }
+        for ty in self.types.values() {
+            out.push('\n');
+            ty.print(&mut out);
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_merges_extensions() {
+        let schema = Schema::parse(
+            r#"
+extend schema @link(url: "https://specs.apollo.dev/federation/v2.3", import: ["@key"])
+
+type Product @key(fields: "id") {
+  id: ID!
+  name: String
+  price(currency: Currency = USD): Float!
+}
+
+extend type Product {
+  sku: String! @deprecated(reason: "use id")
+}
+
+enum Currency { USD EUR }
+
+type Query {
+  product(id: ID!): Product
+}
+"#,
+        )
+        .unwrap();
+
+        let product = &schema.types["Product"];
+        assert_eq!(product.kind, Kind::Object);
+        let names: Vec<&str> = product.fields.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, ["id", "name", "price", "sku"]);
+        let key = product.directives_named("key").next().unwrap();
+        assert_eq!(key.text_argument("fields"), Some("id"));
+        let currency = product.field("price").unwrap().arg("currency").unwrap();
+        assert_eq!(currency.ty.to_string(), "Currency");
+        assert_eq!(currency.default.as_ref().unwrap().to_string(), "USD");
+        assert_eq!(schema.types["Currency"].values.len(), 2);
+        assert_eq!(schema.query_type(), "Query");
+        assert_eq!(schema.mutation_type(), None);
+        assert_eq!(named_type(&product.field("price").unwrap().ty), "Float");
+    }
+
+    #[test]
+    fn test_subgraph_from_supergraph() {
+        let supergraph = Schema::parse(
+            r#"
+schema @link(url: "https://specs.apollo.dev/join/v0.3", for: EXECUTION) {
+  query: Query
+}
+
+enum join__Graph {
+  PRODUCTS @join__graph(name: "products", url: "http://products:8080/graphql")
+  REVIEWS @join__graph(name: "reviews", url: "http://reviews:8080/graphql")
+}
+
+scalar join__FieldSet
+
+type Product @join__type(graph: PRODUCTS, key: "id") @join__type(graph: REVIEWS, key: "id") {
+  id: ID!
+  name: String @join__field(graph: PRODUCTS)
+  reviews: [Review!]! @join__field(graph: REVIEWS)
+}
+
+type Review @join__type(graph: REVIEWS) {
+  body: String
+}
+
+enum Status @join__type(graph: PRODUCTS) @join__type(graph: REVIEWS) {
+  ACTIVE @join__enumValue(graph: PRODUCTS) @join__enumValue(graph: REVIEWS)
+  ARCHIVED @join__enumValue(graph: PRODUCTS)
+}
+
+type Query @join__type(graph: PRODUCTS) @join__type(graph: REVIEWS) {
+  product(id: ID!): Product @join__field(graph: PRODUCTS)
+  latestReviews: [Review!]! @join__field(graph: REVIEWS)
+}
+"#,
+        )
+        .unwrap();
+
+        let products = supergraph.subgraph("products").unwrap();
+        assert_eq!(
+            products.types.keys().collect::<Vec<_>>(),
+            ["Product", "Query", "Status"]
+        );
+        let fields: Vec<&str> = products.types["Product"]
+            .fields
+            .iter()
+            .map(|f| f.name.as_str())
+            .collect();
+        assert_eq!(fields, ["id", "name"]);
+        assert_eq!(products.types["Status"].values.len(), 2);
+        assert!(products.types["Query"].field("latestReviews").is_none());
+
+        let reviews = supergraph.subgraph("reviews").unwrap();
+        assert_eq!(reviews.types["Status"].values.len(), 1);
+        assert!(supergraph.subgraph("inventory").is_none());
+    }
+
+    #[test]
+    fn test_to_sdl_round_trips() {
+        let sdl = r#"
+schema @link(url: "https://specs.apollo.dev/link/v1.0") {
+  query: Query
+}
+
+"""
+A product
+in the catalog
+"""
+type Product implements Node @key(fields: "id") @key(fields: "sku") {
+  id: ID!
+  "Price in the given currency"
+  price(currency: Currency = USD, rounded: Boolean! = false): Float!
+  tags: [String!]! @deprecated(reason: "use \"labels\"")
+}
+
+interface Node { id: ID! }
+
+enum Currency { "US dollar" USD EUR @deprecated }
+
+union Result @tag(name: "public") = Product | Query
+
+input Filter { ids: [ID!], limit: Int = 10 }
+
+scalar DateTime @specifiedBy(url: "https://example.com")
+
+type Query {
+  search(
+    "Ids to look up"
+    filter: Filter
+  ): [Result!]!
+}
+"#;
+        let schema = Schema::parse(sdl).unwrap();
+        let printed = schema.to_sdl();
+        assert_eq!(Schema::parse(&printed).unwrap(), schema, "{}", printed);
+        assert!(printed.contains("type Product implements Node\n  @key(fields: \"id\")"));
+    }
+}
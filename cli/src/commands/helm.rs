@@ -3,6 +3,7 @@
 //! Provides lint, package, push, deploy, release, template, and bump operations
 //! for pleme-io Helm charts distributed via OCI registries.
 
+use super::helm_release::{self, UpgradeOptions};
 use crate::repo::get_tool_path;
 use crate::retry::RetryPolicy;
 use crate::version;
@@ -189,7 +190,7 @@ fn run_program_timed(program: &str, args: &[&str], timeout: Duration) -> Result<
 /// `run_helm_timed`'s own body); each bypassed the env override, so
 /// a Nix-hermetic runner's `HELM_BIN` lost to whatever `helm` was
 /// first on PATH.
-fn helm_bin() -> String {
+pub(crate) fn helm_bin() -> String {
     get_tool_path("HELM_BIN", "helm")
 }
 
@@ -288,7 +289,7 @@ fn format_failure_summary(failed: &[(String, String)]) -> String {
 /// closes the "exit-status → typed Result" boundary at the same surface,
 /// so the three helpers together own the whole "helm output → typed
 /// per-chart Result" pipeline the four consumer sites drive.
-fn ensure_helm_success(output: &std::process::Output, context: &str) -> Result<()> {
+pub(crate) fn ensure_helm_success(output: &std::process::Output, context: &str) -> Result<()> {
     if !output.status.success() {
         bail!(
             "{}: {}",
@@ -1271,19 +1272,26 @@ fn redirect_remote_deps_to_mirror(chart_dir: &Path, registry: &str) -> Result<()
 }
 
 /// Deploy a service by updating the HelmRelease image tag in the k8s repo.
-pub fn deploy(
+///
+/// With `options.diff` the HelmRelease's chart is rendered with the
+/// environment's values before and after the change and the resource diff
+/// is printed; with `options.watch` the pushed upgrade is followed through
+/// the HelmRelease's conditions (see [`super::helm_release`]).
+pub async fn deploy(
     service: &str,
     image_tag: &str,
     k8s_repo: &str,
     environment: &str,
     commit: bool,
-    _watch: bool,
+    options: &UpgradeOptions,
 ) -> Result<()> {
     let k8s_path = Path::new(k8s_repo);
     if !k8s_path.exists() {
         bail!("K8s repo not found: {}", k8s_repo);
     }
-
+    if options.watch && !commit {
+        bail!("--watch needs --commit: nothing is pushed for Flux to apply");
+    }
     // Find the HelmRelease file for this service
     // Convention: clusters/{cluster}/{category}/{service}/kustomization.yaml patches the HelmRelease
     info!(
@@ -1327,8 +1335,46 @@ pub fn deploy(
     // Look for: value: amd64-<hash> and replace with the new tag
     let updated = update_helmrelease_image_tag(&content, image_tag)?;
 
+    // Refuse to deploy a release frozen with `forge flux suspend`; the
+    // lookup is scoped to the HelmRelease's own namespace.
+    let current_release = helm_release::resolve(Path::new(kustomization_path), service);
+    match &current_release {
+        Ok(release) => {
+            super::flux_suspend::ensure_release_not_suspended(&release.name, &release.namespace)
+                .await?
+        }
+        Err(e) => warn!(
+            "Could not resolve the HelmRelease for {}; not checking whether it is suspended: {:#}",
+            service, e
+        ),
+    }
+
+    let previous_release = if options.diff {
+        Some(current_release?)
+    } else {
+        None
+    };
+
     std::fs::write(kustomization_path, &updated)?;
 
+    // The checks below read the updated overlay from disk; put the original
+    // back when any of them fails so no half-applied change is left behind.
+    let prepared = prepare_release(
+        Path::new(kustomization_path),
+        service,
+        previous_release.as_ref(),
+        options,
+    )
+    .await;
+    let (release, previous_generation) = match prepared {
+        Ok(prepared) => prepared,
+        Err(e) => {
+            std::fs::write(kustomization_path, &content)
+                .context("Failed to restore kustomization.yaml")?;
+            return Err(e);
+        }
+    };
+
     if commit {
         info!("Committing changes...");
         // Binary resolution rides `crate::git::git_command_sync()` so a
@@ -1343,6 +1389,8 @@ pub fn deploy(
         // push are advisory (invoked with `--commit`), and the operator
         // sees the failure via inherited stderr; changing that shape
         // belongs in a separate lift, not in this GIT_BIN-routing pass.
+        // Only the push status is read, and only under `--watch`, which
+        // has nothing to follow when the push did not land.
         let _ = crate::git::git_command_sync()
             .args(["add", kustomization_path])
             .current_dir(k8s_repo)
@@ -1354,17 +1402,52 @@ pub fn deploy(
             .current_dir(k8s_repo)
             .status();
 
-        let _ = crate::git::git_command_sync()
+        let pushed = crate::git::git_command_sync()
             .args(["push"])
             .current_dir(k8s_repo)
-            .status();
+            .status()
+            .is_ok_and(|s| s.success());
 
+        if options.watch && !pushed {
+            bail!(
+                "git push of {} failed; not watching the HelmRelease",
+                k8s_repo
+            );
+        }
         info!("Changes committed and pushed");
     }
 
+    if let (Some(release), true) = (&release, options.watch) {
+        helm_release::monitor(release, previous_generation, options.timeout).await?;
+    }
+
     Ok(())
 }
 
+/// Resolve the updated HelmRelease, preview the change against
+/// `previous_release` and read the live generation to watch from, as
+/// `options` ask.
+async fn prepare_release(
+    kustomization_path: &Path,
+    service: &str,
+    previous_release: Option<&helm_release::HelmReleaseSpec>,
+    options: &UpgradeOptions,
+) -> Result<(Option<helm_release::HelmReleaseSpec>, Option<i64>)> {
+    let release = if options.diff || options.watch {
+        Some(helm_release::resolve(kustomization_path, service)?)
+    } else {
+        None
+    };
+    if let (Some(before), Some(after)) = (previous_release, &release) {
+        helm_release::preview(before, after, options)?;
+    }
+    let previous_generation = match &release {
+        Some(release) if options.watch => helm_release::live_generation(release).await?,
+        _ => None,
+    };
+    Ok((release, previous_generation))
+}
+
 /// Full chart lifecycle: lint → package → push.
 pub fn release(chart_dir: &str, registry: &str, version: Option<&str>) -> Result<()> {
     info!("=== Lint ===");
@@ -3124,8 +3207,8 @@ mod deploy_git_bin_routing_tests {
     /// f6be190 / 81d7486 / 8a1958e. Sync half of the same routing
     /// discipline — the async migrations closed every
     /// `git_command_async` surface, this shield closes the first sync
-    /// consumer (`helm::deploy`, invoked outside any tokio runtime
-    /// from `main.rs:952`).
+    /// consumer (`helm::deploy`, whose git spawns stay blocking even
+    /// though the function itself is now async).
     ///
     /// This test reads this module's own source via [`include_str!`]
     /// and asserts the raw `Command::new("git")` string does not
@@ -3160,7 +3243,7 @@ mod deploy_git_bin_routing_tests {
         let fn_body = crate::test_support::fn_body_slice_between_markers(
             SOURCE,
             "helm.rs",
-            "pub fn deploy(",
+            "pub async fn deploy(",
             "\npub fn release(",
         );
 
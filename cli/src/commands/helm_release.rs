@@ -0,0 +1,556 @@
+//! HelmRelease upgrade previews and monitoring (`forge helm deploy --diff --watch`)
+//!
+//! The HelmRelease a service overlay deploys is rendered in-process with
+//! [`crate::kustomize`] before and after forge edits the overlay, so both
+//! sides carry the environment's patched `spec.values`. Each side's chart
+//! is then rendered with `helm template`, and the two manifest sets are
+//! compared per resource:
+//!
+//! - resources are matched by kind, namespace and name; ones present on
+//!   only one side are reported as added or removed
+//! - changed resources list their changed fields, compared the way
+//!   [`drift::diff_object`] compares git with the cluster but in both
+//!   directions, so removed fields show up too
+//!
+//! `spec.valuesFrom` references (ConfigMaps, Secrets) live in the cluster
+//! and are not part of the preview. After the commit is pushed,
+//! [`monitor`] follows the HelmRelease's status conditions until it is
+//! `Ready`, or fails with [`KubernetesError::HelmReleaseRemediated`] when
+//! helm-controller rolled the upgrade back or uninstalled it.
+
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use serde_json::Value;
+use std::io::Write;
+use std::path::Path;
+use std::process::Command;
+use std::time::Duration;
+use tracing::{info, warn};
+
+use super::drift;
+use super::helm::{ensure_helm_success, helm_bin};
+use crate::error::KubernetesError;
+use crate::flux_api::{self, FluxKind};
+use crate::{k8s, kustomize};
+
+// ============================================================================
+// Data Structures
+// ============================================================================
+
+/// What `forge helm deploy` does beyond rewriting the image tag.
+#[derive(Debug, Clone)]
+pub struct UpgradeOptions {
+    /// Render the chart before and after the change and print the diff.
+    pub diff: bool,
+    /// After pushing, follow the HelmRelease to `Ready` or remediation.
+    pub watch: bool,
+    /// Local chart directory, instead of the chart named by the HelmRelease.
+    pub chart_dir: Option<String>,
+    /// OCI registry holding charts referenced through a `HelmRepository`.
+    pub registry: String,
+    /// How long [`monitor`] waits: the Kustomization applying the commit,
+    /// then the upgrade and any rollback.
+    pub timeout: Duration,
+}
+
+/// The HelmRelease a service overlay renders to.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HelmReleaseSpec {
+    pub name: String,
+    pub namespace: String,
+    /// Helm release name (`spec.releaseName`, else Flux's default).
+    pub release_name: String,
+    /// Namespace the chart is installed into.
+    pub target_namespace: String,
+    /// `spec.chart.spec.chart`, `None` for `spec.chartRef`.
+    pub chart: Option<String>,
+    pub version: Option<String>,
+    pub source_kind: Option<String>,
+    pub values: Value,
+    pub has_values_from: bool,
+}
+
+/// How one rendered resource changes with the upgrade.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResourceChange {
+    pub kind: String,
+    pub namespace: Option<String>,
+    pub name: String,
+    pub change: ChangeKind,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Modified(Vec<FieldChange>),
+}
+
+/// One changed field; `before` / `after` is `None` where the field is absent.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldChange {
+    pub path: String,
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+}
+
+impl ResourceChange {
+    /// `Kind/namespace/name`, or `Kind/name` without a namespace.
+    pub fn display_name(&self) -> String {
+        match &self.namespace {
+            Some(ns) => format!("{}/{}/{}", self.kind, ns, self.name),
+            None => format!("{}/{}", self.kind, self.name),
+        }
+    }
+}
+
+// ============================================================================
+// HelmRelease Resolution
+// ============================================================================
+
+/// Render the overlay at `kustomization_path` and extract the HelmRelease
+/// for `service`: the one named after the service, else the only one.
+pub fn resolve(kustomization_path: &Path, service: &str) -> Result<HelmReleaseSpec> {
+    let releases: Vec<Value> = kustomize::render(kustomization_path)?
+        .into_iter()
+        .filter(|o| o.get("kind").and_then(Value::as_str) == Some("HelmRelease"))
+        .collect();
+    let release = match releases
+        .iter()
+        .find(|o| o.pointer("/metadata/name").and_then(Value::as_str) == Some(service))
+    {
+        Some(release) => release,
+        None if releases.len() == 1 => &releases[0],
+        None => bail!(
+            "Expected one HelmRelease for '{}' in {}, found {}",
+            service,
+            kustomization_path.display(),
+            releases.len()
+        ),
+    };
+    Ok(HelmReleaseSpec::from_object(release))
+}
+
+impl HelmReleaseSpec {
+    pub fn from_object(object: &Value) -> Self {
+        let text = |pointer: &str| {
+            object
+                .pointer(pointer)
+                .and_then(Value::as_str)
+                .map(str::to_string)
+        };
+        let name = text("/metadata/name").unwrap_or_default();
+        let namespace = text("/metadata/namespace").unwrap_or_else(|| "default".to_string());
+        let target_namespace = text("/spec/targetNamespace");
+        // Flux's default release name is `[<targetNamespace>-]<name>`.
+        let release_name = text("/spec/releaseName").unwrap_or_else(|| match &target_namespace {
+            Some(target) => format!("{target}-{name}"),
+            None => name.clone(),
+        });
+        HelmReleaseSpec {
+            release_name,
+            target_namespace: target_namespace.unwrap_or_else(|| namespace.clone()),
+            chart: text("/spec/chart/spec/chart"),
+            version: text("/spec/chart/spec/version"),
+            source_kind: text("/spec/chart/spec/sourceRef/kind"),
+            values: object
+                .pointer("/spec/values")
+                .cloned()
+                .unwrap_or(Value::Null),
+            has_values_from: object
+                .pointer("/spec/valuesFrom")
+                .and_then(Value::as_array)
+                .is_some_and(|v| !v.is_empty()),
+            name,
+            namespace,
+        }
+    }
+
+    /// The `helm template` chart argument and `--version` for this release.
+    fn chart_reference(&self, options: &UpgradeOptions) -> Result<(String, Option<String>)> {
+        if let Some(dir) = &options.chart_dir {
+            return Ok((dir.clone(), None));
+        }
+        match (&self.chart, self.source_kind.as_deref()) {
+            (Some(chart), Some("HelmRepository")) => Ok((
+                format!("{}/{}", options.registry.trim_end_matches('/'), chart),
+                self.version.clone(),
+            )),
+            (Some(_), Some(kind)) => bail!(
+                "HelmRelease {}/{} takes its chart from a {}; pass --chart-dir to render it",
+                self.namespace,
+                self.name,
+                kind
+            ),
+            _ => bail!(
+                "HelmRelease {}/{} uses spec.chartRef; pass --chart-dir to render it",
+                self.namespace,
+                self.name
+            ),
+        }
+    }
+}
+
+// ============================================================================
+// Rendering and Diffing
+// ============================================================================
+
+/// Render `release`'s chart with its values via `helm template`.
+pub fn render_chart(release: &HelmReleaseSpec, options: &UpgradeOptions) -> Result<Vec<Value>> {
+    let (chart, version) = release.chart_reference(options)?;
+
+    let mut values = tempfile::NamedTempFile::new().context("Failed to create values file")?;
+    let values_yaml = match &release.values {
+        Value::Null => String::new(),
+        v => serde_yaml::to_string(v)?,
+    };
+    values.write_all(values_yaml.as_bytes())?;
+    let values_path = values.path().to_string_lossy().to_string();
+
+    let mut args = vec![
+        "template",
+        release.release_name.as_str(),
+        chart.as_str(),
+        "--namespace",
+        release.target_namespace.as_str(),
+        "-f",
+        values_path.as_str(),
+    ];
+    if let Some(v) = &version {
+        args.extend(["--version", v.as_str()]);
+    }
+
+    let output = Command::new(helm_bin())
+        .args(&args)
+        .output()
+        .context("Failed to run helm template")?;
+    ensure_helm_success(&output, &format!("helm template for {}", chart))?;
+    kustomize::parse_documents(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Resource-level changes from `before` to `after`, in `after`'s order
+/// followed by removals.
+pub fn diff_manifests(before: &[Value], after: &[Value]) -> Vec<ResourceChange> {
+    let mut changes = Vec::new();
+    for object in after {
+        let change = match before.iter().find(|b| same_resource(b, object)) {
+            None => ChangeKind::Added,
+            Some(previous) => {
+                let fields = diff_fields(previous, object);
+                if fields.is_empty() {
+                    continue;
+                }
+                ChangeKind::Modified(fields)
+            }
+        };
+        changes.push(resource_change(object, change));
+    }
+    for object in before {
+        if !after.iter().any(|a| same_resource(a, object)) {
+            changes.push(resource_change(object, ChangeKind::Removed));
+        }
+    }
+    changes
+}
+
+/// Field changes in both directions: [`drift::diff_object`] only reports
+/// fields its first argument declares, so a second pass picks up fields
+/// the upgrade drops.
+fn diff_fields(before: &Value, after: &Value) -> Vec<FieldChange> {
+    let mut fields: Vec<FieldChange> = drift::diff_object(after, before)
+        .into_iter()
+        .map(|d| FieldChange {
+            path: d.path,
+            before: d.live,
+            after: d.desired,
+        })
+        .collect();
+    for removed in drift::diff_object(before, after) {
+        if removed.live.is_none() && !fields.iter().any(|f| f.path == removed.path) {
+            fields.push(FieldChange {
+                path: removed.path,
+                before: removed.desired,
+                after: None,
+            });
+        }
+    }
+    fields.sort_by(|a, b| a.path.cmp(&b.path));
+    fields
+}
+
+fn resource_key(object: &Value) -> (&str, &str, &str) {
+    let text = |pointer: &str| {
+        object
+            .pointer(pointer)
+            .and_then(Value::as_str)
+            .unwrap_or("")
+    };
+    (
+        text("/kind"),
+        text("/metadata/namespace"),
+        text("/metadata/name"),
+    )
+}
+
+fn same_resource(a: &Value, b: &Value) -> bool {
+    resource_key(a) == resource_key(b)
+}
+
+fn resource_change(object: &Value, change: ChangeKind) -> ResourceChange {
+    let (kind, namespace, name) = resource_key(object);
+    ResourceChange {
+        kind: kind.to_string(),
+        namespace: (!namespace.is_empty()).then(|| namespace.to_string()),
+        name: name.to_string(),
+        change,
+    }
+}
+
+/// Render both sides of an upgrade and print the resource diff.
+pub fn preview(
+    before: &HelmReleaseSpec,
+    after: &HelmReleaseSpec,
+    options: &UpgradeOptions,
+) -> Result<Vec<ResourceChange>> {
+    if after.has_values_from {
+        warn!(
+            "HelmRelease {}/{} has valuesFrom; values from ConfigMaps and Secrets are not part of the diff",
+            after.namespace, after.name
+        );
+    }
+    let old = render_chart(before, options)?;
+    let new = render_chart(after, options)?;
+    let changes = diff_manifests(&old, &new);
+    for line in render_changes(after, &changes) {
+        println!("{line}");
+    }
+    Ok(changes)
+}
+
+/// Text lines for a diff: a header, one line per changed resource with its
+/// changed fields, and a summary.
+pub fn render_changes(release: &HelmReleaseSpec, changes: &[ResourceChange]) -> Vec<String> {
+    let show = |v: &Option<Value>| match v {
+        Some(v) => v.to_string(),
+        None => "<absent>".to_string(),
+    };
+    let mut lines = vec![
+        String::new(),
+        format!(
+            "{} HelmRelease/{}/{}",
+            "Upgrade diff".bright_cyan().bold(),
+            release.namespace,
+            release.name
+        ),
+    ];
+    let (mut added, mut removed, mut modified) = (0, 0, 0);
+    for change in changes {
+        let name = change.display_name();
+        match &change.change {
+            ChangeKind::Added => {
+                added += 1;
+                lines.push(format!("  {} {}", "+".green(), name));
+            }
+            ChangeKind::Removed => {
+                removed += 1;
+                lines.push(format!("  {} {}", "-".red(), name));
+            }
+            ChangeKind::Modified(fields) => {
+                modified += 1;
+                lines.push(format!("  {} {}", "~".yellow(), name));
+                for field in fields {
+                    lines.push(format!(
+                        "      {}: {} → {}",
+                        field.path,
+                        show(&field.before),
+                        show(&field.after)
+                    ));
+                }
+            }
+        }
+    }
+    if changes.is_empty() {
+        lines.push("  no rendered resources change".dimmed().to_string());
+    }
+    lines.push(format!(
+        "{added} added, {removed} removed, {modified} changed"
+    ));
+    lines
+}
+
+// ============================================================================
+// Monitoring
+// ============================================================================
+
+/// Generation of the live HelmRelease, `None` if it does not exist yet.
+/// Read before pushing so [`monitor`] can tell the new spec from the old.
+pub async fn live_generation(release: &HelmReleaseSpec) -> Result<Option<i64>> {
+    let client = k8s::create_client().await?;
+    let live = flux_api::get(
+        &client,
+        FluxKind::HelmRelease,
+        &release.name,
+        &release.namespace,
+    )
+    .await?;
+    if let Some(live) = &live {
+        if live.suspended {
+            return Err(KubernetesError::FluxNotReady {
+                resource: live.display_name(),
+                message: "reconciliation is suspended".to_string(),
+            }
+            .into());
+        }
+    }
+    Ok(live.and_then(|l| l.generation))
+}
+
+/// Follow the HelmRelease after a push until helm-controller has finished
+/// with the new spec.
+pub async fn monitor(
+    release: &HelmReleaseSpec,
+    previous_generation: Option<i64>,
+    timeout: Duration,
+) -> Result<()> {
+    info!(
+        "⏳ Waiting for HelmRelease {}/{} to upgrade...",
+        release.namespace, release.name
+    );
+    let client = k8s::create_client().await?;
+    let ready = flux_api::wait_for_helm_release(
+        &client,
+        &release.name,
+        &release.namespace,
+        previous_generation,
+        timeout,
+    )
+    .await?;
+    info!(
+        "   ✅ HelmRelease {} is Ready at {}",
+        ready.display_name(),
+        ready
+            .status
+            .last_attempted_revision
+            .as_deref()
+            .unwrap_or("unknown revision")
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn deployment(name: &str, image: &str, extra_env: bool) -> Value {
+        let mut env = vec![json!({"name": "RUST_LOG", "value": "info"})];
+        if extra_env {
+            env.push(json!({"name": "FEATURE_X", "value": "1"}));
+        }
+        json!({
+            "apiVersion": "apps/v1",
+            "kind": "Deployment",
+            "metadata": {"name": name},
+            "spec": {"template": {"spec": {"containers": [
+                {"name": "app", "image": image, "env": env}
+            ]}}}
+        })
+    }
+
+    #[test]
+    fn from_object_applies_flux_release_name_defaults() {
+        let object = json!({
+            "kind": "HelmRelease",
+            "metadata": {"name": "cart", "namespace": "flux-system"},
+            "spec": {
+                "targetNamespace": "shop",
+                "chart": {"spec": {
+                    "chart": "pleme-microservice",
+                    "version": "1.4.0",
+                    "sourceRef": {"kind": "HelmRepository", "name": "pleme"}
+                }},
+                "values": {"image": {"tag": "amd64-abc"}},
+                "valuesFrom": [{"kind": "Secret", "name": "cart-values"}]
+            }
+        });
+        let spec = HelmReleaseSpec::from_object(&object);
+        assert_eq!(spec.release_name, "shop-cart");
+        assert_eq!(spec.target_namespace, "shop");
+        assert!(spec.has_values_from);
+
+        let options = UpgradeOptions {
+            diff: true,
+            watch: false,
+            chart_dir: None,
+            registry: "oci://ghcr.io/pleme-io/charts/".into(),
+            timeout: Duration::from_secs(900),
+        };
+        assert_eq!(
+            spec.chart_reference(&options).unwrap(),
+            (
+                "oci://ghcr.io/pleme-io/charts/pleme-microservice".into(),
+                Some("1.4.0".into())
+            )
+        );
+
+        let mut git_chart = object.clone();
+        git_chart["spec"]["chart"]["spec"]["sourceRef"]["kind"] = json!("GitRepository");
+        git_chart["spec"]["releaseName"] = json!("cart");
+        let spec = HelmReleaseSpec::from_object(&git_chart);
+        assert_eq!(spec.release_name, "cart");
+        assert!(spec.chart_reference(&options).is_err());
+    }
+
+    #[test]
+    fn diff_manifests_reports_added_removed_and_changed_fields() {
+        let before = vec![
+            deployment("cart", "ghcr.io/pleme-io/cart:amd64-abc", true),
+            json!({"apiVersion": "v1", "kind": "Service", "metadata": {"name": "cart"}}),
+            json!({"apiVersion": "v1", "kind": "ConfigMap", "metadata": {"name": "old"}}),
+        ];
+        let after = vec![
+            deployment("cart", "ghcr.io/pleme-io/cart:amd64-def", false),
+            json!({"apiVersion": "v1", "kind": "Service", "metadata": {"name": "cart"}}),
+            json!({"apiVersion": "v1", "kind": "ConfigMap", "metadata": {"name": "new"}}),
+        ];
+
+        let changes = diff_manifests(&before, &after);
+        assert_eq!(changes.len(), 3);
+        assert_eq!(changes[0].display_name(), "Deployment/cart");
+        let ChangeKind::Modified(fields) = &changes[0].change else {
+            panic!("expected the Deployment to change");
+        };
+        let paths: Vec<&str> = fields.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            [
+                ".spec.template.spec.containers[app].env[FEATURE_X]",
+                ".spec.template.spec.containers[app].image",
+            ]
+        );
+        assert_eq!(fields[0].after, None);
+        assert_eq!(
+            fields[1].before,
+            Some(json!("ghcr.io/pleme-io/cart:amd64-abc"))
+        );
+        assert_eq!(changes[1].display_name(), "ConfigMap/new");
+        assert_eq!(changes[1].change, ChangeKind::Added);
+        assert_eq!(changes[2].display_name(), "ConfigMap/old");
+        assert_eq!(changes[2].change, ChangeKind::Removed);
+    }
+
+    #[test]
+    fn diff_fields_reports_dropped_fields() {
+        let before =
+            json!({"kind": "Service", "spec": {"type": "ClusterIP", "sessionAffinity": "None"}});
+        let after = json!({"kind": "Service", "spec": {"type": "ClusterIP"}});
+        assert_eq!(
+            diff_fields(&before, &after),
+            vec![FieldChange {
+                path: ".spec.sessionAffinity".into(),
+                before: Some(json!("None")),
+                after: None,
+            }]
+        );
+    }
+}
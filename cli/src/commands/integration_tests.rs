@@ -12,6 +12,7 @@ use tokio::process::Command;
 use tokio::time::{sleep, timeout};
 use tracing::{debug, error, info, warn};
 
+use super::test_reports::{interleave_runner_streams, TestReport};
 use crate::infrastructure::kubectl::kubectl_command_async;
 use crate::retry::RetryPolicy;
 
@@ -1149,11 +1150,16 @@ struct RawDeploymentSection {
 
 /// Execute manual integration tests from CLI (nix run .#test:product:service)
 /// This is called directly from the CLI, not during release workflow
+///
+/// With `junit`, each suite's captured output is parsed into per-test
+/// results (a suite with no recognisable runner output becomes a single
+/// test case) and written to that path as JUnit XML.
 pub async fn execute_manual(
     service: &str,
     service_dir: &str,
     repo_root: &str,
     suite_filter: Option<String>,
+    junit: Option<String>,
 ) -> Result<()> {
     // Set up environment for root flake pattern
     crate::repo::activate_root_flake(repo_root, service_dir)?;
@@ -1273,6 +1279,10 @@ pub async fn execute_manual(
     // Execute tests
     let results = execute(config, working_dir).await?;
 
+    if let Some(path) = junit {
+        suite_reports(&results).write_junit(std::path::Path::new(&path), service)?;
+    }
+
     // Return appropriate exit code
     let failed = results.iter().filter(|r| !r.success).count();
     if failed > 0 {
@@ -1282,6 +1292,26 @@ pub async fn execute_manual(
     Ok(())
 }
 
+/// Per-test results for the suites of one run, for `--junit`
+fn suite_reports(results: &[TestResult]) -> TestReport {
+    let mut report = TestReport::default();
+    for result in results {
+        let parsed = TestReport::parse_runner_output(&result.output, &result.suite_name);
+        if parsed.cases.is_empty() {
+            report.record_step(
+                &result.suite_name,
+                &result.suite_name,
+                result.success,
+                result.duration.as_secs_f64(),
+                (!result.success).then(|| result.output.clone()),
+            );
+        } else {
+            report.cases.extend(parsed.cases);
+        }
+    }
+    report
+}
+
 /// Execute pre-deployment tests (runs BEFORE push/deploy)
 /// Returns Ok(()) if all tests pass, Err if any fail (unless on_failure.action = "warn")
 pub async fn execute_pre_deployment_tests(
@@ -1400,8 +1430,10 @@ pub async fn execute_pre_deployment_tests(
                         let output = child.wait_with_output().await?;
                         Ok::<_, anyhow::Error>((
                             output.status.success(),
-                            String::from_utf8_lossy(&output.stdout).to_string()
-                                + &String::from_utf8_lossy(&output.stderr).to_string(),
+                            interleave_runner_streams(
+                                &String::from_utf8_lossy(&output.stdout),
+                                &String::from_utf8_lossy(&output.stderr),
+                            ),
                         ))
                     })
                     .await
@@ -8,7 +8,10 @@ use std::path::Path;
 use tracing::info;
 
 use crate::commands::push;
-use crate::commands::release_commit::commit_cluster_overlay_release;
+use crate::commands::release_commit::{
+    commit_cluster_overlay_release, wait_for_flux_revision, FluxTarget,
+};
+use crate::infrastructure::git::CommitPushOutcome;
 
 /// Release kenshi operator: push image and update K8s manifests for all clusters
 ///
@@ -17,6 +20,7 @@ use crate::commands::release_commit::commit_cluster_overlay_release;
 /// 2. Update primary cluster kustomization.yaml images[] overlay
 /// 3. Update secondary cluster kustomization.yaml images[] overlay
 /// 4. Commit and push to git
+/// 5. With `wait_for_flux`, wait until each target Kustomization has applied the pushed commit
 pub async fn release(
     image_path: String,
     registry: String,
@@ -24,6 +28,7 @@ pub async fn release(
     secondary_kustomization: String,
     retries: u32,
     token: Option<String>,
+    wait_for_flux: Option<&[FluxTarget]>,
 ) -> Result<()> {
     info!("🚀 Starting kenshi operator release");
     info!("   Image: {}", image_path);
@@ -67,13 +72,16 @@ pub async fn release(
     // Step 5: Commit and push
     info!("━━━ Step 4/4: Commit and Push ━━━");
     info!("📤 Committing release changes...");
-    commit_cluster_overlay_release(
+    let outcome = commit_cluster_overlay_release(
         None,
         "kenshi operator",
         &new_tag,
         &[&primary_kustomization, &secondary_kustomization],
     )
     .await?;
+    if let (Some(targets), CommitPushOutcome::Pushed) = (wait_for_flux, outcome) {
+        wait_for_flux_revision(targets).await?;
+    }
 
     println!();
     info!("╔════════════════════════════════════════════════════════════╗");
@@ -82,7 +90,9 @@ pub async fn release(
     println!();
     info!("Image: {}:{}", registry, new_tag);
     info!("Updated all clusters");
-    info!("FluxCD will reconcile the changes automatically.");
+    if wait_for_flux.is_none() {
+        info!("FluxCD will reconcile the changes automatically.");
+    }
     println!();
 
     Ok(())
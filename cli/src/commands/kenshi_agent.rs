@@ -8,7 +8,10 @@ use std::path::Path;
 use tracing::info;
 
 use crate::commands::push;
-use crate::commands::release_commit::commit_cluster_overlay_release;
+use crate::commands::release_commit::{
+    commit_cluster_overlay_release, wait_for_flux_revision, FluxTarget,
+};
+use crate::infrastructure::git::CommitPushOutcome;
 
 /// Release kenshi-agent: push image and update K8s manifests for all clusters
 ///
@@ -19,6 +22,7 @@ use crate::commands::release_commit::commit_cluster_overlay_release;
 /// 4. Update primary cluster builder-pool agentImage field
 /// 5. Update secondary cluster builder-pool agentImage field
 /// 6. Commit and push to git
+/// 7. With `wait_for_flux`, wait until each target Kustomization has applied the pushed commit
 pub async fn release(
     image_path: String,
     registry: String,
@@ -28,6 +32,7 @@ pub async fn release(
     secondary_builder_pool: String,
     retries: u32,
     token: Option<String>,
+    wait_for_flux: Option<&[FluxTarget]>,
 ) -> Result<()> {
     info!("🚀 Starting kenshi-agent release");
     info!("   Image: {}", image_path);
@@ -81,7 +86,7 @@ pub async fn release(
     // Step 7: Commit and push
     info!("━━━ Step 6/6: Commit and Push ━━━");
     info!("📤 Committing release changes...");
-    commit_cluster_overlay_release(
+    let outcome = commit_cluster_overlay_release(
         None,
         "kenshi-agent",
         &new_tag,
@@ -93,6 +98,9 @@ pub async fn release(
         ],
     )
     .await?;
+    if let (Some(targets), CommitPushOutcome::Pushed) = (wait_for_flux, outcome) {
+        wait_for_flux_revision(targets).await?;
+    }
 
     println!();
     info!("╔════════════════════════════════════════════════════════════╗");
@@ -101,7 +109,9 @@ pub async fn release(
     println!();
     info!("Image: {}:{}", registry, new_tag);
     info!("Updated all clusters");
-    info!("FluxCD will reconcile the changes automatically.");
+    if wait_for_flux.is_none() {
+        info!("FluxCD will reconcile the changes automatically.");
+    }
     println!();
 
     Ok(())
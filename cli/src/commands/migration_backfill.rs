@@ -0,0 +1,466 @@
+//! Online Data Migration (Backfill) Runner
+//!
+//! `forge migrations backfill` runs a data_only migration's backfill outside
+//! the migration job, so a large UPDATE never holds one long transaction
+//! that locks the table and times out the Shinka job:
+//!
+//! 1. Read the migration's `backfill` block from migration-manifest.yaml
+//! 2. Walk `table` in `key` order, `batch_size` rows at a time, running the
+//!    backfill statement for each batch in its own transaction on the CNPG
+//!    primary
+//! 3. Commit the batch's last key to `forge_backfill_progress` in the same
+//!    transaction, so an interrupted run resumes after the last committed
+//!    batch
+//! 4. Sleep between batches and stop cleanly once `--max-duration` is spent
+//!
+//! Every committed batch emits a `BackfillProgress` event; the run ends with
+//! `BackfillCompleted` (or `BackfillFailed`).
+
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use super::migration_validation::{BackfillSpec, MigrationManifest, BACKFILL_BATCH_PLACEHOLDER};
+use super::prerelease::PreReleaseConfig;
+use super::seed::{exec_psql, find_primary_pod, pg_escape};
+use crate::config::DeployConfig;
+use crate::observability::{
+    emit_event, BackfillCompletedEvent, BackfillFailedEvent, BackfillProgressEvent, EventMetadata,
+    ReleaseEvent,
+};
+
+/// Table recording each backfill's resume cursor
+const PROGRESS_TABLE: &str = "forge_backfill_progress";
+
+const DEFAULT_BATCH_SIZE: u64 = 1000;
+
+/// Quiet, unaligned, tuples-only output so query results parse as `a|b|c`
+const PSQL_PREAMBLE: &str = "\\set QUIET on\n\\pset tuples_only on\n\\pset format unaligned\n";
+
+/// Command-line overrides for a backfill run
+#[derive(Debug, Default)]
+pub struct BackfillOptions {
+    pub batch_size: Option<u64>,
+    pub sleep: Option<String>,
+    pub max_duration: Option<String>,
+    /// Discard the recorded cursor and start from the first row
+    pub restart: bool,
+}
+
+/// Progress recorded in [`PROGRESS_TABLE`]
+#[derive(Debug, Default, PartialEq)]
+struct Progress {
+    cursor: Option<String>,
+    rows_processed: u64,
+    completed: bool,
+}
+
+/// Key range of the next batch
+#[derive(Debug, PartialEq)]
+struct Batch {
+    rows: u64,
+    last_key: String,
+}
+
+fn progress_table_sql() -> String {
+    format!(
+        "CREATE TABLE IF NOT EXISTS {} (\n  \
+         name text PRIMARY KEY,\n  \
+         cursor text,\n  \
+         rows_processed bigint NOT NULL DEFAULT 0,\n  \
+         batches bigint NOT NULL DEFAULT 0,\n  \
+         completed_at timestamptz,\n  \
+         updated_at timestamptz NOT NULL DEFAULT now()\n);\n",
+        PROGRESS_TABLE
+    )
+}
+
+fn load_progress_sql(name: &str) -> String {
+    format!(
+        "SELECT rows_processed, completed_at IS NOT NULL, cursor IS NOT NULL, coalesce(cursor, '') \
+         FROM {} WHERE name = '{}';\n",
+        PROGRESS_TABLE,
+        pg_escape(name)
+    )
+}
+
+/// Parse the [`load_progress_sql`] row. The cursor is the last column so a
+/// key containing the `|` separator survives.
+fn parse_progress(output: &str) -> Result<Progress> {
+    let Some(line) = output.lines().find(|l| !l.trim().is_empty()) else {
+        return Ok(Progress::default());
+    };
+    let fields: Vec<&str> = line.splitn(4, '|').collect();
+    if fields.len() != 4 {
+        bail!("Unexpected {} row: {}", PROGRESS_TABLE, line);
+    }
+    Ok(Progress {
+        rows_processed: fields[0]
+            .trim()
+            .parse()
+            .with_context(|| format!("Invalid rows_processed in {}", line))?,
+        completed: fields[1].trim() == "t",
+        cursor: (fields[2].trim() == "t").then(|| fields[3].to_string()),
+    })
+}
+
+/// Predicate for rows after `cursor`, further restricted by the spec's filter
+fn walk_predicate(spec: &BackfillSpec, cursor: Option<&str>) -> String {
+    let mut predicate = match cursor {
+        Some(c) => format!("{} > '{}'", spec.key, pg_escape(c)),
+        None => "TRUE".to_string(),
+    };
+    if let Some(ref filter) = spec.filter {
+        predicate.push_str(&format!(" AND ({})", filter));
+    }
+    predicate
+}
+
+/// Count the next batch and find its last key. `array_agg` rather than
+/// `max` so keys without a `max` aggregate (uuid) work.
+fn next_batch_sql(spec: &BackfillSpec, cursor: Option<&str>, batch_size: u64) -> String {
+    format!(
+        "SELECT count(*), coalesce((array_agg(k ORDER BY k DESC))[1]::text, '') \
+         FROM (SELECT {key} AS k FROM {table} WHERE {predicate} ORDER BY {key} LIMIT {limit}) batch;\n",
+        key = spec.key,
+        table = spec.table,
+        predicate = walk_predicate(spec, cursor),
+        limit = batch_size
+    )
+}
+
+fn parse_batch(output: &str) -> Result<Option<Batch>> {
+    let line = output
+        .lines()
+        .find(|l| !l.trim().is_empty())
+        .context("Batch query returned no row")?;
+    let (rows, last_key) = line
+        .split_once('|')
+        .with_context(|| format!("Unexpected batch row: {}", line))?;
+    let rows: u64 = rows
+        .trim()
+        .parse()
+        .with_context(|| format!("Invalid batch row count in {}", line))?;
+    Ok((rows > 0).then(|| Batch {
+        rows,
+        last_key: last_key.to_string(),
+    }))
+}
+
+/// One batch: the backfill statement over `(cursor, last_key]` and the
+/// cursor advance, committed together.
+fn batch_sql(name: &str, spec: &BackfillSpec, cursor: Option<&str>, batch: &Batch) -> String {
+    let range = format!(
+        "({} AND {} <= '{}')",
+        walk_predicate(spec, cursor),
+        spec.key,
+        pg_escape(&batch.last_key)
+    );
+    let statement = spec
+        .sql
+        .trim()
+        .trim_end_matches(';')
+        .replace(BACKFILL_BATCH_PLACEHOLDER, &range);
+    format!(
+        "BEGIN;\n{statement};\n\
+         INSERT INTO {table} (name, cursor, rows_processed, batches, updated_at) \
+         VALUES ('{name}', '{cursor}', {rows}, 1, now()) \
+         ON CONFLICT (name) DO UPDATE SET cursor = EXCLUDED.cursor, \
+         rows_processed = {table}.rows_processed + EXCLUDED.rows_processed, \
+         batches = {table}.batches + 1, updated_at = now();\n\
+         COMMIT;\n",
+        statement = statement,
+        table = PROGRESS_TABLE,
+        name = pg_escape(name),
+        cursor = pg_escape(&batch.last_key),
+        rows = batch.rows
+    )
+}
+
+fn complete_sql(name: &str) -> String {
+    format!(
+        "INSERT INTO {table} (name, completed_at, updated_at) VALUES ('{name}', now(), now()) \
+         ON CONFLICT (name) DO UPDATE SET completed_at = now(), updated_at = now();\n",
+        table = PROGRESS_TABLE,
+        name = pg_escape(name)
+    )
+}
+
+fn reset_sql(name: &str) -> String {
+    format!(
+        "DELETE FROM {} WHERE name = '{}';\n",
+        PROGRESS_TABLE,
+        pg_escape(name)
+    )
+}
+
+/// Load a data_only migration's backfill block from the manifest
+fn load_backfill_spec(seaorm_dir: &Path, migration: &str) -> Result<BackfillSpec> {
+    let manifest_path = seaorm_dir.join("migration-manifest.yaml");
+    let content = std::fs::read_to_string(&manifest_path)
+        .with_context(|| format!("Failed to read {}", manifest_path.display()))?;
+    let manifest: MigrationManifest = serde_yaml::from_str(&content)
+        .with_context(|| format!("Failed to parse {}", manifest_path.display()))?;
+    let entry = manifest
+        .migrations
+        .get(migration)
+        .with_context(|| format!("'{}' is not in {}", migration, manifest_path.display()))?;
+    let spec = entry
+        .backfill
+        .clone()
+        .with_context(|| format!("'{}' has no backfill block in the manifest", migration))?;
+    if !spec.sql.contains(BACKFILL_BATCH_PLACEHOLDER) {
+        bail!(
+            "Backfill sql of '{}' must restrict each batch with {}",
+            migration,
+            BACKFILL_BATCH_PLACEHOLDER
+        );
+    }
+    Ok(spec)
+}
+
+/// Run (or resume) a batched backfill against a product environment's
+/// primary database.
+pub async fn execute(
+    product: &str,
+    repo_root: &str,
+    env: &str,
+    migration: &str,
+    options: &BackfillOptions,
+) -> Result<()> {
+    let product_config = DeployConfig::load_product_config(product, repo_root)?;
+    let namespace = product_config.namespace_for_env(env);
+    let db_name = product_config.db_name().to_string();
+    let product_dir = crate::config::resolve_product_dir(Path::new(repo_root), product);
+    let seaorm_dir = PreReleaseConfig::from_working_dir(&product_dir).seaorm_migrations_dir;
+    let spec = load_backfill_spec(&seaorm_dir, migration)?;
+
+    let batch_size = options
+        .batch_size
+        .or(spec.batch_size)
+        .unwrap_or(DEFAULT_BATCH_SIZE);
+    if batch_size == 0 {
+        bail!("--batch-size must be at least 1");
+    }
+    let sleep = match options.sleep.as_deref().or(spec.sleep.as_deref()) {
+        Some(s) => crate::duration::parse_duration(s)?,
+        None => Duration::ZERO,
+    };
+    let max_duration = options
+        .max_duration
+        .as_deref()
+        .map(crate::duration::parse_duration)
+        .transpose()?;
+
+    let git_sha = crate::git::get_short_sha_async_in(&product_dir)
+        .await
+        .unwrap_or_else(|_| "unknown".to_string());
+    let metadata = EventMetadata::new(&git_sha, product, "migration", env, &namespace);
+
+    println!(
+        "🧱 {}",
+        format!("Backfill {} ({}, namespace {})", migration, env, namespace).bold()
+    );
+    let pod = find_primary_pod(&namespace, &product_config.postgres_cluster())?;
+    println!("   Primary: {}", pod);
+    let psql = |sql: &str| {
+        exec_psql(
+            &namespace,
+            &pod,
+            &db_name,
+            &format!("{}{}", PSQL_PREAMBLE, sql),
+        )
+    };
+
+    psql(&progress_table_sql())?;
+    if options.restart {
+        psql(&reset_sql(migration))?;
+    }
+    let mut progress = parse_progress(&psql(&load_progress_sql(migration))?)?;
+    if progress.completed {
+        println!(
+            "   {} Already complete ({} rows); pass --restart to run it again",
+            "✅".green(),
+            progress.rows_processed
+        );
+        return Ok(());
+    }
+    match progress.cursor {
+        Some(ref cursor) => println!(
+            "   Resuming after {} = {} ({} rows done)",
+            spec.key, cursor, progress.rows_processed
+        ),
+        None => println!("   Starting from the first row of {}", spec.table),
+    }
+    println!(
+        "   Batch size {}, sleep {}s{}",
+        batch_size,
+        sleep.as_secs(),
+        max_duration
+            .map(|d| format!(", max duration {}s", d.as_secs()))
+            .unwrap_or_default()
+    );
+
+    let start = Instant::now();
+    let mut batches = 0u64;
+    let fail = |cursor: Option<String>, error: &anyhow::Error| {
+        emit_event(ReleaseEvent::BackfillFailed(BackfillFailedEvent {
+            metadata: metadata.clone(),
+            backfill: migration.to_string(),
+            cursor,
+            duration_secs: start.elapsed().as_secs_f64(),
+            error: format!("{:#}", error),
+        }));
+    };
+
+    let finished = loop {
+        if max_duration.is_some_and(|max| start.elapsed() >= max) {
+            break false;
+        }
+
+        let cursor = progress.cursor.as_deref();
+        let batch = match psql(&next_batch_sql(&spec, cursor, batch_size))
+            .and_then(|out| parse_batch(&out))
+        {
+            Ok(batch) => batch,
+            Err(e) => {
+                fail(progress.cursor.clone(), &e);
+                return Err(e.context(format!("Backfill {} failed", migration)));
+            }
+        };
+        let Some(batch) = batch else {
+            if let Err(e) = psql(&complete_sql(migration)) {
+                fail(progress.cursor.clone(), &e);
+                return Err(e);
+            }
+            break true;
+        };
+
+        if let Err(e) = psql(&batch_sql(migration, &spec, cursor, &batch)) {
+            fail(progress.cursor.clone(), &e);
+            return Err(e.context(format!(
+                "Backfill {} failed; rerun to resume after the last committed batch",
+                migration
+            )));
+        }
+
+        batches += 1;
+        progress.rows_processed += batch.rows;
+        println!(
+            "   Batch {}: {} rows (total {}, {} = {})",
+            batches, batch.rows, progress.rows_processed, spec.key, batch.last_key
+        );
+        emit_event(ReleaseEvent::BackfillProgress(BackfillProgressEvent {
+            metadata: metadata.clone(),
+            backfill: migration.to_string(),
+            batch: batches,
+            batch_rows: batch.rows,
+            rows_processed: progress.rows_processed,
+            cursor: batch.last_key.clone(),
+            duration_secs: start.elapsed().as_secs_f64(),
+        }));
+        progress.cursor = Some(batch.last_key);
+
+        // A short batch was the last one; only pause before a full batch's successor.
+        if batch.rows == batch_size && !sleep.is_zero() {
+            tokio::time::sleep(sleep).await;
+        }
+    };
+
+    emit_event(ReleaseEvent::BackfillCompleted(BackfillCompletedEvent {
+        metadata: metadata.clone(),
+        backfill: migration.to_string(),
+        batches,
+        rows_processed: progress.rows_processed,
+        duration_secs: start.elapsed().as_secs_f64(),
+        finished,
+    }));
+
+    println!();
+    if finished {
+        println!(
+            "{} Backfill complete: {} rows in {} batches ({:.1}s)",
+            "✅".green(),
+            progress.rows_processed,
+            batches,
+            start.elapsed().as_secs_f64()
+        );
+    } else {
+        println!(
+            "{} Stopped at --max-duration after {} batches; rerun to resume after {} = {}",
+            "⏸".yellow(),
+            batches,
+            spec.key,
+            progress.cursor.as_deref().unwrap_or("-")
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec() -> BackfillSpec {
+        serde_yaml::from_str(
+            r#"
+table: users
+filter: "deleted_at IS NULL"
+sql: "UPDATE users SET display_name = name WHERE {batch} AND display_name IS NULL;"
+"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_batch_sql_scopes_statement_to_key_range() {
+        let spec = spec();
+        assert_eq!(spec.key, "id");
+
+        let batch = Batch {
+            rows: 1000,
+            last_key: "2000".to_string(),
+        };
+        let sql = batch_sql("m_backfill", &spec, Some("1000"), &batch);
+        assert!(sql.contains(
+            "UPDATE users SET display_name = name WHERE (id > '1000' AND (deleted_at IS NULL) \
+             AND id <= '2000') AND display_name IS NULL;"
+        ));
+        assert!(sql.starts_with("BEGIN;"));
+        assert!(sql.trim_end().ends_with("COMMIT;"));
+        assert!(sql.contains("VALUES ('m_backfill', '2000', 1000, 1, now())"));
+
+        let first = next_batch_sql(&spec, None, 500);
+        assert!(first.contains("WHERE TRUE AND (deleted_at IS NULL) ORDER BY id LIMIT 500"));
+    }
+
+    #[test]
+    fn test_parse_progress() {
+        assert_eq!(parse_progress("").unwrap(), Progress::default());
+        assert_eq!(
+            parse_progress("4000|f|t|a|b\n").unwrap(),
+            Progress {
+                cursor: Some("a|b".to_string()),
+                rows_processed: 4000,
+                completed: false,
+            }
+        );
+        let done = parse_progress("0|t|f|\n").unwrap();
+        assert!(done.completed);
+        assert_eq!(done.cursor, None);
+    }
+
+    #[test]
+    fn test_parse_batch() {
+        assert_eq!(parse_batch("0|\n").unwrap(), None);
+        assert_eq!(
+            parse_batch("250|3f2b9c1e-0000-4000-8000-000000000000\n").unwrap(),
+            Some(Batch {
+                rows: 250,
+                last_key: "3f2b9c1e-0000-4000-8000-000000000000".to_string(),
+            })
+        );
+        assert!(parse_batch("").is_err());
+    }
+}
@@ -0,0 +1,985 @@
+//! Migration Lock-Safety Lint (G8c)
+//!
+//! Where G6-G8 check idempotency, soft deletes and the SeaORM
+//! expand-contract markers, this pass looks at what a migration does to a
+//! running database. It parses the SQL statements of SQLx migrations
+//! (`*.sql`) and of the string literals in SeaORM migrations (`m*.rs`) and
+//! flags:
+//!
+//! - `alter-column-type`: `ALTER COLUMN ... TYPE` rewrites the table under
+//!   an ACCESS EXCLUSIVE lock
+//! - `add-column-volatile-default`: `ADD COLUMN` with a volatile default
+//!   (`gen_random_uuid()`, `random()`, `clock_timestamp()`, `nextval()`,
+//!   serial types) rewrites the table under an ACCESS EXCLUSIVE lock
+//! - `create-index-non-concurrent`: `CREATE INDEX` without `CONCURRENTLY`
+//!   blocks writes for the whole build
+//! - `set-not-null-without-check`: `SET NOT NULL` scans the table under an
+//!   ACCESS EXCLUSIVE lock unless a validated `CHECK (col IS NOT NULL)`
+//!   constraint on the table already proves it
+//! - `drop-without-deprecation`: `DROP COLUMN` / `DROP TABLE` without an
+//!   earlier migration marking the column or table deprecated with
+//!   `COMMENT ON ... IS 'DEPRECATED ...'`
+//! - `rename`: renaming a column or table breaks the code still running
+//!   against the old name during a rolling deploy
+//!
+//! The lock rules skip tables created in the same migration and the
+//! tables listed in `small_tables`. A migration opts out of rules with a
+//! comment anywhere in the file, e.g. `-- forge-lint: allow(rename)` or
+//! `// forge-lint: allow(alter-column-type, rename)`. Rule severities
+//! default to [`RULES`] and are overridden per rule by `lint_severity`;
+//! only `error` findings fail the gate.
+//!
+//! Only migrations added since the previous release are reported on (the
+//! same baseline G8d uses); migrations that already shipped are read for
+//! history but never flagged. Before the first release the whole chain is
+//! linted.
+
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use regex::Regex;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+use tokio::fs;
+
+use super::migration_validation::{find_seaorm_migration_files, find_sql_files_with_config};
+use super::prerelease::{load_gates_config, PreReleaseConfig};
+use crate::config::{LintSeverity, MigrationGatesConfig};
+
+/// A lint rule and its default severity.
+pub struct LintRule {
+    pub id: &'static str,
+    pub severity: LintSeverity,
+    /// Whether the rule is about locking, and so skips small and new tables.
+    pub lock: bool,
+    pub message: &'static str,
+}
+
+/// Every rule the lint knows, with its default severity.
+pub const RULES: &[LintRule] = &[
+    LintRule {
+        id: "alter-column-type",
+        severity: LintSeverity::Error,
+        lock: true,
+        message: "Changing a column type rewrites the table under an ACCESS EXCLUSIVE lock. \
+                  Add a new column, backfill it in batches, and switch reads over.",
+    },
+    LintRule {
+        id: "add-column-volatile-default",
+        severity: LintSeverity::Error,
+        lock: true,
+        message: "A volatile DEFAULT (or serial type) rewrites the table under an ACCESS EXCLUSIVE lock. \
+                  Add the column without a default, then backfill in batches.",
+    },
+    LintRule {
+        id: "create-index-non-concurrent",
+        severity: LintSeverity::Error,
+        lock: true,
+        message: "CREATE INDEX without CONCURRENTLY blocks writes until the index is built. \
+                  Use CREATE INDEX CONCURRENTLY (outside a transaction).",
+    },
+    LintRule {
+        id: "set-not-null-without-check",
+        severity: LintSeverity::Error,
+        lock: true,
+        message: "SET NOT NULL scans the table under an ACCESS EXCLUSIVE lock. \
+                  First add CHECK (col IS NOT NULL) NOT VALID and VALIDATE CONSTRAINT it.",
+    },
+    LintRule {
+        id: "drop-without-deprecation",
+        severity: LintSeverity::Error,
+        lock: false,
+        message: "Dropping a column or table breaks code that still reads it. \
+                  Mark it deprecated in an earlier migration (COMMENT ON ... IS 'DEPRECATED ...') \
+                  and drop it once no release references it.",
+    },
+    LintRule {
+        id: "rename",
+        severity: LintSeverity::Warning,
+        lock: false,
+        message: "Renaming breaks code still running against the old name during the rollout. \
+                  Use expand-contract: add the new name, dual-write, then drop the old one.",
+    },
+];
+
+pub(super) static ALTER_TABLE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"(?i)^ALTER TABLE (?:IF EXISTS )?(?:ONLY )?([\w."]+)"#).unwrap());
+static CREATE_TABLE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?i)^CREATE (?:UNLOGGED |TEMP |TEMPORARY )?TABLE (?:IF NOT EXISTS )?([\w."]+)"#)
+        .unwrap()
+});
+static CREATE_INDEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?i)^CREATE (?:UNIQUE )?INDEX\b.*? ON (?:ONLY )?([\w."]+)"#).unwrap()
+});
+static ALTER_TYPE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"(?i)\bALTER (?:COLUMN )?[\w"]+ (?:SET DATA )?TYPE\b"#).unwrap());
+static VOLATILE_DEFAULT: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r#"(?i)\bADD (?:COLUMN )?(?:IF NOT EXISTS )?[\w"]+ (?:(?:SMALL|BIG)?SERIAL\b|[^,]*\bDEFAULT\b[^,]*\b(?:random|gen_random_uuid|uuid_generate_v[14]|clock_timestamp|timeofday|nextval)\s*\()"#,
+    )
+    .unwrap()
+});
+static SET_NOT_NULL: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"(?i)\bALTER (?:COLUMN )?([\w"]+) SET NOT NULL\b"#).unwrap());
+static NOT_NULL_CHECK: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?i)\bCHECK \(\s*\(?\s*([\w"]+) IS NOT NULL\s*\)?\s*\)"#).unwrap()
+});
+static VALIDATE_CONSTRAINT: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)\bVALIDATE CONSTRAINT\b").unwrap());
+pub(super) static DROP_COLUMN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?i)(?:^ALTER TABLE \S+(?: \S+)?|,) DROP (?:COLUMN )?(?:IF EXISTS )?([\w"]+)"#)
+        .unwrap()
+});
+pub(super) static DROP_TABLE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?i)^DROP TABLE (?:IF EXISTS )?([\w.", ]+?)(?: CASCADE| RESTRICT)?$"#).unwrap()
+});
+pub(super) static RENAME: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"(?i)\bRENAME (?:COLUMN )?([\w"]+ )?TO [\w"]+"#).unwrap());
+static DEPRECATED_COMMENT: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?i)^COMMENT ON (COLUMN|TABLE) ([\w."]+) IS '\s*DEPRECATED"#).unwrap()
+});
+static ALLOW_DIRECTIVE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"forge-lint:\s*allow\(([^)]*)\)").unwrap());
+
+/// `ALTER TABLE ... DROP` targets that are not columns.
+pub(super) const DROP_KEYWORDS: &[&str] =
+    &["CONSTRAINT", "DEFAULT", "NOT", "IDENTITY", "EXPRESSION"];
+
+// =============================================================================
+// Data Structures
+// =============================================================================
+
+/// One statement that breaks a rule.
+#[derive(Debug, Clone)]
+pub struct LintFinding {
+    pub rule: &'static str,
+    pub severity: LintSeverity,
+    pub file: PathBuf,
+    pub line_number: usize,
+    pub statement: String,
+    pub message: &'static str,
+}
+
+impl LintFinding {
+    pub fn format(&self) -> String {
+        let severity = match self.severity {
+            LintSeverity::Error => "error",
+            LintSeverity::Warning => "warning",
+            LintSeverity::Off => "off",
+        };
+        format!(
+            "{}:{} - {} [{}]\n  Statement: {}\n  Suggestion: {}",
+            self.file.display(),
+            self.line_number,
+            severity,
+            self.rule,
+            truncate(&self.statement, 100),
+            self.message
+        )
+    }
+}
+
+/// Result of linting a product's migrations
+#[derive(Debug, Default)]
+pub struct MigrationLintResult {
+    /// Number of migration files linted
+    pub files_checked: usize,
+    pub findings: Vec<LintFinding>,
+}
+
+impl MigrationLintResult {
+    pub fn errors(&self) -> Vec<&LintFinding> {
+        self.findings
+            .iter()
+            .filter(|f| f.severity == LintSeverity::Error)
+            .collect()
+    }
+
+    pub fn warnings(&self) -> Vec<&LintFinding> {
+        self.findings
+            .iter()
+            .filter(|f| f.severity == LintSeverity::Warning)
+            .collect()
+    }
+}
+
+/// Facts established by earlier migrations of one chain.
+#[derive(Debug, Default)]
+pub struct LintHistory {
+    /// `table` or `table.column` marked deprecated.
+    deprecated: BTreeSet<String>,
+    /// `(table, column)` pairs with a `CHECK (column IS NOT NULL)`.
+    not_null_checks: BTreeSet<(String, String)>,
+    /// Tables with a validated constraint.
+    validated: BTreeSet<String>,
+}
+
+/// A SQL statement and the 1-based line it starts on.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct Statement {
+    pub(super) line_number: usize,
+    /// Comment-free text with whitespace collapsed to single spaces.
+    pub(super) text: String,
+}
+
+// =============================================================================
+// Statement Extraction
+// =============================================================================
+
+/// Split SQL into statements at `;`, ignoring semicolons inside quotes and
+/// dollar-quoted bodies, and dropping comments.
+fn split_sql(sql: &str, first_line: usize) -> Vec<Statement> {
+    let chars: Vec<char> = sql.chars().collect();
+    let mut statements = Vec::new();
+    let mut current = String::new();
+    let mut line = first_line;
+    let mut start_line = None;
+    let mut i = 0;
+
+    let mut push = |current: &mut String, start_line: &mut Option<usize>| {
+        let text = current.split_whitespace().collect::<Vec<_>>().join(" ");
+        if let (false, Some(line_number)) = (text.is_empty(), *start_line) {
+            statements.push(Statement { line_number, text });
+        }
+        current.clear();
+        *start_line = None;
+    };
+
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        if c == '-' && next == Some('-') {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+            continue;
+        }
+        if c == '/' && next == Some('*') {
+            i += 2;
+            while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                if chars[i] == '\n' {
+                    line += 1;
+                }
+                i += 1;
+            }
+            i += 2;
+            current.push(' ');
+            continue;
+        }
+        if c == '\n' {
+            line += 1;
+        }
+        if c == ';' {
+            push(&mut current, &mut start_line);
+            i += 1;
+            continue;
+        }
+        if !c.is_whitespace() && start_line.is_none() {
+            start_line = Some(line);
+        }
+
+        // Quoted literals and dollar-quoted bodies are copied verbatim.
+        let closing = match c {
+            '\'' => Some("'".to_string()),
+            '$' => {
+                let tag_len = chars[i + 1..]
+                    .iter()
+                    .take_while(|c| c.is_alphanumeric() || **c == '_')
+                    .count();
+                (chars.get(i + 1 + tag_len) == Some(&'$'))
+                    .then(|| chars[i..i + tag_len + 2].iter().collect())
+            }
+            _ => None,
+        };
+        match closing {
+            Some(closing) => {
+                let opening_len = closing.chars().count();
+                current.extend(&chars[i..i + opening_len]);
+                i += opening_len;
+                let close: Vec<char> = closing.chars().collect();
+                while i < chars.len() && !chars[i..].starts_with(&close) {
+                    if chars[i] == '\n' {
+                        line += 1;
+                    }
+                    current.push(chars[i]);
+                    i += 1;
+                }
+                let end = (i + close.len()).min(chars.len());
+                current.extend(&chars[i..end]);
+                i = end;
+            }
+            None => {
+                current.push(c);
+                i += 1;
+            }
+        }
+    }
+    push(&mut current, &mut start_line);
+    statements
+}
+
+/// String literals in Rust source (`"..."`, `r"..."`, `r#"..."#`) with
+/// the line each starts on. Comments and char literals are skipped.
+fn rust_string_literals(source: &str) -> Vec<(usize, String)> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut literals = Vec::new();
+    let mut line = 1;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        if c == '\n' {
+            line += 1;
+            i += 1;
+        } else if c == '/' && next == Some('/') {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+        } else if c == '\'' && chars.get(i + 2) == Some(&'\'') {
+            i += 3;
+        } else if c == '\'' && next == Some('\\') {
+            i += 2;
+            while i < chars.len() && chars[i] != '\'' {
+                i += 1;
+            }
+            i += 1;
+        } else if c == 'r' && matches!(next, Some('"') | Some('#')) {
+            let hashes = chars[i + 1..].iter().take_while(|c| **c == '#').count();
+            if chars.get(i + 1 + hashes) != Some(&'"') {
+                i += 1;
+                continue;
+            }
+            let start_line = line;
+            let mut close = vec!['"'];
+            close.extend(std::iter::repeat_n('#', hashes));
+            i += hashes + 2;
+            let mut literal = String::new();
+            while i < chars.len() && !chars[i..].starts_with(&close) {
+                if chars[i] == '\n' {
+                    line += 1;
+                }
+                literal.push(chars[i]);
+                i += 1;
+            }
+            i += close.len();
+            literals.push((start_line, literal));
+        } else if c == '"' {
+            let start_line = line;
+            let mut literal = String::new();
+            i += 1;
+            while i < chars.len() && chars[i] != '"' {
+                match chars[i] {
+                    '\\' => {
+                        match chars.get(i + 1) {
+                            Some('n') => literal.push('\n'),
+                            Some('\n') => line += 1,
+                            Some(escaped) => literal.push(*escaped),
+                            None => {}
+                        }
+                        i += 2;
+                        continue;
+                    }
+                    '\n' => line += 1,
+                    _ => {}
+                }
+                literal.push(chars[i]);
+                i += 1;
+            }
+            i += 1;
+            literals.push((start_line, literal));
+        } else if c.is_alphanumeric() || c == '_' {
+            // Skip whole identifiers so a trailing `r` (e.g. `var"`) never
+            // opens a raw string.
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+        } else {
+            i += 1;
+        }
+    }
+    literals
+}
+
+/// The SQL statements of a migration file.
+pub(super) fn statements(path: &Path, content: &str) -> Vec<Statement> {
+    if path.extension().is_some_and(|e| e == "rs") {
+        rust_string_literals(content)
+            .into_iter()
+            .flat_map(|(line, literal)| split_sql(&literal, line))
+            .collect()
+    } else {
+        split_sql(content, 1)
+    }
+}
+
+/// Rules a file opts out of with `forge-lint: allow(...)` comments.
+pub(super) fn allowed_rules(content: &str) -> BTreeSet<String> {
+    ALLOW_DIRECTIVE
+        .captures_iter(content)
+        .flat_map(|c| {
+            c[1].split(',')
+                .map(|r| r.trim().to_string())
+                .collect::<Vec<_>>()
+        })
+        .filter(|r| !r.is_empty())
+        .collect()
+}
+
+/// Lowercased name without quotes or schema.
+pub(super) fn normalize_name(name: &str) -> String {
+    let name = name.rsplit('.').next().unwrap_or(name);
+    name.trim_matches('"').to_lowercase()
+}
+
+/// History key for a `COMMENT ON COLUMN|TABLE <target>` deprecation:
+/// `table.column` for columns (any schema prefix dropped), `table` for tables.
+fn deprecation_target(object: &str, target: &str) -> String {
+    let target = target.replace('"', "").to_lowercase();
+    if object.eq_ignore_ascii_case("COLUMN") {
+        let parts: Vec<&str> = target.split('.').collect();
+        parts[parts.len().saturating_sub(2)..].join(".")
+    } else {
+        normalize_name(&target)
+    }
+}
+
+fn truncate(text: &str, max: usize) -> String {
+    match text.char_indices().nth(max) {
+        Some((index, _)) => format!("{}...", &text[..index]),
+        None => text.to_string(),
+    }
+}
+
+// =============================================================================
+// Linting
+// =============================================================================
+
+/// Effective severity of every rule under `config`.
+fn severities(config: &MigrationGatesConfig) -> BTreeMap<&'static str, LintSeverity> {
+    RULES
+        .iter()
+        .map(|rule| {
+            let severity = config
+                .lint_severity
+                .get(rule.id)
+                .copied()
+                .unwrap_or(rule.severity);
+            (rule.id, severity)
+        })
+        .collect()
+}
+
+/// Lint one migration file, then record what it establishes in `history`
+/// for the files after it.
+pub fn lint_file(
+    path: &Path,
+    content: &str,
+    history: &mut LintHistory,
+    config: &MigrationGatesConfig,
+) -> Vec<LintFinding> {
+    let statements = statements(path, content);
+    let allowed = allowed_rules(content);
+    let severities = severities(config);
+    let small_tables: BTreeSet<String> = config
+        .small_tables
+        .iter()
+        .map(|t| normalize_name(t))
+        .collect();
+    let mut created = BTreeSet::new();
+    let mut findings = Vec::new();
+    let mut deprecated_here = Vec::new();
+
+    for statement in &statements {
+        let text = statement.text.as_str();
+        let mut hits: Vec<&'static str> = Vec::new();
+        // Table whose lock the statement takes, for the lock-rule exemptions.
+        let mut locked_table = None;
+
+        if let Some(c) = CREATE_TABLE.captures(text) {
+            created.insert(normalize_name(&c[1]));
+        }
+        if let Some(c) = DEPRECATED_COMMENT.captures(text) {
+            deprecated_here.push(deprecation_target(&c[1], &c[2]));
+        }
+
+        if let Some(c) = CREATE_INDEX.captures(text) {
+            locked_table = Some(normalize_name(&c[1]));
+            if !text.to_uppercase().contains(" CONCURRENTLY ") {
+                hits.push("create-index-non-concurrent");
+            }
+        }
+
+        if let Some(c) = ALTER_TABLE.captures(text) {
+            let table = normalize_name(&c[1]);
+
+            if ALTER_TYPE.is_match(text) {
+                hits.push("alter-column-type");
+            }
+            if VOLATILE_DEFAULT.is_match(text) {
+                hits.push("add-column-volatile-default");
+            }
+            for c in SET_NOT_NULL.captures_iter(text) {
+                let proven = history
+                    .not_null_checks
+                    .contains(&(table.clone(), normalize_name(&c[1])))
+                    && history.validated.contains(&table);
+                if !proven {
+                    hits.push("set-not-null-without-check");
+                }
+            }
+            for c in DROP_COLUMN.captures_iter(text) {
+                let column = normalize_name(&c[1]);
+                if DROP_KEYWORDS.contains(&column.to_uppercase().as_str()) {
+                    continue;
+                }
+                if !history.deprecated.contains(&format!("{table}.{column}"))
+                    && !history.deprecated.contains(&table)
+                {
+                    hits.push("drop-without-deprecation");
+                }
+            }
+            if RENAME.is_match(text) && !text.to_uppercase().contains(" RENAME CONSTRAINT ") {
+                hits.push("rename");
+            }
+
+            for c in NOT_NULL_CHECK.captures_iter(text) {
+                history
+                    .not_null_checks
+                    .insert((table.clone(), normalize_name(&c[1])));
+            }
+            if VALIDATE_CONSTRAINT.is_match(text) {
+                history.validated.insert(table.clone());
+            }
+            locked_table = Some(table);
+        }
+
+        if let Some(c) = DROP_TABLE.captures(text) {
+            let undeprecated = c[1]
+                .split(',')
+                .map(normalize_name)
+                .any(|table| !history.deprecated.contains(&table));
+            if undeprecated {
+                hits.push("drop-without-deprecation");
+            }
+        }
+
+        let exempt =
+            locked_table.is_some_and(|t| created.contains(&t) || small_tables.contains(&t));
+        for rule in RULES.iter().filter(|r| hits.contains(&r.id)) {
+            let severity = severities[rule.id];
+            if severity == LintSeverity::Off || allowed.contains(rule.id) || (rule.lock && exempt) {
+                continue;
+            }
+            findings.push(LintFinding {
+                rule: rule.id,
+                severity,
+                file: path.to_path_buf(),
+                line_number: statement.line_number,
+                statement: statement.text.clone(),
+                message: rule.message,
+            });
+        }
+    }
+
+    history.deprecated.extend(deprecated_here);
+    findings
+}
+
+/// Lint one migration chain. Every file feeds the history, but only the
+/// files in `targets` (those the gate config does not exclude) are linted.
+async fn lint_chain(
+    all_files: &[PathBuf],
+    targets: &[PathBuf],
+    config: &MigrationGatesConfig,
+    findings: &mut Vec<LintFinding>,
+) -> Result<()> {
+    let mut history = LintHistory::default();
+    for file_path in all_files {
+        let content = fs::read_to_string(file_path)
+            .await
+            .with_context(|| format!("Failed to read: {}", file_path.display()))?;
+        let file_findings = lint_file(file_path, &content, &mut history, config);
+        if targets.contains(file_path) {
+            findings.extend(file_findings);
+        }
+    }
+    Ok(())
+}
+
+/// Keep only the targets listed in `only` (compared canonically).
+fn restrict_targets(targets: &mut Vec<PathBuf>, only: Option<&[PathBuf]>) {
+    if let Some(only) = only {
+        targets.retain(|t| {
+            let canonical = t.canonicalize().unwrap_or_else(|_| t.clone());
+            only.contains(&canonical)
+        });
+    }
+}
+
+/// Lint SQLx and SeaORM migrations for lock-heavy and destructive DDL.
+///
+/// With `only`, findings are reported for those files alone (typically the
+/// migrations added since the previous release); the rest of the chain is
+/// still read so that history-dependent rules see earlier tables.
+pub async fn lint_migrations(
+    migrations_dir: &Path,
+    seaorm_migrations_dir: &Path,
+    config: &MigrationGatesConfig,
+    only: Option<&[PathBuf]>,
+) -> Result<MigrationLintResult> {
+    println!("{}", "Linting migrations for lock safety...".bold());
+
+    let defaults = MigrationGatesConfig::default();
+    let sql_files = find_sql_files_with_config(migrations_dir, &defaults).await?;
+    let mut sql_targets = find_sql_files_with_config(migrations_dir, config).await?;
+    let seaorm_files = find_seaorm_migration_files(seaorm_migrations_dir, &defaults).await?;
+    let mut seaorm_targets = find_seaorm_migration_files(seaorm_migrations_dir, config).await?;
+    restrict_targets(&mut sql_targets, only);
+    restrict_targets(&mut seaorm_targets, only);
+
+    let mut result = MigrationLintResult {
+        files_checked: sql_targets.len() + seaorm_targets.len(),
+        findings: Vec::new(),
+    };
+    if result.files_checked == 0 {
+        println!("   No migration files to lint");
+        return Ok(result);
+    }
+    println!("   Linting {} migration files", result.files_checked);
+
+    lint_chain(&sql_files, &sql_targets, config, &mut result.findings).await?;
+    lint_chain(&seaorm_files, &seaorm_targets, config, &mut result.findings).await?;
+
+    let errors = result.errors();
+    let warnings = result.warnings();
+    if result.findings.is_empty() {
+        println!("   {} No lock-safety issues", "✅".green());
+    } else {
+        let marker = if errors.is_empty() {
+            "⚠️".yellow()
+        } else {
+            "❌".red()
+        };
+        println!(
+            "   {} {} errors, {} warnings",
+            marker,
+            errors.len(),
+            warnings.len()
+        );
+        for finding in &result.findings {
+            println!("\n   {}", finding.format());
+        }
+    }
+
+    Ok(result)
+}
+
+/// Execute `forge migrations lint`
+///
+/// Lints the migrations added since the previous release, or the whole
+/// chain before the first release.
+pub async fn execute(working_dir: &str) -> Result<()> {
+    let gates = load_gates_config(Path::new(working_dir));
+    let config = PreReleaseConfig::from_working_dir_with_gates(Path::new(working_dir), gates);
+    let baseline = super::schema_compat::release_baseline(&config).await?;
+    if let Some(baseline) = &baseline {
+        println!("   Migrations since {}", baseline.base_ref);
+    }
+    let result = lint_migrations(
+        &config.migrations_dir,
+        &config.seaorm_migrations_dir,
+        &config.gates.migrations,
+        baseline.as_ref().map(|b| b.new_migrations.as_slice()),
+    )
+    .await?;
+
+    let errors = result.errors().len();
+    if errors > 0 {
+        bail!("{} migration lint error(s)", errors);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lint(name: &str, content: &str) -> Vec<&'static str> {
+        lint_with(
+            name,
+            content,
+            &mut LintHistory::default(),
+            &Default::default(),
+        )
+    }
+
+    fn lint_with(
+        name: &str,
+        content: &str,
+        history: &mut LintHistory,
+        config: &MigrationGatesConfig,
+    ) -> Vec<&'static str> {
+        lint_file(Path::new(name), content, history, config)
+            .iter()
+            .map(|f| f.rule)
+            .collect()
+    }
+
+    #[test]
+    fn test_split_sql_skips_comments_and_quoted_semicolons() {
+        let sql = "-- header; not a statement\n\
+                   CREATE TABLE t (id int);\n\
+                   /* block; comment */\n\
+                   INSERT INTO t VALUES (';');\n\
+                   DO $body$ BEGIN PERFORM 1; END $body$;";
+        let statements = split_sql(sql, 1);
+        let texts: Vec<&str> = statements.iter().map(|s| s.text.as_str()).collect();
+        assert_eq!(
+            texts,
+            [
+                "CREATE TABLE t (id int)",
+                "INSERT INTO t VALUES (';')",
+                "DO $body$ BEGIN PERFORM 1; END $body$",
+            ]
+        );
+        assert_eq!(statements[0].line_number, 2);
+        assert_eq!(statements[1].line_number, 4);
+    }
+
+    #[test]
+    fn test_rust_string_literals_extracts_sql_with_lines() {
+        let source = "// \"not sql\"\n\
+                      let c = '\"';\n\
+                      db.execute_unprepared(r#\"\n  ALTER TABLE users\n  ADD COLUMN token uuid DEFAULT gen_random_uuid()\n\"#)\n\
+                      .await?;\n\
+                      db.execute_unprepared(\"CREATE INDEX idx ON users (email)\").await?;";
+        let literals = rust_string_literals(source);
+        assert_eq!(literals.len(), 2);
+        assert_eq!(literals[0].0, 3);
+        assert!(literals[0].1.contains("gen_random_uuid()"));
+        assert_eq!(
+            literals[1],
+            (8, "CREATE INDEX idx ON users (email)".to_string())
+        );
+
+        assert_eq!(
+            lint("m20260301_000001_tokens.rs", source),
+            ["add-column-volatile-default", "create-index-non-concurrent"]
+        );
+    }
+
+    #[test]
+    fn test_lock_rules() {
+        assert_eq!(
+            lint(
+                "1.sql",
+                "ALTER TABLE orders ALTER COLUMN total TYPE numeric(12,2);"
+            ),
+            ["alter-column-type"]
+        );
+        assert_eq!(
+            lint(
+                "1.sql",
+                "ALTER TABLE orders ADD COLUMN created_at timestamptz DEFAULT now();"
+            ),
+            Vec::<&str>::new()
+        );
+        assert_eq!(
+            lint("1.sql", "ALTER TABLE orders ADD COLUMN seq bigserial;"),
+            ["add-column-volatile-default"]
+        );
+        assert_eq!(
+            lint(
+                "1.sql",
+                "CREATE UNIQUE INDEX idx_orders_ref ON orders (ref);"
+            ),
+            ["create-index-non-concurrent"]
+        );
+        assert_eq!(
+            lint(
+                "1.sql",
+                "CREATE INDEX CONCURRENTLY IF NOT EXISTS idx ON orders (ref);"
+            ),
+            Vec::<&str>::new()
+        );
+        assert_eq!(
+            lint("1.sql", "ALTER TABLE orders ALTER COLUMN ref SET NOT NULL;"),
+            ["set-not-null-without-check"]
+        );
+    }
+
+    #[test]
+    fn test_new_and_small_tables_are_exempt_from_lock_rules() {
+        assert_eq!(
+            lint(
+                "1.sql",
+                "CREATE TABLE IF NOT EXISTS carts (id uuid);\n\
+                 CREATE INDEX idx_carts_id ON carts (id);\n\
+                 ALTER TABLE carts ALTER COLUMN id SET NOT NULL;"
+            ),
+            Vec::<&str>::new()
+        );
+        let config = MigrationGatesConfig {
+            small_tables: vec!["public.feature_flags".into()],
+            ..Default::default()
+        };
+        assert_eq!(
+            lint_with(
+                "1.sql",
+                "CREATE INDEX idx_flags ON public.feature_flags (key);",
+                &mut LintHistory::default(),
+                &config
+            ),
+            Vec::<&str>::new()
+        );
+    }
+
+    #[test]
+    fn test_validated_check_allows_set_not_null() {
+        let mut history = LintHistory::default();
+        let config = MigrationGatesConfig::default();
+        lint_with(
+            "1.sql",
+            "ALTER TABLE orders ADD CONSTRAINT orders_ref_nn CHECK (ref IS NOT NULL) NOT VALID;\n\
+             ALTER TABLE orders VALIDATE CONSTRAINT orders_ref_nn;",
+            &mut history,
+            &config,
+        );
+        assert_eq!(
+            lint_with(
+                "2.sql",
+                "ALTER TABLE orders ALTER COLUMN ref SET NOT NULL;",
+                &mut history,
+                &config
+            ),
+            Vec::<&str>::new()
+        );
+    }
+
+    #[test]
+    fn test_drop_requires_earlier_deprecation() {
+        let config = MigrationGatesConfig::default();
+        let drop = "ALTER TABLE users DROP COLUMN legacy_name;\nDROP TABLE IF EXISTS old_sessions;";
+        assert_eq!(
+            lint("2.sql", drop),
+            ["drop-without-deprecation", "drop-without-deprecation"]
+        );
+
+        // Deprecation in the same migration does not count.
+        let same_file = format!(
+            "COMMENT ON COLUMN users.legacy_name IS 'DEPRECATED: use display_name';\n{drop}"
+        );
+        assert_eq!(lint("2.sql", &same_file).len(), 2);
+
+        let mut history = LintHistory::default();
+        lint_with(
+            "1.sql",
+            "COMMENT ON COLUMN public.users.legacy_name IS 'DEPRECATED: use display_name';\n\
+             COMMENT ON TABLE old_sessions IS 'deprecated';",
+            &mut history,
+            &config,
+        );
+        assert_eq!(
+            lint_with("2.sql", drop, &mut history, &config),
+            Vec::<&str>::new()
+        );
+
+        // Dropping constraints and defaults is not dropping a column.
+        assert_eq!(
+            lint(
+                "1.sql",
+                "ALTER TABLE users DROP CONSTRAINT users_email_key, ALTER COLUMN name DROP DEFAULT;"
+            ),
+            Vec::<&str>::new()
+        );
+    }
+
+    #[test]
+    fn test_renames_and_severity_overrides() {
+        assert_eq!(
+            lint(
+                "1.sql",
+                "ALTER TABLE users RENAME COLUMN name TO display_name;"
+            ),
+            ["rename"]
+        );
+        assert_eq!(
+            lint("1.sql", "ALTER TABLE users RENAME TO accounts;"),
+            ["rename"]
+        );
+        assert_eq!(
+            lint("1.sql", "ALTER TABLE users RENAME CONSTRAINT a TO b;"),
+            Vec::<&str>::new()
+        );
+
+        let findings = lint_file(
+            Path::new("1.sql"),
+            "ALTER TABLE users RENAME TO accounts;",
+            &mut LintHistory::default(),
+            &MigrationGatesConfig::default(),
+        );
+        assert_eq!(findings[0].severity, LintSeverity::Warning);
+
+        let config = MigrationGatesConfig {
+            lint_severity: BTreeMap::from([
+                ("rename".to_string(), LintSeverity::Error),
+                ("alter-column-type".to_string(), LintSeverity::Off),
+            ]),
+            ..Default::default()
+        };
+        let findings = lint_file(
+            Path::new("1.sql"),
+            "ALTER TABLE users RENAME TO accounts;\nALTER TABLE t ALTER COLUMN c TYPE text;",
+            &mut LintHistory::default(),
+            &config,
+        );
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].severity, LintSeverity::Error);
+    }
+
+    #[test]
+    fn test_allow_directive_suppresses_rules_per_file() {
+        assert_eq!(
+            lint(
+                "1.sql",
+                "-- forge-lint: allow(rename, create-index-non-concurrent)\n\
+                 ALTER TABLE users RENAME TO accounts;\n\
+                 CREATE INDEX idx ON accounts (email);\n\
+                 ALTER TABLE accounts ALTER COLUMN email TYPE citext;"
+            ),
+            ["alter-column-type"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_only_new_migrations_are_reported() {
+        let dir = tempfile::tempdir().unwrap();
+        let migrations = dir.path().join("migrations");
+        std::fs::create_dir_all(&migrations).unwrap();
+        std::fs::write(
+            migrations.join("20240101000000_shipped.sql"),
+            "CREATE TABLE users (id int);\n\
+             CREATE INDEX idx_users_id ON users (id);\n\
+             ALTER TABLE users RENAME TO accounts;",
+        )
+        .unwrap();
+        let new = migrations.join("20250101000000_new.sql");
+        std::fs::write(&new, "ALTER TABLE accounts RENAME TO members;").unwrap();
+
+        let only = [new.canonicalize().unwrap()];
+        let result = lint_migrations(
+            &migrations,
+            &dir.path().join("none"),
+            &Default::default(),
+            Some(&only),
+        )
+        .await
+        .unwrap();
+        assert_eq!(result.files_checked, 1);
+        assert_eq!(
+            result.findings.iter().map(|f| f.rule).collect::<Vec<_>>(),
+            ["rename"]
+        );
+    }
+}
@@ -0,0 +1,800 @@
+//! Migration Rehearsal
+//!
+//! `forge migrations rehearse` applies a release's new migrations to a
+//! throwaway Postgres container, so a migration that fails against the real
+//! schema fails here instead of when Shinka runs it in staging:
+//!
+//! 1. Start a `postgres` container labelled `org.testcontainers=true`, so
+//!    `forge test cleanup` reaps it if a rehearsal dies half-way
+//! 2. Build the baseline schema: restore a schema-only dump
+//!    (`--schema-dump`), or replay the previous release's SQLx and SeaORM
+//!    migrations from a git worktree of `--from-ref` (default: the latest
+//!    `vX.Y.Z` tag)
+//! 3. Apply every migration added since `--from-ref`, SQLx files through
+//!    `psql` and SeaORM migrations one at a time through
+//!    `sea-orm-cli migrate up -n 1`, timing each
+//! 4. Run the rollback-compatibility check against the migration manifest
+//!
+//! New Databend and Elasticsearch migrations get the same treatment in their
+//! own `datafuselabs/databend` and single-node Elasticsearch containers:
+//! replay `--from-ref`'s chain, then apply and time the new files. New
+//! Elasticsearch mapping changes that need a reindex are reported.
+//!
+//! A schema-only dump carries no rows, so when one is restored the SeaORM
+//! versions of `--from-ref` are recorded in `seaql_migrations` by hand.
+
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::{Duration, Instant};
+use tokio::fs;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+
+use super::databend_migrations::{self, RehearsalDatabend};
+use super::e2e::ensure_docker_running;
+use super::elasticsearch_migrations::{self, RehearsalElasticsearch};
+use super::migration_validation::{validate_rollback_compatibility, MigrationIssue};
+use super::prerelease::{load_gates_config, PreReleaseConfig};
+use crate::git::{git_command_async, git_command_sync, max_released_version};
+use crate::repo::get_tool_path;
+use crate::retry::{classify_capture_anyhow, run_capture_anyhow, run_discard_sync};
+
+/// Resolve the `docker` binary via `DOCKER_BIN`, falling back to `docker`
+/// on `PATH`.
+fn docker_bin() -> String {
+    get_tool_path("DOCKER_BIN", "docker")
+}
+
+/// Resolve the `sea-orm-cli` binary via `SEA_ORM_CLI_BIN`, falling back to
+/// `sea-orm-cli` on `PATH`.
+fn sea_orm_cli_bin() -> String {
+    get_tool_path("SEA_ORM_CLI_BIN", "sea-orm-cli")
+}
+
+/// Database created in the rehearsal container.
+const DATABASE: &str = "rehearsal";
+
+/// Password of the rehearsal container's `postgres` user.
+const PASSWORD: &str = "rehearsal";
+
+/// How long to wait for the container to accept TCP connections.
+const READY_TIMEOUT: Duration = Duration::from_secs(60);
+
+// =============================================================================
+// Data Structures
+// =============================================================================
+
+/// Options for `forge migrations rehearse`.
+#[derive(Debug, Clone)]
+pub struct RehearseOptions {
+    /// Git ref of the previous release; the latest `vX.Y.Z` tag if unset
+    pub from_ref: Option<String>,
+    /// Schema-only dump restored as the baseline instead of replaying the
+    /// previous release's migrations
+    pub schema_dump: Option<PathBuf>,
+    /// Postgres image for the rehearsal container
+    pub image: String,
+    /// Databend image, used when Databend migrations were added
+    pub databend_image: String,
+    /// Elasticsearch image, used when Elasticsearch migrations were added
+    pub elasticsearch_image: String,
+    /// Leave the container running after the rehearsal
+    pub keep: bool,
+}
+
+/// Which migration chain a migration belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MigrationKind {
+    Sql,
+    SeaOrm,
+    Databend,
+    Elasticsearch,
+}
+
+impl MigrationKind {
+    fn label(self) -> &'static str {
+        match self {
+            MigrationKind::Sql => "sqlx",
+            MigrationKind::SeaOrm => "seaorm",
+            MigrationKind::Databend => "databend",
+            MigrationKind::Elasticsearch => "elastic",
+        }
+    }
+}
+
+/// How long one new migration took to apply.
+#[derive(Debug, Clone)]
+pub struct MigrationTiming {
+    pub kind: MigrationKind,
+    pub name: String,
+    pub duration: Duration,
+}
+
+/// Outcome of a rehearsal.
+#[derive(Debug)]
+pub struct RehearsalReport {
+    /// The ref the baseline schema was built from
+    pub from_ref: String,
+    /// One entry per new migration, in the order applied
+    pub timings: Vec<MigrationTiming>,
+    /// Warnings from the rollback-compatibility check
+    pub rollback_warnings: Vec<String>,
+    /// New Elasticsearch migrations whose mapping changes need a reindex
+    pub reindex_required: Vec<String>,
+}
+
+// =============================================================================
+// Rehearsal Database
+// =============================================================================
+
+/// A throwaway container publishing one port on 127.0.0.1, removed on drop
+/// unless kept.
+pub(super) struct Container {
+    pub(super) id: String,
+    pub(super) port: u16,
+    /// Printed when the container is kept, e.g. a connection URL
+    endpoint: String,
+    keep: bool,
+}
+
+impl Container {
+    /// `docker run` `image` labelled `org.testcontainers=true`, so
+    /// `forge test cleanup` reaps it if a rehearsal dies half-way.
+    pub(super) async fn start(
+        image: &str,
+        container_port: u16,
+        env: &[String],
+        keep: bool,
+        endpoint: impl FnOnce(u16) -> String,
+    ) -> Result<Self> {
+        let mut cmd = Command::new(docker_bin());
+        cmd.args(["run", "-d", "--label", "org.testcontainers=true"]);
+        for var in env {
+            cmd.args(["-e", var]);
+        }
+        cmd.args(["-p", &format!("127.0.0.1::{}", container_port), image]);
+        let output = run_capture_anyhow(cmd, "docker run").await?;
+        let mut container = Self {
+            id: String::from_utf8_lossy(&output.stdout).trim().to_string(),
+            port: 0,
+            endpoint: String::new(),
+            keep,
+        };
+
+        let mut cmd = Command::new(docker_bin());
+        cmd.args(["port", &container.id, &format!("{}/tcp", container_port)]);
+        let output = run_capture_anyhow(cmd, "docker port").await?;
+        container.port =
+            parse_host_port(&String::from_utf8_lossy(&output.stdout)).with_context(|| {
+                format!(
+                    "docker port did not report a host port for {}/tcp",
+                    container_port
+                )
+            })?;
+        container.endpoint = endpoint(container.port);
+        Ok(container)
+    }
+}
+
+impl Drop for Container {
+    fn drop(&mut self) {
+        if self.keep {
+            println!(
+                "   Rehearsal container kept at {} (container {})",
+                self.endpoint,
+                &self.id[..self.id.len().min(12)]
+            );
+        } else {
+            run_discard_sync(&docker_bin(), &["rm", "-f", &self.id]);
+        }
+    }
+}
+
+/// A throwaway Postgres container.
+struct RehearsalDatabase {
+    container: Container,
+}
+
+impl RehearsalDatabase {
+    async fn start(image: &str, keep: bool) -> Result<Self> {
+        let env = [
+            format!("POSTGRES_PASSWORD={}", PASSWORD),
+            format!("POSTGRES_DB={}", DATABASE),
+        ];
+        let container = Container::start(image, 5432, &env, keep, database_url).await?;
+        let database = Self { container };
+        database.wait_ready().await?;
+        Ok(database)
+    }
+
+    /// Wait until Postgres accepts TCP connections. The image's init phase
+    /// runs a socket-only server, so probing over TCP skips it.
+    async fn wait_ready(&self) -> Result<()> {
+        let start = Instant::now();
+        loop {
+            let ready = Command::new(docker_bin())
+                .args([
+                    "exec",
+                    &self.container.id,
+                    "pg_isready",
+                    "-h",
+                    "127.0.0.1",
+                    "-U",
+                    "postgres",
+                    "-d",
+                    DATABASE,
+                ])
+                .output()
+                .await
+                .is_ok_and(|o| o.status.success());
+            if ready {
+                return Ok(());
+            }
+            if start.elapsed() >= READY_TIMEOUT {
+                bail!(
+                    "Postgres container {} not ready after {}s",
+                    self.container.id,
+                    READY_TIMEOUT.as_secs()
+                );
+            }
+            tokio::time::sleep(Duration::from_secs(1)).await;
+        }
+    }
+
+    fn url(&self) -> String {
+        database_url(self.container.port)
+    }
+
+    /// Run a SQL script through `psql`, stopping at the first error.
+    async fn psql(&self, sql: &str, op: &str) -> Result<()> {
+        let mut child = Command::new(docker_bin())
+            .args([
+                "exec",
+                "-i",
+                "-e",
+                &format!("PGPASSWORD={}", PASSWORD),
+                &self.container.id,
+                "psql",
+                "-h",
+                "127.0.0.1",
+                "-U",
+                "postgres",
+                "-d",
+                DATABASE,
+                "-v",
+                "ON_ERROR_STOP=1",
+                "-q",
+                "-f",
+                "-",
+            ])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .with_context(|| format!("Failed to spawn psql for {}", op))?;
+        let mut stdin = child.stdin.take().context("psql stdin unavailable")?;
+
+        let write = async move {
+            let result = stdin.write_all(sql.as_bytes()).await;
+            drop(stdin);
+            result
+        };
+        let (written, output) = tokio::join!(write, child.wait_with_output());
+        let output = classify_capture_anyhow(output, op)?;
+        // A write error means psql exited early; its stderr says why.
+        written.with_context(|| {
+            format!(
+                "{} failed: {}",
+                op,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )
+        })
+    }
+}
+
+/// A detached git worktree of the baseline ref, removed on drop.
+struct Worktree {
+    repo_root: PathBuf,
+    path: PathBuf,
+    _dir: tempfile::TempDir,
+}
+
+impl Worktree {
+    async fn add(repo_root: &Path, git_ref: &str) -> Result<Self> {
+        let dir = tempfile::tempdir().context("Failed to create worktree directory")?;
+        let path = dir.path().join("baseline");
+        let mut cmd = git_command_async();
+        cmd.arg("-C")
+            .arg(repo_root)
+            .args(["worktree", "add", "--detach"])
+            .arg(&path)
+            .arg(git_ref);
+        run_capture_anyhow(cmd, "git worktree add").await?;
+        Ok(Self {
+            repo_root: repo_root.to_path_buf(),
+            path,
+            _dir: dir,
+        })
+    }
+}
+
+impl Drop for Worktree {
+    fn drop(&mut self) {
+        let _ = git_command_sync()
+            .arg("-C")
+            .arg(&self.repo_root)
+            .args(["worktree", "remove", "--force"])
+            .arg(&self.path)
+            .output();
+    }
+}
+
+// =============================================================================
+// Helpers
+// =============================================================================
+
+fn database_url(port: u16) -> String {
+    format!(
+        "postgres://postgres:{}@127.0.0.1:{}/{}",
+        PASSWORD, port, DATABASE
+    )
+}
+
+/// Parse the host port out of `docker port` output
+/// (`127.0.0.1:49153`, one line per binding).
+fn parse_host_port(output: &str) -> Option<u16> {
+    output
+        .lines()
+        .find_map(|line| line.trim().rsplit_once(':')?.1.parse().ok())
+}
+
+/// Migration files of one chain in `dir`, sorted by name. A missing
+/// directory is an empty chain.
+pub(super) async fn migration_files(dir: &Path, kind: MigrationKind) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    if !dir.is_dir() {
+        return Ok(files);
+    }
+    let mut entries = fs::read_dir(dir)
+        .await
+        .with_context(|| format!("Failed to read directory: {}", dir.display()))?;
+    while let Some(entry) = entries.next_entry().await? {
+        let path = entry.path();
+        let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+            continue;
+        };
+        let is_migration = match kind {
+            MigrationKind::Sql | MigrationKind::Databend => name.ends_with(".sql"),
+            MigrationKind::Elasticsearch => name.ends_with(".json"),
+            MigrationKind::SeaOrm => {
+                name.starts_with('m')
+                    && name.ends_with(".rs")
+                    && !matches!(name, "main.rs" | "mod.rs")
+            }
+        };
+        if is_migration && path.is_file() {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+/// The files in `current` whose names are not in `baseline`.
+pub(super) fn new_migrations(current: &[PathBuf], baseline: &[PathBuf]) -> Vec<PathBuf> {
+    let known: BTreeSet<String> = baseline.iter().map(|p| file_name(p)).collect();
+    current
+        .iter()
+        .filter(|p| !known.contains(&file_name(p)))
+        .cloned()
+        .collect()
+}
+
+/// SQL that records SeaORM migrations as applied, for a baseline restored
+/// from a schema-only dump.
+fn seaql_applied_sql(files: &[PathBuf]) -> String {
+    let mut sql = String::from(
+        "CREATE TABLE IF NOT EXISTS seaql_migrations \
+         (version varchar NOT NULL PRIMARY KEY, applied_at bigint NOT NULL);\n",
+    );
+    for file in files {
+        let version = file_name(file).trim_end_matches(".rs").replace('\'', "''");
+        sql.push_str(&format!(
+            "INSERT INTO seaql_migrations (version, applied_at) VALUES ('{}', 0) \
+             ON CONFLICT (version) DO NOTHING;\n",
+            version
+        ));
+    }
+    sql
+}
+
+/// Run `sea-orm-cli migrate <args>` for the migration crate at `crate_dir`.
+async fn sea_orm_migrate(crate_dir: &Path, url: &str, args: &[&str], op: &str) -> Result<()> {
+    let mut cmd = Command::new(sea_orm_cli_bin());
+    cmd.arg("migrate")
+        .arg("-d")
+        .arg(crate_dir)
+        .args(["-u", url])
+        .args(args);
+    run_capture_anyhow(cmd, op).await?;
+    Ok(())
+}
+
+pub(super) async fn repo_root(working_dir: &Path) -> Result<PathBuf> {
+    let mut cmd = git_command_async();
+    cmd.arg("-C")
+        .arg(working_dir)
+        .args(["rev-parse", "--show-toplevel"]);
+    let output = run_capture_anyhow(cmd, "git rev-parse --show-toplevel").await?;
+    Ok(PathBuf::from(
+        String::from_utf8_lossy(&output.stdout).trim(),
+    ))
+}
+
+/// The latest `vX.Y.Z` release tag, if any.
+pub(super) fn latest_release_ref(repo_root: &Path) -> Result<Option<String>> {
+    let version = max_released_version("v", Some(repo_root))?;
+    Ok((!version.is_empty()).then(|| format!("v{}", version)))
+}
+
+// =============================================================================
+// Rehearsal
+// =============================================================================
+
+/// Rehearse the migrations added since the previous release against a
+/// throwaway Postgres container.
+pub async fn rehearse(working_dir: &Path, options: &RehearseOptions) -> Result<RehearsalReport> {
+    let working_dir = working_dir
+        .canonicalize()
+        .with_context(|| format!("Working directory not found: {}", working_dir.display()))?;
+    let gates = load_gates_config(&working_dir);
+    let config = PreReleaseConfig::from_working_dir_with_gates(&working_dir, gates);
+    let repo_root = repo_root(&working_dir).await?;
+    let relative = |dir: &Path| -> Result<PathBuf> {
+        Ok(dir
+            .strip_prefix(&repo_root)
+            .with_context(|| format!("{} is outside {}", dir.display(), repo_root.display()))?
+            .to_path_buf())
+    };
+    let seaorm_crate = config
+        .seaorm_migrations_dir
+        .parent()
+        .context("SeaORM migrations directory has no parent crate")?
+        .to_path_buf();
+
+    let from_ref = match &options.from_ref {
+        Some(r) => r.clone(),
+        None => latest_release_ref(&repo_root)?
+            .context("No vX.Y.Z release tag found; pass --from-ref")?,
+    };
+
+    println!(
+        "{}",
+        format!("Rehearsing migrations added since {}...", from_ref).bold()
+    );
+
+    let worktree = Worktree::add(&repo_root, &from_ref).await?;
+    let baseline_sql = migration_files(
+        &worktree.path.join(relative(&config.migrations_dir)?),
+        MigrationKind::Sql,
+    )
+    .await?;
+    let baseline_seaorm = migration_files(
+        &worktree.path.join(relative(&config.seaorm_migrations_dir)?),
+        MigrationKind::SeaOrm,
+    )
+    .await?;
+    let new_sql = new_migrations(
+        &migration_files(&config.migrations_dir, MigrationKind::Sql).await?,
+        &baseline_sql,
+    );
+    let new_seaorm = new_migrations(
+        &migration_files(&config.seaorm_migrations_dir, MigrationKind::SeaOrm).await?,
+        &baseline_seaorm,
+    );
+    let baseline_databend = migration_files(
+        &worktree
+            .path
+            .join(relative(&config.databend_migrations_dir)?),
+        MigrationKind::Databend,
+    )
+    .await?;
+    let new_databend = new_migrations(
+        &migration_files(&config.databend_migrations_dir, MigrationKind::Databend).await?,
+        &baseline_databend,
+    );
+    let current_elasticsearch = migration_files(
+        &config.elasticsearch_migrations_dir,
+        MigrationKind::Elasticsearch,
+    )
+    .await?;
+    let baseline_elasticsearch = migration_files(
+        &worktree
+            .path
+            .join(relative(&config.elasticsearch_migrations_dir)?),
+        MigrationKind::Elasticsearch,
+    )
+    .await?;
+    let new_elasticsearch = new_migrations(&current_elasticsearch, &baseline_elasticsearch);
+
+    let mut report = RehearsalReport {
+        from_ref: from_ref.clone(),
+        timings: Vec::new(),
+        rollback_warnings: Vec::new(),
+        reindex_required: Vec::new(),
+    };
+    if new_sql.is_empty()
+        && new_seaorm.is_empty()
+        && new_databend.is_empty()
+        && new_elasticsearch.is_empty()
+    {
+        println!("   No migrations added since {}", from_ref);
+        return Ok(report);
+    }
+
+    tokio::task::spawn_blocking(ensure_docker_running).await??;
+    if !new_sql.is_empty() || !new_seaorm.is_empty() {
+        println!("   Starting {} container", options.image);
+        let database = RehearsalDatabase::start(&options.image, options.keep).await?;
+
+        // Baseline schema
+        match &options.schema_dump {
+            Some(dump) => {
+                println!("   Restoring schema dump {}", dump.display());
+                let sql = fs::read_to_string(dump)
+                    .await
+                    .with_context(|| format!("Failed to read schema dump: {}", dump.display()))?;
+                database.psql(&sql, "restore schema dump").await?;
+                if !baseline_seaorm.is_empty() {
+                    database
+                        .psql(
+                            &seaql_applied_sql(&baseline_seaorm),
+                            "record baseline SeaORM migrations",
+                        )
+                        .await?;
+                }
+            }
+            None => {
+                println!(
+                    "   Replaying {} migrations from {}",
+                    baseline_sql.len() + baseline_seaorm.len(),
+                    from_ref
+                );
+                for file in &baseline_sql {
+                    let sql = fs::read_to_string(file)
+                        .await
+                        .with_context(|| format!("Failed to read: {}", file.display()))?;
+                    let op = format!("baseline migration {}", file_name(file));
+                    database.psql(&sql, &op).await?;
+                }
+                if !baseline_seaorm.is_empty() {
+                    let crate_dir = worktree.path.join(relative(&seaorm_crate)?);
+                    sea_orm_migrate(
+                        &crate_dir,
+                        &database.url(),
+                        &["up"],
+                        "baseline SeaORM migrations",
+                    )
+                    .await?;
+                }
+            }
+        }
+        // New migrations
+        for file in &new_sql {
+            let name = file_name(file);
+            let sql = fs::read_to_string(file)
+                .await
+                .with_context(|| format!("Failed to read: {}", file.display()))?;
+            let start = Instant::now();
+            database.psql(&sql, &format!("migration {}", name)).await?;
+            report.timings.push(MigrationTiming {
+                kind: MigrationKind::Sql,
+                name,
+                duration: start.elapsed(),
+            });
+        }
+        if !new_seaorm.is_empty() {
+            // `status` compiles the migrator so the first timing excludes the build.
+            sea_orm_migrate(
+                &seaorm_crate,
+                &database.url(),
+                &["status"],
+                "build migrator",
+            )
+            .await?;
+            for file in &new_seaorm {
+                let name = file_name(file).trim_end_matches(".rs").to_string();
+                let start = Instant::now();
+                sea_orm_migrate(
+                    &seaorm_crate,
+                    &database.url(),
+                    &["up", "-n", "1"],
+                    &format!("migration {}", name),
+                )
+                .await?;
+                report.timings.push(MigrationTiming {
+                    kind: MigrationKind::SeaOrm,
+                    name,
+                    duration: start.elapsed(),
+                });
+            }
+        }
+
+        let rollback = validate_rollback_compatibility(
+            &config.seaorm_migrations_dir,
+            &config.gates.migrations,
+        )
+        .await?;
+        report.rollback_warnings = rollback.warnings;
+    }
+
+    if !new_databend.is_empty() {
+        println!("   Starting {} container", options.databend_image);
+        let databend = RehearsalDatabend::start(&options.databend_image, options.keep).await?;
+        println!(
+            "   Replaying {} Databend migrations from {}",
+            baseline_databend.len(),
+            from_ref
+        );
+        for file in &baseline_databend {
+            let op = format!("baseline migration {}", file_name(file));
+            databend.apply(file, &op).await?;
+        }
+        for file in &new_databend {
+            let name = file_name(file);
+            let start = Instant::now();
+            databend.apply(file, &format!("migration {}", name)).await?;
+            report.timings.push(MigrationTiming {
+                kind: MigrationKind::Databend,
+                name,
+                duration: start.elapsed(),
+            });
+        }
+        report
+            .rollback_warnings
+            .extend(databend_migrations::rollback_warnings(&new_databend).await?);
+    }
+
+    if !new_elasticsearch.is_empty() {
+        // Reindex findings are reported before applying: an in-place mapping
+        // change is exactly what the cluster will reject.
+        let findings = elasticsearch_migrations::analyze(
+            &elasticsearch_migrations::read_chain(&current_elasticsearch).await?,
+        )?;
+        for finding in findings
+            .into_iter()
+            .filter(|f| new_elasticsearch.contains(&f.file))
+        {
+            report.reindex_required.extend(
+                finding
+                    .issues
+                    .iter()
+                    .filter(|i| matches!(i, MigrationIssue::ReindexRequired { .. }))
+                    .map(MigrationIssue::format),
+            );
+            report.rollback_warnings.extend(finding.rollback_warnings);
+        }
+
+        println!("   Starting {} container", options.elasticsearch_image);
+        let elasticsearch =
+            RehearsalElasticsearch::start(&options.elasticsearch_image, options.keep).await?;
+        println!(
+            "   Replaying {} Elasticsearch migrations from {}",
+            baseline_elasticsearch.len(),
+            from_ref
+        );
+        for file in &baseline_elasticsearch {
+            let op = format!("baseline migration {}", file_name(file));
+            elasticsearch.apply(file, &op).await?;
+        }
+        for file in &new_elasticsearch {
+            let name = file_name(file);
+            let start = Instant::now();
+            elasticsearch
+                .apply(file, &format!("migration {}", name))
+                .await?;
+            report.timings.push(MigrationTiming {
+                kind: MigrationKind::Elasticsearch,
+                name,
+                duration: start.elapsed(),
+            });
+        }
+    }
+    Ok(report)
+}
+
+/// Execute `forge migrations rehearse`
+pub async fn execute(working_dir: &str, options: &RehearseOptions) -> Result<()> {
+    let report = rehearse(Path::new(working_dir), options).await?;
+    if report.timings.is_empty() {
+        return Ok(());
+    }
+
+    println!();
+    println!(
+        "   {} Applied {} migrations added since {}",
+        "✅".green(),
+        report.timings.len(),
+        report.from_ref
+    );
+    for timing in &report.timings {
+        println!(
+            "   {:>9.2}s  {:<8} {}",
+            timing.duration.as_secs_f64(),
+            timing.kind.label(),
+            timing.name
+        );
+    }
+    let total: Duration = report.timings.iter().map(|t| t.duration).sum();
+    println!("   {:>9.2}s  total", total.as_secs_f64());
+
+    if !report.reindex_required.is_empty() {
+        println!();
+        println!(
+            "   {} {} mapping changes need a reindex",
+            "⚠️".yellow(),
+            report.reindex_required.len()
+        );
+        for change in &report.reindex_required {
+            println!("   - {}", change);
+        }
+    }
+
+    if !report.rollback_warnings.is_empty() {
+        println!();
+        println!(
+            "   {} {} rollback warnings",
+            "⚠️".yellow(),
+            report.rollback_warnings.len()
+        );
+        for warning in &report.rollback_warnings {
+            println!("   - {}", warning);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_host_port_takes_first_binding() {
+        assert_eq!(parse_host_port("127.0.0.1:49153\n"), Some(49153));
+        assert_eq!(parse_host_port("0.0.0.0:5000\n[::]:5000\n"), Some(5000));
+        assert_eq!(parse_host_port(""), None);
+    }
+
+    #[test]
+    fn test_new_migrations_compares_file_names_across_trees() {
+        let baseline = vec![
+            PathBuf::from("/tmp/wt/migrations/0001_init.sql"),
+            PathBuf::from("/tmp/wt/migrations/0002_users.sql"),
+        ];
+        let current = vec![
+            PathBuf::from("/repo/migrations/0001_init.sql"),
+            PathBuf::from("/repo/migrations/0002_users.sql"),
+            PathBuf::from("/repo/migrations/0003_orders.sql"),
+        ];
+        assert_eq!(
+            new_migrations(&current, &baseline),
+            vec![PathBuf::from("/repo/migrations/0003_orders.sql")]
+        );
+    }
+
+    #[test]
+    fn test_seaql_applied_sql_records_versions_without_extension() {
+        let sql = seaql_applied_sql(&[PathBuf::from("src/m20240101_000001_init.rs")]);
+        assert!(sql.starts_with("CREATE TABLE IF NOT EXISTS seaql_migrations"));
+        assert!(sql.contains("VALUES ('m20240101_000001_init', 0)"));
+    }
+}
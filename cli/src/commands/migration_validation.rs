@@ -68,6 +68,13 @@ pub enum MigrationIssue {
         issue_type: String,
         suggestion: String,
     },
+    /// Elasticsearch mapping change existing documents cannot take in place
+    ReindexRequired {
+        file: PathBuf,
+        index: String,
+        reason: String,
+        suggestion: String,
+    },
 }
 
 impl MigrationIssue {
@@ -136,6 +143,20 @@ impl MigrationIssue {
             } => {
                 format!("{} - {}\n  Suggestion: {}", file, issue_type, suggestion)
             }
+            MigrationIssue::ReindexRequired {
+                file,
+                index,
+                reason,
+                suggestion,
+            } => {
+                format!(
+                    "{} - Reindex required for {}\n  Change: {}\n  Suggestion: {}",
+                    file.display(),
+                    index,
+                    reason,
+                    suggestion
+                )
+            }
         }
     }
 }
@@ -155,6 +176,17 @@ impl MigrationValidationResult {
     }
 }
 
+/// Result of validating a Databend or Elasticsearch migration chain
+#[derive(Debug, Default)]
+pub struct StoreValidationResult {
+    /// Number of migration files checked
+    pub files_checked: usize,
+    /// Idempotency, manifest and reindex issues (block the gate)
+    pub issues: Vec<MigrationIssue>,
+    /// Changes the previous release may not survive after a rollback (never block)
+    pub rollback_warnings: Vec<String>,
+}
+
 /// Check migrations for idempotency patterns
 ///
 /// Validates that migrations use IF NOT EXISTS / IF EXISTS patterns
@@ -468,7 +500,7 @@ async fn find_sql_files(dir: &Path) -> Result<Vec<PathBuf>> {
 }
 
 /// Find all SQL files in the migrations directory, filtering by config
-async fn find_sql_files_with_config(
+pub(super) async fn find_sql_files_with_config(
     dir: &Path,
     config: &MigrationGatesConfig,
 ) -> Result<Vec<PathBuf>> {
@@ -680,7 +712,7 @@ fn should_exclude_seaorm_file(file_path: &Path, config: &MigrationGatesConfig) -
 }
 
 /// Find all SeaORM migration files (m*.rs) in a directory
-async fn find_seaorm_migration_files(
+pub(super) async fn find_seaorm_migration_files(
     dir: &Path,
     config: &MigrationGatesConfig,
 ) -> Result<Vec<PathBuf>> {
@@ -984,6 +1016,45 @@ pub struct ManifestEntry {
     pub data_forward: Option<String>,
     #[serde(default)]
     pub data_backward: Option<String>,
+    /// Run this data_only migration as a forge-managed batched backfill
+    /// (`forge migrations backfill`) instead of inside the migration job
+    #[serde(default)]
+    pub backfill: Option<BackfillSpec>,
+}
+
+/// Placeholder in [`BackfillSpec::sql`] replaced by each batch's key range
+pub const BACKFILL_BATCH_PLACEHOLDER: &str = "{batch}";
+
+/// Batched backfill definition for a data_only manifest entry.
+///
+/// Rows of `table` are walked in `key` order; each batch runs `sql` with
+/// [`BACKFILL_BATCH_PLACEHOLDER`] replaced by a predicate selecting that
+/// batch's key range, e.g.
+/// `UPDATE users SET display_name = name WHERE {batch} AND display_name IS NULL`.
+/// The SeaORM migration itself stays a no-op so the migration job never
+/// holds the long-running transaction.
+#[derive(Debug, Clone, Deserialize)]
+pub struct BackfillSpec {
+    /// Table whose rows are walked
+    pub table: String,
+    /// Unique, indexed column the batches are keyed on
+    #[serde(default = "default_backfill_key")]
+    pub key: String,
+    /// Optional predicate restricting which rows are walked at all
+    #[serde(default)]
+    pub filter: Option<String>,
+    /// Statement executed once per batch
+    pub sql: String,
+    /// Rows per batch (overridable with --batch-size)
+    #[serde(default)]
+    pub batch_size: Option<u64>,
+    /// Pause between batches, e.g. "2s" (overridable with --sleep)
+    #[serde(default)]
+    pub sleep: Option<String>,
+}
+
+fn default_backfill_key() -> String {
+    "id".to_string()
 }
 
 /// Top-level manifest structure
@@ -1016,6 +1087,7 @@ pub struct RollbackCompatibilityResult {
 /// 1. Every m*.rs file past seaorm_check_after is declared in the manifest
 /// 2. schema_and_data entries reference existing, registered companion files
 /// 3. noop entries have a reason
+/// 4. backfill entries are data_only and batch on `{batch}`
 pub async fn validate_migration_manifest(
     seaorm_dir: &Path,
     config: &MigrationGatesConfig,
@@ -1029,7 +1101,29 @@ pub async fn validate_migration_manifest(
         .bold()
     );
 
-    let manifest_path = seaorm_dir.join("migration-manifest.yaml");
+    // Find all migration files past threshold
+    let migration_files = find_seaorm_migration_files(seaorm_dir, config).await?;
+    check_manifest(
+        seaorm_dir,
+        &migration_files,
+        "rs",
+        config.seaorm_check_after.as_deref(),
+        true,
+    )
+    .await
+}
+
+/// Check `dir/migration-manifest.yaml` against one chain's migration files
+/// (named `<migration>.<extension>`). Shared by the SeaORM, Databend and
+/// Elasticsearch chains; only the Postgres chain may declare backfills.
+pub(super) async fn check_manifest(
+    dir: &Path,
+    migration_files: &[PathBuf],
+    extension: &str,
+    check_after: Option<&str>,
+    allow_backfill: bool,
+) -> Result<ManifestValidationResult> {
+    let manifest_path = dir.join("migration-manifest.yaml");
 
     // Load manifest
     let manifest = if manifest_path.exists() {
@@ -1062,14 +1156,10 @@ pub async fn validate_migration_manifest(
         });
     };
 
-    // Find all migration files past threshold
-    let migration_files = find_seaorm_migration_files(seaorm_dir, config).await?;
     let mut issues = Vec::new();
 
-    for file_path in &migration_files {
-        let filename = file_path.file_name().and_then(|n| n.to_str()).unwrap_or("");
-        // Strip .rs extension to get the migration name
-        let migration_name = filename.trim_end_matches(".rs");
+    for file_path in migration_files {
+        let migration_name = file_path.file_stem().and_then(|n| n.to_str()).unwrap_or("");
 
         // Check 1: Is this migration declared in the manifest?
         match manifest.migrations.get(migration_name) {
@@ -1102,10 +1192,45 @@ pub async fn validate_migration_manifest(
                     }
                 }
 
+                // Check 2b: backfills must be data_only and use the batch placeholder
+                if let Some(ref backfill) = entry.backfill {
+                    if !allow_backfill {
+                        issues.push(MigrationIssue::DataMigrationIncomplete {
+                            file: migration_name.to_string(),
+                            issue_type: "backfill declared outside the Postgres chain".to_string(),
+                            suggestion:
+                                "Batched backfills run on Postgres only; remove the backfill block"
+                                    .to_string(),
+                        });
+                    } else if entry.classification != MigrationClassification::DataOnly {
+                        issues.push(MigrationIssue::DataMigrationIncomplete {
+                            file: migration_name.to_string(),
+                            issue_type: format!(
+                                "backfill declared on a {:?} migration",
+                                entry.classification
+                            ),
+                            suggestion: "Move the backfill to a data_only migration".to_string(),
+                        });
+                    }
+                    if !backfill.sql.contains(BACKFILL_BATCH_PLACEHOLDER) {
+                        issues.push(MigrationIssue::DataMigrationIncomplete {
+                            file: migration_name.to_string(),
+                            issue_type: format!(
+                                "backfill sql does not reference {}",
+                                BACKFILL_BATCH_PLACEHOLDER
+                            ),
+                            suggestion: format!(
+                                "Restrict the backfill statement with WHERE {} so it runs per batch",
+                                BACKFILL_BATCH_PLACEHOLDER
+                            ),
+                        });
+                    }
+                }
+
                 // Check 3: schema_and_data entries must reference existing companion files
                 if entry.classification == MigrationClassification::SchemaAndData {
                     if let Some(ref data_forward) = entry.data_forward {
-                        let forward_path = seaorm_dir.join(format!("{}.rs", data_forward));
+                        let forward_path = dir.join(format!("{}.{}", data_forward, extension));
                         if !forward_path.exists() {
                             issues.push(MigrationIssue::DataMigrationIncomplete {
                                 file: migration_name.to_string(),
@@ -1114,8 +1239,8 @@ pub async fn validate_migration_manifest(
                                     data_forward
                                 ),
                                 suggestion: format!(
-                                    "Create {}.rs or update data_forward reference",
-                                    data_forward
+                                    "Create {}.{} or update data_forward reference",
+                                    data_forward, extension
                                 ),
                             });
                         }
@@ -1187,20 +1312,14 @@ pub async fn validate_migration_manifest(
     // Check 4: Detect orphaned manifest entries (no corresponding file)
     let migration_filenames: std::collections::HashSet<String> = migration_files
         .iter()
-        .filter_map(|p| {
-            p.file_name()
-                .and_then(|n| n.to_str())
-                .map(|n| n.trim_end_matches(".rs").to_string())
-        })
+        .filter_map(|p| p.file_stem().and_then(|n| n.to_str()).map(str::to_string))
         .collect();
 
     for manifest_name in manifest.migrations.keys() {
         if !migration_filenames.contains(manifest_name.as_str()) {
             // Only warn for entries that should be past threshold (not grandfathered)
-            let is_past_threshold = config
-                .seaorm_check_after
-                .as_ref()
-                .map(|threshold| manifest_name.as_str() >= threshold.as_str())
+            let is_past_threshold = check_after
+                .map(|threshold| manifest_name.as_str() >= threshold)
                 .unwrap_or(true);
 
             if is_past_threshold {
@@ -1208,8 +1327,8 @@ pub async fn validate_migration_manifest(
                     file: manifest_name.to_string(),
                     issue_type: "Manifest entry has no corresponding migration file".to_string(),
                     suggestion: format!(
-                        "Remove '{}' from migration-manifest.yaml or create {}.rs",
-                        manifest_name, manifest_name
+                        "Remove '{}' from migration-manifest.yaml or create {}.{}",
+                        manifest_name, manifest_name, extension
                     ),
                 });
             }
@@ -1614,6 +1733,7 @@ migrations:
 
         let data_only = &manifest.migrations["m20260203_000001_backfill"];
         assert_eq!(data_only.classification, MigrationClassification::DataOnly);
+        assert!(data_only.backfill.is_none());
 
         let schema_and_data = &manifest.migrations["m20260204_000001_rename_col"];
         assert_eq!(
@@ -1729,6 +1849,53 @@ migrations:
         ));
     }
 
+    #[tokio::test]
+    async fn test_validate_manifest_backfill_rules() {
+        let dir_guard = crate::test_support::named_scratch_dir("test_manifest_backfill");
+        let dir = dir_guard.path();
+
+        std::fs::write(dir.join("m20260301_000001_add_col.rs"), "// migration").unwrap();
+        std::fs::write(dir.join("m20260301_000002_add_col_data.rs"), "// migration").unwrap();
+
+        std::fs::write(
+            dir.join("migration-manifest.yaml"),
+            r#"
+migrations:
+  m20260301_000001_add_col:
+    classification: schema_only
+    backfill:
+      table: users
+      sql: "UPDATE users SET display_name = name WHERE {batch}"
+  m20260301_000002_add_col_data:
+    classification: data_only
+    backfill:
+      table: users
+      key: user_id
+      batch_size: 500
+      sql: "UPDATE users SET display_name = name"
+"#,
+        )
+        .unwrap();
+
+        let config = MigrationGatesConfig {
+            seaorm_check_after: Some("m20260201".to_string()),
+            ..Default::default()
+        };
+
+        let result = validate_migration_manifest(dir, &config).await.unwrap();
+        assert_eq!(result.issues.len(), 2);
+        assert!(result.issues.iter().any(|issue| matches!(
+            issue,
+            MigrationIssue::DataMigrationIncomplete { file, issue_type, .. }
+            if file == "m20260301_000001_add_col" && issue_type.contains("SchemaOnly")
+        )));
+        assert!(result.issues.iter().any(|issue| matches!(
+            issue,
+            MigrationIssue::DataMigrationIncomplete { file, issue_type, .. }
+            if file == "m20260301_000002_add_col_data" && issue_type.contains("{batch}")
+        )));
+    }
+
     #[tokio::test]
     async fn test_validate_manifest_schema_and_data_missing_forward() {
         let dir_guard = crate::test_support::named_scratch_dir("test_manifest_missing_forward");
@@ -18,8 +18,11 @@
 //!
 //! Static migration YAMLs in k8s manifests are TEMPLATES ONLY.
 
+use crate::commands::migration_validation::MigrationManifest;
+use crate::commands::prerelease::PreReleaseConfig;
 use crate::commands::service_config::{DatabaseType, ServiceConfig};
 use crate::config::DeployConfig;
+use crate::error::KubernetesError;
 use crate::infrastructure::kubectl::kubectl_command_async;
 use crate::observability::{
     emit_event, EventMetadata, MigrationTracker, ReleaseEvent, ShinkaMigrationCompletedEvent,
@@ -28,7 +31,11 @@ use crate::observability::{
 use crate::retry::RetryPolicy;
 use anyhow::{bail, Context, Result};
 use colored::Colorize;
+use k8s_openapi::api::batch::v1::Job;
+use kube::api::{Api, DynamicObject, ListParams};
 use serde::Deserialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
 use std::process::Stdio;
 use std::time::{Duration, Instant};
 
@@ -1179,7 +1186,7 @@ pub async fn set_expected_tag_if_exists(migration_name: &str, namespace: &str, e
 /// 2. Fast-requeue at 1s instead of 60s
 /// Non-fatal if it fails — falls back to normal polling.
 async fn set_expected_tag_annotation(migration_name: &str, namespace: &str, expected_tag: &str) {
-    let annotation = format!("release.shinka.pleme.io/expected-tag={}", expected_tag);
+    let annotation = format!("{}={}", EXPECTED_TAG_ANNOTATION, expected_tag);
     println!("   📌 Setting expected-tag annotation: {}", expected_tag);
 
     let result = kubectl_command_async()
@@ -1272,6 +1279,425 @@ fn format_timeout_diagnostic(
     lines.join("\n")
 }
 
+// =============================================================================
+// Migration Status Overview
+// =============================================================================
+
+/// Annotation carrying the image tag a release expects Shinka to migrate to.
+const EXPECTED_TAG_ANNOTATION: &str = "release.shinka.pleme.io/expected-tag";
+
+/// API group of Shinka's `DatabaseMigration` CRD; the version is discovered.
+const SHINKA_GROUP: &str = "shinka.pleme.io";
+
+#[derive(Debug, Deserialize)]
+struct DatabaseMigrationView {
+    metadata: MigrationMetadataView,
+    #[serde(default)]
+    status: ShinkaMigrationStatusView,
+}
+
+#[derive(Debug, Deserialize)]
+struct MigrationMetadataView {
+    name: String,
+    #[serde(default)]
+    annotations: BTreeMap<String, String>,
+}
+
+/// A Job Shinka ran for a DatabaseMigration.
+#[derive(Debug, Clone, PartialEq)]
+struct MigrationJob {
+    name: String,
+    image_tag: Option<String>,
+    outcome: JobOutcome,
+    started: Option<String>,
+    duration: Option<chrono::Duration>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum JobOutcome {
+    Running,
+    Succeeded,
+    Failed,
+}
+
+/// Whether a `migration-manifest.yaml` migration has run in an environment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ManifestMigrationState {
+    Applied,
+    Pending,
+    Failed,
+    /// No applied tag resolvable to a local commit
+    Unknown,
+}
+
+/// The migration Jobs of `migration_name` among `jobs`, oldest first.
+fn migration_jobs(jobs: &[Job], migration_name: &str) -> Vec<MigrationJob> {
+    let mut found: Vec<MigrationJob> = jobs
+        .iter()
+        .filter_map(|job| {
+            let name = job.metadata.name.as_deref()?;
+            let app = job.metadata.labels.as_ref().and_then(|l| l.get("app"));
+            let owned =
+                app.map(String::as_str) == Some(migration_name) || name.starts_with(migration_name);
+            if !owned || !name.contains("migration") {
+                return None;
+            }
+
+            let status = job.status.clone().unwrap_or_default();
+            let count = |field: Option<i32>| field.unwrap_or(0);
+            let outcome = if count(status.succeeded) > 0 {
+                JobOutcome::Succeeded
+            } else if count(status.failed) > 0 && count(status.active) == 0 {
+                JobOutcome::Failed
+            } else {
+                JobOutcome::Running
+            };
+
+            let started = status.start_time.map(|t| t.0);
+            let finished = status.completion_time.map(|t| t.0).or_else(|| {
+                status
+                    .conditions?
+                    .into_iter()
+                    .find(|c| c.type_ == "Failed" && c.status == "True")?
+                    .last_transition_time
+                    .map(|t| t.0)
+            });
+            let duration = match (started, finished) {
+                (Some(start), Some(end)) => Some(end.signed_duration_since(start)),
+                _ => None,
+            };
+
+            let image_tag = job
+                .spec
+                .as_ref()
+                .and_then(|spec| spec.template.spec.as_ref())
+                .and_then(|pod| pod.containers.first())
+                .and_then(|container| container.image.as_deref())
+                .and_then(|image| crate::oci_manifest::image_repository_and_tag(image).1)
+                .map(str::to_string);
+
+            Some(MigrationJob {
+                name: name.to_string(),
+                image_tag,
+                outcome,
+                started: started.map(|t| t.to_rfc3339_opts(chrono::SecondsFormat::Secs, true)),
+                duration,
+            })
+        })
+        .collect();
+    found.sort_by(|a, b| a.started.cmp(&b.started));
+    found
+}
+
+/// The git commit an image tag was built from (`amd64-abc1234` → `abc1234`).
+fn image_tag_commit(tag: &str) -> Option<&str> {
+    let candidate = tag.rsplit('-').next()?;
+    (candidate.len() >= 7 && candidate.chars().all(|c| c.is_ascii_hexdigit())).then_some(candidate)
+}
+
+/// Classify manifest migrations against the migrations present at the
+/// applied commit and at the commit of a failed run.
+fn classify_manifest_migrations(
+    names: &[String],
+    applied: Option<&BTreeSet<String>>,
+    failed: Option<&BTreeSet<String>>,
+) -> Vec<(String, ManifestMigrationState)> {
+    names
+        .iter()
+        .map(|name| {
+            let state = match applied {
+                Some(applied) if applied.contains(name) => ManifestMigrationState::Applied,
+                None => ManifestMigrationState::Unknown,
+                Some(_) if failed.is_some_and(|f| f.contains(name)) => {
+                    ManifestMigrationState::Failed
+                }
+                Some(_) => ManifestMigrationState::Pending,
+            };
+            (name.clone(), state)
+        })
+        .collect()
+}
+
+/// SeaORM migrations in `seaorm_dir` at `commit`, or `None` when the commit
+/// is not available locally.
+async fn migrations_at_commit(
+    repo_root: &Path,
+    seaorm_dir: &Path,
+    commit: &str,
+) -> Option<BTreeSet<String>> {
+    let relative = seaorm_dir.strip_prefix(repo_root).ok()?;
+    let output = crate::git::git_command_async()
+        .arg("-C")
+        .arg(repo_root)
+        .args(["ls-tree", "--name-only", commit, "--"])
+        .arg(format!("{}/", relative.display()))
+        .output()
+        .await
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(|path| path.rsplit('/').next()?.strip_suffix(".rs"))
+            .filter(|name| name.starts_with('m') && *name != "mod" && *name != "main")
+            .map(str::to_string)
+            .collect(),
+    )
+}
+
+fn format_job_duration(duration: chrono::Duration) -> String {
+    if duration.num_minutes() > 0 {
+        format!(
+            "{}m{:02}s",
+            duration.num_minutes(),
+            duration.num_seconds() % 60
+        )
+    } else {
+        format!("{}s", duration.num_seconds())
+    }
+}
+
+fn print_database_migration(migration: &DatabaseMigrationView, jobs: &[MigrationJob]) {
+    let status = &migration.status;
+    let expected = migration
+        .metadata
+        .annotations
+        .get(EXPECTED_TAG_ANNOTATION)
+        .map(String::as_str);
+
+    println!();
+    println!("   {}", migration.metadata.name.cyan().bold());
+    println!(
+        "      Phase:        {}",
+        status.phase.as_deref().unwrap_or("Unknown")
+    );
+    println!("      Expected tag: {}", expected.unwrap_or("-"));
+    match &status.last_migration {
+        Some(last) if last.success => {
+            let marker = match expected {
+                Some(tag) if tag != last.image_tag => " ⚠️  behind expected".yellow().to_string(),
+                _ => String::new(),
+            };
+            println!(
+                "      Applied tag:  {} ({}){}",
+                last.image_tag,
+                last.duration.as_deref().unwrap_or("?"),
+                marker
+            );
+        }
+        Some(last) => {
+            println!(
+                "      Last run:     {} {} ({})",
+                last.image_tag,
+                "failed".red(),
+                last.duration.as_deref().unwrap_or("?")
+            );
+            if let Some(ref err) = last.error {
+                println!("      Last error:   {}", err.red());
+            }
+        }
+        None => println!("      Applied tag:  -"),
+    }
+    if let Some(retries) = status.retry_count.filter(|r| *r > 0) {
+        println!("      Retries:      {}", retries);
+    }
+
+    if let Some(ref results) = status.migrator_results {
+        println!("      Migrators:");
+        for r in results {
+            let icon = if r.success { "✅" } else { "❌" };
+            println!(
+                "        {} {} ({})",
+                icon,
+                r.name,
+                r.duration.as_deref().unwrap_or("?")
+            );
+            if let Some(ref err) = r.error {
+                println!("           {}", err.red());
+            }
+        }
+    }
+
+    if !jobs.is_empty() {
+        println!("      Jobs:");
+        for job in jobs {
+            let icon = match job.outcome {
+                JobOutcome::Succeeded => "✅",
+                JobOutcome::Failed => "❌",
+                JobOutcome::Running => "⏳",
+            };
+            println!(
+                "        {} {}  {}  {}  {}",
+                icon,
+                job.name,
+                job.image_tag.as_deref().unwrap_or("-"),
+                job.duration
+                    .map(format_job_duration)
+                    .unwrap_or_else(|| "-".to_string()),
+                job.started.as_deref().unwrap_or("-")
+            );
+        }
+    }
+}
+
+/// Read-only overview of a product environment's Shinka DatabaseMigrations:
+/// expected vs applied tag, phase, last error, migration Jobs, and which
+/// `migration-manifest.yaml` migrations are applied, pending or failed.
+///
+/// Manifest state is derived from git: a migration is applied when it
+/// exists at the commit of the applied image tag (the last successful run,
+/// or the newest succeeded Job), failed when it only exists at the commit
+/// of a failed run, and pending otherwise.
+pub async fn show_status(product: &str, repo_root: &str, env: &str) -> Result<()> {
+    let product_config = DeployConfig::load_product_config(product, repo_root)?;
+    let namespace = product_config.namespace_for_env(env);
+
+    let client = crate::k8s::create_client().await?;
+    let (resource, _) = kube::discovery::group(&client, SHINKA_GROUP)
+        .await
+        .with_context(|| format!("Failed to discover the {} API group", SHINKA_GROUP))?
+        .recommended_kind("DatabaseMigration")
+        .with_context(|| format!("{} serves no DatabaseMigration kind", SHINKA_GROUP))?;
+    let api: Api<DynamicObject> = Api::namespaced_with(client.clone(), &namespace, &resource);
+    let migrations: Vec<DatabaseMigrationView> = api
+        .list(&ListParams::default())
+        .await
+        .map_err(|e| KubernetesError::api_failed("list DatabaseMigrations", &namespace, e))?
+        .items
+        .into_iter()
+        .map(|object| {
+            serde_json::to_value(object)
+                .and_then(serde_json::from_value)
+                .context("Failed to parse DatabaseMigration")
+        })
+        .collect::<Result<_>>()?;
+
+    // Jobs are supplementary; an RBAC gap on jobs must not hide the CRs,
+    // but it is reported rather than shown as "no jobs".
+    let jobs: Vec<Job> = match Api::<Job>::namespaced(client, &namespace)
+        .list(&ListParams::default())
+        .await
+    {
+        Ok(list) => list.items,
+        Err(e) => {
+            let err = KubernetesError::api_failed("list jobs", &namespace, e);
+            let hint = match &err {
+                KubernetesError::ApiFailed {
+                    code: Some(403), ..
+                } => " (RBAC: grant list on batch/jobs to see migration Jobs)",
+                _ => "",
+            };
+            println!("⚠️  {}{}", err.to_string().yellow(), hint);
+            Vec::new()
+        }
+    };
+
+    println!(
+        "📋 {}",
+        format!(
+            "Migrations for {} ({}, namespace {})",
+            product, env, namespace
+        )
+        .bold()
+    );
+    if migrations.is_empty() {
+        println!("   No DatabaseMigrations found");
+        return Ok(());
+    }
+
+    let with_jobs: Vec<(&DatabaseMigrationView, Vec<MigrationJob>)> = migrations
+        .iter()
+        .map(|m| (m, migration_jobs(&jobs, &m.metadata.name)))
+        .collect();
+    for (migration, jobs) in &with_jobs {
+        print_database_migration(migration, jobs);
+    }
+
+    // Manifest migrations, compared against the backend DatabaseMigration.
+    let repo_root = Path::new(repo_root);
+    let product_dir = crate::config::resolve_product_dir(repo_root, product);
+    let seaorm_dir = PreReleaseConfig::from_working_dir(&product_dir).seaorm_migrations_dir;
+    let manifest_path = seaorm_dir.join("migration-manifest.yaml");
+    if !manifest_path.exists() {
+        return Ok(());
+    }
+    let content = tokio::fs::read_to_string(&manifest_path)
+        .await
+        .with_context(|| format!("Failed to read {}", manifest_path.display()))?;
+    let manifest: MigrationManifest = serde_yaml::from_str(&content)
+        .with_context(|| format!("Failed to parse {}", manifest_path.display()))?;
+
+    println!();
+    println!("   {}", "Manifest migrations".bold());
+    let backend = format!("{}-backend", product);
+    let target = with_jobs
+        .iter()
+        .find(|(m, _)| m.metadata.name == backend)
+        .or(if with_jobs.len() == 1 {
+            with_jobs.first()
+        } else {
+            None
+        });
+    let Some((migration, jobs)) = target else {
+        println!(
+            "      No {} DatabaseMigration to compare the manifest against",
+            backend
+        );
+        return Ok(());
+    };
+
+    let last = migration.status.last_migration.as_ref();
+    let applied_tag = last
+        .filter(|l| l.success)
+        .map(|l| l.image_tag.clone())
+        .or_else(|| {
+            jobs.iter()
+                .rev()
+                .find(|j| j.outcome == JobOutcome::Succeeded)
+                .and_then(|j| j.image_tag.clone())
+        });
+    let failed_tag = last.filter(|l| !l.success).map(|l| l.image_tag.as_str());
+
+    let applied = match applied_tag.as_deref().and_then(image_tag_commit) {
+        Some(commit) => migrations_at_commit(repo_root, &seaorm_dir, commit).await,
+        None => None,
+    };
+    let failed = match failed_tag.and_then(image_tag_commit) {
+        Some(commit) => migrations_at_commit(repo_root, &seaorm_dir, commit).await,
+        None => None,
+    };
+
+    let mut names: Vec<String> = manifest.migrations.keys().cloned().collect();
+    names.sort();
+    let states = classify_manifest_migrations(&names, applied.as_ref(), failed.as_ref());
+    for (name, state) in &states {
+        let label = match state {
+            ManifestMigrationState::Applied => "✅ applied".green(),
+            ManifestMigrationState::Pending => "⏳ pending".yellow(),
+            ManifestMigrationState::Failed => "❌ failed ".red(),
+            ManifestMigrationState::Unknown => "❔ unknown".dimmed(),
+        };
+        println!("      {}  {}", label, name);
+    }
+    let count = |state| states.iter().filter(|(_, s)| *s == state).count();
+    println!(
+        "      {} applied, {} pending, {} failed",
+        count(ManifestMigrationState::Applied),
+        count(ManifestMigrationState::Pending),
+        count(ManifestMigrationState::Failed)
+    );
+    if applied.is_none() {
+        println!(
+            "      Applied tag {} does not resolve to a local commit (try git fetch)",
+            applied_tag.as_deref().unwrap_or("-")
+        );
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     /// Regression shield: every `kubectl`-spawning site in
@@ -1513,6 +1939,75 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_image_tag_commit_takes_trailing_sha() {
+        use super::image_tag_commit;
+        assert_eq!(image_tag_commit("amd64-abc1234"), Some("abc1234"));
+        assert_eq!(
+            image_tag_commit("0123456789abcdef"),
+            Some("0123456789abcdef")
+        );
+        assert_eq!(image_tag_commit("latest"), None);
+        assert_eq!(image_tag_commit("v1.2.3"), None);
+    }
+
+    #[test]
+    fn test_migration_jobs_reads_outcome_duration_and_tag() {
+        use super::{migration_jobs, JobOutcome};
+        let jobs: Vec<k8s_openapi::api::batch::v1::Job> = serde_json::from_value(serde_json::json!([
+                {
+                    "metadata": {"name": "myapp-backend-migration-2", "labels": {"app": "myapp-backend"}},
+                    "spec": {"template": {"spec": {"containers": [{"name": "migrate", "image": "registry.acme.io:5000/backend:amd64-bbbbbbb"}]}}},
+                    "status": {
+                        "startTime": "2026-01-02T10:00:00Z",
+                        "failed": 1,
+                        "conditions": [{"type": "Failed", "status": "True", "lastTransitionTime": "2026-01-02T10:00:30Z"}]
+                    }
+                },
+                {
+                    "metadata": {"name": "myapp-backend-migration-1", "labels": {"app": "myapp-backend"}},
+                    "spec": {"template": {"spec": {"containers": [{"name": "migrate", "image": "ghcr.io/acme/backend:amd64-aaaaaaa@sha256:0123"}]}}},
+                    "status": {
+                        "startTime": "2026-01-01T10:00:00Z",
+                        "completionTime": "2026-01-01T10:01:05Z",
+                        "succeeded": 1
+                    }
+                },
+                {
+                    "metadata": {"name": "myapp-web-migration-1", "labels": {"app": "myapp-web"}},
+                    "status": {"active": 1}
+                }
+        ]))
+        .unwrap();
+
+        let found = migration_jobs(&jobs, "myapp-backend");
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0].name, "myapp-backend-migration-1");
+        assert_eq!(found[0].outcome, JobOutcome::Succeeded);
+        assert_eq!(found[0].image_tag.as_deref(), Some("amd64-aaaaaaa"));
+        assert_eq!(found[0].duration, Some(chrono::Duration::seconds(65)));
+        assert_eq!(found[1].outcome, JobOutcome::Failed);
+        assert_eq!(found[1].duration, Some(chrono::Duration::seconds(30)));
+        assert_eq!(found[1].image_tag.as_deref(), Some("amd64-bbbbbbb"));
+        assert_eq!(found[0].started.as_deref(), Some("2026-01-01T10:00:00Z"));
+    }
+
+    #[test]
+    fn test_classify_manifest_migrations() {
+        use super::{classify_manifest_migrations, ManifestMigrationState::*};
+        use std::collections::BTreeSet;
+        let names: Vec<String> = ["m1", "m2", "m3"].iter().map(|s| s.to_string()).collect();
+        let applied: BTreeSet<String> = ["m1".to_string()].into();
+        let failed: BTreeSet<String> = ["m1".to_string(), "m2".to_string()].into();
+
+        let states = classify_manifest_migrations(&names, Some(&applied), Some(&failed));
+        let states: Vec<_> = states.into_iter().map(|(_, s)| s).collect();
+        assert_eq!(states, [Applied, Failed, Pending]);
+
+        let states = classify_manifest_migrations(&names, None, Some(&failed));
+        assert!(states.iter().all(|(_, s)| *s == Unknown));
+    }
+
     #[test]
     fn test_migrations_routes_kubectl_through_kubectl_command_async_not_raw_command() {
         let module_body = crate::test_support::module_body_before_tests(
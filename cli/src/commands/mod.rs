@@ -7,26 +7,37 @@ pub mod codegen_validation;
 pub mod comprehensive_release;
 pub mod crossplane;
 pub mod dashboards;
+pub mod databend_migrations;
 pub mod deploy;
 pub mod developer_tools;
+pub mod drift;
 pub mod e2e;
+pub mod elasticsearch_migrations;
 pub mod federation;
 pub mod federation_tests;
+pub mod fleet_status;
 pub mod flux;
+pub mod flux_suspend;
 pub mod frontend_validation;
 pub mod gem;
 pub mod github_runner_ci;
+pub mod graphql_schema;
 pub mod helm;
+pub mod helm_release;
 pub mod image_release;
 pub mod infra;
 pub mod integration_tests;
 pub mod kenshi;
 pub mod kenshi_agent;
 pub mod local;
+pub mod migration_backfill;
+pub mod migration_lint;
 pub mod migration_new;
+pub mod migration_rehearsal;
 pub mod migration_validation;
 pub mod migrations;
 pub mod nix_builder;
+pub mod operation_checks;
 pub mod pangea;
 pub mod pangea_infra;
 pub mod post_deploy_verification;
@@ -38,18 +49,26 @@ pub mod release_commit;
 pub mod rollback;
 pub mod rollout;
 pub mod rust_service;
+pub mod schema_compat;
 pub mod schema_validation;
+pub mod search_reindex;
 pub mod search_sync;
 pub mod seed;
 pub mod service_config;
 pub mod sessions;
 pub mod status;
+pub mod subgraph_changes;
+pub mod supergraph_compose;
 pub mod supergraph_verification;
 pub mod sync;
 pub mod test;
 pub mod test_ci;
+pub mod test_quarantine;
+pub mod test_reports;
+pub mod test_sharding;
 pub mod tool;
 pub mod typescript;
+pub mod watch;
 pub mod web_build_verify;
 pub mod web_service;
 pub mod workspace_deps;
@@ -9,7 +9,10 @@ use std::process::Command;
 use tracing::{info, warn};
 
 use crate::commands::push;
-use crate::commands::release_commit::commit_cluster_overlay_release;
+use crate::commands::release_commit::{
+    commit_cluster_overlay_release, wait_for_flux_revision, FluxTarget,
+};
+use crate::infrastructure::git::CommitPushOutcome;
 use crate::repo::get_tool_path;
 
 /// Resolve the `nc` binary path via the canonical two-argument
@@ -225,6 +228,7 @@ async fn verify_external(hostname: &str, port: u16) -> Result<()> {
 /// 5. Update secondary cluster kenshi kustomization.yaml BUILDER_IMAGE env var
 /// 6. Update secondary cluster builder-pool builderImage field
 /// 7. Commit and push to git
+/// 8. With `wait_for_flux`, wait until each target Kustomization has applied the pushed commit
 pub async fn release(
     image_path: String,
     registry: String,
@@ -235,6 +239,7 @@ pub async fn release(
     secondary_builder_pool: String,
     retries: u32,
     token: Option<String>,
+    wait_for_flux: Option<&[FluxTarget]>,
 ) -> Result<()> {
     info!("🚀 Starting nix-builder release");
     info!("   Image: {}", image_path);
@@ -307,7 +312,10 @@ pub async fn release(
     info!("━━━ Step 7/7: Commit and Push ━━━");
     info!("📤 Committing release changes...");
     let file_refs: Vec<&str> = modified_files.iter().map(String::as_str).collect();
-    commit_cluster_overlay_release(None, "nix-builder", &new_tag, &file_refs).await?;
+    let outcome = commit_cluster_overlay_release(None, "nix-builder", &new_tag, &file_refs).await?;
+    if let (Some(targets), CommitPushOutcome::Pushed) = (wait_for_flux, outcome) {
+        wait_for_flux_revision(targets).await?;
+    }
 
     println!();
     info!("╔════════════════════════════════════════════════════════════╗");
@@ -316,7 +324,9 @@ pub async fn release(
     println!();
     info!("Image: {}:{}", registry, new_tag);
     info!("Updated all clusters");
-    info!("FluxCD will reconcile the changes automatically.");
+    if wait_for_flux.is_none() {
+        info!("FluxCD will reconcile the changes automatically.");
+    }
     println!();
 
     Ok(())
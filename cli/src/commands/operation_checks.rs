@@ -0,0 +1,997 @@
+//! Client Operation Checks
+//!
+//! Schema change classification flags everything that *could* break a
+//! client. This module checks what actually would: the operations the
+//! frontend sends, validated against a newly composed supergraph.
+//!
+//! Operations come from two places:
+//! - A persisted-query manifest, either Apollo's
+//!   `persisted-query-manifest.json` or the `persisted-documents.json`
+//!   map GraphQL Code Generator's client preset writes
+//! - The frontend's GraphQL documents (the ones `codegen` consumes):
+//!   `.graphql` / `.gql` files and `graphql(`...`)` / `gql`...``
+//!   template literals under the product's `dirs.web`
+//!
+//! Fragments are shared across documents, so each operation is made
+//! self-contained with the fragments it spreads before it is validated. A
+//! document that does not parse is reported as a warning and skipped.
+//!
+//! The manifest is the source of truth for persisted-query ids: the client
+//! sends the id its own tooling computed, which forge cannot reproduce from
+//! the documents. When both sources are configured, a document operation
+//! whose name the manifest already has is not checked twice. Only the
+//! operations carrying a client-side id are published next to the
+//! supergraph as an Apollo-format persisted-query manifest.
+
+use anyhow::{bail, Context, Result};
+use async_graphql_parser::types::{
+    DocumentOperations, ExecutableDocument, FragmentDefinition, OperationType, Selection,
+    SelectionSet, VariableDefinition,
+};
+use async_graphql_parser::{Pos, Positioned};
+use async_graphql_value::{Name, Value};
+use colored::Colorize;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+use tokio::fs;
+
+use super::graphql_schema::{named_type, BaseType, InputValue, Kind, Schema, Type, TypeDef};
+use crate::config::DeployConfig;
+
+/// File name of the published manifest, next to `supergraph.graphql`
+pub const MANIFEST_FILE: &str = "persisted-query-manifest.json";
+
+/// Directories never scanned for documents
+const SKIP_DIRS: &[&str] = &[
+    "node_modules",
+    "dist",
+    "build",
+    ".git",
+    ".svelte-kit",
+    ".next",
+];
+
+/// Extensions of source files that may embed documents
+const SOURCE_EXTENSIONS: &[&str] = &["ts", "tsx", "js", "jsx", "mjs", "svelte", "vue"];
+
+static TEMPLATE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\b(?:graphql|gql)\s*(?:\(\s*)?`([^`]*)`").unwrap());
+static BLOCK_COMMENT: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?s)/\*.*?\*/").unwrap());
+static INTERPOLATION: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\$\{[^}]*\}").unwrap());
+static SPREAD: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\.\.\.\s*([_A-Za-z][_0-9A-Za-z]*)").unwrap());
+
+/// A self-contained client operation
+#[derive(Debug, Clone, PartialEq)]
+pub struct Operation {
+    /// Client-side persisted-query id, from the manifest. `None` for
+    /// operations read from documents.
+    pub id: Option<String>,
+    pub name: String,
+    /// `query`, `mutation` or `subscription`
+    pub kind: String,
+    /// The operation followed by every fragment it spreads
+    pub body: String,
+    /// Where the operation was found
+    pub source: String,
+}
+
+/// An operation that does not validate against the schema
+#[derive(Debug)]
+pub struct BrokenOperation {
+    pub operation: Operation,
+    pub problems: Vec<String>,
+}
+
+fn kind_name(ty: OperationType) -> &'static str {
+    match ty {
+        OperationType::Query => "query",
+        OperationType::Mutation => "mutation",
+        OperationType::Subscription => "subscription",
+    }
+}
+
+// =============================================================================
+// Loading
+// =============================================================================
+
+/// Byte offset of a parser position in `text`
+fn offset(text: &str, pos: Pos) -> usize {
+    let line_start: usize = text
+        .split_inclusive('\n')
+        .take(pos.line.saturating_sub(1))
+        .map(str::len)
+        .sum();
+    let line = &text[line_start..];
+    line_start
+        + line
+            .char_indices()
+            .nth(pos.column.saturating_sub(1))
+            .map_or(line.len(), |(i, _)| i)
+}
+
+/// Names of the fragments `text` spreads (inline fragments excluded)
+fn spreads(text: &str) -> impl Iterator<Item = &str> {
+    SPREAD
+        .captures_iter(text)
+        .map(|c| c.get(1).unwrap().as_str())
+        .filter(|name| *name != "on")
+}
+
+/// Placeholder operation that lets a fragment-only document parse
+const FRAGMENTS_ONLY: &str = "__ForgeFragmentsOnly";
+
+/// Parse an executable document, including one that only defines fragments
+fn parse_document(text: &str) -> Result<ExecutableDocument> {
+    async_graphql_parser::parse_query(text).or_else(|e| {
+        async_graphql_parser::parse_query(format!(
+            "{}\nquery {} {{ __typename }}",
+            text, FRAGMENTS_ONLY
+        ))
+        .map_err(|_| e.into())
+    })
+}
+
+/// Split documents into self-contained operations. Fragments may be
+/// defined in any document. Documents that do not parse are skipped and
+/// returned as warnings.
+pub fn operations_from_documents(documents: &[(String, String)]) -> (Vec<Operation>, Vec<String>) {
+    let mut fragments: BTreeMap<String, String> = BTreeMap::new();
+    let mut operations = Vec::new();
+    let mut warnings = Vec::new();
+
+    for (source, text) in documents {
+        let document = match parse_document(text) {
+            Ok(document) => document,
+            Err(e) => {
+                warnings.push(format!(
+                    "Skipping unparsable GraphQL document in {}: {}",
+                    source, e
+                ));
+                continue;
+            }
+        };
+        let mut starts: Vec<Pos> = document
+            .operations
+            .iter()
+            .map(|(_, op)| op.pos)
+            .chain(document.fragments.values().map(|f| f.pos))
+            .collect();
+        starts.sort_by_key(|pos| (pos.line, pos.column));
+        let span = |pos: Pos| {
+            let start = offset(text, pos);
+            let end = starts
+                .iter()
+                .find(|p| (p.line, p.column) > (pos.line, pos.column))
+                .map_or(text.len(), |p| offset(text, *p));
+            text[start..end].trim().to_string()
+        };
+
+        for (name, fragment) in &document.fragments {
+            fragments.insert(name.to_string(), span(fragment.pos));
+        }
+        let mut ops: Vec<_> = document
+            .operations
+            .iter()
+            .filter(|(name, _)| name.map(|n| n.as_str()) != Some(FRAGMENTS_ONLY))
+            .collect();
+        ops.sort_by_key(|(_, op)| (op.pos.line, op.pos.column));
+        for (name, op) in ops {
+            operations.push((
+                name.map_or_else(|| "<anonymous>".to_string(), |n| n.to_string()),
+                kind_name(op.node.ty),
+                span(op.pos),
+                source.clone(),
+            ));
+        }
+    }
+
+    let operations = operations
+        .into_iter()
+        .map(|(name, kind, text, source)| {
+            let mut used = BTreeSet::new();
+            let mut pending: Vec<&str> = spreads(&text).collect();
+            while let Some(name) = pending.pop() {
+                if let Some(fragment) = fragments.get(name) {
+                    if used.insert(name.to_string()) {
+                        pending.extend(spreads(fragment));
+                    }
+                }
+            }
+            let body = std::iter::once(text.as_str())
+                .chain(used.iter().map(|name| fragments[name].as_str()))
+                .collect::<Vec<_>>()
+                .join("\n\n");
+            Operation {
+                id: None,
+                name,
+                kind: kind.to_string(),
+                body,
+                source,
+            }
+        })
+        .collect();
+    (operations, warnings)
+}
+
+/// Embedded documents of a source file. Block comments are skipped: the
+/// generated `gql.ts` carries a `graphql(`...`)` usage example in one.
+fn embedded_documents(content: &str) -> Vec<String> {
+    let content = BLOCK_COMMENT.replace_all(content, "");
+    TEMPLATE
+        .captures_iter(&content)
+        .map(|c| INTERPOLATION.replace_all(&c[1], "").into_owned())
+        .filter(|document| !document.trim().is_empty())
+        .collect()
+}
+
+/// Collect the GraphQL documents under a frontend directory
+pub async fn collect_documents(web_dir: &Path) -> Result<Vec<(String, String)>> {
+    let mut files: Vec<PathBuf> = walkdir::WalkDir::new(web_dir)
+        .into_iter()
+        .filter_entry(|e| {
+            !(e.file_type().is_dir()
+                && SKIP_DIRS.contains(&e.file_name().to_string_lossy().as_ref()))
+        })
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .map(|e| e.into_path())
+        .collect();
+    files.sort();
+
+    let mut documents = Vec::new();
+    for file in files {
+        let extension = file.extension().and_then(|e| e.to_str()).unwrap_or("");
+        let embedded = SOURCE_EXTENSIONS.contains(&extension);
+        if !embedded && !matches!(extension, "graphql" | "gql") {
+            continue;
+        }
+        let content = fs::read_to_string(&file)
+            .await
+            .with_context(|| format!("Failed to read: {}", file.display()))?;
+        let source = file
+            .strip_prefix(web_dir)
+            .unwrap_or(&file)
+            .display()
+            .to_string();
+        if embedded {
+            documents.extend(
+                embedded_documents(&content)
+                    .into_iter()
+                    .map(|document| (source.clone(), document)),
+            );
+        } else {
+            documents.push((source, content));
+        }
+    }
+    Ok(documents)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ManifestOperation {
+    id: String,
+    name: String,
+    #[serde(rename = "type")]
+    kind: String,
+    body: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Manifest {
+    format: String,
+    version: u32,
+    operations: Vec<ManifestOperation>,
+}
+
+/// Parse a persisted-query manifest: Apollo's format, or a plain
+/// `{ "<id>": "<document>" }` map
+pub fn parse_manifest(content: &str, source: &str) -> Result<Vec<Operation>> {
+    let value: serde_json::Value = serde_json::from_str(content)
+        .with_context(|| format!("Failed to parse persisted-query manifest {}", source))?;
+
+    if value.get("operations").is_some() {
+        let manifest: Manifest = serde_json::from_value(value)
+            .with_context(|| format!("Invalid persisted-query manifest {}", source))?;
+        return Ok(manifest
+            .operations
+            .into_iter()
+            .map(|op| Operation {
+                id: Some(op.id),
+                name: op.name,
+                kind: op.kind,
+                body: op.body,
+                source: source.to_string(),
+            })
+            .collect());
+    }
+
+    let map: BTreeMap<String, String> = serde_json::from_value(value).with_context(|| {
+        format!(
+            "{} is neither an Apollo manifest nor an id-to-document map",
+            source
+        )
+    })?;
+    map.into_iter()
+        .map(|(id, body)| {
+            let document = async_graphql_parser::parse_query(&body).with_context(|| {
+                format!("Failed to parse persisted document {} in {}", id, source)
+            })?;
+            let (name, op) = document
+                .operations
+                .iter()
+                .next()
+                .with_context(|| format!("Persisted document {} has no operation", id))?;
+            Ok(Operation {
+                id: Some(id),
+                name: name.map_or_else(|| "<anonymous>".to_string(), |n| n.to_string()),
+                kind: kind_name(op.node.ty).to_string(),
+                body,
+                source: source.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Apollo-format persisted-query manifest of the operations carrying a
+/// client-side id, deduplicated by id and ordered by name
+pub fn persisted_query_manifest(operations: &[Operation]) -> Result<String> {
+    let mut unique: BTreeMap<(&str, &str), &Operation> = BTreeMap::new();
+    for op in operations {
+        if let Some(id) = &op.id {
+            unique.entry((&op.name, id)).or_insert(op);
+        }
+    }
+    let manifest = Manifest {
+        format: "apollo-persisted-query-manifest".to_string(),
+        version: 1,
+        operations: unique
+            .iter()
+            .map(|((_, id), op)| ManifestOperation {
+                id: id.to_string(),
+                name: op.name.clone(),
+                kind: op.kind.clone(),
+                body: op.body.clone(),
+            })
+            .collect(),
+    };
+    Ok(serde_json::to_string_pretty(&manifest)? + "\n")
+}
+
+// =============================================================================
+// Validation
+// =============================================================================
+
+fn is_builtin_scalar(name: &str) -> bool {
+    matches!(name, "String" | "Int" | "Float" | "Boolean" | "ID")
+}
+
+fn same_shape(a: &Type, b: &Type) -> bool {
+    match (&a.base, &b.base) {
+        (BaseType::Named(x), BaseType::Named(y)) => x == y,
+        (BaseType::List(x), BaseType::List(y)) => same_shape(x, y),
+        _ => false,
+    }
+}
+
+fn is_required(value: &InputValue) -> bool {
+    !value.ty.nullable && value.default.is_none()
+}
+
+struct Validator<'a> {
+    schema: &'a Schema,
+    fragments: &'a HashMap<Name, Positioned<FragmentDefinition>>,
+    variables: HashMap<&'a str, &'a VariableDefinition>,
+    spreading: Vec<&'a str>,
+    problems: Vec<String>,
+}
+
+impl<'a> Validator<'a> {
+    fn problem(&mut self, path: &str, message: String) {
+        self.problems.push(format!("{}: {}", path, message));
+    }
+
+    /// The composite type a fragment applies to
+    fn composite(&mut self, name: &str, path: &str) -> Option<&'a TypeDef> {
+        match self.schema.types.get(name) {
+            Some(ty) if matches!(ty.kind, Kind::Object | Kind::Interface | Kind::Union) => Some(ty),
+            Some(_) => {
+                self.problem(path, format!("type \"{}\" is not a composite type", name));
+                None
+            }
+            None => {
+                self.problem(path, format!("type \"{}\" does not exist", name));
+                None
+            }
+        }
+    }
+
+    fn selection_set(&mut self, parent: &'a TypeDef, set: &'a SelectionSet, path: &str) {
+        for item in &set.items {
+            match &item.node {
+                Selection::Field(field) => {
+                    let field = &field.node;
+                    let response = field.alias.as_ref().unwrap_or(&field.name).node.as_str();
+                    let path = if path.is_empty() {
+                        response.to_string()
+                    } else {
+                        format!("{}.{}", path, response)
+                    };
+                    self.field(parent, field, &path);
+                }
+                Selection::InlineFragment(fragment) => {
+                    let ty = match &fragment.node.type_condition {
+                        Some(condition) => match self.composite(&condition.node.on.node, path) {
+                            Some(ty) => ty,
+                            None => continue,
+                        },
+                        None => parent,
+                    };
+                    self.selection_set(ty, &fragment.node.selection_set.node, path);
+                }
+                Selection::FragmentSpread(spread) => {
+                    let name = spread.node.fragment_name.node.as_str();
+                    if self.spreading.contains(&name) {
+                        continue;
+                    }
+                    let Some(fragment) = self.fragments.get(name) else {
+                        self.problem(path, format!("fragment \"{}\" is not defined", name));
+                        continue;
+                    };
+                    let Some(ty) = self.composite(&fragment.node.type_condition.node.on.node, path)
+                    else {
+                        continue;
+                    };
+                    self.spreading.push(name);
+                    self.selection_set(ty, &fragment.node.selection_set.node, path);
+                    self.spreading.pop();
+                }
+            }
+        }
+    }
+
+    fn field(
+        &mut self,
+        parent: &'a TypeDef,
+        field: &'a async_graphql_parser::types::Field,
+        path: &str,
+    ) {
+        let name = field.name.node.as_str();
+        if name == "__typename"
+            || (matches!(name, "__schema" | "__type") && parent.name == self.schema.query_type())
+        {
+            return;
+        }
+        if parent.kind == Kind::Union {
+            self.problem(
+                path,
+                format!("union \"{}\" has no field \"{}\"", parent.name, name),
+            );
+            return;
+        }
+        let Some(definition) = parent.field(name) else {
+            self.problem(
+                path,
+                format!("field \"{}.{}\" does not exist", parent.name, name),
+            );
+            return;
+        };
+
+        for (arg, value) in &field.arguments {
+            let arg = arg.node.as_str();
+            match definition.arg(arg) {
+                Some(def) => self.value(&value.node, &def.ty, &format!("{}({}:)", path, arg)),
+                None => self.problem(
+                    path,
+                    format!(
+                        "argument \"{}\" does not exist on \"{}.{}\"",
+                        arg, parent.name, name
+                    ),
+                ),
+            }
+        }
+        for arg in &definition.args {
+            if is_required(arg) && !field.arguments.iter().any(|(n, _)| n.node == arg.name) {
+                self.problem(
+                    path,
+                    format!(
+                        "required argument \"{}: {}\" is not provided",
+                        arg.name, arg.ty
+                    ),
+                );
+            }
+        }
+
+        let selections = &field.selection_set.node;
+        match self.schema.types.get(named_type(&definition.ty)) {
+            Some(target) if matches!(target.kind, Kind::Object | Kind::Interface | Kind::Union) => {
+                if selections.items.is_empty() {
+                    self.problem(
+                        path,
+                        format!(
+                            "\"{}\" is a composite type and needs a selection",
+                            target.name
+                        ),
+                    );
+                } else {
+                    self.selection_set(target, selections, path);
+                }
+            }
+            _ if !selections.items.is_empty() => self.problem(
+                path,
+                format!(
+                    "\"{}\" is a leaf type and cannot have a selection",
+                    definition.ty
+                ),
+            ),
+            _ => {}
+        }
+    }
+
+    fn value(&mut self, value: &Value, ty: &Type, path: &str) {
+        match value {
+            Value::Variable(variable) => match self.variables.get(variable.as_str()) {
+                None => self.problem(path, format!("variable ${} is not defined", variable)),
+                Some(definition) => {
+                    let declared = &definition.var_type.node;
+                    let nullable_mismatch =
+                        !ty.nullable && declared.nullable && definition.default_value.is_none();
+                    if !same_shape(declared, ty) || nullable_mismatch {
+                        self.problem(
+                            path,
+                            format!(
+                                "variable ${} of type \"{}\" is used where \"{}\" is expected",
+                                variable, declared, ty
+                            ),
+                        );
+                    }
+                }
+            },
+            Value::Null if !ty.nullable => {
+                self.problem(path, format!("null is not allowed for \"{}\"", ty))
+            }
+            Value::Null => {}
+            Value::List(items) => match &ty.base {
+                BaseType::List(inner) => {
+                    for item in items {
+                        self.value(item, inner, path);
+                    }
+                }
+                BaseType::Named(_) => self.problem(
+                    path,
+                    format!("a list is given where \"{}\" is expected", ty),
+                ),
+            },
+            other => match &ty.base {
+                BaseType::List(inner) => self.value(other, inner, path),
+                BaseType::Named(name) => self.named_value(other, name, path),
+            },
+        }
+    }
+
+    fn named_value(&mut self, value: &Value, name: &str, path: &str) {
+        match (value, self.schema.types.get(name)) {
+            (Value::Object(fields), Some(input)) if input.kind == Kind::InputObject => {
+                for (field, value) in fields {
+                    match input.input_field(field) {
+                        Some(def) => self.value(value, &def.ty, &format!("{}.{}", path, field)),
+                        None => self.problem(
+                            path,
+                            format!("input field \"{}.{}\" does not exist", name, field),
+                        ),
+                    }
+                }
+                for field in &input.input_fields {
+                    if is_required(field) && !fields.keys().any(|k| k.as_str() == field.name) {
+                        self.problem(
+                            path,
+                            format!(
+                                "required input field \"{}.{}\" is not provided",
+                                name, field.name
+                            ),
+                        );
+                    }
+                }
+            }
+            (Value::Enum(value), Some(target))
+                if target.kind == Kind::Enum
+                    && !target.values.iter().any(|v| v.name == value.as_str()) =>
+            {
+                self.problem(
+                    path,
+                    format!("enum value \"{}.{}\" does not exist", name, value),
+                );
+            }
+            (Value::Enum(_), Some(target)) if target.kind == Kind::Enum => {}
+            (_, Some(target)) if matches!(target.kind, Kind::InputObject | Kind::Enum) => {
+                self.problem(path, format!("the value does not fit type \"{}\"", name))
+            }
+            (Value::Object(_) | Value::Enum(_), _) => {
+                self.problem(path, format!("the value does not fit type \"{}\"", name))
+            }
+            (_, None) if !is_builtin_scalar(name) => {
+                self.problem(path, format!("type \"{}\" does not exist", name))
+            }
+            (value, None) => {
+                let fits = match (name, value) {
+                    ("Int", Value::Number(n)) => n.is_i64() || n.is_u64(),
+                    ("Float", Value::Number(_)) | ("String", Value::String(_)) => true,
+                    ("Boolean", Value::Boolean(_)) => true,
+                    ("ID", Value::String(_)) => true,
+                    ("ID", Value::Number(n)) => n.is_i64() || n.is_u64(),
+                    _ => false,
+                };
+                if !fits {
+                    self.problem(path, format!("the value does not fit type \"{}\"", name));
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Validate one operation against a schema, returning the problems found
+pub fn validate(schema: &Schema, operation: &Operation) -> Vec<String> {
+    let document: ExecutableDocument = match async_graphql_parser::parse_query(&operation.body) {
+        Ok(document) => document,
+        Err(e) => return vec![format!("invalid document: {}", e)],
+    };
+    let operations: Vec<_> = match &document.operations {
+        DocumentOperations::Single(op) => vec![op],
+        DocumentOperations::Multiple(ops) => ops.values().collect(),
+    };
+
+    let mut problems = Vec::new();
+    for op in operations {
+        let op = &op.node;
+        let mut validator = Validator {
+            schema,
+            fragments: &document.fragments,
+            variables: HashMap::new(),
+            spreading: Vec::new(),
+            problems: Vec::new(),
+        };
+        for variable in &op.variable_definitions {
+            let name = variable.node.name.node.as_str();
+            let ty = named_type(&variable.node.var_type.node);
+            match schema.types.get(ty).map(|t| t.kind) {
+                None if !is_builtin_scalar(ty) => validator.problem(
+                    &format!("${}", name),
+                    format!("type \"{}\" does not exist", ty),
+                ),
+                Some(Kind::Object | Kind::Interface | Kind::Union) => validator.problem(
+                    &format!("${}", name),
+                    format!("type \"{}\" is not an input type", ty),
+                ),
+                _ => {}
+            }
+            validator.variables.insert(name, &variable.node);
+        }
+
+        let root = match op.ty {
+            OperationType::Query => Some(schema.query_type()),
+            OperationType::Mutation => schema.mutation_type(),
+            OperationType::Subscription => schema.subscription_type(),
+        };
+        match root.and_then(|root| schema.types.get(root)) {
+            Some(root) => validator.selection_set(root, &op.selection_set.node, ""),
+            None => validator.problem(
+                kind_name(op.ty),
+                "the schema has no root type for this operation".to_string(),
+            ),
+        }
+        problems.extend(validator.problems);
+    }
+    problems
+}
+
+/// Operations that do not validate against `schema`
+pub fn check_operations(schema: &Schema, operations: &[Operation]) -> Vec<BrokenOperation> {
+    operations
+        .iter()
+        .filter_map(|operation| {
+            let problems = validate(schema, operation);
+            (!problems.is_empty()).then(|| BrokenOperation {
+                operation: operation.clone(),
+                problems,
+            })
+        })
+        .collect()
+}
+
+// =============================================================================
+// Release check
+// =============================================================================
+
+/// Where to read client operations from
+#[derive(Debug, Default)]
+pub struct OperationSources {
+    pub manifest: Option<PathBuf>,
+    pub documents: Option<PathBuf>,
+}
+
+impl OperationSources {
+    /// Sources configured for the product (`federation.operations` and
+    /// `dirs.web` in deploy.yaml)
+    pub fn from_config(deploy_config: &DeployConfig) -> Result<Self> {
+        let repo_root = crate::git::get_repo_root()?;
+        let product_dir =
+            crate::config::resolve_product_dir(&repo_root, &deploy_config.product.name);
+        let operations = &deploy_config
+            .service
+            .federation
+            .as_ref()
+            .unwrap_or(&deploy_config.global.federation)
+            .operations;
+        Ok(Self {
+            manifest: deploy_config
+                .product
+                .resolve_dir(&product_dir, operations.manifest.as_deref()),
+            documents: if operations.documents {
+                deploy_config.product.web_dir(&product_dir)
+            } else {
+                None
+            },
+        })
+    }
+
+    pub async fn load(&self) -> Result<Vec<Operation>> {
+        let mut operations = Vec::new();
+        if let Some(manifest) = &self.manifest {
+            let content = fs::read_to_string(manifest)
+                .await
+                .with_context(|| format!("Failed to read: {}", manifest.display()))?;
+            operations.extend(parse_manifest(&content, &manifest.display().to_string())?);
+        }
+        if let Some(web_dir) = self.documents.as_ref().filter(|dir| dir.exists()) {
+            let (documents, warnings) =
+                operations_from_documents(&collect_documents(web_dir).await?);
+            for warning in warnings {
+                println!("⚠️  {}", warning.yellow());
+            }
+            // The manifest's copy of an operation wins
+            let persisted: BTreeSet<String> = operations.iter().map(|op| op.name.clone()).collect();
+            operations.extend(
+                documents
+                    .into_iter()
+                    .filter(|op| !persisted.contains(&op.name)),
+            );
+        }
+        Ok(operations)
+    }
+}
+
+/// Validate client operations against a supergraph and write the
+/// persisted-query manifest to `manifest_out`. Fails when any operation
+/// would break, unless `allow_broken`.
+pub async fn check_client_operations(
+    sources: &OperationSources,
+    supergraph_path: &Path,
+    manifest_out: Option<&Path>,
+    allow_broken: bool,
+) -> Result<Vec<BrokenOperation>> {
+    let operations = sources.load().await?;
+    if operations.is_empty() {
+        println!("ℹ️  No client operations found - skipping operation checks");
+        return Ok(Vec::new());
+    }
+
+    println!(
+        "🔍 Checking {} client operation(s) against {}...",
+        operations.len(),
+        supergraph_path.display()
+    );
+    let sdl = fs::read_to_string(supergraph_path)
+        .await
+        .with_context(|| format!("Failed to read: {}", supergraph_path.display()))?;
+    let schema = Schema::parse(&sdl)?;
+    let broken = check_operations(&schema, &operations);
+
+    for op in &broken {
+        eprintln!(
+            "   {} {} {} ({})",
+            "✗".red(),
+            op.operation.kind,
+            op.operation.name.bold(),
+            op.operation.source
+        );
+        for problem in &op.problems {
+            eprintln!("       {}", problem);
+        }
+    }
+    if !broken.is_empty() {
+        let message = format!(
+            "{} of {} client operation(s) would break against the new supergraph",
+            broken.len(),
+            operations.len()
+        );
+        if !allow_broken {
+            bail!("{}", message);
+        }
+        println!("⚠️  {}", message.yellow());
+    } else {
+        println!(
+            "   {} All {} client operation(s) valid",
+            "✓".green(),
+            operations.len()
+        );
+    }
+
+    match manifest_out {
+        Some(path) if operations.iter().any(|op| op.id.is_some()) => {
+            fs::write(path, persisted_query_manifest(&operations)?)
+                .await
+                .with_context(|| format!("Failed to write: {}", path.display()))?;
+            println!("   Persisted-query manifest: {}", path.display());
+        }
+        Some(_) => println!(
+            "   No operation carries a client-side id - persisted-query manifest not written"
+        ),
+        None => {}
+    }
+    Ok(broken)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCHEMA: &str = r#"
+type Query {
+  product(id: ID!): Product
+  search(filter: Filter, sort: Sort = NAME): [Product!]!
+}
+
+type Product {
+  id: ID!
+  name: String!
+  price(currency: Currency!): Float
+  related: [Product!]!
+}
+
+input Filter { text: String, minPrice: Float! }
+
+enum Sort { NAME PRICE }
+
+enum Currency { USD EUR }
+"#;
+
+    fn operations(document: &str) -> Vec<Operation> {
+        let (operations, warnings) =
+            operations_from_documents(&[("src/query.ts".to_string(), document.to_string())]);
+        assert!(warnings.is_empty(), "{warnings:?}");
+        operations
+    }
+
+    #[test]
+    fn test_operations_from_documents_inline_fragments() {
+        let documents = vec![
+            (
+                "src/fragments.graphql".to_string(),
+                "fragment Card on Product { id name ...Price }\n\nfragment Price on Product { price(currency: USD) }\n\nfragment Unused on Product { id }".to_string(),
+            ),
+            (
+                "src/routes/product.tsx".to_string(),
+                embedded_documents(
+                    "/**\n * const query = graphql(`query GetUser { user { name } }`);\n */\nconst q = graphql(`\n  query Product($id: ID!) {\n    product(id: $id) { ...Card ... on Product { related { id } } }\n  }\n`);\nconst m = gql`query Other { search { id } }`;",
+                )
+                .join("\n"),
+            ),
+        ];
+        let (ops, warnings) = operations_from_documents(&documents);
+        assert!(warnings.is_empty(), "{warnings:?}");
+        assert_eq!(ops.len(), 2);
+        let product = ops.iter().find(|op| op.name == "Product").unwrap();
+        assert_eq!(product.kind, "query");
+        assert_eq!(product.source, "src/routes/product.tsx");
+        assert!(product.body.starts_with("query Product($id: ID!)"));
+        assert!(product.body.contains("fragment Card on Product"));
+        assert!(product.body.contains("fragment Price on Product"));
+        assert!(!product.body.contains("Unused"));
+        assert_eq!(product.id, None);
+        assert!(check_operations(&Schema::parse(SCHEMA).unwrap(), &ops).is_empty());
+    }
+
+    #[test]
+    fn test_validate_reports_breaking_usage() {
+        let schema = Schema::parse(SCHEMA).unwrap();
+        let ops = operations(
+            r#"
+query Broken($filter: Filter, $sort: Sort!) {
+  product { name sku price(currency: GBP) related }
+  search(filter: $filter, sort: $sort, limit: 5) { id }
+  other: search(filter: { text: "x" }) { id }
+}
+"#,
+        );
+        let problems = validate(&schema, &ops[0]);
+        assert_eq!(
+            problems,
+            [
+                "product: required argument \"id: ID!\" is not provided",
+                "product.sku: field \"Product.sku\" does not exist",
+                "product.price(currency:): enum value \"Currency.GBP\" does not exist",
+                "product.related: \"Product\" is a composite type and needs a selection",
+                "search: argument \"limit\" does not exist on \"Query.search\"",
+                "other(filter:): required input field \"Filter.minPrice\" is not provided",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unparsable_documents_are_warnings() {
+        let (ops, warnings) = operations_from_documents(&[
+            (
+                "src/broken.graphql".to_string(),
+                "query Broken {".to_string(),
+            ),
+            (
+                "src/ok.graphql".to_string(),
+                "query Ok { search { id } }".to_string(),
+            ),
+        ]);
+        assert_eq!(ops.len(), 1);
+        assert_eq!(ops[0].name, "Ok");
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("src/broken.graphql"), "{warnings:?}");
+    }
+
+    #[tokio::test]
+    async fn test_manifest_ids_win_over_documents() {
+        let tmp = tempfile::tempdir().unwrap();
+        let manifest = tmp.path().join("persisted-documents.json");
+        std::fs::write(&manifest, r#"{"client-a": "query A { search { id } }"}"#).unwrap();
+        let web = tmp.path().join("web");
+        std::fs::create_dir_all(&web).unwrap();
+        std::fs::write(
+            web.join("ops.graphql"),
+            "query A { search { id name } }\nquery C { search { id } }",
+        )
+        .unwrap();
+
+        let sources = OperationSources {
+            manifest: Some(manifest),
+            documents: Some(web),
+        };
+        let ops = sources.load().await.unwrap();
+        let names: Vec<_> = ops
+            .iter()
+            .map(|op| (op.name.as_str(), op.id.as_deref()))
+            .collect();
+        assert_eq!(names, [("A", Some("client-a")), ("C", None)]);
+
+        // Only the operation with a client-side id is published
+        let published = parse_manifest(&persisted_query_manifest(&ops).unwrap(), "out").unwrap();
+        assert_eq!(published.len(), 1);
+        assert_eq!(published[0].id.as_deref(), Some("client-a"));
+    }
+
+    #[test]
+    fn test_manifest_round_trip() {
+        let ops = operations("query A { search { id } }\nmutation B { search { id } }");
+        assert_eq!(ops.len(), 2);
+        let codegen = format!(
+            r#"{{"abc123": {:?}, "def456": {:?}}}"#,
+            ops[0].body, ops[1].body
+        );
+        let parsed = parse_manifest(&codegen, "persisted-documents.json").unwrap();
+        assert_eq!(parsed[0].id.as_deref(), Some("abc123"));
+        assert_eq!(parsed[0].name, "A");
+
+        let manifest = persisted_query_manifest(&parsed).unwrap();
+        let parsed = parse_manifest(&manifest, "manifest.json").unwrap();
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed[0].name, "A");
+        assert_eq!(parsed[1].kind, "mutation");
+        assert_eq!(parsed[0].body, ops[0].body);
+        assert_eq!(parsed[1].id.as_deref(), Some("def456"));
+
+        // The mutation root does not exist in this schema
+        let schema = Schema::parse(SCHEMA).unwrap();
+        let broken = check_operations(&schema, &ops);
+        assert_eq!(broken.len(), 1);
+        assert_eq!(broken[0].operation.name, "B");
+    }
+}
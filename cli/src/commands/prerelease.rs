@@ -12,11 +12,15 @@
 //! - G4: cargo test --lib --bins
 //! - G5: extract-schema succeeds
 //!
-//! ### Migration Gates (G6-G8b)
+//! ### Migration Gates (G6-G8f)
 //! - G6: SQLx migration idempotency check (legacy migrations)
 //! - G7: Soft-delete compliance check
 //! - G8: SeaORM migration safety check (current migrations - expand-contract pattern)
 //! - G8b: Migration data completeness check (manifest validation)
+//! - G8c: Migration lock-safety lint (lock-heavy and destructive DDL)
+//! - G8d: Schema backward compatibility (previous release's entities vs new migrations)
+//! - G8e: Databend migration safety (idempotency, manifest, rollback warnings)
+//! - G8f: Elasticsearch migration safety (idempotency, manifest, reindex detection)
 //!
 //! ### Frontend Gates (G9-G12)
 //! - G9: Codegen drift detection
@@ -38,6 +42,9 @@
 //! - Configure SeaORM migration exclusions
 //! - Choose linter (biome vs eslint)
 //! - Control whether failures stop the release
+//!
+//! With `--junit <path>`, every gate is written as a JUnit test case (suite
+//! `prerelease`), alongside the individual tests parsed from G4's output.
 
 use anyhow::{bail, Context, Result};
 use colored::Colorize;
@@ -48,9 +55,14 @@ use tokio::process::Command;
 use crate::config::PreReleaseGatesConfig;
 
 use super::codegen_validation;
+use super::databend_migrations;
 use super::e2e;
+use super::elasticsearch_migrations;
 use super::frontend_validation;
+use super::migration_lint;
 use super::migration_validation;
+use super::schema_compat;
+use super::test_reports::{interleave_runner_streams, TestCase, TestReport, TestStatus};
 
 /// Resolve the `docker` binary path via `DOCKER_BIN`, falling back to
 /// `docker` on `PATH`. Wired through [`crate::repo::get_tool_path`] —
@@ -123,6 +135,10 @@ pub struct PreReleaseConfig {
     pub migrations_dir: PathBuf,
     /// SeaORM migrations directory (current)
     pub seaorm_migrations_dir: PathBuf,
+    /// Databend migrations directory (analytics service)
+    pub databend_migrations_dir: PathBuf,
+    /// Elasticsearch migrations directory (search service)
+    pub elasticsearch_migrations_dir: PathBuf,
     /// Skip backend checks (CLI flag override)
     pub skip_backend: bool,
     /// Skip frontend checks (CLI flag override)
@@ -147,6 +163,8 @@ impl PreReleaseConfig {
             web_dir: working_dir.join("web"),
             migrations_dir: working_dir.join("services/rust/backend/migrations"),
             seaorm_migrations_dir: working_dir.join("services/rust/migration/src"),
+            databend_migrations_dir: working_dir.join("services/rust/analytics/migrations"),
+            elasticsearch_migrations_dir: working_dir.join("services/rust/search/migrations"),
             skip_backend: false,
             skip_frontend: false,
             skip_migrations: false,
@@ -168,6 +186,8 @@ pub struct GateSummary {
     pub skipped: Vec<String>,
     /// Total time taken
     pub total_time_secs: f64,
+    /// Individual test results from gates that run a test suite
+    pub tests: TestReport,
 }
 
 impl GateSummary {
@@ -175,6 +195,46 @@ impl GateSummary {
         self.failed.is_empty()
     }
 
+    /// One test case per gate, followed by the individual test results.
+    pub fn to_test_report(&self) -> TestReport {
+        let gate = |name: &String, status: TestStatus, message: Option<String>| TestCase {
+            suite: "prerelease".to_string(),
+            name: name.clone(),
+            status,
+            duration_secs: 0.0,
+            message,
+            retries: 0,
+        };
+
+        let mut report = TestReport::default();
+        report.cases.extend(
+            self.passed
+                .iter()
+                .map(|name| gate(name, TestStatus::Passed, None)),
+        );
+        report.cases.extend(self.failed.iter().map(|name| {
+            let details: Vec<&str> = self
+                .failed_details
+                .iter()
+                .filter(|(detail_gate, _)| name.starts_with(detail_gate.as_str()))
+                .flat_map(|(_, details)| details.iter().map(String::as_str))
+                .collect();
+            let message = if details.is_empty() {
+                format!("{} failed", name)
+            } else {
+                details.join("\n")
+            };
+            gate(name, TestStatus::Failed, Some(message))
+        }));
+        report.cases.extend(
+            self.skipped
+                .iter()
+                .map(|name| gate(name, TestStatus::Skipped, None)),
+        );
+        report.cases.extend(self.tests.cases.iter().cloned());
+        report
+    }
+
     pub fn print_summary(&self) {
         println!();
         println!(
@@ -240,7 +300,7 @@ impl GateSummary {
 }
 
 /// Load gate configuration from deploy.yaml if it exists
-fn load_gates_config(working_dir: &Path) -> PreReleaseGatesConfig {
+pub(crate) fn load_gates_config(working_dir: &Path) -> PreReleaseGatesConfig {
     // Try to load from backend service deploy.yaml
     // Check deploy/backend.yaml first (new convention), fall back to service dir
     let backend_deploy_yaml = {
@@ -290,6 +350,7 @@ pub async fn execute(
     skip_backend: bool,
     skip_frontend: bool,
     skip_migrations: bool,
+    junit: Option<String>,
 ) -> Result<()> {
     let start = Instant::now();
 
@@ -302,6 +363,10 @@ pub async fn execute(
         web_dir: PathBuf::from(&working_dir).join("web"),
         migrations_dir: PathBuf::from(&working_dir).join("services/rust/backend/migrations"),
         seaorm_migrations_dir: PathBuf::from(&working_dir).join("services/rust/migration/src"),
+        databend_migrations_dir: PathBuf::from(&working_dir)
+            .join("services/rust/analytics/migrations"),
+        elasticsearch_migrations_dir: PathBuf::from(&working_dir)
+            .join("services/rust/search/migrations"),
         skip_backend,
         skip_frontend,
         skip_migrations,
@@ -369,6 +434,7 @@ pub async fn execute(
         .failed_details
         .extend(backend_results.failed_details);
     summary.skipped.extend(backend_results.skipped);
+    summary.tests.cases.extend(backend_results.tests.cases);
 
     // Merge migration results
     let migration_results = migration_results?;
@@ -464,6 +530,12 @@ pub async fn execute(
     summary.total_time_secs = start.elapsed().as_secs_f64();
     summary.print_summary();
 
+    if let Some(path) = junit {
+        summary
+            .to_test_report()
+            .write_junit(Path::new(&path), "prerelease")?;
+    }
+
     // Handle failures based on configuration
     if !summary.all_passed() {
         if config.gates.fail_on_error {
@@ -542,7 +614,7 @@ async fn run_backend_gates(config: &PreReleaseConfig) -> Result<GateSummary> {
         summary
             .skipped
             .push("G4: cargo test (disabled)".to_string());
-    } else if run_cargo_test(&config.backend_dir).await? {
+    } else if run_cargo_test(&config.backend_dir, &mut summary.tests).await? {
         summary.passed.push("G4: cargo test".to_string());
     } else {
         summary.failed.push("G4: cargo test".to_string());
@@ -564,7 +636,7 @@ async fn run_backend_gates(config: &PreReleaseConfig) -> Result<GateSummary> {
     Ok(summary)
 }
 
-/// Run migration gates (G6-G8) sequentially
+/// Run migration gates (G6-G8f) sequentially
 async fn run_migration_gates(config: &PreReleaseConfig) -> Result<GateSummary> {
     let mut summary = GateSummary::default();
 
@@ -581,6 +653,18 @@ async fn run_migration_gates(config: &PreReleaseConfig) -> Result<GateSummary> {
         summary
             .skipped
             .push("G8b: Migration data completeness".to_string());
+        summary
+            .skipped
+            .push("G8c: Migration lock safety".to_string());
+        summary
+            .skipped
+            .push("G8d: Schema backward compatibility".to_string());
+        summary
+            .skipped
+            .push("G8e: Databend migration safety".to_string());
+        summary
+            .skipped
+            .push("G8f: Elasticsearch migration safety".to_string());
         return Ok(summary);
     }
 
@@ -725,11 +809,161 @@ async fn run_migration_gates(config: &PreReleaseConfig) -> Result<GateSummary> {
             ));
         }
     }
+
+    // G8c and G8d only look at migrations added since the previous release
+    let release_baseline = if config.gates.migrations.lock_safety_check
+        || config.gates.migrations.backward_compat_check
+    {
+        schema_compat::release_baseline(config).await?
+    } else {
+        None
+    };
+
+    // G8c: Migration lock-safety lint (warnings are reported, errors fail)
+    if !config.gates.migrations.lock_safety_check {
+        summary
+            .skipped
+            .push("G8c: Migration lock safety (disabled)".to_string());
+    } else {
+        let lint_result = migration_lint::lint_migrations(
+            &config.migrations_dir,
+            &config.seaorm_migrations_dir,
+            &config.gates.migrations,
+            release_baseline
+                .as_ref()
+                .map(|b| b.new_migrations.as_slice()),
+        )
+        .await?;
+
+        let errors = lint_result.errors();
+        if errors.is_empty() {
+            let scope = release_baseline
+                .as_ref()
+                .map(|b| format!(" since {}", b.base_ref))
+                .unwrap_or_default();
+            summary.passed.push(format!(
+                "G8c: Migration lock safety ({} files{}, {} warnings)",
+                lint_result.files_checked,
+                scope,
+                lint_result.warnings().len()
+            ));
+        } else {
+            summary.failed.push(format!(
+                "G8c: Migration lock safety ({} errors)",
+                errors.len()
+            ));
+            summary.failed_details.push((
+                "G8c".to_string(),
+                errors.iter().map(|f| f.format()).collect(),
+            ));
+        }
+    }
     println!();
 
+    // G8d: Schema backward compatibility with the previous release
+    if !config.gates.migrations.backward_compat_check {
+        summary
+            .skipped
+            .push("G8d: Schema backward compatibility (disabled)".to_string());
+    } else {
+        let compat_result = schema_compat::check_backward_compat(release_baseline.as_ref()).await?;
+
+        match &compat_result.base_ref {
+            None => summary
+                .skipped
+                .push("G8d: Schema backward compatibility (no previous release)".to_string()),
+            Some(base_ref) if compat_result.violations.is_empty() => summary.passed.push(format!(
+                "G8d: Schema backward compatibility ({} migrations since {})",
+                compat_result.migrations_checked, base_ref
+            )),
+            Some(base_ref) => {
+                summary.failed.push(format!(
+                    "G8d: Schema backward compatibility ({} breaking changes)",
+                    compat_result.violations.len()
+                ));
+                summary.failed_details.push((
+                    "G8d".to_string(),
+                    compat_result
+                        .violations
+                        .iter()
+                        .map(|v| v.format(base_ref))
+                        .collect(),
+                ));
+            }
+        }
+    }
+    println!();
+
+    // G8e: Databend migration safety (analytics chain, if the product has one)
+    if !config.gates.migrations.databend_check {
+        summary
+            .skipped
+            .push("G8e: Databend migration safety (disabled)".to_string());
+    } else if !config.databend_migrations_dir.is_dir() {
+        summary
+            .skipped
+            .push("G8e: Databend migration safety (no migrations)".to_string());
+    } else {
+        let result =
+            databend_migrations::validate_databend_migrations(&config.databend_migrations_dir)
+                .await?;
+        record_store_gate(&mut summary, "G8e", "Databend migration safety", &result);
+        println!();
+    }
+
+    // G8f: Elasticsearch migration safety (search chain, if the product has one)
+    if !config.gates.migrations.elasticsearch_check {
+        summary
+            .skipped
+            .push("G8f: Elasticsearch migration safety (disabled)".to_string());
+    } else if !config.elasticsearch_migrations_dir.is_dir() {
+        summary
+            .skipped
+            .push("G8f: Elasticsearch migration safety (no migrations)".to_string());
+    } else {
+        let result = elasticsearch_migrations::validate_elasticsearch_migrations(
+            &config.elasticsearch_migrations_dir,
+        )
+        .await?;
+        record_store_gate(
+            &mut summary,
+            "G8f",
+            "Elasticsearch migration safety",
+            &result,
+        );
+        println!();
+    }
+
     Ok(summary)
 }
 
+/// Record a Databend or Elasticsearch chain's result: issues fail the gate,
+/// rollback warnings are only counted.
+fn record_store_gate(
+    summary: &mut GateSummary,
+    id: &str,
+    name: &str,
+    result: &migration_validation::StoreValidationResult,
+) {
+    if result.issues.is_empty() {
+        summary.passed.push(format!(
+            "{}: {} ({} files, {} rollback warnings)",
+            id,
+            name,
+            result.files_checked,
+            result.rollback_warnings.len()
+        ));
+    } else {
+        summary
+            .failed
+            .push(format!("{}: {} ({} issues)", id, name, result.issues.len()));
+        summary.failed_details.push((
+            id.to_string(),
+            result.issues.iter().map(|i| i.format()).collect(),
+        ));
+    }
+}
+
 /// Run frontend gates (G9-G12) sequentially
 async fn run_frontend_gates(config: &PreReleaseConfig) -> Result<GateSummary> {
     let mut summary = GateSummary::default();
@@ -1327,7 +1561,9 @@ async fn run_cargo_fmt_check(backend_dir: &Path) -> Result<bool> {
 }
 
 /// G4: Run cargo test
-async fn run_cargo_test(backend_dir: &Path) -> Result<bool> {
+///
+/// The individual test results are parsed from cargo's output into `tests`.
+async fn run_cargo_test(backend_dir: &Path, tests: &mut TestReport) -> Result<bool> {
     println!("{}", "G4: cargo test".bold());
     let start = Instant::now();
 
@@ -1341,6 +1577,10 @@ async fn run_cargo_test(backend_dir: &Path) -> Result<bool> {
 
     let duration = start.elapsed();
     let stdout = String::from_utf8_lossy(&output.stdout);
+    let combined = interleave_runner_streams(&stdout, &String::from_utf8_lossy(&output.stderr));
+    tests
+        .cases
+        .extend(TestReport::parse_runner_output(&combined, "G4").cases);
 
     // Parse test count from output
     let test_count = stdout
@@ -1465,6 +1705,35 @@ mod tests {
         assert!(!merged.all_passed());
     }
 
+    #[test]
+    fn test_gate_summary_to_test_report() {
+        let mut summary = GateSummary::default();
+        summary.passed.push("G1: cargo check".to_string());
+        summary
+            .failed
+            .push("G7: Soft-delete compliance".to_string());
+        summary.failed_details.push((
+            "G7".to_string(),
+            vec!["users.rs: missing deleted_at".to_string()],
+        ));
+        summary
+            .skipped
+            .push("G13: Integration tests (disabled)".to_string());
+        summary
+            .tests
+            .record_step("G4", "api::tests::works", true, 0.1, None);
+
+        let report = summary.to_test_report();
+        let counts = report.counts();
+        assert_eq!((counts.passed, counts.failed, counts.skipped), (2, 1, 1));
+        let failed = report.failures().next().unwrap();
+        assert_eq!(failed.id(), "prerelease::G7: Soft-delete compliance");
+        assert_eq!(
+            failed.message.as_deref(),
+            Some("users.rs: missing deleted_at")
+        );
+    }
+
     // ====================================================================
     // PreReleaseConfig tests
     // ====================================================================
@@ -1585,11 +1854,15 @@ mod tests {
 
         let result = run_migration_gates(&config).await.unwrap();
         assert!(result.all_passed());
-        assert_eq!(result.skipped.len(), 4); // G6-G8b
+        assert_eq!(result.skipped.len(), 8); // G6-G8f
         assert!(result.skipped.iter().any(|s| s.contains("G6")));
         assert!(result.skipped.iter().any(|s| s.contains("G7")));
         assert!(result.skipped.iter().any(|s| s.contains("G8:")));
         assert!(result.skipped.iter().any(|s| s.contains("G8b")));
+        assert!(result.skipped.iter().any(|s| s.contains("G8c")));
+        assert!(result.skipped.iter().any(|s| s.contains("G8d")));
+        assert!(result.skipped.iter().any(|s| s.contains("G8e")));
+        assert!(result.skipped.iter().any(|s| s.contains("G8f")));
     }
 
     #[tokio::test]
@@ -1633,10 +1906,14 @@ mod tests {
         config.gates.migrations.soft_delete_check = false;
         config.gates.migrations.seaorm_safety_check = false;
         config.gates.migrations.data_completeness_check = false;
+        config.gates.migrations.lock_safety_check = false;
+        config.gates.migrations.backward_compat_check = false;
+        config.gates.migrations.databend_check = false;
+        config.gates.migrations.elasticsearch_check = false;
 
         let result = run_migration_gates(&config).await.unwrap();
         assert!(result.all_passed());
-        assert_eq!(result.skipped.len(), 4);
+        assert_eq!(result.skipped.len(), 8);
         for s in &result.skipped {
             assert!(s.contains("disabled"), "Expected 'disabled' in: {}", s);
         }
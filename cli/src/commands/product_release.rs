@@ -18,6 +18,7 @@ use tokio::process::Command;
 
 #[cfg(feature = "attestation")]
 use crate::commands::attestation;
+use crate::commands::flux_suspend;
 use crate::config::DeployConfig;
 use crate::infrastructure::git::{CommitPushOutcome, GitClient};
 use crate::infrastructure::kubectl::kubectl_command_async;
@@ -25,11 +26,18 @@ use crate::repo::get_tool_path;
 
 /// Run a forge subcommand by re-invoking the current binary.
 pub(crate) async fn run_forge_subcommand(args: &[&str]) -> Result<()> {
+    run_forge_subcommand_with_env(args, &[]).await
+}
+
+/// Run a forge subcommand with extra environment variables set on the child
+/// only.
+async fn run_forge_subcommand_with_env(args: &[&str], envs: &[(&str, &str)]) -> Result<()> {
     let exe = std::env::current_exe().context("Failed to get current executable path")?;
     println!("   {} forge {}", ">>".dimmed(), args.join(" ").dimmed());
 
     let mut cmd = Command::new(exe);
     cmd.args(args);
+    cmd.envs(envs.iter().copied());
     crate::retry::run_inherited_status(cmd, &format!("forge {}", args.join(" "))).await
 }
 
@@ -318,6 +326,7 @@ pub async fn product_release(
     skip_gates: bool,
     skip_dashboards: bool,
     build_only: bool,
+    server_dry_run: bool,
 ) -> Result<()> {
     // Load product release config
     let product_config = DeployConfig::load_product_release_config(&product, &repo_root)?;
@@ -366,8 +375,45 @@ pub async fn product_release(
     );
     println!("   Target:   {}", target_env.cyan());
     println!("   SHA:      {}", git_sha.yellow());
+    if server_dry_run {
+        println!(
+            "   Dry run:  {}",
+            "server-side (dryRun=All) before each commit".cyan()
+        );
+    }
     println!();
 
+    // Load environments from the first service's deploy.yaml
+    // (all services share the same environment topology)
+    let first_svc = &product_config.services[0];
+    let first_release =
+        DeployConfig::load_service_release_config(&product, &first_svc.path, &repo_root)?;
+
+    let environments: Vec<String> = if build_only {
+        Vec::new()
+    } else if env.is_some() {
+        // Single environment mode
+        first_release.get_environments(target_env)
+    } else {
+        // All active environments
+        first_release.get_environments("all")
+    };
+
+    if !build_only && environments.is_empty() {
+        bail!(
+            "No active environments to deploy to for '{}'.\n  \
+             Check active_environments in service deploy.yaml.",
+            target_env
+        );
+    }
+
+    // Refuse to deploy into an environment frozen with `forge flux suspend`,
+    // before the gates and builds pay for a release that cannot land
+    let flux_product = DeployConfig::load_product_config(&product, &repo_root)?;
+    for env_name in &environments {
+        flux_suspend::ensure_not_suspended(&flux_product, env_name).await?;
+    }
+
     // ─── Phase 0: Pre-release gates ─────────────────────────────────────────
     // Gates automatically skip for non-staging environments because production
     // promotes the same image that already passed staging gates.
@@ -652,28 +698,6 @@ pub async fn product_release(
 
     println!("{}", "Phase 2: Deploy services".bold());
 
-    // Load environments from the first service's deploy.yaml
-    // (all services share the same environment topology)
-    let first_svc = &product_config.services[0];
-    let first_release =
-        DeployConfig::load_service_release_config(&product, &first_svc.path, &repo_root)?;
-
-    let environments: Vec<String> = if env.is_some() {
-        // Single environment mode
-        first_release.get_environments(target_env)
-    } else {
-        // All active environments
-        first_release.get_environments("all")
-    };
-
-    if environments.is_empty() {
-        bail!(
-            "No active environments to deploy to for '{}'.\n  \
-             Check active_environments in service deploy.yaml.",
-            target_env
-        );
-    }
-
     for env_name in &environments {
         println!("   {} {}", ">>".dimmed(), env_name.cyan().bold());
 
@@ -704,23 +728,33 @@ pub async fn product_release(
             // Always call orchestrate-release directly (not via nix run) to avoid
             // re-evaluating the nix derivation which would rebuild the docker image.
             // Phase 1 already built and pushed the image; Phase 2 only needs to deploy.
-            run_forge_subcommand(&[
-                "orchestrate-release",
-                "--service",
-                &svc.name,
-                "--service-dir",
-                &service_dir,
-                "--repo-root",
-                &repo_root,
-                "--registry",
-                &registry_url,
-                "--deploy-only",
-                "--image-tag",
-                &image_tag,
-                "--single-environment",
-                "--environment",
-                env_name,
-            ])
+            // The subprocess's environment carries the dry-run opt-in down to
+            // the commit site.
+            let dry_run_env: &[(&str, &str)] = if server_dry_run {
+                &[(crate::server_dry_run::ENV_VAR, "true")]
+            } else {
+                &[]
+            };
+            run_forge_subcommand_with_env(
+                &[
+                    "orchestrate-release",
+                    "--service",
+                    &svc.name,
+                    "--service-dir",
+                    &service_dir,
+                    "--repo-root",
+                    &repo_root,
+                    "--registry",
+                    &registry_url,
+                    "--deploy-only",
+                    "--image-tag",
+                    &image_tag,
+                    "--single-environment",
+                    "--environment",
+                    env_name,
+                ],
+                dry_run_env,
+            )
             .await?;
 
             println!(
@@ -26,10 +26,17 @@
 //! consumers (THEORY §V.4) compose on a single typed surface across
 //! every release-commit path in forge.
 
-use anyhow::Result;
+use std::fmt;
+use std::process::Stdio;
+use std::str::FromStr;
+
+use anyhow::{Context, Result};
 use tracing::info;
 
+use crate::flux_api;
+use crate::git::git_command_sync;
 use crate::infrastructure::git::{CommitPushOutcome, GitClient};
+use crate::{git, k8s};
 
 /// Build the canonical cluster-overlay release commit subject.
 ///
@@ -83,10 +90,130 @@ pub async fn commit_cluster_overlay_release(
     Ok(outcome)
 }
 
+/// A Flux Kustomization to wait on after a release push, written
+/// `NAMESPACE/NAME[@CONTEXT]` on the command line. Without `@CONTEXT` the
+/// current kube context is used.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FluxTarget {
+    pub namespace: String,
+    pub name: String,
+    pub context: Option<String>,
+}
+
+impl Default for FluxTarget {
+    /// The root `flux-system` Kustomization on the current kube context.
+    fn default() -> Self {
+        Self {
+            namespace: "flux-system".to_string(),
+            name: "flux-system".to_string(),
+            context: None,
+        }
+    }
+}
+
+impl FromStr for FluxTarget {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Namespaces and names are DNS labels, so the first `@` always starts
+        // the context, which may itself contain `@`, `/` or `:`.
+        let (object, context) = match s.split_once('@') {
+            Some((object, context)) if !context.is_empty() => (object, Some(context)),
+            Some(_) => return Err(format!("missing context after '@' in '{s}'")),
+            None => (s, None),
+        };
+        match object.split_once('/') {
+            Some((namespace, name))
+                if !namespace.is_empty() && !name.is_empty() && !name.contains('/') =>
+            {
+                Ok(Self {
+                    namespace: namespace.to_string(),
+                    name: name.to_string(),
+                    context: context.map(str::to_string),
+                })
+            }
+            _ => Err(format!("expected NAMESPACE/NAME[@CONTEXT], got '{s}'")),
+        }
+    }
+}
+
+impl fmt::Display for FluxTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.namespace, self.name)?;
+        if let Some(context) = &self.context {
+            write!(f, " on {context}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Block until every Kustomization in `targets` has applied the commit at
+/// `HEAD` — the one [`commit_cluster_overlay_release`] just pushed — or a
+/// later commit built on top of it. An empty `targets` waits on
+/// [`FluxTarget::default`]. A plain reconcile only proves Flux ran, not
+/// that it picked up this commit rather than an older one.
+pub async fn wait_for_flux_revision(targets: &[FluxTarget]) -> Result<()> {
+    let sha = git::get_full_sha().context("Failed to read pushed commit")?;
+    let default_targets = [FluxTarget::default()];
+    let targets = if targets.is_empty() {
+        &default_targets[..]
+    } else {
+        targets
+    };
+    for target in targets {
+        info!(
+            "⏳ Waiting for Flux {} to apply {}...",
+            target,
+            &sha[..sha.len().min(7)]
+        );
+        let client = k8s::create_client_for_context(target.context.as_deref()).await?;
+        let applied = flux_api::wait_for_revision(
+            &client,
+            &target.name,
+            &target.namespace,
+            &sha,
+            |applied| includes_commit(applied, &sha),
+            flux_api::REVISION_TIMEOUT,
+        )
+        .await?;
+        info!(
+            "   ✅ Flux applied {}",
+            applied.revision().unwrap_or_default()
+        );
+    }
+    Ok(())
+}
+
+/// Whether commit `applied` has `pushed` in its history. Flux may be on a
+/// commit pushed after ours that this checkout has not seen yet, so an
+/// unknown commit triggers one `git fetch origin` before asking
+/// `git merge-base --is-ancestor`.
+fn includes_commit(applied: &str, pushed: &str) -> bool {
+    let known = |commit: &str| {
+        git_command_sync()
+            .args(["cat-file", "-e", &format!("{commit}^{{commit}}")])
+            .stderr(Stdio::null())
+            .status()
+            .is_ok_and(|s| s.success())
+    };
+    if !known(applied) {
+        let fetched = git_command_sync()
+            .args(["fetch", "--quiet", "origin"])
+            .status()
+            .is_ok_and(|s| s.success());
+        if !fetched || !known(applied) {
+            return false;
+        }
+    }
+    git_command_sync()
+        .args(["merge-base", "--is-ancestor", pushed, applied])
+        .status()
+        .is_ok_and(|s| s.success())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::git::git_command_sync;
     use crate::test_support::{add_bare_origin, init_repo_with_one_commit};
 
     /// The pure commit-subject helper MUST produce the canonical
@@ -108,6 +235,32 @@ mod tests {
         );
     }
 
+    /// `--flux-target` values name a Kustomization and optionally the
+    /// kube context of the cluster it lives on; everything after the first
+    /// `@` is the context, so EKS ARNs and `user@cluster` contexts survive.
+    #[test]
+    fn test_flux_target_parses_namespace_name_and_context() {
+        assert_eq!(
+            "flux-system/apps".parse::<FluxTarget>(),
+            Ok(FluxTarget {
+                namespace: "flux-system".into(),
+                name: "apps".into(),
+                context: None,
+            })
+        );
+        let target: FluxTarget = "flux-system/kenshi@arn:aws:eks:eu-west-1:1:cluster/secondary"
+            .parse()
+            .unwrap();
+        assert_eq!(target.name, "kenshi");
+        assert_eq!(
+            target.context.as_deref(),
+            Some("arn:aws:eks:eu-west-1:1:cluster/secondary")
+        );
+        assert!("flux-system".parse::<FluxTarget>().is_err());
+        assert!("flux-system/apps@".parse::<FluxTarget>().is_err());
+        assert!("a/b/c".parse::<FluxTarget>().is_err());
+    }
+
     /// `commit_cluster_overlay_release` MUST land the canonical commit
     /// subject on `origin/main` via the underlying
     /// `stage_commit_push_release` primitive. Pins the round-trip
@@ -1,18 +1,45 @@
+//! Rollout monitoring (`forge rollout`)
+//!
+//! Follows a Deployment or StatefulSet rollout through
+//! [`kube::runtime::watcher`] streams over the workload, its ReplicaSets
+//! and its Pods, so a pod entering CrashLoopBackOff / ImagePullBackOff
+//! ([`k8s::is_bad_state`]) or a Deployment exceeding its progress
+//! deadline is reported the moment the apiserver publishes it — with the
+//! failing pod's events and logs captured at that moment — instead of on
+//! the next poll.
+
 use anyhow::Result;
 use colored::Colorize;
+use futures::stream::{self, BoxStream};
+use futures::{StreamExt, TryStreamExt};
+use k8s_openapi::api::apps::v1::{Deployment, ReplicaSet, StatefulSet};
+use k8s_openapi::api::core::v1::{Pod, PodTemplateSpec};
+use kube::api::Api;
+use kube::runtime::reflector::{self, Store};
+use kube::runtime::{watcher, WatchStreamExt};
+use kube::{Client, Resource};
 use std::collections::HashMap;
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::Instant;
 use tracing::{debug, error, info, warn};
 
 use crate::infrastructure::kubectl::kubectl_command_async;
-use crate::k8s;
-
-#[derive(Clone, Debug)]
-struct PodStateHistory {
-    last_state: String,
-    last_reason: Option<String>,
-    iterations_in_state: u32,
-    last_restart_count: i32,
-}
+use crate::k8s::{self, PodStatus};
+
+/// A pod unready in the same container state for this long is stuck.
+const STUCK_AFTER: Duration = Duration::from_secs(30);
+
+/// Restart count at which a pod is flagged for excessive restarts.
+const RESTART_THRESHOLD: i32 = 3;
+
+/// Rollout timeout when `--timeout` is not given.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// Annotation the Deployment controller stamps on each ReplicaSet revision.
+const REVISION_ANNOTATION: &str = "deployment.kubernetes.io/revision";
 
 pub async fn execute(
     namespace: String,
@@ -29,33 +56,13 @@ pub async fn execute(
         info!("   Deployment: {}", name);
         println!();
 
-        let rollback_result = kubectl_command_async()
-            .args(&[
-                "rollout",
-                "undo",
-                &format!("deployment/{}", name),
-                "-n",
-                &namespace,
-            ])
-            .status()
-            .await;
-
-        match rollback_result {
-            Ok(status) if status.success() => {
-                info!("✅ Rollback initiated successfully");
-                println!();
+        rollback_deployment(&namespace, &name).await?;
+        info!("✅ Rollback initiated successfully");
+        println!();
 
-                // Monitor the rollback
-                info!("🔄 Monitoring rollback progress...");
-                println!();
-            }
-            Ok(status) => {
-                anyhow::bail!("{}", format_rollback_non_success(&name, &namespace, status));
-            }
-            Err(e) => {
-                anyhow::bail!("{}", format_rollback_spawn_failure(&name, &namespace, &e));
-            }
-        }
+        // Monitor the rollback
+        info!("🔄 Monitoring rollback progress...");
+        println!();
     }
 
     info!("🔄 Rolling out new image to all pods...");
@@ -70,325 +77,607 @@ pub async fn execute(
     info!("   Total replicas: {}", replicas);
     info!("   Expected image tag: {}", expected_tag);
 
-    // Parse timeout if provided
-    let max_iterations = if let Some(timeout_str) = timeout {
-        parse_timeout(&timeout_str)?
-    } else {
-        200 // Default: 200 * 3s = 10 minutes
-    };
+    let timeout = parse_timeout(timeout.as_deref().unwrap_or(""))?;
+    info!("   Timeout: {}", humantime::format_duration(timeout));
+    println!();
 
-    if max_iterations < 200 {
-        info!(
-            "   Timeout: {} iterations (~{}s)",
-            max_iterations,
-            max_iterations * interval
-        );
-    }
+    info!("Watching workload, ReplicaSets and pods for changes:");
     println!();
 
-    info!(
-        "Tracking pod updates (refreshing every {} seconds):",
-        interval
+    let label_selector = format!("app={}", name);
+    let (workload, workload_changes) =
+        WatchedWorkload::start(&client, &namespace, &name, &label_selector).await?;
+    let (pods, pod_changes) = reflect(
+        Api::<Pod>::namespaced(client.clone(), &namespace),
+        watcher::Config::default().labels(&label_selector),
     );
-    println!();
+    let mut events = stream::select_all(workload_changes);
+    events.push(pod_changes);
 
-    let mut iteration = 0;
+    // Events drive progress; the heartbeat only re-renders so stuck
+    // timers advance while the cluster is quiet.
+    let deadline = Instant::now() + timeout;
+    let mut heartbeat = tokio::time::interval(Duration::from_secs(interval.max(1)));
+    let mut tracker = RolloutTracker::default();
+    let mut rendered_lines = 0;
 
-    let mut pod_status_history: HashMap<String, String> = HashMap::new();
-    let mut pod_state_tracking: HashMap<String, PodStateHistory> = HashMap::new();
+    loop {
+        tokio::select! {
+            _ = tokio::time::sleep_until(deadline) => {
+                warn!("⏱️  Timeout reached after {}", humantime::format_duration(timeout));
+                anyhow::bail!("Rollout timeout exceeded");
+            }
+            event = events.next() => match event {
+                Some(Ok(())) => {}
+                Some(Err(e)) => {
+                    // `default_backoff` re-establishes the watch; keep going.
+                    debug!("Rollout watch error (retrying): {}", e);
+                    continue;
+                }
+                None => anyhow::bail!("Rollout watch streams ended unexpectedly"),
+            },
+            _ = heartbeat.tick() => {}
+        }
 
-    // Build label selector for the deployment
-    let label_selector = format!("app={}", name);
+        // Nothing to evaluate until the workload's initial list lands.
+        let Some(state) = workload.state(&name) else {
+            continue;
+        };
 
-    // Thresholds for stuck detection
-    const STUCK_THRESHOLD_ITERATIONS: u32 = 10; // 30 seconds with 3s interval
-    const RESTART_THRESHOLD: i32 = 3;
+        let mut pod_statuses: Vec<PodStatus> =
+            pods.state().iter().map(|p| k8s::pod_status(p)).collect();
+        pod_statuses.sort_by(|a, b| a.name.cmp(&b.name));
+        let verdicts = tracker.observe(pod_statuses, &state.expected_tag, Instant::now());
 
-    loop {
-        iteration += 1;
+        rendered_lines = render_progress(&state, &verdicts, rendered_lines);
 
-        if iteration > max_iterations {
-            warn!("⏱️  Timeout reached after {} iterations", max_iterations);
-            anyhow::bail!("Rollout timeout exceeded");
+        if let Some(reason) = &state.failure {
+            error!("🔬 {} — capturing diagnostics...", reason);
+            println!();
+            for verdict in verdicts.iter().filter(|v| !v.is_updated()) {
+                capture_diagnostics(&client, &namespace, verdict).await;
+            }
+            anyhow::bail!("Rollout failed: {}", reason);
         }
 
-        // Get pod statuses
-        let pods = k8s::get_pod_statuses(&client, &namespace, &label_selector).await?;
-
-        // Clear screen for refresh (move cursor up if not first iteration)
-        if iteration > 1 {
-            for _ in 0..=pods.len() + 2 {
-                print!("\x1B[1A\x1B[2K"); // Move up and clear line
+        let fatal: Vec<&PodVerdict> = verdicts.iter().filter(|v| v.is_fatal(safe_mode)).collect();
+        if !fatal.is_empty() {
+            error!("🔬 Problems detected! Capturing diagnostics...");
+            println!();
+            for verdict in &fatal {
+                capture_diagnostics(&client, &namespace, verdict).await;
             }
+            if safe_mode {
+                anyhow::bail!(
+                    "Rollout failed with {} problem(s) detected in SAFE mode",
+                    fatal.len()
+                );
+            }
+            anyhow::bail!(
+                "Rollout failed: {} pod(s) on {} in a bad state",
+                fatal.len(),
+                state.expected_tag
+            );
         }
 
-        // Print timestamp
-        let now = chrono::Local::now();
-        println!(
-            "{} - Rollout Progress (pods: {}):",
-            now.format("%H:%M:%S"),
-            pods.len()
-        );
-        println!();
+        if rollout_complete(&verdicts, state.desired) {
+            println!();
+            break;
+        }
+    }
 
-        let mut all_ready = true;
-        let mut updated_count = 0;
-        let mut problems_detected = Vec::new();
-
-        for pod in &pods {
-            // Check for bad states
-            let is_bad = k8s::is_bad_state(pod);
-
-            // Track state history
-            let current_state = pod
-                .container_state
-                .as_ref()
-                .map(|s| s.state.clone())
-                .unwrap_or_else(|| pod.phase.clone());
-
-            let current_reason = pod.container_state.as_ref().and_then(|s| s.reason.clone());
-
-            let current_restarts = pod
-                .container_state
-                .as_ref()
-                .map(|s| s.restart_count)
-                .unwrap_or(0);
-
-            let state_key = format!(
-                "{}:{}",
-                current_state,
-                current_reason.as_deref().unwrap_or("")
-            );
+    println!();
+    info!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    println!();
 
-            let history = pod_state_tracking
-                .entry(pod.name.clone())
-                .or_insert_with(|| PodStateHistory {
-                    last_state: state_key.clone(),
-                    last_reason: current_reason.clone(),
-                    iterations_in_state: 0,
-                    last_restart_count: current_restarts,
-                });
-
-            // Check if state changed
-            if history.last_state == state_key {
-                history.iterations_in_state += 1;
-            } else {
-                debug!(
-                    "Pod {} state changed: {} -> {}",
-                    pod.name, history.last_state, state_key
-                );
-                history.last_state = state_key.clone();
-                history.last_reason = current_reason.clone();
-                history.iterations_in_state = 1;
-            }
+    info!("{}", "✅ All pods updated and ready!".bright_green().bold());
+    println!();
 
-            // Update restart count
-            history.last_restart_count = current_restarts;
-
-            // Detect stuck pods
-            let is_stuck = history.iterations_in_state >= STUCK_THRESHOLD_ITERATIONS
-                && !pod.ready
-                && pod.phase != "Succeeded";
-
-            // Detect excessive restarts
-            let excessive_restarts = current_restarts >= RESTART_THRESHOLD;
-
-            // Choose icon based on state
-            let status_icon = if is_bad {
-                "❌".bright_red()
-            } else if excessive_restarts {
-                "🔄".bright_red()
-            } else if is_stuck {
-                "⚠️ ".bright_yellow()
-            } else if pod.ready && pod.phase == "Running" {
-                "✅".bright_green()
-            } else if pod.phase == "Running" {
-                "🔄".bright_yellow()
-            } else {
-                "⏳".bright_blue()
-            };
-
-            // Build status message with detailed state info
-            let mut status_parts = vec![format!("Image: {}", pod.image_tag)];
-
-            if let Some(state) = &pod.container_state {
-                status_parts.push(format!("State: {}", state.state));
-
-                if let Some(reason) = &state.reason {
-                    status_parts.push(format!("Reason: {}", reason));
-                }
+    Ok(())
+}
 
-                if state.restart_count > 0 {
-                    status_parts.push(format!("Restarts: {}", state.restart_count));
-                }
-            } else {
-                status_parts.push(format!("Phase: {}", pod.phase));
-            }
+// ============================================================================
+// Watch streams
+// ============================================================================
+
+/// Change notifications from one reflector-backed watch.
+type ChangeStream = BoxStream<'static, Result<(), watcher::Error>>;
+
+/// Start a reflector-backed watch on `api`: the returned [`Store`] holds
+/// the current objects, the stream yields once per watch event (including
+/// the end of the initial list) so the caller re-evaluates from the store.
+fn reflect<K>(api: Api<K>, config: watcher::Config) -> (Store<K>, ChangeStream)
+where
+    K: Resource + Clone + Debug + serde::de::DeserializeOwned + Send + Sync + 'static,
+    K::DynamicType: Default + Eq + Hash + Clone,
+{
+    let (store, writer) = reflector::store();
+    let changes = reflector::reflector(writer, watcher(api, config))
+        .default_backoff()
+        .map_ok(|_| ())
+        .boxed();
+    (store, changes)
+}
 
-            if is_stuck {
-                status_parts.push(format!(
-                    "⚠️  STUCK ({}s)",
-                    history.iterations_in_state * interval as u32
-                ));
-            }
+/// The workload under rollout, watched by name.
+enum WatchedWorkload {
+    Deployment {
+        deployments: Store<Deployment>,
+        replica_sets: Store<ReplicaSet>,
+    },
+    StatefulSet {
+        statefulsets: Store<StatefulSet>,
+    },
+}
 
-            let status_msg = if is_bad || excessive_restarts {
-                status_parts.join(" | ").bright_red()
-            } else if is_stuck {
-                status_parts.join(" | ").bright_yellow()
-            } else if pod.ready && pod.phase == "Running" {
-                status_parts.join(" | ").bright_green()
-            } else {
-                status_parts.join(" | ").bright_blue()
-            };
-
-            // Check if pod has the expected image
-            let has_correct_image = pod.image_tag == expected_tag;
-            let needs_update = !has_correct_image;
-
-            // Show indicator if pod needs update
-            let update_indicator = if needs_update {
-                " 🔄 UPDATE PENDING".bright_yellow()
-            } else {
-                "".normal()
-            };
-
-            println!(
-                "  {} {} | {}{}",
-                status_icon, pod.name, status_msg, update_indicator
+/// What the workload's spec and status say about the rollout.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkloadState {
+    pub desired: i32,
+    pub expected_tag: String,
+    /// Newest revision (Deployment ReplicaSet revision or StatefulSet
+    /// update revision), if known.
+    pub revision: Option<String>,
+    /// Set when the controller itself has given up on the rollout.
+    pub failure: Option<String>,
+}
+
+impl WatchedWorkload {
+    /// Resolve `name` to a Deployment or StatefulSet (Deployment first,
+    /// like [`k8s::get_replicas`]) and start watching it — plus, for a
+    /// Deployment, its ReplicaSets, which is how old-revision scale-down
+    /// is observed.
+    async fn start(
+        client: &Client,
+        namespace: &str,
+        name: &str,
+        label_selector: &str,
+    ) -> Result<(Self, Vec<ChangeStream>)> {
+        let by_name = watcher::Config::default().fields(&format!("metadata.name={}", name));
+
+        let deployments: Api<Deployment> = Api::namespaced(client.clone(), namespace);
+        if deployments.get_opt(name).await?.is_some() {
+            let (deployments, deployment_changes) = reflect(deployments, by_name);
+            let (replica_sets, replica_set_changes) = reflect(
+                Api::<ReplicaSet>::namespaced(client.clone(), namespace),
+                watcher::Config::default().labels(label_selector),
             );
+            return Ok((
+                Self::Deployment {
+                    deployments,
+                    replica_sets,
+                },
+                vec![deployment_changes, replica_set_changes],
+            ));
+        }
 
-            // Track problems for SAFE mode
-            if is_bad || excessive_restarts || is_stuck {
-                problems_detected.push((pod.clone(), is_bad, excessive_restarts, is_stuck));
-                all_ready = false;
-            } else if pod.ready && pod.phase == "Running" && has_correct_image {
-                // Only count as updated if pod is ready AND has the correct image
-                updated_count += 1;
-            } else {
-                all_ready = false;
-            }
+        let statefulsets: Api<StatefulSet> = Api::namespaced(client.clone(), namespace);
+        if statefulsets.get_opt(name).await?.is_some() {
+            let (statefulsets, changes) = reflect(statefulsets, by_name);
+            return Ok((Self::StatefulSet { statefulsets }, vec![changes]));
+        }
 
-            // Update image tag history
-            pod_status_history.insert(pod.name.clone(), pod.image_tag.clone());
+        anyhow::bail!(
+            "Could not find Deployment or StatefulSet named '{}' in namespace '{}'",
+            name,
+            namespace
+        )
+    }
+
+    fn state(&self, name: &str) -> Option<WorkloadState> {
+        match self {
+            Self::Deployment {
+                deployments,
+                replica_sets,
+                ..
+            } => {
+                let deployment = deployments.state().into_iter().next()?;
+                Some(deployment_state(&deployment, &replica_sets.state(), name))
+            }
+            Self::StatefulSet { statefulsets, .. } => {
+                let sts = statefulsets.state().into_iter().next()?;
+                let spec = sts.spec.as_ref();
+                Some(WorkloadState {
+                    desired: spec.and_then(|s| s.replicas).unwrap_or(0),
+                    expected_tag: template_image_tag(spec.map(|s| &s.template)),
+                    revision: sts.status.as_ref().and_then(|s| s.update_revision.clone()),
+                    failure: None,
+                })
+            }
         }
+    }
+}
 
-        // Check if we should scrape diagnostics (SAFE mode)
-        if safe_mode && !problems_detected.is_empty() {
-            error!("🔬 Problems detected! Scraping diagnostics...");
-            println!();
+/// Summarize a watched Deployment: desired replicas, the pod template's
+/// tag, the newest owned ReplicaSet revision, and whether the controller
+/// has reported `ProgressDeadlineExceeded`.
+pub fn deployment_state(
+    deployment: &Deployment,
+    replica_sets: &[Arc<ReplicaSet>],
+    name: &str,
+) -> WorkloadState {
+    let spec = deployment.spec.as_ref();
+    let failure = deployment
+        .status
+        .as_ref()
+        .and_then(|s| s.conditions.as_ref())
+        .and_then(|conditions| {
+            conditions.iter().find(|c| {
+                c.type_ == "Progressing"
+                    && c.status == "False"
+                    && c.reason.as_deref() == Some("ProgressDeadlineExceeded")
+            })
+        })
+        .map(|c| {
+            format!(
+                "deployment/{} exceeded its progress deadline: {}",
+                name,
+                c.message.as_deref().unwrap_or("no message")
+            )
+        });
+
+    let revision = replica_sets
+        .iter()
+        .filter(|rs| {
+            rs.metadata
+                .owner_references
+                .as_deref()
+                .unwrap_or_default()
+                .iter()
+                .any(|o| o.kind == "Deployment" && o.name == name)
+        })
+        .filter_map(|rs| {
+            rs.metadata
+                .annotations
+                .as_ref()?
+                .get(REVISION_ANNOTATION)?
+                .parse::<u64>()
+                .ok()
+        })
+        .max()
+        .map(|r| r.to_string());
+
+    WorkloadState {
+        desired: spec.and_then(|s| s.replicas).unwrap_or(1),
+        expected_tag: template_image_tag(spec.map(|s| &s.template)),
+        revision,
+        failure,
+    }
+}
 
-            for (pod, is_bad, excessive_restarts, _is_stuck) in &problems_detected {
-                error!("━━━ Diagnostics for {} ━━━", pod.name);
+/// Tag of the first container image in a pod template (the same
+/// container [`k8s::get_expected_image_tag`] reads).
+fn template_image_tag(template: Option<&PodTemplateSpec>) -> String {
+    let image = template
+        .and_then(|t| t.spec.as_ref())
+        .and_then(|s| s.containers.first())
+        .and_then(|c| c.image.as_deref())
+        .unwrap_or_default();
+    crate::oci_manifest::image_tag_display(image).to_string()
+}
 
-                let problem_type = if *is_bad {
-                    "BAD STATE"
-                } else if *excessive_restarts {
-                    "EXCESSIVE RESTARTS"
-                } else {
-                    "STUCK"
-                };
+// ============================================================================
+// Pod tracking
+// ============================================================================
 
-                error!("Problem: {}", problem_type);
-
-                // Show container state details
-                if let Some(state) = &pod.container_state {
-                    error!("Container State: {}", state.state);
-                    if let Some(reason) = &state.reason {
-                        error!("Reason: {}", reason);
-                    }
-                    if let Some(message) = &state.message {
-                        error!("Message: {}", message);
-                    }
-                    error!("Restart Count: {}", state.restart_count);
-                }
+/// When a pod entered its current container state.
+#[derive(Debug, Clone)]
+struct PodStateSince {
+    state_key: String,
+    since: Instant,
+}
+
+/// Per-pod state history across watch events, for stuck detection.
+#[derive(Debug, Default)]
+pub struct RolloutTracker {
+    pods: HashMap<String, PodStateSince>,
+}
 
-                // Get events
-                match k8s::get_pod_events(&client, &namespace, &pod.name).await {
-                    Ok(events) => {
-                        if !events.is_empty() {
-                            error!("Recent Events:");
-                            for event in events.iter().rev().take(10) {
-                                error!("  {}", event);
-                            }
-                        }
-                    }
-                    Err(e) => {
-                        warn!("Failed to get events: {}", e);
-                    }
+/// One pod's classification at a point in the rollout.
+#[derive(Debug, Clone)]
+pub struct PodVerdict {
+    pub pod: PodStatus,
+    pub bad: bool,
+    pub excessive_restarts: bool,
+    /// How long the pod has been unready in its current state, once past
+    /// [`STUCK_AFTER`].
+    pub stuck_for: Option<Duration>,
+    pub on_expected_tag: bool,
+}
+
+impl RolloutTracker {
+    /// Classify `pods` against `expected_tag` at `now`, updating each
+    /// pod's state history and forgetting pods that are gone.
+    pub fn observe(
+        &mut self,
+        pods: Vec<PodStatus>,
+        expected_tag: &str,
+        now: Instant,
+    ) -> Vec<PodVerdict> {
+        self.pods
+            .retain(|name, _| pods.iter().any(|p| &p.name == name));
+
+        pods.into_iter()
+            .map(|pod| {
+                let state_key = match &pod.container_state {
+                    Some(s) => format!("{}:{}", s.state, s.reason.as_deref().unwrap_or("")),
+                    None => pod.phase.clone(),
+                };
+                let history = self
+                    .pods
+                    .entry(pod.name.clone())
+                    .or_insert_with(|| PodStateSince {
+                        state_key: state_key.clone(),
+                        since: now,
+                    });
+                if history.state_key != state_key {
+                    debug!(
+                        "Pod {} state changed: {} -> {}",
+                        pod.name, history.state_key, state_key
+                    );
+                    history.state_key = state_key;
+                    history.since = now;
                 }
 
-                // Get logs
-                match k8s::get_pod_logs(&client, &namespace, &pod.name, 30).await {
-                    Ok(logs) => {
-                        if !logs.is_empty() {
-                            error!("Recent Logs (last 30 lines):");
-                            for line in logs.lines().take(30) {
-                                error!("  {}", line);
-                            }
-                        }
-                    }
-                    Err(e) => {
-                        warn!("Failed to get logs: {}", e);
-                    }
+                let in_state = now.saturating_duration_since(history.since);
+                let stuck_for = (in_state >= STUCK_AFTER && !pod.ready && pod.phase != "Succeeded")
+                    .then_some(in_state);
+
+                PodVerdict {
+                    bad: k8s::is_bad_state(&pod),
+                    excessive_restarts: pod
+                        .container_state
+                        .as_ref()
+                        .is_some_and(|s| s.restart_count >= RESTART_THRESHOLD),
+                    stuck_for,
+                    on_expected_tag: pod.image_tag == expected_tag,
+                    pod,
                 }
+            })
+            .collect()
+    }
+}
 
-                println!();
-            }
+impl PodVerdict {
+    fn has_problem(&self) -> bool {
+        self.bad || self.excessive_restarts || self.stuck_for.is_some()
+    }
 
-            anyhow::bail!(
-                "Rollout failed with {} problem(s) detected in SAFE mode",
-                problems_detected.len()
-            );
+    /// Ready, running, on the expected tag, and problem-free.
+    pub fn is_updated(&self) -> bool {
+        !self.has_problem() && self.pod.ready && self.pod.phase == "Running" && self.on_expected_tag
+    }
+
+    /// Whether this pod fails the rollout. A new-revision pod in a bad
+    /// container state always does; in SAFE mode any problem does
+    /// (including stuck and restart-looping pods of either revision).
+    pub fn is_fatal(&self, safe_mode: bool) -> bool {
+        if safe_mode {
+            self.has_problem()
+        } else {
+            self.bad && self.on_expected_tag
         }
+    }
 
-        // Check if rollout complete
-        if all_ready && updated_count == replicas as usize {
-            println!();
-            break;
+    fn problem_type(&self) -> &'static str {
+        if self.bad {
+            "BAD STATE"
+        } else if self.excessive_restarts {
+            "EXCESSIVE RESTARTS"
+        } else {
+            "STUCK"
         }
+    }
+}
+
+/// Every observed pod is updated and exactly `desired` of them exist
+/// (old-revision pods still terminating keep the rollout open).
+pub fn rollout_complete(verdicts: &[PodVerdict], desired: i32) -> bool {
+    verdicts.iter().all(PodVerdict::is_updated) && verdicts.len() == desired.max(0) as usize
+}
 
-        // Sleep before next iteration
-        tokio::time::sleep(tokio::time::Duration::from_secs(interval)).await;
+// ============================================================================
+// Output
+// ============================================================================
+
+/// Redraw the progress block in place, returning how many lines it took.
+fn render_progress(state: &WorkloadState, verdicts: &[PodVerdict], previous_lines: usize) -> usize {
+    for _ in 0..previous_lines {
+        print!("\x1B[1A\x1B[2K"); // Move up and clear line
     }
 
-    println!();
-    info!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    let now = chrono::Local::now();
+    let revision = state
+        .revision
+        .as_deref()
+        .map(|r| format!(", revision {}", r))
+        .unwrap_or_default();
+    println!(
+        "{} - Rollout Progress (pods: {}/{} updated{}):",
+        now.format("%H:%M:%S"),
+        verdicts.iter().filter(|v| v.is_updated()).count(),
+        state.desired,
+        revision
+    );
     println!();
 
-    info!("{}", "✅ All pods updated and ready!".bright_green().bold());
-    println!();
+    for verdict in verdicts {
+        println!("{}", render_pod_line(verdict));
+    }
 
-    Ok(())
+    verdicts.len() + 2
+}
+
+fn render_pod_line(verdict: &PodVerdict) -> String {
+    let pod = &verdict.pod;
+    let status_icon = if verdict.bad {
+        "❌".bright_red()
+    } else if verdict.excessive_restarts {
+        "🔄".bright_red()
+    } else if verdict.stuck_for.is_some() {
+        "⚠️ ".bright_yellow()
+    } else if pod.ready && pod.phase == "Running" {
+        "✅".bright_green()
+    } else if pod.phase == "Running" {
+        "🔄".bright_yellow()
+    } else {
+        "⏳".bright_blue()
+    };
+
+    // Build status message with detailed state info
+    let mut status_parts = vec![format!("Image: {}", pod.image_tag)];
+
+    if let Some(state) = &pod.container_state {
+        status_parts.push(format!("State: {}", state.state));
+
+        if let Some(reason) = &state.reason {
+            status_parts.push(format!("Reason: {}", reason));
+        }
+
+        if state.restart_count > 0 {
+            status_parts.push(format!("Restarts: {}", state.restart_count));
+        }
+    } else {
+        status_parts.push(format!("Phase: {}", pod.phase));
+    }
+
+    if let Some(stuck_for) = verdict.stuck_for {
+        status_parts.push(format!("⚠️  STUCK ({}s)", stuck_for.as_secs()));
+    }
+
+    let status_msg = if verdict.bad || verdict.excessive_restarts {
+        status_parts.join(" | ").bright_red()
+    } else if verdict.stuck_for.is_some() {
+        status_parts.join(" | ").bright_yellow()
+    } else if pod.ready && pod.phase == "Running" {
+        status_parts.join(" | ").bright_green()
+    } else {
+        status_parts.join(" | ").bright_blue()
+    };
+
+    // Show indicator if pod needs update
+    let update_indicator = if verdict.on_expected_tag {
+        "".normal()
+    } else {
+        " 🔄 UPDATE PENDING".bright_yellow()
+    };
+
+    format!(
+        "  {} {} | {}{}",
+        status_icon, pod.name, status_msg, update_indicator
+    )
 }
 
-/// Parse the `rollout --timeout` CLI flag into a number of poll
-/// iterations at the 3-second poll interval. The string grammar is the
-/// canonical [`crate::duration::parse_duration`] oracle (lifted out of
-/// this module's prior hand-rolled suffix match); an empty string keeps
-/// this CLI's historical default of 200 iterations (≈10 minutes).
+/// Log the pod's container state, recent events and log tail — captured
+/// at the moment the failure is observed, before the pod restarts again
+/// or is replaced.
+async fn capture_diagnostics(client: &Client, namespace: &str, verdict: &PodVerdict) {
+    let pod = &verdict.pod;
+    error!("━━━ Diagnostics for {} ━━━", pod.name);
+    error!("Problem: {}", verdict.problem_type());
+
+    // Show container state details
+    if let Some(state) = &pod.container_state {
+        error!("Container State: {}", state.state);
+        if let Some(reason) = &state.reason {
+            error!("Reason: {}", reason);
+        }
+        if let Some(message) = &state.message {
+            error!("Message: {}", message);
+        }
+        error!("Restart Count: {}", state.restart_count);
+    }
+
+    let (events, logs) = tokio::join!(
+        k8s::get_pod_events(client, namespace, &pod.name),
+        k8s::get_pod_logs(client, namespace, &pod.name, 30),
+    );
+
+    match events {
+        Ok(events) => {
+            if !events.is_empty() {
+                error!("Recent Events:");
+                for event in events.iter().rev().take(10) {
+                    error!("  {}", event);
+                }
+            }
+        }
+        Err(e) => {
+            warn!("Failed to get events: {}", e);
+        }
+    }
+
+    match logs {
+        Ok(logs) => {
+            if !logs.is_empty() {
+                error!("Recent Logs (last 30 lines):");
+                for line in logs.lines().take(30) {
+                    error!("  {}", line);
+                }
+            }
+        }
+        Err(e) => {
+            warn!("Failed to get logs: {}", e);
+        }
+    }
+
+    println!();
+}
+
+/// Parse the `rollout --timeout` CLI flag into the overall rollout
+/// deadline. The string grammar is the canonical
+/// [`crate::duration::parse_duration`] oracle; an empty string keeps this
+/// CLI's historical default of 10 minutes.
 ///
 /// Routes through [`crate::duration::parse_timeout_field`] so a malformed
 /// `--timeout 5min` surfaces both the offending value AND the field name
 /// (`rollout --timeout`) — byte-for-byte the same
-/// `"invalid timeout '{value}' for {field}"` shape the five `deploy.yaml`
-/// timeout-parse sites (`PreDeploymentTestsConfig::validate`,
-/// `run_test_suite`, `execute_pre_deployment_tests`, `execute::warmup_delay`,
-/// `execute_suite`) already emit. Was the last inline
-/// `parse_duration(&s)?` call outside the tests module that named the
-/// offending value without naming the field.
-fn parse_timeout(timeout_str: &str) -> Result<u64> {
+/// `"invalid timeout '{value}' for {field}"` shape the `deploy.yaml`
+/// timeout-parse sites emit. A zero timeout is raised to one second so
+/// the watch still evaluates the rollout once before reporting timeout.
+fn parse_timeout(timeout_str: &str) -> Result<Duration> {
     let timeout_str = timeout_str.trim();
 
     if timeout_str.is_empty() {
-        return Ok(200); // Default
+        return Ok(DEFAULT_TIMEOUT);
     }
 
-    let total_seconds =
-        crate::duration::parse_timeout_field(timeout_str, "rollout --timeout")?.as_secs();
+    let timeout = crate::duration::parse_timeout_field(timeout_str, "rollout --timeout")?;
+    Ok(timeout.max(Duration::from_secs(1)))
+}
 
-    // Convert to iterations (assuming 3 second interval); at least 1.
-    Ok((total_seconds / 3).max(1))
+/// Roll `deployment/<name>` in `namespace` back to its previous
+/// ReplicaSet via `kubectl rollout undo`. Returns once kubectl has
+/// accepted the undo; the caller decides whether to monitor the
+/// resulting rollout (`forge rollout --rollback` does, the `forge watch`
+/// dashboard keeps refreshing its own view).
+pub async fn rollback_deployment(namespace: &str, name: &str) -> Result<()> {
+    let rollback_result = kubectl_command_async()
+        .args([
+            "rollout",
+            "undo",
+            &format!("deployment/{}", name),
+            "-n",
+            namespace,
+        ])
+        .output()
+        .await;
+
+    // Output is captured rather than inherited so the full-screen
+    // dashboard is not scribbled over by kubectl's own status line.
+    match rollback_result {
+        Ok(output) if output.status.success() => Ok(()),
+        Ok(output) => anyhow::bail!(
+            "{}",
+            format_rollback_non_success(
+                name,
+                namespace,
+                output.status,
+                &String::from_utf8_lossy(&output.stderr)
+            )
+        ),
+        Err(e) => anyhow::bail!("{}", format_rollback_spawn_failure(name, namespace, &e)),
+    }
 }
 
 /// Format the operator-facing bail message for the "kubectl spawn
@@ -409,17 +698,27 @@ fn parse_timeout(timeout_str: &str) -> Result<u64> {
 /// `commands/status.rs` — pulled into a formatter here so a
 /// hermetic unit test can pin the message shape without spawning
 /// kubectl.
+///
+/// kubectl's stderr is appended: the output is captured, so without it
+/// the reason (no previous revision, RBAC denial, ...) is lost.
 fn format_rollback_non_success(
     name: &str,
     namespace: &str,
     status: std::process::ExitStatus,
+    stderr: &str,
 ) -> String {
-    format!(
+    let mut message = format!(
         "kubectl rollout undo deployment/{} -n {} failed with exit code: {:?}",
         name,
         namespace,
         status.code()
-    )
+    );
+    let stderr = stderr.trim();
+    if !stderr.is_empty() {
+        message.push_str(": ");
+        message.push_str(stderr);
+    }
+    message
 }
 
 /// Format the operator-facing bail message for the "kubectl spawn
@@ -442,37 +741,208 @@ fn format_rollback_spawn_failure(name: &str, namespace: &str, err: &std::io::Err
 mod tests {
     use super::*;
 
-    /// An empty timeout string keeps the historical default of 200
-    /// iterations (≈10 minutes at the 3-second poll interval), so the
-    /// caller pattern `parse_timeout(&timeout_str)?` at line ~75 keeps
-    /// the same default the old inline `200 // Default` fallback gave
-    /// when no `--timeout` was provided.
+    /// An empty timeout string keeps the historical default of 10 minutes.
+    #[test]
+    fn empty_returns_default_timeout() {
+        assert_eq!(parse_timeout("").unwrap(), DEFAULT_TIMEOUT);
+        assert_eq!(parse_timeout("   ").unwrap(), Duration::from_secs(600));
+    }
+
+    /// Well-formed timeouts are the rollout deadline itself — no longer
+    /// rounded to a count of 3-second polls.
+    #[test]
+    fn well_formed_timeouts_convert_to_duration() {
+        assert_eq!(parse_timeout("5m").unwrap(), Duration::from_secs(300));
+        assert_eq!(parse_timeout("2h").unwrap(), Duration::from_secs(7200));
+        assert_eq!(parse_timeout("30").unwrap(), Duration::from_secs(30));
+        assert_eq!(parse_timeout("4s").unwrap(), Duration::from_secs(4));
+    }
+
+    /// A zero timeout saturates to one second rather than expiring before
+    /// the first watch event is evaluated.
+    #[test]
+    fn zero_timeout_saturates_to_one_second() {
+        assert_eq!(parse_timeout("0s").unwrap(), Duration::from_secs(1));
+    }
+
+    fn pod(name: &str, tag: &str, ready: bool, reason: Option<&str>, restarts: i32) -> PodStatus {
+        PodStatus {
+            name: name.to_string(),
+            phase: "Running".to_string(),
+            ready,
+            image_tag: tag.to_string(),
+            container_state: Some(k8s::ContainerStateInfo {
+                state: if reason.is_some() {
+                    "waiting"
+                } else {
+                    "running"
+                }
+                .to_string(),
+                reason: reason.map(str::to_string),
+                message: None,
+                restart_count: restarts,
+                started: ready,
+            }),
+            creation_time: None,
+        }
+    }
+
+    fn replica_set(owner: &str, revision: &str) -> Arc<ReplicaSet> {
+        Arc::new(
+            serde_json::from_value(serde_json::json!({
+                "metadata": {
+                    "name": format!("{owner}-{revision}"),
+                    "annotations": { REVISION_ANNOTATION: revision },
+                    "ownerReferences": [{
+                        "apiVersion": "apps/v1",
+                        "kind": "Deployment",
+                        "name": owner,
+                        "uid": "u"
+                    }]
+                }
+            }))
+            .unwrap(),
+        )
+    }
+
+    #[test]
+    fn deployment_state_reads_template_tag_and_newest_owned_revision() {
+        let deployment: Deployment = serde_json::from_value(serde_json::json!({
+            "metadata": { "name": "api" },
+            "spec": {
+                "replicas": 3,
+                "selector": { "matchLabels": { "app": "api" } },
+                "template": { "spec": { "containers": [
+                    { "name": "api", "image": "ghcr.io/org/api:amd64-new" }
+                ] } }
+            }
+        }))
+        .unwrap();
+        let replica_sets = vec![
+            replica_set("api", "9"),
+            replica_set("api", "10"),
+            replica_set("other", "42"),
+        ];
+
+        let state = deployment_state(&deployment, &replica_sets, "api");
+
+        assert_eq!(state.desired, 3);
+        assert_eq!(state.expected_tag, "amd64-new");
+        assert_eq!(state.revision.as_deref(), Some("10"));
+        assert_eq!(state.failure, None);
+    }
+
     #[test]
-    fn empty_returns_default_iterations() {
-        assert_eq!(parse_timeout("").unwrap(), 200);
-        assert_eq!(parse_timeout("   ").unwrap(), 200);
+    fn deployment_state_reports_progress_deadline_exceeded() {
+        let deployment: Deployment = serde_json::from_value(serde_json::json!({
+            "metadata": { "name": "api" },
+            "spec": {
+                "selector": { "matchLabels": { "app": "api" } },
+                "template": { "spec": { "containers": [{ "name": "api", "image": "api:v2" }] } }
+            },
+            "status": { "conditions": [{
+                "type": "Progressing",
+                "status": "False",
+                "reason": "ProgressDeadlineExceeded",
+                "message": "ReplicaSet \"api-7d9\" has timed out progressing."
+            }] }
+        }))
+        .unwrap();
+
+        let failure = deployment_state(&deployment, &[], "api").failure.unwrap();
+        assert!(failure.contains("deployment/api exceeded its progress deadline"));
+        assert!(failure.contains("timed out progressing"));
     }
 
-    /// Well-formed timeouts convert to iteration counts at the 3-second
-    /// poll interval. `"5m"` = 300s ÷ 3 = 100 iterations; `"2h"` =
-    /// 7200s ÷ 3 = 2400 iterations; a bare `"30"` = 30s ÷ 3 = 10.
     #[test]
-    fn well_formed_timeouts_convert_to_iteration_count() {
-        assert_eq!(parse_timeout("5m").unwrap(), 100);
-        assert_eq!(parse_timeout("2h").unwrap(), 2400);
-        assert_eq!(parse_timeout("30").unwrap(), 10);
-        assert_eq!(parse_timeout("30s").unwrap(), 10);
+    fn tracker_flags_pods_unready_in_one_state_past_threshold() {
+        let mut tracker = RolloutTracker::default();
+        let start = Instant::now();
+        let starting = || vec![pod("api-1", "v2", false, None, 0)];
+
+        let verdicts = tracker.observe(starting(), "v2", start);
+        assert!(verdicts[0].stuck_for.is_none());
+
+        let verdicts = tracker.observe(starting(), "v2", start + STUCK_AFTER);
+        assert_eq!(verdicts[0].stuck_for, Some(STUCK_AFTER));
+
+        // A state change restarts the clock.
+        let verdicts = tracker.observe(
+            vec![pod("api-1", "v2", false, Some("ContainerCreating"), 0)],
+            "v2",
+            start + STUCK_AFTER * 2,
+        );
+        assert!(verdicts[0].stuck_for.is_none());
     }
 
-    /// Sub-poll-interval timeouts saturate to a single iteration rather
-    /// than 0, so the poll loop still runs once and either succeeds or
-    /// reports timeout — the same `.max(1)` guard the prior inline
-    /// arithmetic used.
     #[test]
-    fn sub_interval_timeout_saturates_to_one_iteration() {
-        assert_eq!(parse_timeout("1s").unwrap(), 1);
-        assert_eq!(parse_timeout("2s").unwrap(), 1);
-        assert_eq!(parse_timeout("3s").unwrap(), 1);
+    fn bad_state_on_new_revision_is_fatal_even_outside_safe_mode() {
+        let mut tracker = RolloutTracker::default();
+        let verdicts = tracker.observe(
+            vec![
+                pod("new", "v2", false, Some("CrashLoopBackOff"), 1),
+                pod("old", "v1", false, Some("ImagePullBackOff"), 0),
+                pod("flappy", "v2", true, None, 5),
+            ],
+            "v2",
+            Instant::now(),
+        );
+
+        assert!(verdicts[0].is_fatal(false));
+        assert!(
+            !verdicts[1].is_fatal(false),
+            "old-revision pods are being replaced"
+        );
+        assert!(!verdicts[2].is_fatal(false));
+        assert!(verdicts.iter().all(|v| v.is_fatal(true)));
+    }
+
+    #[test]
+    fn rollout_complete_requires_every_pod_updated_and_desired_count() {
+        let mut tracker = RolloutTracker::default();
+        let now = Instant::now();
+        let done = tracker.observe(
+            vec![pod("a", "v2", true, None, 0), pod("b", "v2", true, None, 0)],
+            "v2",
+            now,
+        );
+        assert!(rollout_complete(&done, 2));
+        assert!(!rollout_complete(&done, 3));
+
+        let terminating_old = tracker.observe(
+            vec![
+                pod("a", "v2", true, None, 0),
+                pod("b", "v2", true, None, 0),
+                pod("c", "v1", true, None, 0),
+            ],
+            "v2",
+            now,
+        );
+        assert!(!rollout_complete(&terminating_old, 2));
+    }
+
+    /// Regression shield: rollout progress is event-driven through
+    /// `kube::runtime::watcher`, not a fixed-interval re-list. The
+    /// pre-watch loop slept `interval` seconds between full pod lists, so
+    /// a CrashLoopBackOff surfaced up to one interval late and the
+    /// `--timeout` was a count of polls rather than a deadline.
+    #[test]
+    fn rollout_monitor_is_watch_driven_not_polled() {
+        let body = crate::test_support::module_body_before_tests(
+            include_str!("rollout.rs"),
+            "commands/rollout.rs",
+        );
+        assert!(
+            body.contains("watcher(api, config)"),
+            "rollout.rs must follow the rollout through kube::runtime::watcher"
+        );
+        for needle in ["get_pod_statuses(", "tokio::time::sleep("] {
+            let hits = crate::test_support::code_line_hits(body, needle);
+            assert!(
+                hits.is_empty(),
+                "rollout.rs must not re-list pods on a timer; found {needle:?} at: {hits:?}"
+            );
+        }
     }
 
     /// The load-bearing shape-uniformity assertion: a malformed
@@ -555,7 +1025,12 @@ mod tests {
             .expect("spawn `false`");
         assert!(!status.success(), "`false` must exit non-zero");
 
-        let msg = format_rollback_non_success("hive-router", "hive-production", status);
+        let msg = format_rollback_non_success(
+            "hive-router",
+            "hive-production",
+            status,
+            "error: no rollout history found for deployment \"hive-router\"\n",
+        );
 
         assert!(
             msg.contains("kubectl rollout undo deployment/hive-router -n hive-production"),
@@ -574,6 +1049,10 @@ mod tests {
              so a jsonpath-style status decode remains grep-friendly: \
              {msg}"
         );
+        assert!(
+            msg.ends_with(": error: no rollout history found for deployment \"hive-router\""),
+            "message must carry kubectl's trimmed stderr: {msg}"
+        );
     }
 
     /// `format_rollback_spawn_failure` names the exact
@@ -1098,6 +1098,14 @@ pub async fn orchestrate_release(
         );
     }
 
+    // Refuse to deploy into an environment frozen with `forge flux suspend`
+    if !push_only {
+        for env in &environments {
+            crate::commands::flux_suspend::ensure_not_suspended(&deploy_config.product, env)
+                .await?;
+        }
+    }
+
     let mode_label = if push_only {
         "Push-Only"
     } else if deploy_only {
@@ -1558,6 +1566,36 @@ pub async fn orchestrate_release(
     }
     println!();
 
+    // Step 7.5: Federation integration tests (if enabled in deploy.yaml),
+    // against the router Step 6 just updated. The job's report is summarized
+    // and stored for flaky-test detection.
+    let federation_tests = &deploy_config.service.federation_tests;
+    if federation_tests.enabled {
+        println!(
+            "Step 7.5: {}",
+            "Running federation integration tests...".bold()
+        );
+        let environment = environments
+            .last()
+            .map(String::as_str)
+            .unwrap_or(deploy_config.product.environment.as_str());
+        crate::commands::federation_tests::run_federation_tests(
+            &service,
+            &deploy_config.product.name,
+            environment,
+            &namespace,
+            &federation_tests.suite,
+            &federation_tests.router_url,
+            federation_tests.timeout_seconds,
+            federation_tests.fail_fast,
+            &git_sha,
+            &deploy_config,
+            None,
+        )
+        .await?;
+        println!();
+    }
+
     // Step 8: Run integration tests (if configured in deploy.yaml)
     // CRITICAL: Tests run AFTER Hive Router is updated and FluxCD has reconciled
     // This ensures we're testing against the latest federated schema
@@ -1912,6 +1950,42 @@ pub async fn deploy_rust_service_with_tag(
         .await
         .context("Failed to write updated manifest")?;
 
+    // Render the overlay in-process and validate it against the bundled
+    // Kubernetes schemas, so a broken patch fails here instead of in Flux.
+    match crate::manifest_schema::validate_overlay(std::path::Path::new(&manifest)) {
+        Ok(Some(validation)) => println!(
+            "   ✅ Overlay renders ({} objects, {} schema-validated)",
+            validation.objects, validation.validated
+        ),
+        Ok(None) => {}
+        Err(e) => {
+            tokio::fs::write(&manifest, &manifest_content)
+                .await
+                .context("Failed to restore manifest after validation failure")?;
+            return Err(e.into());
+        }
+    }
+
+    // Opt-in: let the apiserver judge the change (admission webhooks,
+    // immutable fields, quotas) before it is committed.
+    if crate::server_dry_run::enabled() {
+        match crate::server_dry_run::dry_run_overlay(std::path::Path::new(&manifest), &namespace)
+            .await
+        {
+            Ok(Some(outcome)) => println!(
+                "   ✅ Server-side dry run accepted ({} objects, {} SOPS-encrypted skipped)",
+                outcome.submitted, outcome.skipped
+            ),
+            Ok(None) => {}
+            Err(e) => {
+                tokio::fs::write(&manifest, &manifest_content)
+                    .await
+                    .context("Failed to restore manifest after dry-run rejection")?;
+                return Err(e);
+            }
+        }
+    }
+
     // Git commit and push (in k8s repo if configured, otherwise product repo)
     let git_workdir = k8s_workdir.as_deref();
     let git_branch = k8s_branch.as_deref().unwrap_or("main");
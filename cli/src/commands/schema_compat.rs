@@ -0,0 +1,591 @@
+//! Schema Backward-Compatibility Check (G8d)
+//!
+//! During a rolling deploy the previous release's pods keep running against
+//! the new schema, so a migration may only drop or rename what that release
+//! no longer reads (expand/contract). This check collects the tables and
+//! columns mapped by the previous release's SeaORM entities
+//! (`DeriveEntityModel` structs read from git at the previous release's
+//! ref) and fails when a migration added since that ref drops or renames
+//! one of them.
+//!
+//! Destructive changes are found in SQL, for SQLx migrations and the string
+//! literals of SeaORM migrations as in G8c, and in the SeaQuery builder
+//! calls SeaORM migrations are usually written with: `Table::drop()`,
+//! `Table::rename()`, `.drop_column(...)` and `.rename_column(...)`.
+//! A migration opts out with `forge-lint: allow(backward-compat)`.
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use regex::Regex;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+use tokio::fs;
+
+use super::migration_lint::{
+    allowed_rules, normalize_name, statements, ALTER_TABLE, DROP_COLUMN, DROP_KEYWORDS, DROP_TABLE,
+    RENAME,
+};
+use super::migration_rehearsal::{
+    latest_release_ref, migration_files, new_migrations, repo_root, MigrationKind,
+};
+use super::prerelease::PreReleaseConfig;
+use crate::git::git_command_async;
+use crate::retry::run_capture_anyhow;
+
+/// Rule id for `forge-lint: allow(...)`.
+const RULE: &str = "backward-compat";
+
+static TABLE_NAME: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"#\[sea_orm\s*\([^\]]*\btable_name\s*=\s*"([^"]+)""#).unwrap());
+static MODEL_STRUCT: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?s)\bpub struct Model\s*\{(.*?)\n\s*\}").unwrap());
+static FIELD: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\s*pub\s+(?:r#)?(\w+)\s*:").unwrap());
+static COLUMN_NAME: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"\bcolumn_name\s*=\s*"([^"]+)""#).unwrap());
+static SEA_ORM_IGNORE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"#\[sea_orm\s*\([^\]]*\bignore\b").unwrap());
+static BUILDER_TABLE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\.table\(\s*([^,()]+(?:\([^()]*\))?)\s*[,)]").unwrap());
+static BUILDER_DROP_COLUMN: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\.drop_column\(\s*([^,()]+(?:\([^()]*\))?)\s*\)").unwrap());
+static BUILDER_RENAME_COLUMN: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\.rename_column\(\s*([^,()]+(?:\([^()]*\))?)\s*,").unwrap());
+static ALIAS: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"^Alias::new\(\s*"([^"]+)"\s*\)$"#).unwrap());
+
+// =============================================================================
+// Data Structures
+// =============================================================================
+
+/// A table mapped by an entity of the previous release.
+#[derive(Debug, Clone, Default)]
+pub struct EntityTable {
+    /// Entity source file, relative to the repo root
+    pub file: String,
+    pub columns: BTreeSet<String>,
+}
+
+/// Tables and columns the previous release's entities map, keyed by
+/// lowercased table name.
+#[derive(Debug, Default)]
+pub struct EntityReferences {
+    pub tables: BTreeMap<String, EntityTable>,
+}
+
+/// A change that breaks code still reading the old schema.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchemaChange {
+    DropTable(String),
+    RenameTable(String),
+    DropColumn { table: String, column: String },
+    RenameColumn { table: String, column: String },
+}
+
+impl SchemaChange {
+    fn describe(&self) -> String {
+        match self {
+            SchemaChange::DropTable(table) => format!("drops table {}", table),
+            SchemaChange::RenameTable(table) => format!("renames table {}", table),
+            SchemaChange::DropColumn { table, column } => {
+                format!("drops column {}.{}", table, column)
+            }
+            SchemaChange::RenameColumn { table, column } => {
+                format!("renames column {}.{}", table, column)
+            }
+        }
+    }
+}
+
+/// A new migration change that breaks the previous release.
+#[derive(Debug, Clone)]
+pub struct CompatViolation {
+    pub file: PathBuf,
+    pub change: SchemaChange,
+    /// Entity file of the previous release that references the object
+    pub entity_file: String,
+}
+
+impl CompatViolation {
+    pub fn format(&self, base_ref: &str) -> String {
+        format!(
+            "{} - {} still referenced by {} at {}\n  Suggestion: \
+             Keep it until a release no longer maps it (expand/contract), \
+             then drop it in a later migration",
+            self.file.display(),
+            self.change.describe(),
+            self.entity_file,
+            base_ref
+        )
+    }
+}
+
+/// Result of the backward-compatibility check
+#[derive(Debug, Default)]
+pub struct CompatCheckResult {
+    /// The previous release compared against; `None` when there is none
+    pub base_ref: Option<String>,
+    /// Number of migrations added since `base_ref`
+    pub migrations_checked: usize,
+    pub violations: Vec<CompatViolation>,
+}
+
+// =============================================================================
+// Entity Parsing
+// =============================================================================
+
+/// The table and columns of a SeaORM entity (`#[sea_orm(table_name = ...)]`
+/// on `pub struct Model`). Fields marked `#[sea_orm(ignore)]` are not
+/// columns; `#[sea_orm(column_name = "...")]` overrides the field name.
+pub fn parse_entity(content: &str) -> Option<(String, BTreeSet<String>)> {
+    let table = TABLE_NAME.captures(content)?[1].to_lowercase();
+    let body = MODEL_STRUCT.captures(content)?.get(1)?.as_str();
+
+    let mut columns = BTreeSet::new();
+    let mut attributes = String::new();
+    for line in body.lines() {
+        match FIELD.captures(line) {
+            Some(field) => {
+                if !SEA_ORM_IGNORE.is_match(&attributes) {
+                    let column = COLUMN_NAME
+                        .captures(&attributes)
+                        .map_or(&field[1], |c| c.get(1).map_or("", |m| m.as_str()));
+                    columns.insert(column.to_lowercase());
+                }
+                attributes.clear();
+            }
+            None => attributes.push_str(line),
+        }
+    }
+    Some((table, columns))
+}
+
+// =============================================================================
+// Change Detection
+// =============================================================================
+
+/// `UserProfiles` -> `user_profiles`, `HTTPLogs` -> `http_logs`, the way
+/// `DeriveIden` names identifiers.
+fn snake_case(ident: &str) -> String {
+    let chars: Vec<char> = ident.chars().collect();
+    let mut out = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            let prev = chars[i - 1];
+            let next_lower = chars.get(i + 1).is_some_and(|n| n.is_lowercase());
+            if prev.is_lowercase() || prev.is_ascii_digit() || (prev.is_uppercase() && next_lower) {
+                out.push('_');
+            }
+        }
+        out.extend(c.to_lowercase());
+    }
+    out
+}
+
+/// Name of a SeaQuery identifier expression: `Alias::new("users")`,
+/// `Users::Table` (the enum's name) or `Users::DisplayName` (the variant's).
+fn iden_name(expr: &str) -> Option<String> {
+    let expr = expr.trim();
+    if let Some(c) = ALIAS.captures(expr) {
+        return Some(c[1].to_lowercase());
+    }
+    let (enum_name, variant) = expr.rsplit_once("::")?;
+    let enum_name = enum_name.rsplit("::").next()?;
+    let name = if variant == "Table" {
+        enum_name
+    } else {
+        variant
+    };
+    Some(snake_case(name))
+}
+
+/// Destructive changes made through the SeaQuery builder.
+fn builder_changes(source: &str) -> Vec<SchemaChange> {
+    let mut changes = Vec::new();
+    for chunk in source.split(';') {
+        let tables: Vec<String> = BUILDER_TABLE
+            .captures_iter(chunk)
+            .filter_map(|c| iden_name(&c[1]))
+            .collect();
+        if chunk.contains("Table::drop()") {
+            changes.extend(tables.iter().cloned().map(SchemaChange::DropTable));
+        }
+        if chunk.contains("Table::rename()") {
+            changes.extend(tables.first().cloned().map(SchemaChange::RenameTable));
+        }
+        if let (true, Some(table)) = (chunk.contains("Table::alter()"), tables.first()) {
+            for c in BUILDER_DROP_COLUMN.captures_iter(chunk) {
+                if let Some(column) = iden_name(&c[1]) {
+                    changes.push(SchemaChange::DropColumn {
+                        table: table.clone(),
+                        column,
+                    });
+                }
+            }
+            for c in BUILDER_RENAME_COLUMN.captures_iter(chunk) {
+                if let Some(column) = iden_name(&c[1]) {
+                    changes.push(SchemaChange::RenameColumn {
+                        table: table.clone(),
+                        column,
+                    });
+                }
+            }
+        }
+    }
+    changes
+}
+
+/// Destructive changes a migration file makes, in SQL or through the
+/// SeaQuery builder.
+pub fn schema_changes(path: &Path, content: &str) -> Vec<SchemaChange> {
+    let mut changes = Vec::new();
+    for statement in statements(path, content) {
+        let text = statement.text.as_str();
+        if let Some(c) = ALTER_TABLE.captures(text) {
+            let table = normalize_name(&c[1]);
+            for c in DROP_COLUMN.captures_iter(text) {
+                let column = normalize_name(&c[1]);
+                if !DROP_KEYWORDS.contains(&column.to_uppercase().as_str()) {
+                    changes.push(SchemaChange::DropColumn {
+                        table: table.clone(),
+                        column,
+                    });
+                }
+            }
+            if !text.to_uppercase().contains(" RENAME CONSTRAINT ") {
+                for c in RENAME.captures_iter(text) {
+                    changes.push(match c.get(1) {
+                        Some(column) => SchemaChange::RenameColumn {
+                            table: table.clone(),
+                            column: normalize_name(column.as_str().trim()),
+                        },
+                        None => SchemaChange::RenameTable(table.clone()),
+                    });
+                }
+            }
+        }
+        if let Some(c) = DROP_TABLE.captures(text) {
+            changes.extend(
+                c[1].split(',')
+                    .map(normalize_name)
+                    .map(SchemaChange::DropTable),
+            );
+        }
+    }
+    if path.extension().is_some_and(|e| e == "rs") {
+        changes.extend(builder_changes(content));
+    }
+    changes
+}
+
+/// The entity file that still references what `change` removes, if any.
+fn referencing_entity<'a>(
+    references: &'a EntityReferences,
+    change: &SchemaChange,
+) -> Option<&'a str> {
+    let (table, column) = match change {
+        SchemaChange::DropTable(table) | SchemaChange::RenameTable(table) => (table, None),
+        SchemaChange::DropColumn { table, column }
+        | SchemaChange::RenameColumn { table, column } => (table, Some(column)),
+    };
+    let entity = references.tables.get(table)?;
+    match column {
+        Some(column) if !entity.columns.contains(column) => None,
+        _ => Some(entity.file.as_str()),
+    }
+}
+
+/// Check one migration file against the previous release's entities.
+pub fn check_file(
+    path: &Path,
+    content: &str,
+    references: &EntityReferences,
+) -> Vec<CompatViolation> {
+    if allowed_rules(content).contains(RULE) {
+        return Vec::new();
+    }
+    schema_changes(path, content)
+        .into_iter()
+        .filter_map(|change| {
+            let entity_file = referencing_entity(references, &change)?.to_string();
+            Some(CompatViolation {
+                file: path.to_path_buf(),
+                change,
+                entity_file,
+            })
+        })
+        .collect()
+}
+
+// =============================================================================
+// Previous Release
+// =============================================================================
+
+/// Load the entities of `base_ref` under `pathspec`.
+async fn load_entity_references(
+    repo_root: &Path,
+    base_ref: &str,
+    pathspec: &str,
+) -> Result<EntityReferences> {
+    // `git grep` exits 1 when nothing matches, which is not an error here.
+    let output = git_command_async()
+        .arg("-C")
+        .arg(repo_root)
+        .args(["grep", "-l", "DeriveEntityModel", base_ref, "--", pathspec])
+        .output()
+        .await
+        .context("Failed to spawn git grep")?;
+    if !output.status.success() && output.status.code() != Some(1) {
+        anyhow::bail!(
+            "git grep failed at {}: {}",
+            base_ref,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    let mut references = EntityReferences::default();
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        // Lines are `<ref>:<path>`.
+        let Some(file) = line
+            .strip_prefix(base_ref)
+            .and_then(|l| l.strip_prefix(':'))
+        else {
+            continue;
+        };
+        if !file.ends_with(".rs") {
+            continue;
+        }
+        let mut cmd = git_command_async();
+        cmd.arg("-C")
+            .arg(repo_root)
+            .args(["show", &format!("{}:{}", base_ref, file)]);
+        let output = run_capture_anyhow(cmd, "git show").await?;
+        if let Some((table, columns)) = parse_entity(&String::from_utf8_lossy(&output.stdout)) {
+            let entry = references.tables.entry(table).or_default();
+            entry.file = file.to_string();
+            entry.columns.extend(columns);
+        }
+    }
+    Ok(references)
+}
+
+/// Migration files in `dir` at `base_ref`.
+async fn migrations_at(repo_root: &Path, base_ref: &str, dir: &Path) -> Result<Vec<PathBuf>> {
+    let Ok(relative) = dir.strip_prefix(repo_root) else {
+        return Ok(Vec::new());
+    };
+    let mut cmd = git_command_async();
+    cmd.arg("-C")
+        .arg(repo_root)
+        .args(["ls-tree", "--name-only", base_ref, "--"])
+        .arg(format!("{}/", relative.display()));
+    let output = run_capture_anyhow(cmd, "git ls-tree").await?;
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(PathBuf::from)
+        .collect())
+}
+
+/// The previous release and the migrations added since it.
+#[derive(Debug, Clone)]
+pub struct ReleaseBaseline {
+    pub base_ref: String,
+    repo_root: PathBuf,
+    working_dir: PathBuf,
+    /// SQLx and SeaORM migration files added since `base_ref`
+    pub new_migrations: Vec<PathBuf>,
+}
+
+/// Find the previous release (`compat_base_ref`, or the latest vX.Y.Z tag)
+/// and the migrations added since it. `None` outside a git repository or
+/// before the first release.
+pub async fn release_baseline(config: &PreReleaseConfig) -> Result<Option<ReleaseBaseline>> {
+    let Ok(working_dir) = config.working_dir.canonicalize() else {
+        return Ok(None);
+    };
+    let Ok(repo_root) = repo_root(&working_dir).await else {
+        return Ok(None);
+    };
+    let base_ref = match &config.gates.migrations.compat_base_ref {
+        Some(r) => r.clone(),
+        None => match latest_release_ref(&repo_root)? {
+            Some(r) => r,
+            None => return Ok(None),
+        },
+    };
+
+    let relocate =
+        |dir: &Path| working_dir.join(dir.strip_prefix(&config.working_dir).unwrap_or(dir));
+    let mut new_files = Vec::new();
+    for (dir, kind) in [
+        (relocate(&config.migrations_dir), MigrationKind::Sql),
+        (
+            relocate(&config.seaorm_migrations_dir),
+            MigrationKind::SeaOrm,
+        ),
+    ] {
+        let baseline = migrations_at(&repo_root, &base_ref, &dir).await?;
+        new_files.extend(new_migrations(
+            &migration_files(&dir, kind).await?,
+            &baseline,
+        ));
+    }
+    Ok(Some(ReleaseBaseline {
+        base_ref,
+        repo_root,
+        working_dir,
+        new_migrations: new_files,
+    }))
+}
+
+/// Check the migrations added since the previous release against the
+/// tables and columns its entities map.
+pub async fn check_backward_compat(
+    baseline: Option<&ReleaseBaseline>,
+) -> Result<CompatCheckResult> {
+    println!("{}", "Checking schema backward compatibility...".bold());
+
+    let mut result = CompatCheckResult::default();
+    let Some(baseline) = baseline else {
+        println!("   No previous release, skipping");
+        return Ok(result);
+    };
+    let base_ref = baseline.base_ref.clone();
+    let repo_root = &baseline.repo_root;
+    result.base_ref = Some(base_ref.clone());
+
+    let pathspec = match baseline.working_dir.strip_prefix(repo_root) {
+        Ok(p) if !p.as_os_str().is_empty() => p.display().to_string(),
+        _ => ".".to_string(),
+    };
+    let references = load_entity_references(repo_root, &base_ref, &pathspec).await?;
+
+    let new_files = &baseline.new_migrations;
+    result.migrations_checked = new_files.len();
+
+    for file in new_files {
+        let content = fs::read_to_string(file)
+            .await
+            .with_context(|| format!("Failed to read: {}", file.display()))?;
+        result
+            .violations
+            .extend(check_file(file, &content, &references));
+    }
+
+    if result.violations.is_empty() {
+        println!(
+            "   {} {} new migrations compatible with the {} entities of {}",
+            "✅".green(),
+            result.migrations_checked,
+            references.tables.len(),
+            base_ref
+        );
+    } else {
+        println!(
+            "   {} {} changes break {}",
+            "❌".red(),
+            result.violations.len(),
+            base_ref
+        );
+        for violation in &result.violations {
+            println!("\n   {}", violation.format(&base_ref));
+        }
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ENTITY: &str = r#"
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "user_profiles")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: Uuid,
+    pub display_name: String,
+    #[sea_orm(column_name = "avatar")]
+    pub avatar_url: Option<String>,
+    #[sea_orm(ignore)]
+    pub computed: i32,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+"#;
+
+    fn references() -> EntityReferences {
+        let (table, columns) = parse_entity(ENTITY).unwrap();
+        let mut references = EntityReferences::default();
+        references.tables.insert(
+            table,
+            EntityTable {
+                file: "src/entities/user_profiles.rs".to_string(),
+                columns,
+            },
+        );
+        references
+    }
+
+    #[test]
+    fn test_parse_entity_maps_columns() {
+        let (table, columns) = parse_entity(ENTITY).unwrap();
+        assert_eq!(table, "user_profiles");
+        assert_eq!(
+            columns.into_iter().collect::<Vec<_>>(),
+            ["avatar", "display_name", "id"]
+        );
+    }
+
+    #[test]
+    fn test_sql_drop_and_rename_of_referenced_objects_violate() {
+        let sql = "ALTER TABLE user_profiles DROP COLUMN avatar;\n\
+                   ALTER TABLE user_profiles DROP COLUMN legacy_flag;\n\
+                   ALTER TABLE user_profiles RENAME COLUMN display_name TO name;\n\
+                   DROP TABLE IF EXISTS old_sessions;";
+        let violations = check_file(Path::new("20250101_contract.sql"), sql, &references());
+        let changes: Vec<String> = violations.iter().map(|v| v.change.describe()).collect();
+        assert_eq!(
+            changes,
+            [
+                "drops column user_profiles.avatar",
+                "renames column user_profiles.display_name"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_seaorm_builder_changes_are_detected() {
+        let source = r#"
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(UserProfiles::Table)
+                    .drop_column(UserProfiles::DisplayName)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_table(Table::drop().table(Alias::new("user_profiles")).to_owned())
+            .await
+        "#;
+        let changes = schema_changes(Path::new("m20250101_000001_contract.rs"), source);
+        assert_eq!(
+            changes,
+            [
+                SchemaChange::DropColumn {
+                    table: "user_profiles".to_string(),
+                    column: "display_name".to_string()
+                },
+                SchemaChange::DropTable("user_profiles".to_string()),
+            ]
+        );
+
+        let allowed = format!("// forge-lint: allow(backward-compat)\n{}", source);
+        assert!(check_file(Path::new("m.rs"), &allowed, &references()).is_empty());
+    }
+}
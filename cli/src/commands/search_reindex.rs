@@ -0,0 +1,707 @@
+//! Zero-downtime Elasticsearch reindex
+//!
+//! `forge search reindex` moves an alias onto a fresh index built from the
+//! current index template, without a window where the alias is missing or
+//! half-filled:
+//!
+//! 1. Create `<alias>-v<N>` with the template's settings and mappings
+//! 2. `_reindex` from the index the alias points at, as a polled task
+//! 3. Refresh both indexes and compare `_count`; on a mismatch the new index
+//!    is deleted and the alias is left alone
+//! 4. Swap the alias in one `_aliases` request (remove + add)
+//! 5. Delete versioned indexes retired longer ago than the rollback window
+//!
+//! A version is retired when its successor is created, so the retirement
+//! time is read from the successor's `creation.date` and no extra state is
+//! kept in the cluster. Rolling back is a manual `_aliases` swap onto the
+//! retained previous version.
+//!
+//! Index templates matching `<alias>-v*` must not declare aliases:
+//! Elasticsearch would attach them when the new index is created, so reads
+//! would hit the half-filled index before the swap. The reindex refuses to
+//! start when the simulated new index would get any.
+
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::time::{Duration, Instant};
+
+use crate::duration::parse_duration;
+
+/// How often the reindex task is polled
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Options for `forge search reindex`
+pub struct ReindexOptions {
+    /// Elasticsearch URL
+    pub url: String,
+    /// Alias the search service reads and writes through
+    pub alias: String,
+    /// Index template to build the new index from (defaults to the alias)
+    pub template: Option<String>,
+    /// How long a retired index is kept for rollback (e.g. "168h")
+    pub retain: String,
+    /// Maximum time to wait for the reindex task (e.g. "1h")
+    pub timeout: String,
+}
+
+/// One `<alias>-v<N>` index
+#[derive(Debug, Clone, PartialEq)]
+struct IndexVersion {
+    name: String,
+    version: u64,
+    created_ms: i64,
+}
+
+#[derive(Debug, Deserialize)]
+struct CatIndex {
+    index: String,
+    #[serde(rename = "creation.date")]
+    creation_date: String,
+}
+
+/// What a completed reindex did
+#[derive(Debug)]
+struct ReindexOutcome {
+    previous: String,
+    current: String,
+    documents: u64,
+    deleted: Vec<String>,
+}
+
+/// Version number of `name` if it is `<alias>-v<N>`
+fn version_of(alias: &str, name: &str) -> Option<u64> {
+    name.strip_prefix(alias)?.strip_prefix("-v")?.parse().ok()
+}
+
+/// Name of the next version after `versions`
+fn next_version_name(alias: &str, versions: &[IndexVersion]) -> String {
+    let next = versions.iter().map(|v| v.version).max().unwrap_or(0) + 1;
+    format!("{}-v{}", alias, next)
+}
+
+/// Create-index body from a `GET _index_template/<name>` response: the
+/// template's settings and mappings. Aliases are left out — the alias only
+/// moves once the new index is verified.
+fn index_body_from_template(response: &Value, name: &str) -> Result<Value> {
+    let template = response["index_templates"]
+        .as_array()
+        .and_then(|templates| templates.iter().find(|t| t["name"] == name))
+        .with_context(|| format!("Index template '{}' not found", name))?;
+    let inner = &template["index_template"]["template"];
+    let mut body = serde_json::Map::new();
+    for key in ["settings", "mappings"] {
+        if let Some(value) = inner.get(key) {
+            body.insert(key.to_string(), value.clone());
+        }
+    }
+    Ok(Value::Object(body))
+}
+
+/// Aliases the index templates would attach to a new index, from a
+/// `POST _index_template/_simulate_index/<name>` response
+fn template_aliases(response: &Value) -> Vec<String> {
+    let mut aliases: Vec<String> = response["template"]["aliases"]
+        .as_object()
+        .map(|aliases| aliases.keys().cloned().collect())
+        .unwrap_or_default();
+    aliases.sort();
+    aliases
+}
+
+/// Atomic alias swap from `previous` to `current`
+fn alias_swap_body(alias: &str, previous: &str, current: &str) -> Value {
+    json!({
+        "actions": [
+            { "remove": { "index": previous, "alias": alias } },
+            { "add": { "index": current, "alias": alias } },
+        ]
+    })
+}
+
+/// Versions retired longer than `retain` ago. A version is retired when the
+/// next one is created; `current` and anything newer are never expired.
+fn expired_versions(
+    versions: &[IndexVersion],
+    current: &str,
+    now_ms: i64,
+    retain: Duration,
+) -> Vec<String> {
+    let mut sorted = versions.to_vec();
+    sorted.sort_by_key(|v| v.version);
+    let Some(current_version) = sorted.iter().find(|v| v.name == current).map(|v| v.version) else {
+        return Vec::new();
+    };
+    let retain_ms = i64::try_from(retain.as_millis()).unwrap_or(i64::MAX);
+    sorted
+        .windows(2)
+        .filter(|pair| pair[0].version < current_version)
+        .filter(|pair| now_ms.saturating_sub(pair[1].created_ms) >= retain_ms)
+        .map(|pair| pair[0].name.clone())
+        .collect()
+}
+
+/// Failure summary from a finished `_reindex` task, if it failed
+fn task_failure(task: &Value) -> Option<String> {
+    if let Some(error) = task.get("error") {
+        return Some(
+            error["reason"]
+                .as_str()
+                .unwrap_or("unknown error")
+                .to_string(),
+        );
+    }
+    let failures = task["response"]["failures"].as_array()?;
+    let first = failures.first()?;
+    Some(format!(
+        "{} document failures, first: {}",
+        failures.len(),
+        first["cause"]["reason"].as_str().unwrap_or("unknown cause")
+    ))
+}
+
+/// Minimal REST client for one cluster
+struct Cluster {
+    client: reqwest::Client,
+    url: String,
+}
+
+impl Cluster {
+    fn new(url: &str) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            url: url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    /// Send a request; `Ok(None)` on 404
+    async fn try_request(
+        &self,
+        method: reqwest::Method,
+        path: &str,
+        body: Option<&Value>,
+    ) -> Result<Option<Value>> {
+        let url = format!("{}/{}", self.url, path.trim_start_matches('/'));
+        let mut builder = self.client.request(method.clone(), &url);
+        if let Some(body) = body {
+            builder = builder.json(body);
+        }
+        let response = builder
+            .send()
+            .await
+            .with_context(|| format!("{} {} request failed", method, path))?;
+        let status = response.status();
+        if status == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        if !status.is_success() {
+            let text = response.text().await.unwrap_or_default();
+            bail!("{} {} failed ({}): {}", method, path, status, text.trim());
+        }
+        let value = response
+            .json()
+            .await
+            .with_context(|| format!("Failed to parse {} {} response", method, path))?;
+        Ok(Some(value))
+    }
+
+    async fn request(
+        &self,
+        method: reqwest::Method,
+        path: &str,
+        body: Option<&Value>,
+    ) -> Result<Value> {
+        self.try_request(method.clone(), path, body)
+            .await?
+            .with_context(|| format!("{} {} failed (404 Not Found)", method, path))
+    }
+
+    /// The single index `alias` points at
+    async fn alias_target(&self, alias: &str) -> Result<String> {
+        let response = self
+            .try_request(reqwest::Method::GET, &format!("_alias/{}", alias), None)
+            .await?
+            .with_context(|| format!("Alias '{}' does not exist", alias))?;
+        let indexes: Vec<&String> = response
+            .as_object()
+            .map(|map| map.keys().collect())
+            .unwrap_or_default();
+        match indexes.as_slice() {
+            [index] => Ok(index.to_string()),
+            _ => bail!(
+                "Alias '{}' points at {} indexes; expected exactly one",
+                alias,
+                indexes.len()
+            ),
+        }
+    }
+
+    /// All `<alias>-v<N>` indexes
+    async fn versions(&self, alias: &str) -> Result<Vec<IndexVersion>> {
+        let path = format!(
+            "_cat/indices/{}-v*?format=json&h=index,creation.date&expand_wildcards=all",
+            alias
+        );
+        let response = self.request(reqwest::Method::GET, &path, None).await?;
+        let indexes: Vec<CatIndex> =
+            serde_json::from_value(response).context("Failed to parse _cat/indices response")?;
+        Ok(indexes
+            .into_iter()
+            .filter_map(|index| {
+                Some(IndexVersion {
+                    version: version_of(alias, &index.index)?,
+                    created_ms: index.creation_date.parse().ok()?,
+                    name: index.index,
+                })
+            })
+            .collect())
+    }
+
+    async fn count(&self, index: &str) -> Result<u64> {
+        self.request(reqwest::Method::POST, &format!("{}/_refresh", index), None)
+            .await?;
+        let response = self
+            .request(reqwest::Method::GET, &format!("{}/_count", index), None)
+            .await?;
+        response["count"]
+            .as_u64()
+            .with_context(|| format!("No count in {}/_count response", index))
+    }
+
+    /// Run `_reindex` as a task and poll it until it finishes
+    async fn reindex(&self, source: &str, dest: &str, timeout: Duration) -> Result<()> {
+        let body = json!({
+            "source": { "index": source },
+            "dest": { "index": dest, "op_type": "create" },
+        });
+        let response = self
+            .request(
+                reqwest::Method::POST,
+                "_reindex?wait_for_completion=false",
+                Some(&body),
+            )
+            .await?;
+        let task_id = response["task"]
+            .as_str()
+            .context("No task id in _reindex response")?
+            .to_string();
+
+        let start = Instant::now();
+        loop {
+            let task = self
+                .request(reqwest::Method::GET, &format!("_tasks/{}", task_id), None)
+                .await?;
+            if task["completed"].as_bool() == Some(true) {
+                if let Some(failure) = task_failure(&task) {
+                    bail!("Reindex task {} failed: {}", task_id, failure);
+                }
+                return Ok(());
+            }
+            if start.elapsed() >= timeout {
+                let _ = self
+                    .try_request(
+                        reqwest::Method::POST,
+                        &format!("_tasks/{}/_cancel", task_id),
+                        None,
+                    )
+                    .await;
+                bail!(
+                    "Reindex task {} did not finish within {}s (cancelled)",
+                    task_id,
+                    timeout.as_secs()
+                );
+            }
+            let status = &task["task"]["status"];
+            println!(
+                "   {} {}/{} documents",
+                "→".cyan(),
+                status["created"].as_u64().unwrap_or(0),
+                status["total"].as_u64().unwrap_or(0)
+            );
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    }
+
+    async fn delete_index(&self, index: &str) -> Result<()> {
+        self.request(reqwest::Method::DELETE, index, None).await?;
+        Ok(())
+    }
+}
+
+/// Build, verify and swap to a new version, then prune expired versions.
+/// The new index is deleted if anything fails before the swap.
+async fn reindex(
+    cluster: &Cluster,
+    alias: &str,
+    template: &str,
+    retain: Duration,
+    timeout: Duration,
+) -> Result<ReindexOutcome> {
+    let previous = cluster.alias_target(alias).await?;
+    let templates = cluster
+        .request(
+            reqwest::Method::GET,
+            &format!("_index_template/{}", template),
+            None,
+        )
+        .await
+        .with_context(|| format!("Failed to load index template '{}'", template))?;
+    let body = index_body_from_template(&templates, template)?;
+    let current = next_version_name(alias, &cluster.versions(alias).await?);
+
+    let simulated = cluster
+        .request(
+            reqwest::Method::POST,
+            &format!("_index_template/_simulate_index/{}", current),
+            None,
+        )
+        .await
+        .with_context(|| format!("Failed to simulate the index templates for {}", current))?;
+    let aliases = template_aliases(&simulated);
+    if !aliases.is_empty() {
+        bail!(
+            "Index templates matching {} declare aliases ({}), which would be attached \
+             before the new index is filled. Remove them from the templates; \
+             the reindex moves '{}' itself.",
+            current,
+            aliases.join(", "),
+            alias
+        );
+    }
+
+    println!(
+        "   {} Creating {} from template {}",
+        "→".cyan(),
+        current,
+        template
+    );
+    cluster
+        .request(reqwest::Method::PUT, &current, Some(&body))
+        .await?;
+
+    let verified = async {
+        println!("   {} Reindexing {} → {}", "→".cyan(), previous, current);
+        cluster.reindex(&previous, &current, timeout).await?;
+        let expected = cluster.count(&previous).await?;
+        let actual = cluster.count(&current).await?;
+        if expected != actual {
+            bail!(
+                "Document count mismatch: {} has {}, {} has {}. \
+                 Writes during the reindex land only in {}; re-run once they settle.",
+                previous,
+                expected,
+                current,
+                actual,
+                previous
+            );
+        }
+        Ok::<_, anyhow::Error>(actual)
+    }
+    .await;
+    let documents = match verified {
+        Ok(documents) => documents,
+        Err(e) => {
+            if let Err(cleanup) = cluster.delete_index(&current).await {
+                println!(
+                    "   {} Failed to delete {}: {}",
+                    "⚠️".yellow(),
+                    current,
+                    cleanup
+                );
+            }
+            return Err(e.context(format!("Alias '{}' still points at {}", alias, previous)));
+        }
+    };
+
+    cluster
+        .request(
+            reqwest::Method::POST,
+            "_aliases",
+            Some(&alias_swap_body(alias, &previous, &current)),
+        )
+        .await
+        .context("Alias swap failed")?;
+    println!(
+        "   {} {} now points at {} ({} documents)",
+        "✓".green(),
+        alias,
+        current,
+        documents
+    );
+
+    let now_ms = chrono::Utc::now().timestamp_millis();
+    let expired = expired_versions(&cluster.versions(alias).await?, &current, now_ms, retain);
+    let mut deleted = Vec::new();
+    for index in expired {
+        cluster.delete_index(&index).await?;
+        println!(
+            "   {} Deleted {} (rollback window passed)",
+            "✓".green(),
+            index
+        );
+        deleted.push(index);
+    }
+
+    Ok(ReindexOutcome {
+        previous,
+        current,
+        documents,
+        deleted,
+    })
+}
+
+/// `forge search reindex`
+pub async fn execute(options: &ReindexOptions) -> Result<()> {
+    let retain = parse_duration(&options.retain)
+        .with_context(|| format!("Invalid --retain '{}'", options.retain))?;
+    let timeout = parse_duration(&options.timeout)
+        .with_context(|| format!("Invalid --timeout '{}'", options.timeout))?;
+    let template = options.template.as_deref().unwrap_or(&options.alias);
+
+    println!(
+        "{}",
+        format!("Reindexing alias {} on {}...", options.alias, options.url).bold()
+    );
+    let cluster = Cluster::new(&options.url);
+    let outcome = reindex(&cluster, &options.alias, template, retain, timeout).await?;
+
+    println!(
+        "\n{} Reindexed {} documents into {}",
+        "✅".green(),
+        outcome.documents,
+        outcome.current
+    );
+    if !outcome.deleted.contains(&outcome.previous) {
+        println!(
+            "   Previous index {} is kept for {}; roll back by pointing {} at it again",
+            outcome.previous,
+            humantime::format_duration(retain),
+            options.alias
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn version(alias: &str, n: u64, created_ms: i64) -> IndexVersion {
+        IndexVersion {
+            name: format!("{}-v{}", alias, n),
+            version: n,
+            created_ms,
+        }
+    }
+
+    #[test]
+    fn test_version_naming() {
+        assert_eq!(version_of("products", "products-v3"), Some(3));
+        assert_eq!(version_of("products", "products-v"), None);
+        assert_eq!(version_of("products", "products-archive-v3"), None);
+        assert_eq!(version_of("products", "products"), None);
+
+        assert_eq!(next_version_name("products", &[]), "products-v1");
+        let versions = [version("products", 2, 0), version("products", 7, 0)];
+        assert_eq!(next_version_name("products", &versions), "products-v8");
+    }
+
+    #[test]
+    fn test_index_body_from_template_keeps_settings_and_mappings() {
+        let response = json!({
+            "index_templates": [{
+                "name": "products",
+                "index_template": {
+                    "index_patterns": ["products-*"],
+                    "template": {
+                        "settings": { "index": { "number_of_shards": "1" } },
+                        "mappings": { "properties": { "name": { "type": "text" } } },
+                        "aliases": { "products": {} }
+                    }
+                }
+            }]
+        });
+        let body = index_body_from_template(&response, "products").unwrap();
+        assert_eq!(body["settings"]["index"]["number_of_shards"], "1");
+        assert_eq!(body["mappings"]["properties"]["name"]["type"], "text");
+        assert!(body.get("aliases").is_none());
+
+        assert!(index_body_from_template(&response, "orders").is_err());
+    }
+
+    #[test]
+    fn test_template_aliases() {
+        let response = json!({
+            "template": {
+                "settings": {},
+                "aliases": { "products": {}, "catalog": { "is_write_index": true } }
+            },
+            "overlapping": []
+        });
+        assert_eq!(template_aliases(&response), ["catalog", "products"]);
+        assert!(template_aliases(&json!({ "template": { "settings": {} } })).is_empty());
+    }
+
+    #[test]
+    fn test_alias_swap_is_one_request() {
+        let body = alias_swap_body("products", "products-v1", "products-v2");
+        let actions = body["actions"].as_array().unwrap();
+        assert_eq!(actions.len(), 2);
+        assert_eq!(actions[0]["remove"]["index"], "products-v1");
+        assert_eq!(actions[1]["add"]["index"], "products-v2");
+        assert_eq!(actions[1]["add"]["alias"], "products");
+    }
+
+    #[test]
+    fn test_expired_versions_measures_from_retirement() {
+        let hour = 3_600_000;
+        let versions = [
+            version("products", 3, 30 * hour),
+            version("products", 1, 0),
+            version("products", 2, 10 * hour),
+        ];
+        let retain = Duration::from_secs(24 * 3600);
+
+        // v1 retired at hour 10, v2 at hour 30
+        assert_eq!(
+            expired_versions(&versions, "products-v3", 40 * hour, retain),
+            vec!["products-v1"]
+        );
+        assert!(expired_versions(&versions, "products-v3", 20 * hour, retain).is_empty());
+        assert_eq!(
+            expired_versions(&versions, "products-v3", 60 * hour, retain),
+            vec!["products-v1", "products-v2"]
+        );
+        // Nothing at or after the current version is expired
+        assert_eq!(
+            expired_versions(&versions, "products-v2", 60 * hour, retain),
+            vec!["products-v1"]
+        );
+        assert!(expired_versions(&versions, "products-v9", 60 * hour, retain).is_empty());
+    }
+
+    #[test]
+    fn test_task_failure() {
+        assert_eq!(
+            task_failure(&json!({ "completed": true, "response": { "failures": [] } })),
+            None
+        );
+        let failed = json!({
+            "completed": true,
+            "response": { "failures": [{ "cause": { "reason": "mapper_parsing_exception" } }] }
+        });
+        assert_eq!(
+            task_failure(&failed).unwrap(),
+            "1 document failures, first: mapper_parsing_exception"
+        );
+        let errored = json!({ "completed": true, "error": { "reason": "index_not_found" } });
+        assert_eq!(task_failure(&errored).unwrap(), "index_not_found");
+    }
+
+    async fn put(cluster: &Cluster, path: &str, body: Value) {
+        cluster
+            .request(reqwest::Method::PUT, path, Some(&body))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "requires docker for a single-node Elasticsearch container"]
+    async fn test_reindex_against_single_node_cluster() {
+        use crate::commands::elasticsearch_migrations::RehearsalElasticsearch;
+
+        let node = RehearsalElasticsearch::start(
+            "docker.elastic.co/elasticsearch/elasticsearch:8.15.3",
+            false,
+        )
+        .await
+        .unwrap();
+        let cluster = Cluster::new(&node.base_url());
+
+        put(
+            &cluster,
+            "_index_template/products",
+            json!({
+                "index_patterns": ["products-v*"],
+                "template": { "mappings": { "properties": { "name": { "type": "keyword" } } } }
+            }),
+        )
+        .await;
+        put(
+            &cluster,
+            "products-v1",
+            json!({ "aliases": { "products": {} } }),
+        )
+        .await;
+        for id in 1..=3 {
+            put(
+                &cluster,
+                &format!("products-v1/_doc/{}", id),
+                json!({ "name": format!("p{}", id) }),
+            )
+            .await;
+        }
+
+        let outcome = reindex(
+            &cluster,
+            "products",
+            "products",
+            Duration::from_secs(3600),
+            Duration::from_secs(60),
+        )
+        .await
+        .unwrap();
+        assert_eq!(outcome.previous, "products-v1");
+        assert_eq!(outcome.current, "products-v2");
+        assert_eq!(outcome.documents, 3);
+        assert!(outcome.deleted.is_empty());
+        assert_eq!(
+            cluster.alias_target("products").await.unwrap(),
+            "products-v2"
+        );
+
+        // With no rollback window both retired versions are deleted
+        let outcome = reindex(
+            &cluster,
+            "products",
+            "products",
+            Duration::ZERO,
+            Duration::from_secs(60),
+        )
+        .await
+        .unwrap();
+        assert_eq!(outcome.current, "products-v3");
+        assert_eq!(outcome.deleted, vec!["products-v1", "products-v2"]);
+
+        // A template attaching the alias to new versions is refused up front
+        put(
+            &cluster,
+            "_index_template/products-aliased",
+            json!({
+                "index_patterns": ["products-v*"],
+                "priority": 10,
+                "template": { "aliases": { "products": {} } }
+            }),
+        )
+        .await;
+        let err = reindex(
+            &cluster,
+            "products",
+            "products",
+            Duration::from_secs(3600),
+            Duration::from_secs(60),
+        )
+        .await
+        .unwrap_err();
+        assert!(
+            err.to_string().contains("declare aliases (products)"),
+            "{err}"
+        );
+        assert_eq!(
+            cluster.alias_target("products").await.unwrap(),
+            "products-v3"
+        );
+        assert_eq!(cluster.versions("products").await.unwrap().len(), 1);
+    }
+}
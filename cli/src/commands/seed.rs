@@ -80,7 +80,7 @@ fn env_config_from_product(product: &crate::config::ProductConfig, env: &str) ->
 /// `std::process::Command` + `.spawn()` shape because the seed SQL
 /// payload writes into `stdin` synchronously; the stdin-piping
 /// discipline is orthogonal to the binary-resolution lift.
-fn exec_psql(namespace: &str, pod: &str, db_name: &str, sql: &str) -> Result<String> {
+pub(crate) fn exec_psql(namespace: &str, pod: &str, db_name: &str, sql: &str) -> Result<String> {
     let kubectl = get_tool_path(tools::KUBECTL);
     let mut child = Command::new(&kubectl)
         .args([
@@ -154,7 +154,7 @@ fn exec_psql(namespace: &str, pod: &str, db_name: &str, sql: &str) -> Result<Str
 /// Harmonizing the two sibling seed-module spawn surfaces closes
 /// the last raw `kubectl` name-resolution bypass on
 /// `commands/seed.rs`.
-fn find_primary_pod(namespace: &str, postgres_cluster: &str) -> Result<String> {
+pub(crate) fn find_primary_pod(namespace: &str, postgres_cluster: &str) -> Result<String> {
     let label = format!("cnpg.io/cluster={},role=primary", postgres_cluster);
     let kubectl = get_tool_path(tools::KUBECTL);
     crate::retry::run_query_capture_sync(
@@ -174,7 +174,7 @@ fn find_primary_pod(namespace: &str, postgres_cluster: &str) -> Result<String> {
 }
 
 /// Escape a string for PostgreSQL single-quoted literals
-fn pg_escape(s: &str) -> String {
+pub(crate) fn pg_escape(s: &str) -> String {
     s.replace('\'', "''")
 }
 
@@ -0,0 +1,745 @@
+//! Subgraph Breaking-Change Detection
+//!
+//! Before a federation update, the newly extracted subgraph SDL is diffed
+//! against the one currently deployed and every change is classified:
+//!
+//! - **Breaking**: existing clients fail — removed types, fields, arguments,
+//!   enum values or union members, output fields that became nullable,
+//!   arguments or input fields that became required, changed types
+//! - **Dangerous**: existing clients keep working but may misbehave — added
+//!   enum values or union members, changed argument defaults
+//! - **Safe**: additions and deprecations
+//!
+//! The deployed schema is read from the composed supergraph the last
+//! federation update wrote for the router (the source of its ConfigMap),
+//! from the router's ConfigMap itself, or from the subgraph file at a git
+//! ref. A supergraph is narrowed to this subgraph through its `@join__*`
+//! directives. The working branch's `HEAD` is not a safe default: a subgraph
+//! file committed earlier on the branch is already in it.
+//! Breaking changes block the release unless their schema coordinates
+//! (`Type`, `Type.field`, `Type.field(arg:)`, `Enum.VALUE`, and
+//! `Union.Member` / `Type.Interface` for union members and implemented
+//! interfaces) are listed in the service's
+//! `graphql.acknowledged_breaking_changes`.
+
+use anyhow::{bail, Context, Result};
+use async_graphql_value::ConstValue;
+use colored::Colorize;
+use k8s_openapi::api::core::v1::ConfigMap;
+use kube::api::Api;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+use super::graphql_schema::{
+    is_builtin_type, BaseType, Directive, Field, InputValue, Kind, Schema, Type,
+};
+use crate::config::DeployConfig;
+use crate::error::KubernetesError;
+use crate::git::git_command_async;
+use crate::retry::run_capture_anyhow;
+
+/// How a schema change affects existing clients
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Breaking,
+    Dangerous,
+    Safe,
+}
+
+/// One classified difference between two schemas
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaChange {
+    pub severity: Severity,
+    /// Schema coordinate of the changed element, e.g. `Product.price(currency:)`
+    pub coordinate: String,
+    pub message: String,
+}
+
+/// Where the currently deployed subgraph SDL is read from
+#[derive(Debug, Clone)]
+pub enum Baseline {
+    /// The supergraph file the last federation update composed for the router
+    Supergraph { path: PathBuf },
+    /// The subgraph file at a git ref
+    Git { base_ref: String },
+    /// The supergraph in the router's ConfigMap
+    ConfigMap { namespace: String, name: String },
+}
+
+impl std::fmt::Display for Baseline {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Baseline::Supergraph { path } => write!(f, "supergraph {}", path.display()),
+            Baseline::Git { base_ref } => write!(f, "git {}", base_ref),
+            Baseline::ConfigMap { namespace, name } => {
+                write!(f, "ConfigMap {}/{}", namespace, name)
+            }
+        }
+    }
+}
+
+// =============================================================================
+// Classification
+// =============================================================================
+
+/// Whether an output position typed `old` may become `new` without
+/// breaking readers: nullable may become non-null, never the reverse.
+fn output_compatible(old: &Type, new: &Type) -> bool {
+    if !old.nullable && new.nullable {
+        return false;
+    }
+    match (&old.base, &new.base) {
+        (BaseType::Named(a), BaseType::Named(b)) => a == b,
+        (BaseType::List(a), BaseType::List(b)) => output_compatible(a, b),
+        _ => false,
+    }
+}
+
+/// Whether an input position typed `old` may become `new` without breaking
+/// writers: non-null may become nullable, never the reverse.
+fn input_compatible(old: &Type, new: &Type) -> bool {
+    if old.nullable && !new.nullable {
+        return false;
+    }
+    match (&old.base, &new.base) {
+        (BaseType::Named(a), BaseType::Named(b)) => a == b,
+        (BaseType::List(a), BaseType::List(b)) => input_compatible(a, b),
+        _ => false,
+    }
+}
+
+fn is_deprecated(directives: &[Directive]) -> bool {
+    directives.iter().any(|d| d.name == "deprecated")
+}
+
+fn show_default(value: &Option<ConstValue>) -> String {
+    value
+        .as_ref()
+        .map_or_else(|| "none".to_string(), ConstValue::to_string)
+}
+
+fn names(items: &[String]) -> BTreeSet<&str> {
+    items.iter().map(String::as_str).collect()
+}
+
+struct Changes(Vec<SchemaChange>);
+
+impl Changes {
+    fn push(&mut self, severity: Severity, coordinate: String, message: String) {
+        self.0.push(SchemaChange {
+            severity,
+            coordinate,
+            message,
+        });
+    }
+
+    /// Arguments or input fields, each named by `coordinate`
+    fn input_values(
+        &mut self,
+        kind: &str,
+        coordinate: impl Fn(&str) -> String,
+        old: &[InputValue],
+        new: &[InputValue],
+    ) {
+        for before in old {
+            let at = coordinate(&before.name);
+            let Some(after) = new.iter().find(|v| v.name == before.name) else {
+                self.push(Severity::Breaking, at, format!("{} was removed", kind));
+                continue;
+            };
+            if !input_compatible(&before.ty, &after.ty) {
+                self.push(
+                    Severity::Breaking,
+                    at,
+                    format!("{} changed type from {} to {}", kind, before.ty, after.ty),
+                );
+            } else if before.ty != after.ty {
+                self.push(
+                    Severity::Safe,
+                    at.clone(),
+                    format!("{} changed type from {} to {}", kind, before.ty, after.ty),
+                );
+            }
+            if before.default != after.default {
+                self.push(
+                    Severity::Dangerous,
+                    coordinate(&before.name),
+                    format!(
+                        "{} default changed from {} to {}",
+                        kind,
+                        show_default(&before.default),
+                        show_default(&after.default)
+                    ),
+                );
+            }
+        }
+        for after in new {
+            if old.iter().any(|v| v.name == after.name) {
+                continue;
+            }
+            if !after.ty.nullable && after.default.is_none() {
+                self.push(
+                    Severity::Breaking,
+                    coordinate(&after.name),
+                    format!("required {} was added", kind),
+                );
+            } else {
+                self.push(
+                    Severity::Safe,
+                    coordinate(&after.name),
+                    format!("optional {} was added", kind),
+                );
+            }
+        }
+    }
+
+    fn fields(&mut self, type_name: &str, old: &[Field], new: &[Field]) {
+        for before in old {
+            let at = format!("{}.{}", type_name, before.name);
+            let Some(after) = new.iter().find(|f| f.name == before.name) else {
+                let note = if is_deprecated(&before.directives) {
+                    " (was deprecated)"
+                } else {
+                    ""
+                };
+                self.push(Severity::Breaking, at, format!("field was removed{}", note));
+                continue;
+            };
+            if !output_compatible(&before.ty, &after.ty) {
+                self.push(
+                    Severity::Breaking,
+                    at.clone(),
+                    format!("field changed type from {} to {}", before.ty, after.ty),
+                );
+            } else if before.ty != after.ty {
+                self.push(
+                    Severity::Safe,
+                    at.clone(),
+                    format!("field changed type from {} to {}", before.ty, after.ty),
+                );
+            }
+            if !is_deprecated(&before.directives) && is_deprecated(&after.directives) {
+                self.push(
+                    Severity::Safe,
+                    at.clone(),
+                    "field was deprecated".to_string(),
+                );
+            }
+            self.input_values(
+                "argument",
+                |arg| format!("{}({}:)", at, arg),
+                &before.args,
+                &after.args,
+            );
+        }
+        for after in new {
+            if !old.iter().any(|f| f.name == after.name) {
+                self.push(
+                    Severity::Safe,
+                    format!("{}.{}", type_name, after.name),
+                    "field was added".to_string(),
+                );
+            }
+        }
+    }
+
+    /// Members of a list-valued type property (enum values, union members,
+    /// implemented interfaces)
+    fn members(
+        &mut self,
+        what: &str,
+        coordinate: impl Fn(&str) -> String,
+        old: &BTreeSet<&str>,
+        new: &BTreeSet<&str>,
+        added: Severity,
+    ) {
+        for removed in old.difference(new) {
+            self.push(
+                Severity::Breaking,
+                coordinate(removed),
+                format!("{} {} was removed", what, removed),
+            );
+        }
+        for added_member in new.difference(old) {
+            self.push(
+                added,
+                coordinate(added_member),
+                format!("{} {} was added", what, added_member),
+            );
+        }
+    }
+}
+
+/// Classify every difference between the deployed and the new schema
+pub fn diff(old: &Schema, new: &Schema) -> Vec<SchemaChange> {
+    let mut changes = Changes(Vec::new());
+
+    for before in old.types.values().filter(|t| !is_builtin_type(&t.name)) {
+        let name = &before.name;
+        let Some(after) = new.types.get(name) else {
+            changes.push(
+                Severity::Breaking,
+                name.clone(),
+                format!("{} was removed", before.kind.as_str()),
+            );
+            continue;
+        };
+        if before.kind != after.kind {
+            changes.push(
+                Severity::Breaking,
+                name.clone(),
+                format!(
+                    "changed from {} to {}",
+                    before.kind.as_str(),
+                    after.kind.as_str()
+                ),
+            );
+            continue;
+        }
+        match before.kind {
+            Kind::Object | Kind::Interface => {
+                changes.fields(name, &before.fields, &after.fields);
+                changes.members(
+                    "interface",
+                    |interface| format!("{}.{}", name, interface),
+                    &names(&before.implements),
+                    &names(&after.implements),
+                    Severity::Safe,
+                );
+            }
+            Kind::InputObject => changes.input_values(
+                "input field",
+                |field| format!("{}.{}", name, field),
+                &before.input_fields,
+                &after.input_fields,
+            ),
+            Kind::Enum => changes.members(
+                "value",
+                |value| format!("{}.{}", name, value),
+                &before.values.iter().map(|v| v.name.as_str()).collect(),
+                &after.values.iter().map(|v| v.name.as_str()).collect(),
+                Severity::Dangerous,
+            ),
+            Kind::Union => changes.members(
+                "member",
+                |member| format!("{}.{}", name, member),
+                &names(&before.members),
+                &names(&after.members),
+                Severity::Dangerous,
+            ),
+            Kind::Scalar => {}
+        }
+    }
+    for after in new.types.values().filter(|t| !is_builtin_type(&t.name)) {
+        if !old.types.contains_key(&after.name) {
+            changes.push(
+                Severity::Safe,
+                after.name.clone(),
+                format!("{} was added", after.kind.as_str()),
+            );
+        }
+    }
+
+    changes
+        .0
+        .sort_by(|a, b| (a.severity, &a.coordinate).cmp(&(b.severity, &b.coordinate)));
+    changes.0
+}
+
+// =============================================================================
+// Baselines
+// =============================================================================
+
+/// The subgraph SDL at `base_ref`, or `None` if the file did not exist
+async fn sdl_at_ref(repo_root: &Path, base_ref: &str, relative: &Path) -> Result<Option<String>> {
+    let mut cmd = git_command_async();
+    cmd.arg("-C")
+        .arg(repo_root)
+        .args(["ls-tree", "--name-only", base_ref, "--"])
+        .arg(relative);
+    let output = run_capture_anyhow(cmd, "git ls-tree").await?;
+    if output.stdout.is_empty() {
+        return Ok(None);
+    }
+    let mut cmd = git_command_async();
+    cmd.arg("-C")
+        .arg(repo_root)
+        .args(["show", &format!("{}:{}", base_ref, relative.display())]);
+    let output = run_capture_anyhow(cmd, "git show").await?;
+    Ok(Some(String::from_utf8_lossy(&output.stdout).into_owned()))
+}
+
+/// The `supergraph.graphql` value of ConfigMap data, else the only
+/// `.graphql` value
+fn supergraph_key(data: &BTreeMap<String, String>) -> Option<&str> {
+    data.get("supergraph.graphql")
+        .or_else(|| {
+            let mut graphql = data.iter().filter(|(key, _)| key.ends_with(".graphql"));
+            match (graphql.next(), graphql.next()) {
+                (Some((_, value)), None) => Some(value),
+                _ => None,
+            }
+        })
+        .map(String::as_str)
+}
+
+/// The supergraph SDL stored in a router ConfigMap: the `supergraph.graphql`
+/// key, else the only `.graphql` key.
+async fn supergraph_from_configmap(namespace: &str, name: &str) -> Result<String> {
+    let client = crate::k8s::create_client().await?;
+    let configmap = Api::<ConfigMap>::namespaced(client, namespace)
+        .get(name)
+        .await
+        .map_err(|e| {
+            KubernetesError::api_failed(format!("get configmap {}", name), namespace, e)
+        })?;
+    let data = configmap
+        .data
+        .with_context(|| format!("ConfigMap {}/{} has no data", namespace, name))?;
+    supergraph_key(&data).map(str::to_string).with_context(|| {
+        format!(
+            "ConfigMap {}/{} has no supergraph.graphql key",
+            namespace, name
+        )
+    })
+}
+
+/// The deployed schema of subgraph `subgraph`, or `None` if it has never
+/// been deployed
+async fn load_baseline(
+    baseline: &Baseline,
+    repo_root: &Path,
+    schema_path: &Path,
+    subgraph: &str,
+) -> Result<Option<Schema>> {
+    match baseline {
+        Baseline::Supergraph { path } => {
+            if !path.exists() {
+                return Ok(None);
+            }
+            let sdl = tokio::fs::read_to_string(path)
+                .await
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            let supergraph =
+                Schema::parse(&sdl).with_context(|| format!("Supergraph {}", path.display()))?;
+            Ok(supergraph.subgraph(subgraph))
+        }
+        Baseline::Git { base_ref } => {
+            let relative = schema_path.strip_prefix(repo_root).unwrap_or(schema_path);
+            match sdl_at_ref(repo_root, base_ref, relative).await? {
+                Some(sdl) => Schema::parse(&sdl)
+                    .with_context(|| format!("{} at {}", relative.display(), base_ref))
+                    .map(Some),
+                None => Ok(None),
+            }
+        }
+        Baseline::ConfigMap { namespace, name } => {
+            let sdl = supergraph_from_configmap(namespace, name).await?;
+            let supergraph = Schema::parse(&sdl)
+                .with_context(|| format!("Supergraph in ConfigMap {}/{}", namespace, name))?;
+            Ok(supergraph.subgraph(subgraph))
+        }
+    }
+}
+
+// =============================================================================
+// Check
+// =============================================================================
+
+/// Breaking changes not covered by `acknowledged`
+pub fn unacknowledged<'a>(
+    changes: &'a [SchemaChange],
+    acknowledged: &[String],
+) -> Vec<&'a SchemaChange> {
+    changes
+        .iter()
+        .filter(|c| c.severity == Severity::Breaking)
+        .filter(|c| !acknowledged.iter().any(|a| a == &c.coordinate))
+        .collect()
+}
+
+/// Diff the service's extracted subgraph schema against the deployed one
+/// and fail on unacknowledged breaking changes.
+pub async fn check_subgraph_changes(
+    deploy_config: &DeployConfig,
+    baseline: &Baseline,
+    allow_breaking: bool,
+) -> Result<Vec<SchemaChange>> {
+    let graphql = &deploy_config.service.graphql;
+    let schema_path = deploy_config
+        .subgraph_schema_path()
+        .context("Failed to compute subgraph schema path from config")?;
+    let subgraph = schema_path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or(&deploy_config.service.name)
+        .to_string();
+
+    println!(
+        "🔍 {}",
+        format!(
+            "Checking {} subgraph changes against {}...",
+            subgraph, baseline
+        )
+        .bold()
+    );
+
+    let sdl = tokio::fs::read_to_string(&schema_path)
+        .await
+        .with_context(|| format!("Failed to read {}", schema_path.display()))?;
+    let new = Schema::parse(&sdl).with_context(|| schema_path.display().to_string())?;
+    let repo_root = crate::git::get_repo_root()?;
+    let Some(old) = load_baseline(baseline, &repo_root, &schema_path, &subgraph).await? else {
+        println!("   Subgraph not deployed yet - nothing to compare");
+        return Ok(Vec::new());
+    };
+
+    let changes = diff(&old, &new);
+    if changes.is_empty() {
+        println!("   {} No schema changes", "✓".green());
+        return Ok(changes);
+    }
+    let blocking = unacknowledged(&changes, &graphql.acknowledged_breaking_changes);
+    for change in &changes {
+        let line = format!("{}: {}", change.coordinate, change.message);
+        match change.severity {
+            Severity::Breaking if blocking.contains(&change) => {
+                println!("   {} {}", "✗".red(), line.red())
+            }
+            Severity::Breaking => println!("   {} {} (acknowledged)", "✗".yellow(), line),
+            Severity::Dangerous => println!("   {} {}", "⚠️".yellow(), line.yellow()),
+            Severity::Safe => println!("   {} {}", "✓".green(), line),
+        }
+    }
+
+    if !blocking.is_empty() {
+        if allow_breaking {
+            println!(
+                "   {} {} breaking change(s) allowed by --allow-breaking",
+                "⚠️".yellow(),
+                blocking.len()
+            );
+        } else {
+            let coordinates: Vec<&str> = blocking.iter().map(|c| c.coordinate.as_str()).collect();
+            bail!(
+                "{} unacknowledged breaking change(s) to the {} subgraph.\n  \
+                 If every client has moved off them, acknowledge them in deploy.yaml:\n  \
+                 graphql:\n    acknowledged_breaking_changes:\n{}",
+                blocking.len(),
+                subgraph,
+                coordinates
+                    .iter()
+                    .map(|c| format!("      - \"{}\"", c))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            );
+        }
+    }
+    Ok(changes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OLD: &str = r#"
+type Product @key(fields: "id") {
+  id: ID!
+  name: String!
+  description: String
+  legacyCode: String
+  price(currency: Currency = USD): Float
+}
+
+enum Currency { USD EUR GBP }
+
+union SearchResult = Product | Category
+
+type Category { id: ID! }
+
+input ProductFilter {
+  name: String
+  minPrice: Float!
+}
+
+type Query {
+  products(filter: ProductFilter, limit: Int): [Product!]!
+  search(term: String!): [SearchResult!]!
+}
+"#;
+
+    const NEW: &str = r#"
+type Product @key(fields: "id") {
+  id: ID!
+  name: String
+  description: String!
+  price(currency: Currency = EUR, round: Boolean): Float
+  sku: String @deprecated(reason: "unused")
+}
+
+enum Currency { USD EUR JPY }
+
+union SearchResult = Product
+
+type Category { id: ID! }
+
+input ProductFilter {
+  name: String!
+  minPrice: Float
+  inStock: Boolean!
+}
+
+type Query {
+  products(filter: ProductFilter, limit: Int!): [Product!]!
+  search(term: String!): [SearchResult!]!
+  categories: [Category!]!
+}
+"#;
+
+    fn changes() -> Vec<SchemaChange> {
+        diff(&Schema::parse(OLD).unwrap(), &Schema::parse(NEW).unwrap())
+    }
+
+    fn find(changes: &[SchemaChange], coordinate: &str, severity: Severity) -> bool {
+        changes
+            .iter()
+            .any(|c| c.coordinate == coordinate && c.severity == severity)
+    }
+
+    #[test]
+    fn test_diff_classifies_breaking_changes() {
+        let changes = changes();
+        let breaking: Vec<&str> = changes
+            .iter()
+            .filter(|c| c.severity == Severity::Breaking)
+            .map(|c| c.coordinate.as_str())
+            .collect();
+        assert_eq!(
+            breaking,
+            [
+                "Currency.GBP",
+                "Product.legacyCode",
+                "Product.name",
+                "ProductFilter.inStock",
+                "ProductFilter.name",
+                "Query.products(limit:)",
+                "SearchResult.Category",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_classifies_dangerous_and_safe_changes() {
+        let changes = changes();
+        assert!(find(&changes, "Currency.JPY", Severity::Dangerous));
+        assert!(find(
+            &changes,
+            "Product.price(currency:)",
+            Severity::Dangerous
+        ));
+        assert!(find(&changes, "Product.description", Severity::Safe));
+        assert!(find(&changes, "Product.price(round:)", Severity::Safe));
+        assert!(find(&changes, "Product.sku", Severity::Safe));
+        assert!(find(&changes, "ProductFilter.minPrice", Severity::Safe));
+        assert!(find(&changes, "Query.categories", Severity::Safe));
+        // Ordered breaking → dangerous → safe
+        assert!(changes.windows(2).all(|w| w[0].severity <= w[1].severity));
+    }
+
+    #[test]
+    fn test_diff_type_removal_and_kind_change() {
+        let old = Schema::parse("type Query { a: A b: B }\ntype A { id: ID }\ntype B { id: ID }")
+            .unwrap();
+        let new = Schema::parse("type Query { a: A b: B }\ninput B { id: ID }").unwrap();
+        let changes = diff(&old, &new);
+        assert!(find(&changes, "A", Severity::Breaking));
+        assert!(changes
+            .iter()
+            .any(|c| c.coordinate == "B" && c.message == "changed from type to input"));
+        assert!(diff(&old, &old).is_empty());
+    }
+
+    #[test]
+    fn test_diff_member_and_interface_coordinates() {
+        let old = Schema::parse(
+            "interface Node { id: ID! }
+interface Named { id: ID! }
+             type A implements Node & Named { id: ID! }
+type B { id: ID! }
+             union U = A | B
+type Query { u: U a: A }",
+        )
+        .unwrap();
+        let new = Schema::parse(
+            "interface Node { id: ID! }
+interface Named { id: ID! }
+             type A implements Node { id: ID! }
+type B { id: ID! }
+             union U = A
+type Query { u: U a: A }",
+        )
+        .unwrap();
+        let changes = diff(&old, &new);
+        assert!(find(&changes, "U.B", Severity::Breaking));
+        assert!(find(&changes, "A.Named", Severity::Breaking));
+        assert!(!changes
+            .iter()
+            .any(|c| c.coordinate == "U" || c.coordinate == "A"));
+    }
+
+    #[test]
+    fn test_supergraph_key() {
+        let mut data = BTreeMap::new();
+        data.insert("router.yaml".to_string(), "x".to_string());
+        data.insert("schema.graphql".to_string(), "type Query".to_string());
+        assert_eq!(supergraph_key(&data), Some("type Query"));
+        data.insert("other.graphql".to_string(), "type Other".to_string());
+        assert_eq!(supergraph_key(&data), None);
+        data.insert("supergraph.graphql".to_string(), "type Super".to_string());
+        assert_eq!(supergraph_key(&data), Some("type Super"));
+    }
+
+    #[tokio::test]
+    async fn test_supergraph_baseline_is_narrowed_to_the_subgraph() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("supergraph.graphql");
+        let baseline = Baseline::Supergraph { path: path.clone() };
+        let load = |name: &'static str| load_baseline(&baseline, dir.path(), &path, name);
+
+        assert!(load("products").await.unwrap().is_none());
+
+        std::fs::write(
+            &path,
+            r#"
+enum join__Graph {
+  PRODUCTS @join__graph(name: "products", url: "http://products:8080/graphql")
+  REVIEWS @join__graph(name: "reviews", url: "http://reviews:8080/graphql")
+}
+
+type Query @join__type(graph: PRODUCTS) @join__type(graph: REVIEWS) {
+  product(id: ID!): String @join__field(graph: PRODUCTS)
+  review(id: ID!): String @join__field(graph: REVIEWS)
+}
+"#,
+        )
+        .unwrap();
+        let products = load("products").await.unwrap().unwrap();
+        assert!(products.types["Query"].field("product").is_some());
+        assert!(products.types["Query"].field("review").is_none());
+        assert!(load("inventory").await.unwrap().is_none());
+    }
+
+    #[test]
+    fn test_unacknowledged_filters_by_coordinate() {
+        let changes = changes();
+        let acknowledged = vec![
+            "Product.legacyCode".to_string(),
+            "SearchResult.Category".to_string(),
+        ];
+        let blocking = unacknowledged(&changes, &acknowledged);
+        assert_eq!(blocking.len(), 5);
+        assert!(blocking
+            .iter()
+            .all(|c| c.coordinate != "Product.legacyCode"));
+    }
+}
@@ -0,0 +1,1378 @@
+//! Native Supergraph Composition
+//!
+//! Composes Federation v2 subgraph schemas into a Hive Router supergraph
+//! in-process, without `rover` and the composition binary it downloads at
+//! run time. Output is deterministic: subgraphs are ordered by name and
+//! types by name, so the same inputs always produce the same bytes.
+//! Products opt in with `composer: native`; Rover remains the default.
+//!
+//! Each subgraph is normalized first: federation directives are resolved
+//! through the `@link` imports (including `as` renames and the
+//! `federation__` prefix), and the `_service` / `_entities` plumbing is
+//! removed. A subgraph without a federation `@link` is treated as
+//! Federation 1, where every field is implicitly shareable.
+//!
+//! Composition validates:
+//! - `@key`, `@requires` and `@provides` field sets against the subgraph
+//! - Shareable conflicts: a field resolved by several subgraphs must be
+//!   `@shareable` (or a key field) in each of them
+//! - `@override` usage: a known source subgraph other than itself, not
+//!   combined with `@external`, not chained
+//! - Type consistency of fields, arguments, input fields and enums
+//!
+//! The supported federation directives are `@key`, `@shareable`,
+//! `@external`, `@requires`, `@provides`, `@override` and `@extends`.
+//! Anything else federation-specific (`@inaccessible`, `@tag`,
+//! `@interfaceObject`, ...) is rejected with a pointer to
+//! `composer: rover`. Custom directives are not carried into the
+//! supergraph.
+
+use anyhow::{bail, Context, Result};
+use async_graphql_parser::types::{DocumentOperations, Selection};
+use async_graphql_value::{ConstValue, Name};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use super::graphql_schema::{
+    is_builtin_type, named_type, BaseType, Directive, EnumValue, Field, InputValue, Kind, Schema,
+    Type, TypeDef,
+};
+
+const KEY: &str = "federation__key";
+const SHAREABLE: &str = "federation__shareable";
+const EXTERNAL: &str = "federation__external";
+const REQUIRES: &str = "federation__requires";
+const PROVIDES: &str = "federation__provides";
+const OVERRIDE: &str = "federation__override";
+const EXTENDS: &str = "federation__extends";
+
+/// Federation directives the native composer understands
+const SUPPORTED: &[&str] = &[
+    "key",
+    "shareable",
+    "external",
+    "requires",
+    "provides",
+    "override",
+    "extends",
+];
+
+/// Directives Federation 1 subgraphs use without a `@link`
+const FEDERATION_1: &[&str] = &["key", "external", "requires", "provides", "extends"];
+
+/// Built-in directives carried into the supergraph
+const BUILTIN_DIRECTIVES: &[&str] = &["deprecated", "specifiedBy"];
+
+const JOIN_DIRECTIVES: &[&str] = &[
+    "directive @join__enumValue(graph: join__Graph!) repeatable on ENUM_VALUE",
+    "directive @join__field(graph: join__Graph, requires: join__FieldSet, provides: join__FieldSet, type: String, external: Boolean, override: String, usedOverridden: Boolean) repeatable on FIELD_DEFINITION | INPUT_FIELD_DEFINITION",
+    "directive @join__graph(name: String!, url: String!) on ENUM_VALUE",
+    "directive @join__implements(graph: join__Graph!, interface: String!) repeatable on OBJECT | INTERFACE",
+    "directive @join__type(graph: join__Graph!, key: join__FieldSet, extension: Boolean! = false, resolvable: Boolean! = true, isInterfaceObject: Boolean! = false) repeatable on OBJECT | INTERFACE | UNION | ENUM | INPUT_OBJECT | SCALAR",
+    "directive @join__unionMember(graph: join__Graph!, member: String!) repeatable on UNION",
+    "directive @link(url: String, as: String, for: link__Purpose, import: [link__Import]) repeatable on SCHEMA",
+];
+
+/// A subgraph to compose
+#[derive(Debug, Clone)]
+pub struct Subgraph {
+    pub name: String,
+    pub url: String,
+    pub sdl: String,
+}
+
+/// A composed supergraph
+#[derive(Debug, Clone)]
+pub struct Composition {
+    pub supergraph: String,
+    /// Non-fatal findings, e.g. optional arguments dropped from the
+    /// supergraph because not every subgraph declares them
+    pub hints: Vec<String>,
+}
+
+// =============================================================================
+// Subgraph normalization
+// =============================================================================
+
+/// A normalized subgraph
+struct Source {
+    name: String,
+    /// `join__Graph` enum value
+    graph: String,
+    url: String,
+    schema: Schema,
+    fed2: bool,
+}
+
+/// `join__Graph` enum value for a subgraph name
+fn graph_name(name: &str) -> String {
+    let mut graph: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if !graph.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_') {
+        graph.insert(0, '_');
+    }
+    graph
+}
+
+fn strip_at(name: &str) -> &str {
+    name.strip_prefix('@').unwrap_or(name)
+}
+
+/// Local directive name to canonical federation name, from the federation
+/// `@link`. `None` when the subgraph has no federation link (Federation 1).
+fn federation_names(schema: &Schema) -> Option<(String, HashMap<String, String>)> {
+    let link = schema.schema_directives.iter().find(|d| {
+        d.name == "link"
+            && d.text_argument("url")
+                .is_some_and(|url| url.contains("specs.apollo.dev/federation/"))
+    })?;
+    let prefix = link.text_argument("as").unwrap_or("federation").to_string();
+    let mut imports = HashMap::new();
+    if let Some(ConstValue::List(list)) = link.argument("import") {
+        for import in list {
+            match import {
+                ConstValue::String(name) => {
+                    imports.insert(strip_at(name).to_string(), strip_at(name).to_string());
+                }
+                ConstValue::Object(object) => {
+                    let text = |key: &str| match object.get(key) {
+                        Some(ConstValue::String(s)) => Some(strip_at(s).to_string()),
+                        _ => None,
+                    };
+                    if let Some(name) = text("name") {
+                        imports.insert(text("as").unwrap_or_else(|| name.clone()), name);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    Some((prefix, imports))
+}
+
+fn directive_lists(schema: &mut Schema) -> Vec<&mut Vec<Directive>> {
+    let mut lists = Vec::new();
+    for ty in schema.types.values_mut() {
+        lists.push(&mut ty.directives);
+        for field in &mut ty.fields {
+            lists.push(&mut field.directives);
+            for arg in &mut field.args {
+                lists.push(&mut arg.directives);
+            }
+        }
+        for field in &mut ty.input_fields {
+            lists.push(&mut field.directives);
+        }
+        for value in &mut ty.values {
+            lists.push(&mut value.directives);
+        }
+    }
+    lists
+}
+
+/// Parse a subgraph and rename its federation directives to
+/// `federation__<name>`, dropping custom ones.
+fn normalize(subgraph: &Subgraph, errors: &mut Vec<String>) -> Option<Source> {
+    let mut schema = match Schema::parse(&subgraph.sdl) {
+        Ok(schema) => schema,
+        Err(e) => {
+            errors.push(format!(
+                "[INVALID_GRAPHQL] subgraph \"{}\": {:#}",
+                subgraph.name, e
+            ));
+            return None;
+        }
+    };
+    let federation = federation_names(&schema);
+    let fed2 = federation.is_some();
+
+    let canonical = |name: &str| -> Option<String> {
+        match &federation {
+            Some((prefix, imports)) => imports.get(name).cloned().or_else(|| {
+                name.strip_prefix(prefix.as_str())
+                    .and_then(|rest| rest.strip_prefix("__"))
+                    .map(str::to_string)
+            }),
+            None => FEDERATION_1.contains(&name).then(|| name.to_string()),
+        }
+    };
+    let mut unsupported = BTreeSet::new();
+    for list in directive_lists(&mut schema) {
+        list.retain_mut(|d| match canonical(&d.name) {
+            Some(name) => {
+                if !SUPPORTED.contains(&name.as_str()) {
+                    unsupported.insert(name.clone());
+                }
+                d.name = format!("federation__{}", name);
+                true
+            }
+            None => BUILTIN_DIRECTIVES.contains(&d.name.as_str()),
+        });
+    }
+    for name in unsupported {
+        errors.push(format!(
+            "[UNSUPPORTED_DIRECTIVE] subgraph \"{}\" uses @{}, which the native composer does not support (set `composer: rover` in the federation config)",
+            subgraph.name, name
+        ));
+    }
+
+    for (op, root, default) in [
+        ("query", &schema.query, "Query"),
+        ("mutation", &schema.mutation, "Mutation"),
+        ("subscription", &schema.subscription, "Subscription"),
+    ] {
+        if let Some(root) = root.as_ref().filter(|root| *root != default) {
+            errors.push(format!(
+                "[ROOT_TYPE_NAME] subgraph \"{}\" names its {} root \"{}\"; only \"{}\" is supported",
+                subgraph.name, op, root, default
+            ));
+        }
+    }
+
+    schema
+        .types
+        .retain(|name, _| !(is_builtin_type(name) || fed2 && name == "FieldSet"));
+    if let Some(query) = schema.types.get_mut("Query") {
+        query
+            .fields
+            .retain(|f| f.name != "_service" && f.name != "_entities");
+        if query.fields.is_empty() {
+            schema.types.remove("Query");
+        }
+    }
+
+    Some(Source {
+        name: subgraph.name.clone(),
+        graph: graph_name(&subgraph.name),
+        url: subgraph.url.clone(),
+        schema,
+        fed2,
+    })
+}
+
+// =============================================================================
+// Field sets
+// =============================================================================
+
+/// One field of a `@key` / `@requires` / `@provides` field set
+struct FieldSelection {
+    name: String,
+    has_arguments: bool,
+    children: Vec<FieldSelection>,
+}
+
+fn parse_field_set(fields: &str) -> Result<Vec<FieldSelection>> {
+    fn selections(set: &async_graphql_parser::types::SelectionSet) -> Result<Vec<FieldSelection>> {
+        set.items
+            .iter()
+            .map(|item| match &item.node {
+                Selection::Field(field) => Ok(FieldSelection {
+                    name: field.node.name.node.to_string(),
+                    has_arguments: !field.node.arguments.is_empty(),
+                    children: selections(&field.node.selection_set.node)?,
+                }),
+                _ => bail!("fragments are not supported in field sets"),
+            })
+            .collect()
+    }
+
+    let document = async_graphql_parser::parse_query(format!("{{{}}}", fields))
+        .with_context(|| format!("invalid field set \"{}\"", fields))?;
+    match &document.operations {
+        DocumentOperations::Single(op) => selections(&op.node.selection_set.node),
+        DocumentOperations::Multiple(_) => bail!("invalid field set \"{}\"", fields),
+    }
+}
+
+/// Check a field set against `ty` in `schema`. With `external`, every
+/// top-level field must be `@external` in this subgraph.
+fn check_field_set(
+    schema: &Schema,
+    ty: &TypeDef,
+    selections: &[FieldSelection],
+    key: bool,
+    external: bool,
+) -> Result<(), String> {
+    for selection in selections {
+        let Some(field) = ty.field(&selection.name) else {
+            return Err(format!(
+                "field \"{}.{}\" does not exist",
+                ty.name, selection.name
+            ));
+        };
+        if external && !is_external(ty, field) {
+            return Err(format!(
+                "field \"{}.{}\" is not marked @external",
+                ty.name, field.name
+            ));
+        }
+        if key && selection.has_arguments {
+            return Err(format!(
+                "field \"{}.{}\" takes arguments",
+                ty.name, field.name
+            ));
+        }
+        match schema.types.get(named_type(&field.ty)) {
+            Some(target) if key && matches!(target.kind, Kind::Interface | Kind::Union) => {
+                return Err(format!(
+                    "field \"{}.{}\" returns an abstract type, which keys cannot select",
+                    ty.name, field.name
+                ));
+            }
+            Some(target) if target.kind == Kind::Union => {
+                return Err(format!(
+                    "field \"{}.{}\" returns a union, which field sets cannot select",
+                    ty.name, field.name
+                ));
+            }
+            Some(target) if matches!(target.kind, Kind::Object | Kind::Interface) => {
+                if selection.children.is_empty() {
+                    return Err(format!(
+                        "field \"{}.{}\" returns a composite type and needs a selection",
+                        ty.name, field.name
+                    ));
+                }
+                check_field_set(schema, target, &selection.children, key, false)?;
+            }
+            _ if !selection.children.is_empty() => {
+                return Err(format!(
+                    "field \"{}.{}\" is a leaf and cannot have a selection",
+                    ty.name, field.name
+                ));
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Top-level fields of every `@key` on `ty`
+fn key_fields(ty: &TypeDef) -> BTreeSet<String> {
+    ty.directives_named(KEY)
+        .filter_map(|d| d.text_argument("fields"))
+        .filter_map(|fields| parse_field_set(fields).ok())
+        .flatten()
+        .map(|selection| selection.name)
+        .collect()
+}
+
+fn is_external(ty: &TypeDef, field: &Field) -> bool {
+    has_directive(&field.directives, EXTERNAL) || ty.directives_named(EXTERNAL).next().is_some()
+}
+
+fn has_directive(directives: &[Directive], name: &str) -> bool {
+    directives.iter().any(|d| d.name == name)
+}
+
+fn text_directive<'a>(directives: &'a [Directive], name: &str, argument: &str) -> Option<&'a str> {
+    directives
+        .iter()
+        .find(|d| d.name == name)
+        .and_then(|d| d.text_argument(argument))
+}
+
+/// Validate the `@key`, `@requires` and `@provides` field sets of one
+/// subgraph
+fn check_field_sets(source: &Source, errors: &mut Vec<String>) {
+    let schema = &source.schema;
+    for ty in schema.types.values() {
+        for key in ty.directives_named(KEY) {
+            let Some(fields) = key.text_argument("fields") else {
+                continue;
+            };
+            if !matches!(ty.kind, Kind::Object | Kind::Interface) {
+                errors.push(format!(
+                    "[KEY_UNSUPPORTED_ON_TYPE] subgraph \"{}\": @key on {} \"{}\"",
+                    source.name,
+                    ty.kind.as_str(),
+                    ty.name
+                ));
+                continue;
+            }
+            if let Err(e) = parse_field_set(fields)
+                .map_err(|e| format!("{:#}", e))
+                .and_then(|selections| check_field_set(schema, ty, &selections, true, false))
+            {
+                errors.push(format!(
+                    "[INVALID_KEY] subgraph \"{}\": @key(fields: \"{}\") on \"{}\": {}",
+                    source.name, fields, ty.name, e
+                ));
+            }
+        }
+
+        for field in &ty.fields {
+            if let Some(fields) = text_directive(&field.directives, REQUIRES, "fields") {
+                if let Err(e) = parse_field_set(fields)
+                    .map_err(|e| format!("{:#}", e))
+                    .and_then(|selections| check_field_set(schema, ty, &selections, false, true))
+                {
+                    errors.push(format!(
+                        "[INVALID_REQUIRES] subgraph \"{}\": @requires on \"{}.{}\": {}",
+                        source.name, ty.name, field.name, e
+                    ));
+                }
+            }
+            if let Some(fields) = text_directive(&field.directives, PROVIDES, "fields") {
+                let result = match schema.types.get(named_type(&field.ty)) {
+                    Some(target) if matches!(target.kind, Kind::Object | Kind::Interface) => {
+                        parse_field_set(fields)
+                            .map_err(|e| format!("{:#}", e))
+                            .and_then(|selections| {
+                                check_field_set(schema, target, &selections, false, source.fed2)
+                            })
+                    }
+                    _ => Err("the field does not return an object or interface".to_string()),
+                };
+                if let Err(e) = result {
+                    errors.push(format!(
+                        "[INVALID_PROVIDES] subgraph \"{}\": @provides on \"{}.{}\": {}",
+                        source.name, ty.name, field.name, e
+                    ));
+                }
+            }
+        }
+    }
+}
+
+// =============================================================================
+// Type merging
+// =============================================================================
+
+fn same_shape(a: &Type, b: &Type) -> bool {
+    match (&a.base, &b.base) {
+        (BaseType::Named(x), BaseType::Named(y)) => x == y,
+        (BaseType::List(x), BaseType::List(y)) => same_shape(x, y),
+        _ => false,
+    }
+}
+
+/// Merge two types of the same shape, deciding nullability per level
+fn merge_type(a: &Type, b: &Type, nullable: fn(bool, bool) -> bool) -> Type {
+    let base = match (&a.base, &b.base) {
+        (BaseType::List(x), BaseType::List(y)) => {
+            BaseType::List(Box::new(merge_type(x, y, nullable)))
+        }
+        _ => a.base.clone(),
+    };
+    Type {
+        base,
+        nullable: nullable(a.nullable, b.nullable),
+    }
+}
+
+fn is_required(value: &InputValue) -> bool {
+    !value.ty.nullable && value.default.is_none()
+}
+
+fn directive(name: &str, arguments: Vec<(&str, ConstValue)>) -> Directive {
+    Directive {
+        name: name.to_string(),
+        arguments: arguments
+            .into_iter()
+            .map(|(name, value)| (name.to_string(), value))
+            .collect(),
+    }
+}
+
+fn string(value: &str) -> ConstValue {
+    ConstValue::String(value.to_string())
+}
+
+fn graph_value(source: &Source) -> ConstValue {
+    ConstValue::Enum(Name::new(&source.graph))
+}
+
+fn builtin_directives(directives: &[Directive]) -> Vec<Directive> {
+    directives
+        .iter()
+        .filter(|d| BUILTIN_DIRECTIVES.contains(&d.name.as_str()))
+        .cloned()
+        .collect()
+}
+
+/// Usage of an enum across all subgraphs
+#[derive(Default, Clone, Copy)]
+struct EnumUsage {
+    input: bool,
+    output: bool,
+}
+
+struct Composer<'a> {
+    sources: &'a [Source],
+    enum_usage: HashMap<String, EnumUsage>,
+    errors: Vec<String>,
+    hints: Vec<String>,
+}
+
+impl<'a> Composer<'a> {
+    fn new(sources: &'a [Source]) -> Self {
+        let mut enum_usage: HashMap<String, EnumUsage> = HashMap::new();
+        for source in sources {
+            for ty in source.schema.types.values() {
+                for field in &ty.fields {
+                    enum_usage
+                        .entry(named_type(&field.ty).to_string())
+                        .or_default()
+                        .output = true;
+                    for arg in &field.args {
+                        enum_usage
+                            .entry(named_type(&arg.ty).to_string())
+                            .or_default()
+                            .input = true;
+                    }
+                }
+                for field in &ty.input_fields {
+                    enum_usage
+                        .entry(named_type(&field.ty).to_string())
+                        .or_default()
+                        .input = true;
+                }
+            }
+        }
+        Self {
+            sources,
+            enum_usage,
+            errors: Vec::new(),
+            hints: Vec::new(),
+        }
+    }
+
+    fn source_index(&self, name: &str) -> Option<usize> {
+        self.sources.iter().position(|s| s.name == name)
+    }
+
+    /// Merge input values (arguments or input fields) present in several
+    /// subgraphs: the intersection, each with its most restrictive type
+    fn merge_input_values(
+        &mut self,
+        coordinate: &str,
+        what: &str,
+        lists: &[(usize, &[InputValue])],
+    ) -> Vec<InputValue> {
+        let mut names: Vec<&str> = Vec::new();
+        for (_, list) in lists {
+            for value in *list {
+                if !names.contains(&value.name.as_str()) {
+                    names.push(&value.name);
+                }
+            }
+        }
+
+        let mut merged = Vec::new();
+        for name in names {
+            let present: Vec<(usize, &InputValue)> = lists
+                .iter()
+                .filter_map(|(i, list)| list.iter().find(|v| v.name == name).map(|v| (*i, v)))
+                .collect();
+            let missing: Vec<&str> = lists
+                .iter()
+                .filter(|(i, _)| !present.iter().any(|(j, _)| i == j))
+                .map(|(i, _)| self.sources[*i].name.as_str())
+                .collect();
+            if !missing.is_empty() {
+                if let Some((i, _)) = present.iter().find(|(_, v)| is_required(v)) {
+                    self.errors.push(format!(
+                        "[REQUIRED_{}_MISSING_IN_SOME_SUBGRAPH] {} \"{}.{}\" is required in subgraph \"{}\" but missing in {}",
+                        what.to_uppercase().replace(' ', "_"),
+                        what,
+                        coordinate,
+                        name,
+                        self.sources[*i].name,
+                        missing.join(", ")
+                    ));
+                } else {
+                    self.hints.push(format!(
+                        "{} \"{}.{}\" is not in every subgraph ({} lack it) and was removed from the supergraph",
+                        what,
+                        coordinate,
+                        name,
+                        missing.join(", ")
+                    ));
+                }
+                continue;
+            }
+
+            let (_, first) = present[0];
+            let mut value = InputValue {
+                name: first.name.clone(),
+                description: present.iter().find_map(|(_, v)| v.description.clone()),
+                ty: first.ty.clone(),
+                default: first.default.clone(),
+                directives: builtin_directives(&first.directives),
+            };
+            for (i, other) in &present[1..] {
+                if !same_shape(&value.ty, &other.ty) {
+                    self.errors.push(format!(
+                        "[{}_TYPE_MISMATCH] {} \"{}.{}\" has type \"{}\" in subgraph \"{}\" but \"{}\" in subgraph \"{}\"",
+                        what.to_uppercase().replace(' ', "_"),
+                        what,
+                        coordinate,
+                        name,
+                        first.ty,
+                        self.sources[present[0].0].name,
+                        other.ty,
+                        self.sources[*i].name
+                    ));
+                    break;
+                }
+                if other.default != first.default {
+                    self.errors.push(format!(
+                        "[{}_DEFAULT_MISMATCH] {} \"{}.{}\" has inconsistent default values across subgraphs",
+                        what.to_uppercase().replace(' ', "_"),
+                        what,
+                        coordinate,
+                        name
+                    ));
+                    break;
+                }
+                value.ty = merge_type(&value.ty, &other.ty, |a, b| a && b);
+            }
+            merged.push(value);
+        }
+        merged
+    }
+
+    /// Fields of an object or interface type defined in `defs`
+    fn merge_fields(&mut self, defs: &[(usize, &TypeDef)], out: &mut TypeDef) {
+        let mut names: Vec<&str> = Vec::new();
+        for (_, ty) in defs {
+            for field in &ty.fields {
+                if !names.contains(&field.name.as_str()) {
+                    names.push(&field.name);
+                }
+            }
+        }
+
+        for name in names {
+            let entries: Vec<(usize, &TypeDef, &Field)> = defs
+                .iter()
+                .filter_map(|(i, ty)| ty.field(name).map(|f| (*i, *ty, f)))
+                .collect();
+            if let Some(field) = self.merge_field(&out.name, out.kind, defs.len(), &entries) {
+                out.fields.push(field);
+            }
+        }
+    }
+
+    fn merge_field(
+        &mut self,
+        type_name: &str,
+        kind: Kind,
+        type_graphs: usize,
+        entries: &[(usize, &TypeDef, &Field)],
+    ) -> Option<Field> {
+        let coordinate = format!("{}.{}", type_name, entries[0].2.name);
+        let errors_before = self.errors.len();
+
+        // Type: the least restrictive across subgraphs
+        let mut ty = entries[0].2.ty.clone();
+        for (_, _, field) in &entries[1..] {
+            if !same_shape(&ty, &field.ty) {
+                let types: Vec<String> = entries
+                    .iter()
+                    .map(|(i, _, f)| format!("\"{}\" in \"{}\"", f.ty, self.sources[*i].name))
+                    .collect();
+                self.errors.push(format!(
+                    "[FIELD_TYPE_MISMATCH] field \"{}\" has incompatible types: {}",
+                    coordinate,
+                    types.join(", ")
+                ));
+                break;
+            }
+            ty = merge_type(&ty, &field.ty, |a, b| a || b);
+        }
+
+        // @override
+        let overrides: Vec<(usize, &str)> = entries
+            .iter()
+            .filter_map(|(i, _, f)| {
+                text_directive(&f.directives, OVERRIDE, "from").map(|from| (*i, from))
+            })
+            .collect();
+        for (i, from) in &overrides {
+            let source = &self.sources[*i].name;
+            let entry = entries.iter().find(|(j, _, _)| j == i).unwrap();
+            if from == source {
+                self.errors.push(format!(
+                    "[OVERRIDE_FROM_SELF_ERROR] field \"{}\" in subgraph \"{}\" overrides itself",
+                    coordinate, source
+                ));
+            } else if self.source_index(from).is_none() {
+                self.errors.push(format!(
+                    "[OVERRIDE_SOURCE_NOT_FOUND] field \"{}\" in subgraph \"{}\" overrides unknown subgraph \"{}\"",
+                    coordinate, source, from
+                ));
+            } else {
+                match entries.iter().find(|(j, _, _)| self.sources[*j].name == *from) {
+                    None => self.hints.push(format!(
+                        "@override on \"{}\" in subgraph \"{}\" has no effect: \"{}\" does not define the field",
+                        coordinate, source, from
+                    )),
+                    Some((_, _, f)) if has_directive(&f.directives, OVERRIDE) => {
+                        self.errors.push(format!(
+                            "[OVERRIDE_SOURCE_HAS_OVERRIDE] field \"{}\" in subgraph \"{}\" overrides \"{}\", which itself uses @override",
+                            coordinate, source, from
+                        ))
+                    }
+                    Some(_) => {}
+                }
+            }
+            if is_external(entry.1, entry.2) {
+                self.errors.push(format!(
+                    "[OVERRIDE_COLLISION_WITH_ANOTHER_DIRECTIVE] field \"{}\" in subgraph \"{}\" is both @override and @external",
+                    coordinate, source
+                ));
+            }
+        }
+        let overridden = |i: usize| {
+            overrides
+                .iter()
+                .any(|(j, from)| *j != i && self.sources[i].name == *from)
+        };
+
+        // Shareability
+        let resolving: Vec<&(usize, &TypeDef, &Field)> = entries
+            .iter()
+            .filter(|(i, ty, f)| !is_external(ty, f) && !overridden(*i))
+            .collect();
+        if resolving.is_empty() {
+            self.errors.push(format!(
+                "[EXTERNAL_MISSING_ON_BASE] field \"{}\" is marked @external in every subgraph that defines it",
+                coordinate
+            ));
+        } else if kind == Kind::Object && resolving.len() > 1 {
+            let non_shareable: Vec<&str> = resolving
+                .iter()
+                .filter(|(i, ty, f)| {
+                    self.sources[*i].fed2
+                        && !has_directive(&f.directives, SHAREABLE)
+                        && ty.directives_named(SHAREABLE).next().is_none()
+                        && !key_fields(ty).contains(&f.name)
+                })
+                .map(|(i, _, _)| self.sources[*i].name.as_str())
+                .collect();
+            if !non_shareable.is_empty() {
+                let all: Vec<&str> = resolving
+                    .iter()
+                    .map(|(i, _, _)| self.sources[*i].name.as_str())
+                    .collect();
+                self.errors.push(format!(
+                    "[INVALID_FIELD_SHARING] field \"{}\" is resolved by subgraphs {} but is not @shareable in {}",
+                    coordinate,
+                    all.join(", "),
+                    non_shareable.join(", ")
+                ));
+            }
+        }
+
+        // Arguments: merged across the subgraphs that resolve the field
+        let arg_lists: Vec<(usize, &[InputValue])> = if resolving.is_empty() {
+            entries
+                .iter()
+                .map(|(i, _, f)| (*i, f.args.as_slice()))
+                .collect()
+        } else {
+            resolving
+                .iter()
+                .map(|(i, _, f)| (*i, f.args.as_slice()))
+                .collect()
+        };
+        let args = self.merge_input_values(&coordinate, "argument", &arg_lists);
+
+        if self.errors.len() > errors_before {
+            return None;
+        }
+
+        let mut directives = builtin_directives(
+            &entries
+                .iter()
+                .find(|(_, _, f)| has_directive(&f.directives, "deprecated"))
+                .unwrap_or(&entries[0])
+                .2
+                .directives,
+        );
+        let needs_join = entries.len() != type_graphs
+            || !overrides.is_empty()
+            || entries.iter().any(|(_, t, f)| {
+                is_external(t, f)
+                    || has_directive(&f.directives, REQUIRES)
+                    || has_directive(&f.directives, PROVIDES)
+                    || f.ty != ty
+            });
+        if needs_join {
+            for (i, t, f) in entries {
+                let source = &self.sources[*i];
+                if overridden(*i) {
+                    if key_fields(t).contains(&f.name) {
+                        directives.push(directive(
+                            "join__field",
+                            vec![
+                                ("graph", graph_value(source)),
+                                ("usedOverridden", ConstValue::Boolean(true)),
+                            ],
+                        ));
+                    }
+                    continue;
+                }
+                let mut arguments = vec![("graph", graph_value(source))];
+                if let Some(fields) = text_directive(&f.directives, REQUIRES, "fields") {
+                    arguments.push(("requires", string(fields)));
+                }
+                if let Some(fields) = text_directive(&f.directives, PROVIDES, "fields") {
+                    arguments.push(("provides", string(fields)));
+                }
+                if f.ty != ty {
+                    arguments.push(("type", string(&f.ty.to_string())));
+                }
+                if is_external(t, f) {
+                    arguments.push(("external", ConstValue::Boolean(true)));
+                }
+                if let Some(from) = text_directive(&f.directives, OVERRIDE, "from") {
+                    arguments.push(("override", string(from)));
+                }
+                directives.push(directive("join__field", arguments));
+            }
+        }
+
+        Some(Field {
+            name: entries[0].2.name.clone(),
+            description: entries.iter().find_map(|(_, _, f)| f.description.clone()),
+            ty,
+            args,
+            directives,
+        })
+    }
+
+    fn merge_enum(&mut self, defs: &[(usize, &TypeDef)], out: &mut TypeDef) {
+        let usage = self.enum_usage.get(&out.name).copied().unwrap_or_default();
+        let mut names: Vec<&str> = Vec::new();
+        for (_, ty) in defs {
+            for value in &ty.values {
+                if !names.contains(&value.name.as_str()) {
+                    names.push(&value.name);
+                }
+            }
+        }
+
+        for name in names {
+            let present: Vec<(usize, &EnumValue)> = defs
+                .iter()
+                .filter_map(|(i, ty)| ty.values.iter().find(|v| v.name == name).map(|v| (*i, v)))
+                .collect();
+            if present.len() != defs.len() {
+                let missing: Vec<&str> = defs
+                    .iter()
+                    .filter(|(i, _)| !present.iter().any(|(j, _)| i == j))
+                    .map(|(i, _)| self.sources[*i].name.as_str())
+                    .collect();
+                if usage.input && usage.output {
+                    self.errors.push(format!(
+                        "[ENUM_VALUE_MISMATCH] enum \"{}\" is used as both input and output, so every subgraph must define the same values; \"{}\" is missing in {}",
+                        out.name,
+                        name,
+                        missing.join(", ")
+                    ));
+                    continue;
+                }
+                if usage.input {
+                    self.hints.push(format!(
+                        "enum value \"{}.{}\" is not in every subgraph ({} lack it) and was removed from the input-only enum",
+                        out.name,
+                        name,
+                        missing.join(", ")
+                    ));
+                    continue;
+                }
+            }
+            out.values.push(EnumValue {
+                name: name.to_string(),
+                description: present.iter().find_map(|(_, v)| v.description.clone()),
+                directives: builtin_directives(&present[0].1.directives)
+                    .into_iter()
+                    .chain(present.iter().map(|(i, _)| {
+                        directive(
+                            "join__enumValue",
+                            vec![("graph", graph_value(&self.sources[*i]))],
+                        )
+                    }))
+                    .collect(),
+            });
+        }
+        if out.values.is_empty() {
+            self.errors.push(format!(
+                "[EMPTY_MERGED_ENUM_TYPE] enum \"{}\" has no value defined in every subgraph",
+                out.name
+            ));
+        }
+    }
+
+    fn merge_type(&mut self, name: &str, defs: &[(usize, &TypeDef)]) -> Option<TypeDef> {
+        let kind = defs[0].1.kind;
+        if defs.iter().any(|(_, ty)| ty.kind != kind) {
+            let kinds: Vec<String> = defs
+                .iter()
+                .map(|(i, ty)| format!("{} in \"{}\"", ty.kind.as_str(), self.sources[*i].name))
+                .collect();
+            self.errors.push(format!(
+                "[TYPE_KIND_MISMATCH] type \"{}\" is defined as {}",
+                name,
+                kinds.join(", ")
+            ));
+            return None;
+        }
+
+        let mut out = TypeDef::new(name, kind);
+        out.description = defs.iter().find_map(|(_, ty)| ty.description.clone());
+        for (i, ty) in defs {
+            let source = &self.sources[*i];
+            let extension = ty.extension || ty.directives_named(EXTENDS).next().is_some();
+            let mut keys: Vec<&Directive> = ty.directives_named(KEY).collect();
+            if !matches!(kind, Kind::Object | Kind::Interface) {
+                keys.clear();
+            }
+            let mut join = |key: Option<&Directive>| {
+                let mut arguments = vec![("graph", graph_value(source))];
+                if let Some(key) = key {
+                    arguments.push(("key", string(key.text_argument("fields").unwrap_or(""))));
+                }
+                if extension {
+                    arguments.push(("extension", ConstValue::Boolean(true)));
+                }
+                if key.and_then(|k| k.argument("resolvable")) == Some(&ConstValue::Boolean(false)) {
+                    arguments.push(("resolvable", ConstValue::Boolean(false)));
+                }
+                out.directives.push(directive("join__type", arguments));
+            };
+            if keys.is_empty() {
+                join(None);
+            }
+            for key in keys {
+                join(Some(key));
+            }
+        }
+
+        match kind {
+            Kind::Scalar => {
+                out.directives
+                    .extend(builtin_directives(&defs[0].1.directives));
+            }
+            Kind::Object | Kind::Interface => {
+                for (i, ty) in defs {
+                    for interface in &ty.implements {
+                        if !out.implements.contains(interface) {
+                            out.implements.push(interface.clone());
+                        }
+                        out.directives.push(directive(
+                            "join__implements",
+                            vec![
+                                ("graph", graph_value(&self.sources[*i])),
+                                ("interface", string(interface)),
+                            ],
+                        ));
+                    }
+                }
+                self.merge_fields(defs, &mut out);
+            }
+            Kind::Union => {
+                for (i, ty) in defs {
+                    for member in &ty.members {
+                        if !out.members.contains(member) {
+                            out.members.push(member.clone());
+                        }
+                        out.directives.push(directive(
+                            "join__unionMember",
+                            vec![
+                                ("graph", graph_value(&self.sources[*i])),
+                                ("member", string(member)),
+                            ],
+                        ));
+                    }
+                }
+            }
+            Kind::Enum => self.merge_enum(defs, &mut out),
+            Kind::InputObject => {
+                let lists: Vec<(usize, &[InputValue])> = defs
+                    .iter()
+                    .map(|(i, ty)| (*i, ty.input_fields.as_slice()))
+                    .collect();
+                out.input_fields = self.merge_input_values(name, "input field", &lists);
+                if out.input_fields.is_empty() {
+                    self.errors.push(format!(
+                        "[EMPTY_MERGED_INPUT_TYPE] input \"{}\" has no field defined in every subgraph",
+                        name
+                    ));
+                }
+            }
+        }
+        Some(out)
+    }
+}
+
+// =============================================================================
+// Composition
+// =============================================================================
+
+/// The `join__Graph` enum, one value per subgraph
+fn join_graph_enum(sources: &[Source]) -> TypeDef {
+    let mut graph = TypeDef::new("join__Graph", Kind::Enum);
+    graph.values = sources
+        .iter()
+        .map(|source| EnumValue {
+            name: source.graph.clone(),
+            description: None,
+            directives: vec![directive(
+                "join__graph",
+                vec![("name", string(&source.name)), ("url", string(&source.url))],
+            )],
+        })
+        .collect();
+    graph
+}
+
+fn link_purpose_enum() -> TypeDef {
+    let mut purpose = TypeDef::new("link__Purpose", Kind::Enum);
+    for (name, description) in [
+        (
+            "SECURITY",
+            "`SECURITY` features provide metadata necessary to securely resolve fields.",
+        ),
+        (
+            "EXECUTION",
+            "`EXECUTION` features provide metadata necessary for operation execution.",
+        ),
+    ] {
+        purpose.values.push(EnumValue {
+            name: name.to_string(),
+            description: Some(description.to_string()),
+            directives: Vec::new(),
+        });
+    }
+    purpose
+}
+
+/// Compose subgraphs into a supergraph. Every validation error is
+/// collected and reported together.
+pub fn compose(subgraphs: &[Subgraph]) -> Result<Composition> {
+    if subgraphs.is_empty() {
+        bail!("No subgraphs to compose");
+    }
+    let mut subgraphs: Vec<&Subgraph> = subgraphs.iter().collect();
+    subgraphs.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let mut errors = Vec::new();
+    let sources: Vec<Source> = subgraphs
+        .iter()
+        .filter_map(|subgraph| normalize(subgraph, &mut errors))
+        .collect();
+    let mut graphs: HashMap<&str, &str> = HashMap::new();
+    for source in &sources {
+        if let Some(other) = graphs.insert(&source.graph, &source.name) {
+            errors.push(format!(
+                "[DUPLICATE_GRAPH_NAME] subgraphs \"{}\" and \"{}\" map to the same graph name {}",
+                other, source.name, source.graph
+            ));
+        }
+        check_field_sets(source, &mut errors);
+    }
+    if !errors.is_empty() {
+        bail!(
+            "Supergraph composition failed with {} error(s):\n  {}",
+            errors.len(),
+            errors.join("\n  ")
+        );
+    }
+
+    let mut by_type: BTreeMap<&str, Vec<(usize, &TypeDef)>> = BTreeMap::new();
+    for (i, source) in sources.iter().enumerate() {
+        for (name, ty) in &source.schema.types {
+            by_type.entry(name).or_default().push((i, ty));
+        }
+    }
+
+    let mut composer = Composer::new(&sources);
+    let mut supergraph = Schema {
+        schema_directives: vec![
+            directive(
+                "link",
+                vec![("url", string("https://specs.apollo.dev/link/v1.0"))],
+            ),
+            directive(
+                "link",
+                vec![
+                    ("url", string("https://specs.apollo.dev/join/v0.3")),
+                    ("for", ConstValue::Enum(Name::new("EXECUTION"))),
+                ],
+            ),
+        ],
+        directive_definitions: JOIN_DIRECTIVES.iter().map(|d| d.to_string()).collect(),
+        query: Some("Query".to_string()),
+        ..Default::default()
+    };
+    for (name, defs) in &by_type {
+        if let Some(ty) = composer.merge_type(name, defs) {
+            supergraph.types.insert(name.to_string(), ty);
+        }
+    }
+    if !supergraph.types.contains_key("Query") {
+        composer
+            .errors
+            .push("[NO_QUERIES] no subgraph defines a Query type".to_string());
+    }
+    if !composer.errors.is_empty() {
+        bail!(
+            "Supergraph composition failed with {} error(s):\n  {}",
+            composer.errors.len(),
+            composer.errors.join("\n  ")
+        );
+    }
+
+    supergraph.mutation = supergraph
+        .types
+        .contains_key("Mutation")
+        .then(|| "Mutation".to_string());
+    supergraph.subscription = supergraph
+        .types
+        .contains_key("Subscription")
+        .then(|| "Subscription".to_string());
+    for ty in [
+        TypeDef::new("join__FieldSet", Kind::Scalar),
+        join_graph_enum(&sources),
+        TypeDef::new("link__Import", Kind::Scalar),
+        link_purpose_enum(),
+    ] {
+        supergraph.types.insert(ty.name.clone(), ty);
+    }
+
+    Ok(Composition {
+        supergraph: supergraph.to_sdl(),
+        hints: composer.hints,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn subgraph(name: &str, sdl: &str) -> Subgraph {
+        Subgraph {
+            name: name.to_string(),
+            url: format!("http://{}:8080/graphql", name),
+            sdl: sdl.to_string(),
+        }
+    }
+
+    const PRODUCTS: &str = r#"
+extend schema @link(url: "https://specs.apollo.dev/federation/v2.3", import: ["@key", {name: "@shareable", as: "@share"}])
+
+type Product @key(fields: "id") {
+  id: ID!
+  name: String!
+  price(currency: Currency = USD, rounded: Boolean): Float
+  weight: Float
+}
+
+enum Currency { USD EUR }
+
+type Query {
+  product(id: ID!): Product
+  _service: _Service!
+}
+
+scalar _Any
+type _Service { sdl: String }
+"#;
+
+    const REVIEWS: &str = r#"
+extend schema @link(url: "https://specs.apollo.dev/federation/v2.3", import: ["@key", "@shareable", "@override", "@requires", "@external"])
+
+type Product @key(fields: "id") {
+  id: ID!
+  name: String @shareable
+  price(currency: Currency = USD): Float! @shareable
+  weight: Float @external
+  shippingEstimate: Float @requires(fields: "weight")
+  reviews: [Review!]!
+}
+
+type Review {
+  body: String! @override(from: "products")
+  rating: Int @deprecated(reason: "use stars")
+}
+
+enum Currency { USD GBP }
+
+type Query {
+  topReviews: [Review!]!
+}
+"#;
+
+    #[test]
+    fn test_compose_two_subgraphs() {
+        let products = PRODUCTS.replace("name: String!", "name: String! @share");
+        let products = products.replace(
+            "price(currency: Currency = USD, rounded: Boolean): Float",
+            "price(currency: Currency = USD, rounded: Boolean): Float @share",
+        );
+        let products = products.replace(
+            "enum Currency",
+            "type Review @key(fields: \"body\") { body: String! }\n\nenum Currency",
+        );
+        let composition = compose(&[
+            subgraph("reviews", REVIEWS),
+            subgraph("products", &products),
+        ])
+        .unwrap();
+        let supergraph = Schema::parse(&composition.supergraph).unwrap();
+
+        let graph = &supergraph.types["join__Graph"];
+        let names: Vec<&str> = graph.values.iter().map(|v| v.name.as_str()).collect();
+        assert_eq!(names, ["PRODUCTS", "REVIEWS"]);
+
+        let product = &supergraph.types["Product"];
+        assert_eq!(product.directives_named("join__type").count(), 2);
+        // Nullable wins for outputs, the non-shared argument is dropped
+        let price = product.field("price").unwrap();
+        assert_eq!(price.ty.to_string(), "Float");
+        assert_eq!(price.args.len(), 1);
+        assert!(composition
+            .hints
+            .iter()
+            .any(|h| h.contains("Product.price.rounded")));
+        let estimate = product.field("shippingEstimate").unwrap();
+        let join = estimate
+            .directives
+            .iter()
+            .find(|d| d.name == "join__field")
+            .unwrap();
+        assert_eq!(join.text_argument("requires"), Some("weight"));
+
+        // Currency is input-only: intersection
+        let currency: Vec<&str> = supergraph.types["Currency"]
+            .values
+            .iter()
+            .map(|v| v.name.as_str())
+            .collect();
+        assert_eq!(currency, ["USD"]);
+
+        // The overriding graph takes the field; the key in the overridden
+        // graph keeps it as usedOverridden
+        let body = supergraph.types["Review"].field("body").unwrap();
+        let joins: Vec<String> = body
+            .directives
+            .iter()
+            .filter(|d| d.name == "join__field")
+            .map(|d| d.to_string())
+            .collect();
+        assert_eq!(
+            joins,
+            [
+                "@join__field(graph: PRODUCTS, usedOverridden: true)",
+                "@join__field(graph: REVIEWS, override: \"products\")"
+            ]
+        );
+        let rating = supergraph.types["Review"].field("rating").unwrap();
+        assert!(rating.directives.iter().any(|d| d.name == "deprecated"));
+
+        // Round trip through the subgraph view of the supergraph
+        let reviews = supergraph.subgraph("reviews").unwrap();
+        let fields: Vec<&str> = reviews.types["Product"]
+            .fields
+            .iter()
+            .map(|f| f.name.as_str())
+            .collect();
+        assert_eq!(
+            fields,
+            [
+                "id",
+                "name",
+                "price",
+                "weight",
+                "shippingEstimate",
+                "reviews"
+            ]
+        );
+        assert!(reviews.types["Query"].field("product").is_none());
+        assert!(!supergraph.types["Query"]
+            .fields
+            .iter()
+            .any(|f| f.name == "_service"));
+
+        // Deterministic regardless of input order
+        let again = compose(&[
+            subgraph("products", &products),
+            subgraph("reviews", REVIEWS),
+        ])
+        .unwrap();
+        assert_eq!(again.supergraph, composition.supergraph);
+    }
+
+    #[test]
+    fn test_compose_reports_shareable_conflict() {
+        let err = compose(&[subgraph("reviews", REVIEWS), subgraph("products", PRODUCTS)])
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("[INVALID_FIELD_SHARING] field \"Product.name\""),
+            "{}",
+            err
+        );
+        assert!(err.contains("not @shareable in products"), "{}", err);
+    }
+
+    #[test]
+    fn test_compose_validates_keys_and_overrides() {
+        let bad = r#"
+extend schema @link(url: "https://specs.apollo.dev/federation/v2.3", import: ["@key", "@override", "@inaccessible"])
+
+type Item @key(fields: "sku") {
+  id: ID!
+  name: String @override(from: "invetory")
+  secret: String @inaccessible
+}
+
+type Query { item: Item }
+"#;
+        let err = compose(&[subgraph("catalog", bad)])
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("[UNSUPPORTED_DIRECTIVE]"), "{}", err);
+        assert!(err.contains("[INVALID_KEY]"), "{}", err);
+        assert!(err.contains("\"Item.sku\" does not exist"), "{}", err);
+
+        let bad = bad
+            .replace("\"sku\"", "\"id\"")
+            .replace(", \"@inaccessible\"", "")
+            .replace(" @inaccessible", "");
+        let err = compose(&[subgraph("catalog", &bad)])
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("[OVERRIDE_SOURCE_NOT_FOUND]"), "{}", err);
+
+        let err = compose(&[subgraph("catalog", &bad.replace("invetory", "catalog"))])
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("[OVERRIDE_FROM_SELF_ERROR]"), "{}", err);
+    }
+
+    #[test]
+    fn test_federation_1_fields_are_shareable() {
+        let a = "type Money { amount: Float currency: String }\ntype Query { price: Money }";
+        let b = "type Money { amount: Float currency: String }\ntype Query { tax: Money }";
+        let composition = compose(&[subgraph("a", a), subgraph("b", b)]).unwrap();
+        let supergraph = Schema::parse(&composition.supergraph).unwrap();
+        assert_eq!(
+            supergraph.types["Money"]
+                .directives_named("join__type")
+                .count(),
+            2
+        );
+        assert_eq!(graph_name("order-service"), "ORDER_SERVICE");
+    }
+}
@@ -14,6 +14,7 @@ use std::path::{Path, PathBuf};
 use tokio::fs;
 use tokio::process::Command;
 
+use crate::config::Composer;
 use crate::infrastructure::kubectl::kubectl_command_async;
 
 /// Resolve the `rover-fhs` binary path via `ROVER_FHS_BIN`, falling back to
@@ -77,7 +78,7 @@ pub struct SupergraphMetadata {
     /// Federation version used for composition
     pub federation_version: String,
 
-    /// Rover version used for composition
+    /// Composer used: the Rover version, or `native (forge <version>)`
     pub rover_version: String,
 }
 
@@ -128,6 +129,7 @@ impl SupergraphMetadata {
         federation_dir: &Path,
         triggering_service: String,
         git_commit: String,
+        composer: Composer,
     ) -> Result<Self> {
         let supergraph_path = federation_dir.join("supergraph.graphql");
         let subgraphs_dir = federation_dir.join("subgraphs");
@@ -167,9 +169,12 @@ impl SupergraphMetadata {
         }
 
         // Get Rover version
-        let rover_version = get_rover_version()
-            .await
-            .unwrap_or_else(|_| "unknown".to_string());
+        let rover_version = match composer {
+            Composer::Native => format!("native (forge {})", env!("CARGO_PKG_VERSION")),
+            Composer::Rover => get_rover_version()
+                .await
+                .unwrap_or_else(|_| "unknown".to_string()),
+        };
 
         Ok(Self {
             supergraph_hash,
@@ -395,7 +400,10 @@ pub struct CheckResult {
 }
 
 /// Run pre-composition validation checks
-pub async fn run_pre_composition_checks(subgraphs_dir: &Path) -> Result<PreCompositionCheck> {
+pub async fn run_pre_composition_checks(
+    subgraphs_dir: &Path,
+    composer: Composer,
+) -> Result<PreCompositionCheck> {
     let mut checks = Vec::new();
 
     // Check 1: Subgraphs directory exists
@@ -467,19 +475,21 @@ pub async fn run_pre_composition_checks(subgraphs_dir: &Path) -> Result<PreCompo
         });
     }
 
-    // Check 4: Rover CLI is available
-    let rover = rover_fhs_bin();
-    let rover_available = Command::new(&rover).arg("--version").output().await.is_ok();
+    // Check 4: Rover CLI is available (native composition needs no tooling)
+    if composer == Composer::Rover {
+        let rover = rover_fhs_bin();
+        let rover_available = Command::new(&rover).arg("--version").output().await.is_ok();
 
-    checks.push(CheckResult {
-        name: "Rover CLI available".to_string(),
-        passed: rover_available,
-        message: if rover_available {
-            "✓ Rover CLI is installed".to_string()
-        } else {
-            "✗ Rover CLI not found (rover-fhs command)".to_string()
-        },
-    });
+        checks.push(CheckResult {
+            name: "Rover CLI available".to_string(),
+            passed: rover_available,
+            message: if rover_available {
+                "✓ Rover CLI is installed".to_string()
+            } else {
+                "✗ Rover CLI not found (rover-fhs command)".to_string()
+            },
+        });
+    }
 
     let all_passed = checks.iter().all(|c| c.passed);
 
@@ -8,10 +8,25 @@
 //! - deployment.tests.e2e: E2E browser tests (playwright)
 //!
 //! Each test type has its own `enabled` flag for granular control.
+//!
+//! With `--junit <path>`, per-test results are collected and written as one
+//! JUnit XML file. Configured web suite commands receive a scratch JUnit path
+//! in `FORGE_JUNIT_OUTPUT` (and `PLAYWRIGHT_JUNIT_OUTPUT_NAME`); a suite that
+//! writes nothing there is recorded as a single test case.
+//!
+//! With `--shard i/n`, only this worker's share runs: Rust integration test
+//! targets, vitest files and playwright specs are partitioned by their
+//! historical durations (see [`super::test_sharding`]). Configured web suite
+//! commands receive their files in `FORGE_SHARD_FILES`. The duration history
+//! is only updated when `--durations` is given explicitly.
+//!
+//! Tests listed in the product's `quarantine.yaml` are non-blocking (see
+//! [`super::test_quarantine`]); a failed suite retry that later passes
+//! counts toward the tests' retries, which proposes them for quarantine.
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Stdio;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use anyhow::{bail, Context, Result};
 use colored::Colorize;
@@ -21,6 +36,9 @@ use tokio::process::Command;
 use tokio::time::timeout;
 use tracing::info;
 
+use super::test_quarantine::{self, Quarantine};
+use super::test_reports::{TestReport, JUNIT_OUTPUT_ENV};
+use super::test_sharding::{self, Shard, ShardPlan, TestDurations, SHARD_FILES_ENV};
 use crate::retry::RetryPolicy;
 
 /// The typed exponential-backoff policy for [`run_test_suite`]'s
@@ -208,15 +226,29 @@ fn load_web_tests_config(service: &str, service_dir: &str) -> Result<WebTestsCon
 }
 
 /// Execute test command
+#[allow(clippy::too_many_arguments)]
 pub async fn execute(
     service: &str,
     service_dir: &str,
     _repo_root: &str,
     service_type: &str,
     test_type: &str,
+    junit: Option<&str>,
+    shard: Option<&str>,
+    durations: Option<&str>,
 ) -> Result<()> {
     let service_type = ServiceType::from_str(service_type)?;
     let test_type = TestType::from_str(test_type)?;
+    let shard = shard.map(Shard::parse).transpose()?;
+    let service_dir_path = PathBuf::from(service_dir);
+    let product_dir =
+        crate::repo::find_product_dir(&service_dir_path, crate::repo::ProductDirLayout::Monorepo)
+            .unwrap_or(service_dir_path);
+    let durations_path = durations
+        .map(PathBuf::from)
+        .unwrap_or_else(|| test_sharding::default_durations_path(&product_dir));
+    let mut history = TestDurations::load(&durations_path)?;
+    let quarantine = Quarantine::load(&product_dir)?;
 
     println!();
     println!(
@@ -238,16 +270,121 @@ pub async fn execute(
     );
     println!();
 
-    match service_type {
-        ServiceType::Rust => run_rust_tests(service, service_dir, test_type).await,
-        ServiceType::Web => run_web_tests(service, service_dir, test_type).await,
+    let mut results = TestReport::default();
+    let collecting = junit.is_some() || shard.is_some() || !quarantine.is_empty();
+    let collect = collecting.then_some(&mut results);
+    let outcome = match service_type {
+        ServiceType::Rust => {
+            run_rust_tests(
+                service,
+                service_dir,
+                test_type,
+                shard,
+                &history,
+                &quarantine,
+                collect,
+            )
+            .await
+        }
+        ServiceType::Web => {
+            run_web_tests(
+                service,
+                service_dir,
+                test_type,
+                shard,
+                &history,
+                &quarantine,
+                collect,
+            )
+            .await
+        }
+    };
+
+    let today = test_quarantine::today();
+    if collecting {
+        quarantine.apply(&mut results, today);
+        results.print_summary();
+        if let Some(path) = junit {
+            results.write_junit(Path::new(path), service)?;
+        }
+        if durations.is_some() {
+            history.record(&results);
+            history.save(&durations_path)?;
+        }
+    }
+    outcome?;
+    quarantine.check_expiry(today)
+}
+
+/// What a suite runs on this shard: `None` when none of its work landed
+/// here, otherwise its shard files (empty when the suite runs whole).
+fn shard_selection(plan: Option<&ShardPlan>, suite: &str) -> Option<Vec<String>> {
+    let Some(plan) = plan else {
+        return Some(Vec::new());
+    };
+    if plan.contains(suite) {
+        return Some(Vec::new());
     }
+    let files = plan.files_under(suite);
+    (!files.is_empty()).then_some(files)
+}
+
+fn print_not_in_shard(label: &str, shard: Option<Shard>) {
+    println!(
+        "  {} {}: {} (not in shard {})",
+        "⏭️ ".bright_yellow(),
+        label,
+        "skipped".dimmed(),
+        shard.map(|s| s.to_string()).unwrap_or_default()
+    );
 }
 
 /// Run Rust tests
-async fn run_rust_tests(service: &str, service_dir: &str, test_type: TestType) -> Result<()> {
-    let run_unit = test_type == TestType::Unit || test_type == TestType::All;
-    let run_integration = test_type == TestType::Integration || test_type == TestType::All;
+///
+/// With `results`, cargo's output is captured and parsed into per-test results.
+/// With `shard`, the unit tests and each integration test target are
+/// separate shard units.
+async fn run_rust_tests(
+    service: &str,
+    service_dir: &str,
+    test_type: TestType,
+    shard: Option<Shard>,
+    history: &TestDurations,
+    quarantine: &Quarantine,
+    mut results: Option<&mut TestReport>,
+) -> Result<()> {
+    let mut run_unit = test_type == TestType::Unit || test_type == TestType::All;
+    let mut run_integration = test_type == TestType::Integration || test_type == TestType::All;
+
+    // `None` runs every integration target (`--test *`)
+    let mut integration_targets: Option<Vec<String>> = None;
+    if let Some(shard) = shard {
+        let mut units = Vec::new();
+        if run_unit {
+            units.push("unit".to_string());
+        }
+        if run_integration {
+            units.extend(
+                test_sharding::find_rust_test_targets(Path::new(service_dir))
+                    .into_iter()
+                    .map(|target| format!("integration/{}", target)),
+            );
+        }
+        let plan = ShardPlan::new(shard, &units, history);
+        plan.print_summary();
+        println!();
+
+        if run_unit && !plan.contains("unit") {
+            print_not_in_shard("Rust unit tests", Some(shard));
+            run_unit = false;
+        }
+        let targets = plan.files_under("integration");
+        if run_integration && targets.is_empty() {
+            print_not_in_shard("Rust integration tests", Some(shard));
+            run_integration = false;
+        }
+        integration_targets = Some(targets);
+    }
 
     let cargo = crate::repo::get_tool_path("CARGO", "cargo");
 
@@ -258,11 +395,30 @@ async fn run_rust_tests(service: &str, service_dir: &str, test_type: TestType) -
             service.bright_cyan()
         );
 
-        let mut cmd = Command::new(&cargo);
-        cmd.args(["test", "--lib", "--bins"])
-            .current_dir(service_dir);
-        crate::retry::run_inherited_status(cmd, "cargo test --lib --bins")
-            .await
+        let cargo_test = || {
+            let mut cmd = Command::new(&cargo);
+            cmd.args(["test", "--lib", "--bins"])
+                .current_dir(service_dir);
+            cmd
+        };
+        let mut cmd = cargo_test();
+        cmd.arg("--").args(quarantine.libtest_skip_args("unit"));
+        let outcome = match results.as_deref_mut() {
+            Some(results) => {
+                results.record_output(cmd.output().await, "unit", "cargo test --lib --bins")
+            }
+            None => crate::retry::run_inherited_status(cmd, "cargo test --lib --bins").await,
+        };
+        let quarantined = run_quarantined_cargo_tests(
+            cargo_test(),
+            "unit",
+            "cargo test --lib --bins",
+            quarantine,
+            results.as_deref_mut(),
+        )
+        .await;
+        outcome
+            .and(quarantined)
             .context("Failed to run cargo test")?;
 
         println!("  {} Rust unit tests passed", "✅".bright_green());
@@ -275,10 +431,40 @@ async fn run_rust_tests(service: &str, service_dir: &str, test_type: TestType) -
             service.bright_cyan()
         );
 
-        let mut cmd = Command::new(&cargo);
-        cmd.args(["test", "--test", "*"]).current_dir(service_dir);
-        crate::retry::run_inherited_status(cmd, "cargo test --test *")
-            .await
+        let cargo_test = || {
+            let mut cmd = Command::new(&cargo);
+            cmd.arg("test").current_dir(service_dir);
+            match &integration_targets {
+                Some(targets) => {
+                    for target in targets {
+                        cmd.args(["--test", target]);
+                    }
+                }
+                None => {
+                    cmd.args(["--test", "*"]);
+                }
+            }
+            cmd
+        };
+        let mut cmd = cargo_test();
+        cmd.arg("--")
+            .args(quarantine.libtest_skip_args("integration"));
+        let outcome = match results.as_deref_mut() {
+            Some(results) => {
+                results.record_output(cmd.output().await, "integration", "cargo test --test *")
+            }
+            None => crate::retry::run_inherited_status(cmd, "cargo test --test *").await,
+        };
+        let quarantined = run_quarantined_cargo_tests(
+            cargo_test(),
+            "integration",
+            "cargo test --test *",
+            quarantine,
+            results,
+        )
+        .await;
+        outcome
+            .and(quarantined)
             .context("Failed to run cargo integration tests")?;
 
         println!("  {} Rust integration tests passed", "✅".bright_green());
@@ -288,14 +474,82 @@ async fn run_rust_tests(service: &str, service_dir: &str, test_type: TestType) -
     Ok(())
 }
 
+/// Run the quarantined tests of a cargo test step on their own, with
+/// `--no-fail-fast`, after the blocking run skipped them. Their failures do
+/// not fail the step. `cmd` is the step's `cargo test` command up to `--`.
+async fn run_quarantined_cargo_tests(
+    mut cmd: Command,
+    suite: &str,
+    op: &str,
+    quarantine: &Quarantine,
+    results: Option<&mut TestReport>,
+) -> Result<()> {
+    let names = quarantine.libtest_names(suite);
+    if names.is_empty() {
+        return Ok(());
+    }
+    println!(
+        "  {} Running {} quarantined test(s) (non-blocking)...",
+        "🧊".bright_cyan(),
+        names.len()
+    );
+    cmd.args(["--no-fail-fast", "--"]).args(&names);
+
+    let mut scratch = TestReport::default();
+    let results = results.unwrap_or(&mut scratch);
+    let before = results.cases.len();
+    let outcome = results.record_tests(cmd.output().await, suite, &format!("{} (quarantined)", op));
+    quarantine.settle_quarantined_run(outcome, &results.cases[before..])
+}
+
 /// Run Web tests based on deploy.yaml configuration
-async fn run_web_tests(service: &str, service_dir: &str, test_type: TestType) -> Result<()> {
+///
+/// With `shard`, each suite's test files (vitest files, playwright specs) are
+/// shard units; a suite whose files cannot be found is one unit.
+async fn run_web_tests(
+    service: &str,
+    service_dir: &str,
+    test_type: TestType,
+    shard: Option<Shard>,
+    history: &TestDurations,
+    quarantine: &Quarantine,
+    mut results: Option<&mut TestReport>,
+) -> Result<()> {
     // Load configuration from deploy.yaml
     let config = load_web_tests_config(service, service_dir)?;
 
     let run_unit = test_type == TestType::Unit || test_type == TestType::All;
     let run_integration = test_type == TestType::Integration || test_type == TestType::All;
 
+    let plan = shard.map(|shard| {
+        let suites = [
+            ("unit", &config.unit, run_unit),
+            ("api_integration", &config.api_integration, run_integration),
+            ("e2e", &config.e2e, run_integration),
+        ];
+        let mut units = Vec::new();
+        for (name, suite, selected) in suites {
+            if !selected || !suite.enabled || suite.command.is_empty() {
+                continue;
+            }
+            let dir = suite_working_dir(service_dir, suite);
+            let files = if name == "e2e" {
+                test_sharding::find_playwright_specs(&dir)
+            } else {
+                test_sharding::find_vitest_files(&dir)
+            };
+            if files.is_empty() {
+                units.push(name.to_string());
+            } else {
+                units.extend(files.into_iter().map(|f| format!("{}/{}", name, f)));
+            }
+        }
+        let plan = ShardPlan::new(shard, &units, history);
+        plan.print_summary();
+        println!();
+        plan
+    });
+
     let mut tests_run = 0;
     let mut tests_skipped = 0;
 
@@ -330,8 +584,25 @@ async fn run_web_tests(service: &str, service_dir: &str, test_type: TestType) ->
     // Unit tests
     if run_unit {
         if config.unit.enabled && !config.unit.command.is_empty() {
-            run_test_suite(service, service_dir, "unit", &config.unit).await?;
-            tests_run += 1;
+            match shard_selection(plan.as_ref(), "unit") {
+                Some(files) => {
+                    run_test_suite(
+                        service,
+                        service_dir,
+                        "unit",
+                        &config.unit,
+                        &files,
+                        quarantine,
+                        results.as_deref_mut(),
+                    )
+                    .await?;
+                    tests_run += 1;
+                }
+                None => {
+                    print_not_in_shard("Unit tests", shard);
+                    tests_skipped += 1;
+                }
+            }
         } else if !config.unit.enabled {
             println!(
                 "  {} Unit tests: {} (disabled in deploy.yaml)",
@@ -345,14 +616,25 @@ async fn run_web_tests(service: &str, service_dir: &str, test_type: TestType) ->
     // API Integration tests
     if run_integration {
         if config.api_integration.enabled && !config.api_integration.command.is_empty() {
-            run_test_suite(
-                service,
-                service_dir,
-                "api_integration",
-                &config.api_integration,
-            )
-            .await?;
-            tests_run += 1;
+            match shard_selection(plan.as_ref(), "api_integration") {
+                Some(files) => {
+                    run_test_suite(
+                        service,
+                        service_dir,
+                        "api_integration",
+                        &config.api_integration,
+                        &files,
+                        quarantine,
+                        results.as_deref_mut(),
+                    )
+                    .await?;
+                    tests_run += 1;
+                }
+                None => {
+                    print_not_in_shard("API integration tests", shard);
+                    tests_skipped += 1;
+                }
+            }
         } else if !config.api_integration.enabled {
             println!(
                 "  {} API integration tests: {} (disabled in deploy.yaml)",
@@ -364,8 +646,25 @@ async fn run_web_tests(service: &str, service_dir: &str, test_type: TestType) ->
 
         // E2E tests
         if config.e2e.enabled && !config.e2e.command.is_empty() {
-            run_test_suite(service, service_dir, "e2e", &config.e2e).await?;
-            tests_run += 1;
+            match shard_selection(plan.as_ref(), "e2e") {
+                Some(files) => {
+                    run_test_suite(
+                        service,
+                        service_dir,
+                        "e2e",
+                        &config.e2e,
+                        &files,
+                        quarantine,
+                        results,
+                    )
+                    .await?;
+                    tests_run += 1;
+                }
+                None => {
+                    print_not_in_shard("E2E tests", shard);
+                    tests_skipped += 1;
+                }
+            }
         } else if !config.e2e.enabled {
             println!(
                 "  {} E2E tests: {} (disabled in deploy.yaml)",
@@ -412,11 +711,88 @@ async fn run_web_tests(service: &str, service_dir: &str, test_type: TestType) ->
 }
 
 /// Run a single test suite
+///
+/// With `results`, the suite command is pointed at a scratch JUnit file via
+/// [`JUNIT_OUTPUT_ENV`]; its contents (from the last attempt) are merged
+/// into `results`, or the suite is recorded as one test case if the command
+/// wrote nothing. Non-empty `shard_files` are passed in [`SHARD_FILES_ENV`].
+/// Tests that failed in an earlier attempt count as retried, and a failure
+/// of only quarantined tests does not fail the suite.
 async fn run_test_suite(
     service: &str,
     service_dir: &str,
     suite_name: &str,
     config: &TestSuiteConfig,
+    shard_files: &[String],
+    quarantine: &Quarantine,
+    results: Option<&mut TestReport>,
+) -> Result<()> {
+    let mut failed_attempts = Vec::new();
+    let Some(results) = results else {
+        return run_test_suite_attempts(
+            service,
+            service_dir,
+            suite_name,
+            config,
+            shard_files,
+            None,
+            &mut failed_attempts,
+        )
+        .await;
+    };
+
+    let junit_dir = tempfile::Builder::new()
+        .prefix("forge-test-junit-")
+        .tempdir()
+        .context("create test suite JUnit scratch tempdir")?;
+    let junit_file = junit_dir.path().join(format!("{}.xml", suite_name));
+    let start = Instant::now();
+    let outcome = run_test_suite_attempts(
+        service,
+        service_dir,
+        suite_name,
+        config,
+        shard_files,
+        Some(&junit_file),
+        &mut failed_attempts,
+    )
+    .await;
+
+    let before = results.cases.len();
+    if !results.merge_junit_file(&junit_file, suite_name)? {
+        results.record_step(
+            suite_name,
+            &config.command,
+            outcome.is_ok(),
+            start.elapsed().as_secs_f64(),
+            outcome.as_ref().err().map(|e| format!("{:#}", e)),
+        );
+    }
+    results.add_retries(before, &failed_attempts);
+    quarantine.settle(outcome, &results.cases[before..])
+}
+
+/// Working directory of a configured test suite
+fn suite_working_dir(service_dir: &str, config: &TestSuiteConfig) -> PathBuf {
+    if config.working_dir.is_empty() || config.working_dir == "." {
+        PathBuf::from(service_dir)
+    } else {
+        PathBuf::from(service_dir).join(&config.working_dir)
+    }
+}
+
+/// Run a test suite's command with its configured timeout and retries
+///
+/// The JUnit results of each failed attempt that is retried are collected
+/// into `failed_attempts`.
+async fn run_test_suite_attempts(
+    service: &str,
+    service_dir: &str,
+    suite_name: &str,
+    config: &TestSuiteConfig,
+    shard_files: &[String],
+    junit_file: Option<&Path>,
+    failed_attempts: &mut Vec<TestReport>,
 ) -> Result<()> {
     println!();
     println!(
@@ -454,11 +830,7 @@ async fn run_test_suite(
         1
     };
 
-    let working_dir = if config.working_dir.is_empty() || config.working_dir == "." {
-        PathBuf::from(service_dir)
-    } else {
-        PathBuf::from(service_dir).join(&config.working_dir)
-    };
+    let working_dir = suite_working_dir(service_dir, config);
 
     for attempt in 1..=max_attempts {
         if attempt > 1 {
@@ -480,14 +852,20 @@ async fn run_test_suite(
         spinner.enable_steady_tick(Duration::from_millis(100));
 
         let result = timeout(test_timeout, async {
-            let output = Command::new(crate::repo::get_tool_path("SH_BIN", "sh"))
-                .arg("-c")
+            let mut cmd = Command::new(crate::repo::get_tool_path("SH_BIN", "sh"));
+            cmd.arg("-c")
                 .arg(&config.command)
                 .current_dir(&working_dir)
                 .stdout(Stdio::inherit())
-                .stderr(Stdio::inherit())
-                .status()
-                .await?;
+                .stderr(Stdio::inherit());
+            if let Some(junit_file) = junit_file {
+                cmd.env(JUNIT_OUTPUT_ENV, junit_file)
+                    .env("PLAYWRIGHT_JUNIT_OUTPUT_NAME", junit_file);
+            }
+            if !shard_files.is_empty() {
+                cmd.env(SHARD_FILES_ENV, shard_files.join(" "));
+            }
+            let output = cmd.status().await?;
 
             Ok::<_, anyhow::Error>(output.success())
         })
@@ -495,6 +873,19 @@ async fn run_test_suite(
 
         spinner.finish_and_clear();
 
+        if let Some(junit_file) = junit_file {
+            if !matches!(result, Ok(Ok(true))) && attempt < max_attempts {
+                let mut earlier = TestReport::default();
+                if earlier
+                    .merge_junit_file(junit_file, suite_name)
+                    .unwrap_or(false)
+                {
+                    failed_attempts.push(earlier);
+                }
+                let _ = std::fs::remove_file(junit_file);
+            }
+        }
+
         match result {
             Ok(Ok(true)) => {
                 println!("  {} {} tests passed", "✅".bright_green(), suite_name);
@@ -553,6 +944,36 @@ fn print_success_summary() {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_shard_selection() {
+        // Unsharded runs execute every suite whole
+        assert_eq!(shard_selection(None, "e2e"), Some(vec![]));
+
+        let mut history = TestDurations::default();
+        history
+            .suites
+            .insert("e2e/checkout.spec.ts".to_string(), 60.0);
+        history.suites.insert("unit/forge".to_string(), 10.0);
+        let units: Vec<String> = ["unit", "e2e/checkout.spec.ts", "e2e/login.spec.ts"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let first = ShardPlan::new(Shard::parse("1/2").unwrap(), &units, &history);
+        let second = ShardPlan::new(Shard::parse("2/2").unwrap(), &units, &history);
+
+        assert_eq!(
+            shard_selection(Some(&first), "e2e"),
+            Some(vec!["checkout.spec.ts".to_string()])
+        );
+        assert_eq!(shard_selection(Some(&first), "unit"), None);
+        assert_eq!(
+            shard_selection(Some(&second), "e2e"),
+            Some(vec!["login.spec.ts".to_string()])
+        );
+        assert_eq!(shard_selection(Some(&second), "unit"), Some(vec![]));
+        assert_eq!(shard_selection(Some(&second), "api_integration"), None);
+    }
+
     #[test]
     fn test_test_type_from_str_unit() {
         assert_eq!(TestType::from_str("unit").unwrap(), TestType::Unit);
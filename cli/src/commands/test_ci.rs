@@ -7,6 +7,8 @@ use std::path::Path;
 use std::process::Command;
 use tracing::info;
 
+use super::test_quarantine::{self, Quarantine};
+use super::test_reports::TestReport;
 use crate::repo::get_tool_path;
 use crate::retry::run_inherited_status_sync;
 
@@ -31,43 +33,104 @@ fn cargo_bin() -> String {
 }
 
 /// Run tests in CI mode: prefer cargo-nextest, fall back to cargo test.
-pub fn execute(working_dir: &str, threads: u32) -> Result<()> {
+///
+/// With `junit`, the runner's output is captured and echoed once it
+/// finishes, and the parsed per-test results are written to that path as
+/// JUnit XML (also when tests fail). Output is also captured when the
+/// product has a `quarantine.yaml`: quarantined tests are filtered out of
+/// the blocking run and run afterwards, with `--no-fail-fast`, in a second
+/// run whose failures do not fail the command.
+pub fn execute(working_dir: &str, threads: u32, junit: Option<&str>) -> Result<()> {
     let dir = Path::new(working_dir);
     if !dir.exists() {
         bail!("Working directory not found: {}", working_dir);
     }
 
     let cargo = cargo_bin();
+    let mut report = TestReport::default();
+    let product_dir = crate::repo::find_product_dir(dir, crate::repo::ProductDirLayout::Monorepo)
+        .unwrap_or_else(|| dir.to_path_buf());
+    let quarantine = Quarantine::load(&product_dir)?;
+    let collect = junit.is_some() || !quarantine.is_empty();
 
-    if which::which("cargo-nextest").is_ok() {
+    let threads = threads.to_string();
+    let outcome = if which::which("cargo-nextest").is_ok() {
         info!("Running tests with cargo nextest (threads={})...", threads);
-        let mut cmd = Command::new(&cargo);
-        cmd.args([
-            "nextest",
-            "run",
-            "--profile",
-            "ci",
-            "--test-threads",
-            &threads.to_string(),
-        ])
-        .current_dir(dir);
-        run_inherited_status_sync(cmd, "cargo nextest run")?;
+        let nextest = || {
+            let mut cmd = Command::new(&cargo);
+            cmd.args([
+                "nextest",
+                "run",
+                "--profile",
+                "ci",
+                "--test-threads",
+                &threads,
+            ])
+            .current_dir(dir);
+            cmd
+        };
+        let filter = quarantine.nextest_filter();
+        let mut cmd = nextest();
+        if let Some(filter) = &filter {
+            cmd.args(["-E", &format!("not ({})", filter)]);
+        }
+        let outcome = if collect {
+            report.record_output(cmd.output(), "", "cargo nextest run")
+        } else {
+            run_inherited_status_sync(cmd, "cargo nextest run")
+        };
+        let quarantined = match &filter {
+            Some(filter) => {
+                info!("Running quarantined tests (non-blocking)...");
+                let mut cmd = nextest();
+                cmd.args(["--no-fail-fast", "-E", filter]);
+                let before = report.cases.len();
+                let quarantined =
+                    report.record_tests(cmd.output(), "", "cargo nextest run (quarantined)");
+                quarantine.settle_quarantined_run(quarantined, &report.cases[before..])
+            }
+            None => Ok(()),
+        };
+        outcome.and(quarantined)
     } else {
         info!(
             "cargo-nextest not found, falling back to cargo test (threads={})...",
             threads
         );
         let mut cmd = Command::new(&cargo);
-        cmd.args([
-            "test",
-            "--no-fail-fast",
-            "--",
-            "--test-threads",
-            &threads.to_string(),
-        ])
-        .current_dir(dir);
-        run_inherited_status_sync(cmd, "cargo test")?;
+        cmd.args(["test", "--no-fail-fast", "--", "--test-threads", &threads])
+            .args(quarantine.libtest_skip_args(""))
+            .current_dir(dir);
+        let outcome = if collect {
+            report.record_output(cmd.output(), "", "cargo test")
+        } else {
+            run_inherited_status_sync(cmd, "cargo test")
+        };
+        let names = quarantine.libtest_names("");
+        let quarantined = if names.is_empty() {
+            Ok(())
+        } else {
+            info!("Running quarantined tests (non-blocking)...");
+            let mut cmd = Command::new(&cargo);
+            cmd.args(["test", "--no-fail-fast", "--", "--test-threads", &threads])
+                .args(&names)
+                .current_dir(dir);
+            let before = report.cases.len();
+            let quarantined = report.record_tests(cmd.output(), "", "cargo test (quarantined)");
+            quarantine.settle_quarantined_run(quarantined, &report.cases[before..])
+        };
+        outcome.and(quarantined)
+    };
+
+    let today = test_quarantine::today();
+    if collect {
+        quarantine.apply(&mut report, today);
+    }
+    if let Some(path) = junit {
+        report.write_junit(Path::new(path), "test-ci")?;
     }
+    outcome?;
+    quarantine.check_expiry(today)?;
 
     info!("All tests passed");
     Ok(())
@@ -0,0 +1,574 @@
+//! Flaky Test Quarantine
+//!
+//! A product's `quarantine.yaml` lists known-flaky tests:
+//!
+//! ```yaml
+//! quarantine:
+//!   - test: "e2e/e2e_tests::checkout::applies_coupon"
+//!     reason: "Races the payment webhook"
+//!     owner: "payments"
+//!     issue: "https://github.com/org/repo/issues/123"
+//!     expires: "2026-11-30"
+//! ```
+//!
+//! `test` is a test id as it appears in forge's test reports
+//! (`suite::name`) or just the test name. Quarantined tests are kept apart
+//! from the blocking ones:
+//! - cargo test and nextest steps leave them out (`--skip`, a `-E`
+//!   filterset) and run them afterwards in a second, non-blocking
+//!   invocation with `--no-fail-fast`
+//! - runners that cannot filter by test name (vitest, configured web suite
+//!   commands) run everything; a failing step whose failures are all
+//!   quarantined does not fail the command
+//! - quarantined results are listed separately, and written to `--junit` as
+//!   skipped with the real outcome in the message
+//! - an entry past its `expires` date fails the command, so a quarantined
+//!   test has to be fixed (or its quarantine consciously extended)
+//!
+//! Tests that fail and then pass on a retry within the same run are printed
+//! as proposed entries, ready to paste into `quarantine.yaml`.
+
+use anyhow::{bail, Context, Result};
+use chrono::NaiveDate;
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use super::test_reports::{TestCase, TestReport, TestStatus};
+
+/// File name of the quarantine list in a product directory
+pub const QUARANTINE_FILE: &str = "quarantine.yaml";
+
+/// How long a proposed quarantine entry lasts
+const PROPOSED_QUARANTINE_DAYS: u64 = 14;
+
+/// One quarantined test.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QuarantineEntry {
+    /// Test id (`suite::name`) or test name
+    pub test: String,
+    pub reason: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub issue: Option<String>,
+    /// Date (YYYY-MM-DD) after which the quarantine fails the build
+    pub expires: String,
+}
+
+impl QuarantineEntry {
+    pub fn expires_on(&self) -> Result<NaiveDate> {
+        NaiveDate::parse_from_str(&self.expires, "%Y-%m-%d").with_context(|| {
+            format!(
+                "Invalid expires '{}' for quarantined test '{}' (expected YYYY-MM-DD)",
+                self.expires, self.test
+            )
+        })
+    }
+
+    pub fn matches(&self, case: &TestCase) -> bool {
+        self.test == case.id() || self.test == case.name
+    }
+}
+
+/// A product's quarantine list.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Quarantine {
+    #[serde(default)]
+    pub quarantine: Vec<QuarantineEntry>,
+}
+
+impl Quarantine {
+    /// Location of the quarantine list below a product root.
+    pub fn path(product_dir: &Path) -> PathBuf {
+        product_dir.join(QUARANTINE_FILE)
+    }
+
+    /// Load the product's quarantine list; a missing file is an empty list.
+    pub fn load(product_dir: &Path) -> Result<Self> {
+        let path = Self::path(product_dir);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        Self::parse(&content).with_context(|| format!("Invalid {}", path.display()))
+    }
+
+    pub fn parse(content: &str) -> Result<Self> {
+        let quarantine: Self = serde_yaml::from_str(content)?;
+        for entry in &quarantine.quarantine {
+            if entry.test.trim().is_empty() {
+                bail!("Quarantine entry with an empty test id");
+            }
+            if entry.reason.trim().is_empty() {
+                bail!("Quarantined test '{}' needs a reason", entry.test);
+            }
+            entry.expires_on()?;
+        }
+        Ok(quarantine)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.quarantine.is_empty()
+    }
+
+    pub fn entry_for(&self, case: &TestCase) -> Option<&QuarantineEntry> {
+        self.quarantine.iter().find(|entry| entry.matches(case))
+    }
+
+    /// Names to filter a cargo test (libtest) run by for the entries that
+    /// can belong to results recorded under `suite`: `<suite>/<binary>::name`
+    /// ids give `name`, bare test names are used as they are, and ids of
+    /// other suites are left out. libtest matches these as substrings.
+    pub fn libtest_names(&self, suite: &str) -> Vec<String> {
+        let mut names: Vec<String> = Vec::new();
+        let mut add = |name: &str| {
+            if !name.is_empty() && !names.iter().any(|n| n == name) {
+                names.push(name.to_string());
+            }
+        };
+        for entry in &self.quarantine {
+            let test = entry.test.as_str();
+            if suite.is_empty() {
+                // Ids are `<binary>::name` here, which look like bare names
+                add(test);
+                if let Some((_, name)) = test.split_once("::") {
+                    add(name);
+                }
+            } else if let Some(scoped) = test
+                .strip_prefix(suite)
+                .and_then(|rest| rest.strip_prefix('/'))
+            {
+                // Binary names never contain `::`
+                if let Some((_, name)) = scoped.split_once("::") {
+                    add(name);
+                }
+            } else if !test.contains('/') {
+                add(test);
+            }
+        }
+        names
+    }
+
+    /// libtest arguments (after `--`) that keep the quarantined tests of
+    /// `suite` out of a blocking run.
+    pub fn libtest_skip_args(&self, suite: &str) -> Vec<String> {
+        self.libtest_names(suite)
+            .into_iter()
+            .flat_map(|name| ["--skip".to_string(), name])
+            .collect()
+    }
+
+    /// Nextest filterset matching the quarantined tests, or `None` when the
+    /// list is empty. Ids are `<binary id>::name`, and binary ids contain
+    /// `::` themselves, so every split of an id is matched exactly.
+    pub fn nextest_filter(&self) -> Option<String> {
+        let mut alternatives = Vec::new();
+        for entry in &self.quarantine {
+            let test = entry.test.as_str();
+            alternatives.push(format!("test(={})", test));
+            for (i, _) in test.match_indices("::") {
+                alternatives.push(format!(
+                    "(binary_id(={}) & test(={}))",
+                    &test[..i],
+                    &test[i + 2..]
+                ));
+            }
+        }
+        (!alternatives.is_empty()).then(|| alternatives.join(" | "))
+    }
+
+    /// Outcome of the non-blocking run of a step's quarantined tests. Only
+    /// a failing test that is not quarantined itself (caught by a broad
+    /// name filter) fails it; the run's own exit status does not.
+    pub fn settle_quarantined_run(&self, outcome: Result<()>, step: &[TestCase]) -> Result<()> {
+        let blocking: Vec<String> = step
+            .iter()
+            .filter(|c| c.status == TestStatus::Failed && self.entry_for(c).is_none())
+            .map(TestCase::id)
+            .collect();
+        if !blocking.is_empty() {
+            bail!(
+                "{} test(s) outside the quarantine failed in the quarantined run: {}",
+                blocking.len(),
+                blocking.join(", ")
+            );
+        }
+        if let Err(e) = outcome {
+            println!(
+                "   {} Quarantined tests did not pass (non-blocking): {:#}",
+                "⚠️ ".yellow(),
+                e
+            );
+        }
+        Ok(())
+    }
+
+    /// Downgrade a failed step to a pass when it reported failures and
+    /// every one of them is quarantined. A step that failed without
+    /// reporting any failing test (e.g. a build error) still fails.
+    ///
+    /// Only for runners that run every test of the step whatever fails
+    /// (vitest, configured web suites); cargo test and nextest stop early,
+    /// so their quarantined tests are run separately instead.
+    pub fn settle(&self, outcome: Result<()>, step: &[TestCase]) -> Result<()> {
+        let Err(e) = outcome else {
+            return Ok(());
+        };
+        let mut failures = step
+            .iter()
+            .filter(|c| c.status == TestStatus::Failed)
+            .peekable();
+        if failures.peek().is_none() || !failures.all(|c| self.entry_for(c).is_some()) {
+            return Err(e);
+        }
+        println!(
+            "   {} Only quarantined tests failed; not blocking",
+            "⚠️ ".yellow()
+        );
+        Ok(())
+    }
+
+    /// Print the quarantined results and proposed additions, and turn
+    /// quarantined failures into skipped cases (keeping the failure in the
+    /// message) so they stay visible without failing report consumers.
+    pub fn apply(&self, report: &mut TestReport, today: NaiveDate) {
+        let quarantined: Vec<(&TestCase, &QuarantineEntry)> = report
+            .cases
+            .iter()
+            .filter_map(|case| self.entry_for(case).map(|entry| (case, entry)))
+            .collect();
+        if !quarantined.is_empty() {
+            println!();
+            println!("{} Quarantined tests (non-blocking):", "🧊".cyan());
+            for (case, entry) in &quarantined {
+                let status = match case.status {
+                    TestStatus::Passed => "passed".green(),
+                    TestStatus::Failed => "failed".red(),
+                    TestStatus::Skipped => "skipped".yellow(),
+                };
+                println!(
+                    "   {} [{}] {} (until {})",
+                    case.id(),
+                    status,
+                    entry.reason.dimmed(),
+                    entry.expires
+                );
+            }
+        }
+
+        let proposed = self.propose(report, today);
+        if !proposed.is_empty() {
+            println!();
+            println!(
+                "{} Flaky tests (failed, then passed on retry) — proposed {} entries:",
+                "⚠️ ".yellow(),
+                QUARANTINE_FILE
+            );
+            let yaml = serde_yaml::to_string(&Quarantine {
+                quarantine: proposed,
+            })
+            .unwrap_or_default();
+            for line in yaml.lines() {
+                println!("   {}", line);
+            }
+        }
+
+        for case in &mut report.cases {
+            let Some(entry) = self.entry_for(case) else {
+                continue;
+            };
+            if case.status == TestStatus::Failed {
+                let failure = case.message.take().unwrap_or_default();
+                case.status = TestStatus::Skipped;
+                case.message = Some(format!(
+                    "Quarantined until {} ({}); failed: {}",
+                    entry.expires, entry.reason, failure
+                ));
+            }
+        }
+    }
+
+    /// Entries for tests that passed only after failing within this run and
+    /// are not quarantined yet.
+    pub fn propose(&self, report: &TestReport, today: NaiveDate) -> Vec<QuarantineEntry> {
+        let expires = today
+            .checked_add_days(chrono::Days::new(PROPOSED_QUARANTINE_DAYS))
+            .unwrap_or(today);
+        let mut proposed: Vec<QuarantineEntry> = Vec::new();
+        for case in &report.cases {
+            if case.status != TestStatus::Passed
+                || case.retries == 0
+                || self.entry_for(case).is_some()
+                || proposed.iter().any(|entry| entry.matches(case))
+            {
+                continue;
+            }
+            proposed.push(QuarantineEntry {
+                test: case.id(),
+                reason: format!(
+                    "Failed {} time(s) before passing on retry ({})",
+                    case.retries, today
+                ),
+                owner: None,
+                issue: None,
+                expires: expires.format("%Y-%m-%d").to_string(),
+            });
+        }
+        proposed
+    }
+
+    /// Fail when any entry is past its expiry date.
+    pub fn check_expiry(&self, today: NaiveDate) -> Result<()> {
+        let mut expired = Vec::new();
+        for entry in &self.quarantine {
+            if entry.expires_on()? < today {
+                expired.push(format!("{} (expired {})", entry.test, entry.expires));
+            }
+        }
+        if !expired.is_empty() {
+            bail!(
+                "{} quarantined test(s) past their expiry date:\n  {}\n\
+                 Fix them and remove them from {}, or extend their expiry.",
+                expired.len(),
+                expired.join("\n  "),
+                QUARANTINE_FILE
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Today's date for expiry checks and proposals
+pub fn today() -> NaiveDate {
+    chrono::Utc::now().date_naive()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn case(suite: &str, name: &str, status: TestStatus, retries: u32) -> TestCase {
+        TestCase {
+            suite: suite.to_string(),
+            name: name.to_string(),
+            status,
+            duration_secs: 0.1,
+            message: Some("boom".to_string()),
+            retries,
+        }
+    }
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    const YAML: &str = r#"
+quarantine:
+  - test: "e2e/e2e_tests::checkout::applies_coupon"
+    reason: "Races the payment webhook"
+    owner: payments
+    expires: "2026-11-30"
+  - test: "login shows error"
+    reason: "Animation timing"
+    expires: "2026-10-01"
+"#;
+
+    #[test]
+    fn test_parse_and_match() {
+        let quarantine = Quarantine::parse(YAML).unwrap();
+        assert_eq!(quarantine.quarantine.len(), 2);
+        assert_eq!(quarantine.quarantine[0].owner.as_deref(), Some("payments"));
+
+        let by_id = case(
+            "e2e/e2e_tests",
+            "checkout::applies_coupon",
+            TestStatus::Failed,
+            0,
+        );
+        let by_name = case(
+            "frontend-unit/src/login.test.ts",
+            "login shows error",
+            TestStatus::Failed,
+            0,
+        );
+        let other = case("e2e/e2e_tests", "checkout::totals", TestStatus::Failed, 0);
+        assert!(quarantine.entry_for(&by_id).is_some());
+        assert!(quarantine.entry_for(&by_name).is_some());
+        assert!(quarantine.entry_for(&other).is_none());
+
+        assert!(
+            Quarantine::parse("quarantine:\n  - test: a\n    reason: b\n    expires: soon\n")
+                .is_err()
+        );
+        assert!(Quarantine::parse(
+            "quarantine:\n  - test: a\n    reason: \"\"\n    expires: 2026-01-01\n"
+        )
+        .is_err());
+        assert_eq!(Quarantine::parse("{}").unwrap(), Quarantine::default());
+    }
+
+    #[test]
+    fn test_settle_only_downgrades_all_quarantined_failures() {
+        let quarantine = Quarantine::parse(YAML).unwrap();
+        let failed = || Err(anyhow::anyhow!("cargo test failed (exit 101)"));
+        let quarantined = case(
+            "e2e/e2e_tests",
+            "checkout::applies_coupon",
+            TestStatus::Failed,
+            0,
+        );
+        let blocking = case("e2e/e2e_tests", "checkout::totals", TestStatus::Failed, 0);
+        let passed = case("e2e/e2e_tests", "checkout::totals", TestStatus::Passed, 0);
+
+        assert!(quarantine.settle(Ok(()), &[]).is_ok());
+        assert!(quarantine
+            .settle(failed(), &[quarantined.clone(), passed])
+            .is_ok());
+        assert!(quarantine
+            .settle(failed(), &[quarantined, blocking])
+            .is_err());
+        // No reported failures: the step failed for another reason
+        assert!(quarantine.settle(failed(), &[]).is_err());
+    }
+
+    #[test]
+    fn test_libtest_names_per_suite() {
+        let quarantine = Quarantine::parse(YAML).unwrap();
+        assert_eq!(
+            quarantine.libtest_names("e2e"),
+            vec!["checkout::applies_coupon", "login shows error"]
+        );
+        // Ids of another suite are left out
+        assert_eq!(quarantine.libtest_names("unit"), vec!["login shows error"]);
+        assert_eq!(
+            quarantine.libtest_skip_args("unit"),
+            vec!["--skip", "login shows error"]
+        );
+
+        let quarantine = Quarantine::parse(
+            "quarantine:\n  - test: \"backend::auth::tests::login\"\n    reason: r\n    expires: 2026-11-30\n",
+        )
+        .unwrap();
+        assert_eq!(
+            quarantine.libtest_names(""),
+            vec!["backend::auth::tests::login", "auth::tests::login"]
+        );
+    }
+
+    #[test]
+    fn test_nextest_filter_matches_every_split_of_an_id() {
+        assert_eq!(Quarantine::default().nextest_filter(), None);
+        let quarantine = Quarantine::parse(
+            "quarantine:\n  - test: \"backend::integration_tests::orders::create\"\n    reason: r\n    expires: 2026-11-30\n",
+        )
+        .unwrap();
+        assert_eq!(
+            quarantine.nextest_filter().unwrap(),
+            "test(=backend::integration_tests::orders::create) \
+             | (binary_id(=backend) & test(=integration_tests::orders::create)) \
+             | (binary_id(=backend::integration_tests) & test(=orders::create)) \
+             | (binary_id(=backend::integration_tests::orders) & test(=create))"
+        );
+    }
+
+    #[test]
+    fn test_settle_quarantined_run_only_fails_on_other_tests() {
+        let quarantine = Quarantine::parse(YAML).unwrap();
+        let failed = || Err(anyhow::anyhow!("cargo test failed (exit 101)"));
+        let quarantined = case(
+            "e2e/e2e_tests",
+            "checkout::applies_coupon",
+            TestStatus::Failed,
+            0,
+        );
+        // Caught by the substring filter, but not quarantined itself
+        let collateral = case(
+            "e2e/e2e_tests",
+            "checkout::applies_coupon_twice",
+            TestStatus::Failed,
+            0,
+        );
+
+        assert!(quarantine
+            .settle_quarantined_run(failed(), std::slice::from_ref(&quarantined))
+            .is_ok());
+        assert!(quarantine.settle_quarantined_run(failed(), &[]).is_ok());
+        let err = quarantine
+            .settle_quarantined_run(failed(), &[quarantined, collateral])
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("checkout::applies_coupon_twice"), "{}", err);
+    }
+
+    #[test]
+    fn test_apply_marks_quarantined_failures_skipped() {
+        let quarantine = Quarantine::parse(YAML).unwrap();
+        let mut report = TestReport {
+            cases: vec![
+                case(
+                    "e2e/e2e_tests",
+                    "checkout::applies_coupon",
+                    TestStatus::Failed,
+                    0,
+                ),
+                case("e2e/e2e_tests", "checkout::totals", TestStatus::Failed, 0),
+            ],
+        };
+        quarantine.apply(&mut report, date("2026-10-18"));
+        assert_eq!(report.cases[0].status, TestStatus::Skipped);
+        assert_eq!(
+            report.cases[0].message.as_deref(),
+            Some("Quarantined until 2026-11-30 (Races the payment webhook); failed: boom")
+        );
+        assert_eq!(report.cases[1].status, TestStatus::Failed);
+        assert_eq!(report.cases[1].message.as_deref(), Some("boom"));
+    }
+
+    #[test]
+    fn test_propose_flaky_tests() {
+        let quarantine = Quarantine::parse(YAML).unwrap();
+        let report = TestReport {
+            cases: vec![
+                case("e2e/e2e_tests", "checkout::totals", TestStatus::Passed, 1),
+                case("e2e/e2e_tests", "checkout::totals", TestStatus::Passed, 2),
+                case(
+                    "e2e/e2e_tests",
+                    "checkout::applies_coupon",
+                    TestStatus::Passed,
+                    1,
+                ),
+                case("e2e/e2e_tests", "checkout::refund", TestStatus::Failed, 2),
+                case("e2e/e2e_tests", "checkout::cart", TestStatus::Passed, 0),
+            ],
+        };
+        let proposed = quarantine.propose(&report, date("2026-10-18"));
+        assert_eq!(proposed.len(), 1);
+        assert_eq!(proposed[0].test, "e2e/e2e_tests::checkout::totals");
+        assert_eq!(proposed[0].expires, "2026-11-01");
+        // Proposals are valid entries
+        let yaml = serde_yaml::to_string(&Quarantine {
+            quarantine: proposed,
+        })
+        .unwrap();
+        assert_eq!(Quarantine::parse(&yaml).unwrap().quarantine.len(), 1);
+    }
+
+    #[test]
+    fn test_check_expiry() {
+        let quarantine = Quarantine::parse(YAML).unwrap();
+        assert!(quarantine.check_expiry(date("2026-09-30")).is_ok());
+        assert!(quarantine.check_expiry(date("2026-10-01")).is_ok());
+        let err = quarantine
+            .check_expiry(date("2026-10-18"))
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("login shows error (expired 2026-10-01)"),
+            "{}",
+            err
+        );
+        assert!(!err.contains("applies_coupon"), "{}", err);
+    }
+}
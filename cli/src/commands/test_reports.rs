@@ -0,0 +1,924 @@
+//! Structured Test Reports
+//!
+//! A small per-test result model shared by the commands that run test
+//! suites. Reports are read from JUnit XML (the format cargo-nextest,
+//! vitest, playwright and most other runners can emit) or from this
+//! module's own JSON serialization, so a runner that cannot produce JUnit
+//! can write `{"cases": [...]}` instead.
+//!
+//! Retries are kept: nextest records a test that failed and then passed on
+//! retry as a passing `<testcase>` with `<flakyFailure>` children, and one
+//! that failed every attempt with `<rerunFailure>` children. Either counts
+//! toward [`detect_flaky`], which also flags tests whose outcome flipped
+//! between runs.
+//!
+//! The test commands' `--junit <path>` flag merges everything a command ran
+//! into one report and writes it back out as JUnit XML:
+//! - cargo test and cargo-nextest results are parsed from the captured
+//!   runner output ([`TestReport::parse_runner_output`]); libtest only
+//!   prints a per-binary time, which is spread evenly over that binary's
+//!   tests. cargo writes each binary's `Running` line to stderr and its
+//!   results to stdout, so separately captured streams are first put back
+//!   in order with [`interleave_runner_streams`]
+//! - vitest and playwright write JUnit files of their own, which are read
+//!   back with [`TestReport::merge_junit_file`]; configured suite commands
+//!   receive the file path in [`JUNIT_OUTPUT_ENV`]
+//! - a step with no per-test output is recorded as a single test case
+
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::LazyLock;
+
+/// Environment variable carrying the JUnit file path a configured test
+/// command should write (e.g. `vitest --outputFile.junit=$FORGE_JUNIT_OUTPUT`).
+pub const JUNIT_OUTPUT_ENV: &str = "FORGE_JUNIT_OUTPUT";
+
+/// Colour escape sequences, stripped before parsing runner output
+static ANSI_ESCAPE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\x1b\[[0-9;]*m").unwrap());
+
+/// `Running unittests src/lib.rs (target/debug/deps/forge-0123abcd)`
+static LIBTEST_RUNNING: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^\s*Running .*\((?:.*/)?([^/()]+?)(?:-[0-9a-f]{16})?(?:\.exe)?\)\s*$").unwrap()
+});
+
+/// `Doc-tests forge`
+static LIBTEST_DOCTESTS: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\s*Doc-tests (\S+)\s*$").unwrap());
+
+/// `running 3 tests`, the first line libtest prints for every binary
+static LIBTEST_BINARY_START: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^running \d+ tests?$").unwrap());
+
+/// `test commands::tests::test_x ... ok`
+static LIBTEST_RESULT: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^test (.+?) \.\.\. (ok|FAILED|ignored)\b").unwrap());
+
+/// `test result: FAILED. 3 passed; 1 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.52s`
+static LIBTEST_FINISHED: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^test result: .*finished in ([\d.]+)s").unwrap());
+
+/// `---- commands::tests::test_x stdout ----`
+static LIBTEST_FAILURE_HEADER: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^---- (.+?) stdout ----$").unwrap());
+
+/// `TRY 2 PASS [   0.004s] (12/80) forge::bin/forge commands::tests::test_x`
+static NEXTEST_RESULT: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"^\s*(?:TRY (\d+) )?(PASS|FAIL|SKIP|FLAKY (\d+)/\d+|TIMEOUT|LEAK-FAIL|LEAK|ABORT|SIG[A-Z]+)\s+\[\s*([\d.]+)s\]\s+(?:\(\s*\d+/\d+\)\s+)?(\S+)\s+(\S.*?)\s*$",
+    )
+    .unwrap()
+});
+
+/// Outcome of a single test.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TestStatus {
+    Passed,
+    Failed,
+    Skipped,
+}
+
+/// One test's result.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TestCase {
+    /// Suite (JUnit `testsuite`) the test belongs to
+    #[serde(default)]
+    pub suite: String,
+    pub name: String,
+    pub status: TestStatus,
+    #[serde(default)]
+    pub duration_secs: f64,
+    /// Failure or skip message, if the runner gave one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    /// Failed attempts before the final outcome
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub retries: u32,
+}
+
+fn is_zero(n: &u32) -> bool {
+    *n == 0
+}
+
+impl TestCase {
+    /// Stable identifier used to match a test across runs.
+    pub fn id(&self) -> String {
+        if self.suite.is_empty() {
+            self.name.clone()
+        } else {
+            format!("{}::{}", self.suite, self.name)
+        }
+    }
+}
+
+/// Results of one test run.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TestReport {
+    pub cases: Vec<TestCase>,
+}
+
+/// Per-status totals of a [`TestReport`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TestCounts {
+    pub passed: usize,
+    pub failed: usize,
+    pub skipped: usize,
+}
+
+impl TestReport {
+    /// Parse a report, detecting JUnit XML or JSON from the first character.
+    pub fn parse(content: &str) -> Result<Self> {
+        match content.trim_start().chars().next() {
+            Some('<') => Self::parse_junit(content),
+            Some('{') | Some('[') => Self::parse_json(content),
+            Some(_) => bail!("Test report is neither JUnit XML nor JSON"),
+            None => bail!("Test report is empty"),
+        }
+    }
+
+    /// Parse a JUnit XML report (`<testsuites>` or a single `<testsuite>`).
+    pub fn parse_junit(xml: &str) -> Result<Self> {
+        let doc = roxmltree::Document::parse(xml).context("Failed to parse JUnit XML")?;
+        let root = doc.root_element();
+        if !matches!(root.tag_name().name(), "testsuites" | "testsuite") {
+            bail!(
+                "JUnit report root must be <testsuites> or <testsuite>, found <{}>",
+                root.tag_name().name()
+            );
+        }
+
+        let cases = root
+            .descendants()
+            .filter(|node| node.has_tag_name("testcase"))
+            .map(|node| {
+                let suite = node
+                    .ancestors()
+                    .find(|a| a.has_tag_name("testsuite"))
+                    .and_then(|s| s.attribute("name"))
+                    .or_else(|| node.attribute("classname"))
+                    .unwrap_or_default()
+                    .to_string();
+                let mut status = TestStatus::Passed;
+                let mut message = None;
+                let mut retries = 0;
+                for child in node.children().filter(|c| c.is_element()) {
+                    match child.tag_name().name() {
+                        "failure" | "error" => {
+                            status = TestStatus::Failed;
+                            message = junit_message(child);
+                        }
+                        "skipped" if status != TestStatus::Failed => {
+                            status = TestStatus::Skipped;
+                            message = junit_message(child);
+                        }
+                        "flakyFailure" | "flakyError" | "rerunFailure" | "rerunError" => {
+                            retries += 1;
+                        }
+                        _ => {}
+                    }
+                }
+                TestCase {
+                    suite,
+                    name: node.attribute("name").unwrap_or_default().to_string(),
+                    status,
+                    duration_secs: node
+                        .attribute("time")
+                        .and_then(|t| t.parse().ok())
+                        .unwrap_or(0.0),
+                    message,
+                    retries,
+                }
+            })
+            .collect();
+
+        Ok(Self { cases })
+    }
+
+    /// Parse a JSON report: a serialized [`TestReport`] or a bare array of cases.
+    pub fn parse_json(json: &str) -> Result<Self> {
+        if json.trim_start().starts_with('[') {
+            let cases = serde_json::from_str(json).context("Failed to parse JSON test cases")?;
+            return Ok(Self { cases });
+        }
+        serde_json::from_str(json).context("Failed to parse JSON test report")
+    }
+
+    pub fn counts(&self) -> TestCounts {
+        let mut counts = TestCounts::default();
+        for case in &self.cases {
+            match case.status {
+                TestStatus::Passed => counts.passed += 1,
+                TestStatus::Failed => counts.failed += 1,
+                TestStatus::Skipped => counts.skipped += 1,
+            }
+        }
+        counts
+    }
+
+    pub fn failures(&self) -> impl Iterator<Item = &TestCase> {
+        self.cases.iter().filter(|c| c.status == TestStatus::Failed)
+    }
+
+    pub fn duration_secs(&self) -> f64 {
+        self.cases.iter().map(|c| c.duration_secs).sum()
+    }
+
+    /// Print totals and each failed test with the first line of its message.
+    pub fn print_summary(&self) {
+        let counts = self.counts();
+        println!(
+            "   {} passed, {} failed, {} skipped ({:.1}s)",
+            counts.passed.to_string().green(),
+            if counts.failed > 0 {
+                counts.failed.to_string().red().bold()
+            } else {
+                counts.failed.to_string().normal()
+            },
+            counts.skipped.to_string().dimmed(),
+            self.duration_secs()
+        );
+        for case in self.failures() {
+            println!("   ❌ {}", case.id().red());
+            if let Some(line) = case.message.as_deref().and_then(|m| m.lines().next()) {
+                println!("      {}", line.dimmed());
+            }
+        }
+    }
+}
+
+/// Merge a runner's separately captured stdout and stderr into one text
+/// [`TestReport::parse_runner_output`] can attribute.
+///
+/// cargo test prints each binary's `Running …` / `Doc-tests …` line on
+/// stderr and that binary's libtest output on stdout, which always starts
+/// with `running N tests`. The n-th header goes back in front of the n-th
+/// such line; the rest of stderr (nextest results, compiler output)
+/// follows stdout.
+pub fn interleave_runner_streams(stdout: &str, stderr: &str) -> String {
+    let is_header = |line: &str| {
+        let line = ANSI_ESCAPE.replace_all(line, "");
+        LIBTEST_RUNNING.is_match(&line) || LIBTEST_DOCTESTS.is_match(&line)
+    };
+    let (headers, rest): (Vec<&str>, Vec<&str>) = stderr.lines().partition(|l| is_header(l));
+    let mut headers = headers.into_iter();
+
+    let mut merged = String::new();
+    for line in stdout.lines() {
+        if LIBTEST_BINARY_START.is_match(&ANSI_ESCAPE.replace_all(line, "")) {
+            if let Some(header) = headers.next() {
+                merged.push_str(header);
+                merged.push('\n');
+            }
+        }
+        merged.push_str(line);
+        merged.push('\n');
+    }
+    // Binaries that never started (a build or spawn failure) report nothing.
+    for line in headers.chain(rest) {
+        merged.push_str(line);
+        merged.push('\n');
+    }
+    merged
+}
+
+/// `message` attribute of a `<failure>`/`<skipped>` element, else its text.
+fn junit_message(node: roxmltree::Node) -> Option<String> {
+    node.attribute("message")
+        .or_else(|| node.text())
+        .map(str::trim)
+        .filter(|m| !m.is_empty())
+        .map(str::to_string)
+}
+
+impl TestReport {
+    /// Parse cargo test (libtest) and cargo-nextest output into test cases.
+    ///
+    /// Suites are `<suite>/<test binary>` (libtest) or `<suite>/<binary id>`
+    /// (nextest). A nextest test that is retried appears once, with its
+    /// final outcome and the number of failed attempts before it.
+    pub fn parse_runner_output(output: &str, suite: &str) -> Self {
+        let scoped = |binary: &str| {
+            if suite.is_empty() {
+                binary.to_string()
+            } else {
+                format!("{}/{}", suite, binary)
+            }
+        };
+
+        let mut cases: Vec<TestCase> = Vec::new();
+        let mut index: BTreeMap<String, usize> = BTreeMap::new();
+        let mut binary = String::new();
+        let mut binary_start = 0;
+        let mut failure: Option<(String, Vec<String>)> = None;
+        let mut messages: BTreeMap<String, String> = BTreeMap::new();
+
+        let output = ANSI_ESCAPE.replace_all(output, "");
+        for line in output.lines() {
+            // libtest failure output, collected until the next section
+            if let Some((_, lines)) = failure.as_mut() {
+                if !line.starts_with("---- ") && line != "failures:" {
+                    lines.push(line.to_string());
+                    continue;
+                }
+                let (id, lines) = failure.take().unwrap_or_default();
+                let text = lines.join("\n").trim().to_string();
+                if !text.is_empty() {
+                    messages.insert(id, text);
+                }
+            }
+
+            if let Some(caps) = LIBTEST_RUNNING.captures(line) {
+                binary = scoped(&caps[1]);
+                binary_start = cases.len();
+            } else if let Some(caps) = LIBTEST_DOCTESTS.captures(line) {
+                binary = scoped(&format!("{}-doctests", &caps[1]));
+                binary_start = cases.len();
+            } else if let Some(caps) = LIBTEST_RESULT.captures(line) {
+                cases.push(TestCase {
+                    suite: binary.clone(),
+                    name: caps[1].to_string(),
+                    status: match &caps[2] {
+                        "ok" => TestStatus::Passed,
+                        "ignored" => TestStatus::Skipped,
+                        _ => TestStatus::Failed,
+                    },
+                    duration_secs: 0.0,
+                    message: None,
+                    retries: 0,
+                });
+            } else if let Some(caps) = LIBTEST_FINISHED.captures(line) {
+                let ran = &mut cases[binary_start..];
+                let counted = ran
+                    .iter()
+                    .filter(|c| c.status != TestStatus::Skipped)
+                    .count();
+                if let (Ok(total), true) = (caps[1].parse::<f64>(), counted > 0) {
+                    for case in ran.iter_mut().filter(|c| c.status != TestStatus::Skipped) {
+                        case.duration_secs = total / counted as f64;
+                    }
+                }
+                binary_start = cases.len();
+            } else if let Some(caps) = LIBTEST_FAILURE_HEADER.captures(line) {
+                failure = Some((format!("{}::{}", binary, &caps[1]), Vec::new()));
+            } else if let Some(caps) = NEXTEST_RESULT.captures(line) {
+                let attempt: u32 = caps
+                    .get(1)
+                    .or_else(|| caps.get(3))
+                    .and_then(|m| m.as_str().parse().ok())
+                    .unwrap_or(1);
+                let status = match &caps[2] {
+                    "PASS" => TestStatus::Passed,
+                    "SKIP" => TestStatus::Skipped,
+                    flaky if flaky.starts_with("FLAKY") => TestStatus::Passed,
+                    _ => TestStatus::Failed,
+                };
+                let case = TestCase {
+                    suite: scoped(&caps[5]),
+                    name: caps[6].to_string(),
+                    status,
+                    duration_secs: caps[4].parse().unwrap_or(0.0),
+                    message: None,
+                    retries: attempt.saturating_sub(1),
+                };
+                // The final summary repeats failures; keep one entry per test
+                match index.get(&case.id()) {
+                    Some(&i) => {
+                        let retries = cases[i].retries.max(case.retries);
+                        cases[i] = TestCase { retries, ..case };
+                    }
+                    None => {
+                        index.insert(case.id(), cases.len());
+                        cases.push(case);
+                    }
+                }
+            }
+        }
+        if let Some((id, lines)) = failure {
+            let text = lines.join("\n").trim().to_string();
+            if !text.is_empty() {
+                messages.insert(id, text);
+            }
+        }
+
+        for case in &mut cases {
+            if case.status == TestStatus::Failed {
+                case.message = messages.remove(&case.id());
+            }
+        }
+        Self { cases }
+    }
+
+    /// Add the tests from a runner's captured output to this report.
+    ///
+    /// The output is echoed first, since capturing it kept it off the
+    /// terminal. When no individual tests can be parsed from it, the whole
+    /// command is recorded as one test named `op`. Fails like
+    /// [`crate::retry::classify_capture_anyhow`] when the command failed.
+    pub fn record_output(
+        &mut self,
+        captured: std::io::Result<std::process::Output>,
+        suite: &str,
+        op: &str,
+    ) -> Result<()> {
+        let output = captured.map_err(|e| anyhow::anyhow!("Failed to spawn {}: {}", op, e))?;
+        let parsed = Self::echo_and_parse(&output, suite);
+        if parsed.cases.is_empty() {
+            self.record_step(suite, op, output.status.success(), 0.0, None);
+        } else {
+            self.cases.extend(parsed.cases);
+        }
+        crate::retry::classify_capture_anyhow(Ok(output), op).map(|_| ())
+    }
+
+    /// Like [`Self::record_output`], but a command that reports no
+    /// individual tests adds nothing.
+    pub fn record_tests(
+        &mut self,
+        captured: std::io::Result<std::process::Output>,
+        suite: &str,
+        op: &str,
+    ) -> Result<()> {
+        let output = captured.map_err(|e| anyhow::anyhow!("Failed to spawn {}: {}", op, e))?;
+        self.cases
+            .extend(Self::echo_and_parse(&output, suite).cases);
+        crate::retry::classify_capture_anyhow(Ok(output), op).map(|_| ())
+    }
+
+    fn echo_and_parse(output: &std::process::Output, suite: &str) -> Self {
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        print!("{}", stdout);
+        eprint!("{}", stderr);
+        Self::parse_runner_output(&interleave_runner_streams(&stdout, &stderr), suite)
+    }
+
+    /// Record a step without per-test output as a single test case.
+    pub fn record_step(
+        &mut self,
+        suite: &str,
+        name: &str,
+        passed: bool,
+        duration_secs: f64,
+        message: Option<String>,
+    ) {
+        self.cases.push(TestCase {
+            suite: suite.to_string(),
+            name: name.to_string(),
+            status: if passed {
+                TestStatus::Passed
+            } else {
+                TestStatus::Failed
+            },
+            duration_secs,
+            message,
+            retries: 0,
+        });
+    }
+
+    /// Merge a JUnit file written by a runner, if it exists.
+    ///
+    /// Suites are prefixed with `suite` so results from different commands
+    /// stay apart. Returns whether the file was found.
+    pub fn merge_junit_file(&mut self, path: &Path, suite: &str) -> Result<bool> {
+        if !path.exists() {
+            return Ok(false);
+        }
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let report = Self::parse_junit(&content)
+            .with_context(|| format!("Failed to parse {}", path.display()))?;
+        self.cases.extend(report.cases.into_iter().map(|mut case| {
+            case.suite = match case.suite.as_str() {
+                "" => suite.to_string(),
+                inner => format!("{}/{}", suite, inner),
+            };
+            case
+        }));
+        Ok(true)
+    }
+
+    /// Count the failures of earlier attempts of the same command as
+    /// retries of the matching cases recorded from index `start` on.
+    pub fn add_retries(&mut self, start: usize, attempts: &[TestReport]) {
+        for case in self.cases.iter_mut().skip(start) {
+            let id = case.id();
+            let failed = attempts
+                .iter()
+                .filter(|attempt| attempt.failures().any(|earlier| earlier.id() == id))
+                .count();
+            case.retries += failed as u32;
+        }
+    }
+
+    /// Serialize as JUnit XML, one `<testsuite>` per suite in first-seen order.
+    pub fn to_junit(&self, name: &str) -> String {
+        let mut suites: Vec<(&str, Vec<&TestCase>)> = Vec::new();
+        for case in &self.cases {
+            match suites.iter_mut().find(|(suite, _)| *suite == case.suite) {
+                Some((_, cases)) => cases.push(case),
+                None => suites.push((&case.suite, vec![case])),
+            }
+        }
+
+        let counts = self.counts();
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        xml.push_str(&format!(
+            "<testsuites name=\"{}\" tests=\"{}\" failures=\"{}\" skipped=\"{}\" time=\"{:.3}\">\n",
+            xml_escape(name),
+            self.cases.len(),
+            counts.failed,
+            counts.skipped,
+            self.duration_secs()
+        ));
+        for (suite, cases) in suites {
+            let failed = cases
+                .iter()
+                .filter(|c| c.status == TestStatus::Failed)
+                .count();
+            let skipped = cases
+                .iter()
+                .filter(|c| c.status == TestStatus::Skipped)
+                .count();
+            let time: f64 = cases.iter().map(|c| c.duration_secs).sum();
+            xml.push_str(&format!(
+                "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" skipped=\"{}\" time=\"{:.3}\">\n",
+                xml_escape(suite),
+                cases.len(),
+                failed,
+                skipped,
+                time
+            ));
+            for case in cases {
+                xml.push_str(&format!(
+                    "    <testcase name=\"{}\" classname=\"{}\" time=\"{:.3}\"",
+                    xml_escape(&case.name),
+                    xml_escape(suite),
+                    case.duration_secs
+                ));
+                let message = case.message.as_deref().unwrap_or_default();
+                let summary = xml_escape(message.lines().next().unwrap_or_default());
+                let retry_tag = match case.status {
+                    TestStatus::Failed => "rerunFailure",
+                    _ => "flakyFailure",
+                };
+                let mut children = Vec::new();
+                for _ in 0..case.retries {
+                    children.push(format!("<{}/>", retry_tag));
+                }
+                match case.status {
+                    TestStatus::Passed => {}
+                    TestStatus::Failed => children.push(format!(
+                        "<failure message=\"{}\">{}</failure>",
+                        summary,
+                        xml_escape(message)
+                    )),
+                    TestStatus::Skipped if message.is_empty() => {
+                        children.push("<skipped/>".to_string())
+                    }
+                    TestStatus::Skipped => {
+                        children.push(format!("<skipped message=\"{}\"/>", summary))
+                    }
+                }
+                if children.is_empty() {
+                    xml.push_str("/>\n");
+                } else {
+                    xml.push_str(">\n");
+                    for child in children {
+                        xml.push_str(&format!("      {}\n", child));
+                    }
+                    xml.push_str("    </testcase>\n");
+                }
+            }
+            xml.push_str("  </testsuite>\n");
+        }
+        xml.push_str("</testsuites>\n");
+        xml
+    }
+
+    /// Write the report as JUnit XML to `path`, creating parent directories.
+    pub fn write_junit(&self, path: &Path, name: &str) -> Result<()> {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        std::fs::write(path, self.to_junit(name))
+            .with_context(|| format!("Failed to write JUnit report {}", path.display()))?;
+        let counts = self.counts();
+        println!(
+            "📄 JUnit report: {} ({} tests, {} failed)",
+            path.display(),
+            self.cases.len(),
+            counts.failed
+        );
+        Ok(())
+    }
+}
+
+/// Escape text for XML, dropping control characters XML 1.0 cannot carry
+/// (ANSI colour codes from runner output, for one).
+fn xml_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\t' | '\n' | '\r' => escaped.push(c),
+            c if (c as u32) < 0x20 => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// A test that both passed and failed within the examined runs.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FlakyTest {
+    pub id: String,
+    pub passes: usize,
+    /// Failed runs plus failed attempts that were retried
+    pub failures: usize,
+}
+
+/// Find flaky tests across `runs`, most failures first.
+///
+/// A test is flaky when it passed at least once and failed at least once,
+/// counting failed attempts before a retry as failures, so a test that
+/// needed a retry in a single run is already reported.
+pub fn detect_flaky<'a>(runs: impl IntoIterator<Item = &'a TestReport>) -> Vec<FlakyTest> {
+    let mut tally: BTreeMap<String, (usize, usize)> = BTreeMap::new();
+    for report in runs {
+        for case in &report.cases {
+            let entry = tally.entry(case.id()).or_default();
+            match case.status {
+                TestStatus::Passed => entry.0 += 1,
+                TestStatus::Failed => entry.1 += 1,
+                TestStatus::Skipped => {}
+            }
+            entry.1 += case.retries as usize;
+        }
+    }
+
+    let mut flaky: Vec<FlakyTest> = tally
+        .into_iter()
+        .filter(|(_, (passes, failures))| *passes > 0 && *failures > 0)
+        .map(|(id, (passes, failures))| FlakyTest {
+            id,
+            passes,
+            failures,
+        })
+        .collect();
+    flaky.sort_by(|a, b| b.failures.cmp(&a.failures).then_with(|| a.id.cmp(&b.id)));
+    flaky
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const JUNIT: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites name="federation-tests" tests="4" failures="1">
+  <testsuite name="auth" tests="4">
+    <testcase name="login" classname="auth" time="0.25"/>
+    <testcase name="refresh" classname="auth" time="1.5">
+      <failure message="expected 200, got 502" type="assertion">stack trace</failure>
+    </testcase>
+    <testcase name="logout" classname="auth" time="0">
+      <skipped/>
+    </testcase>
+    <testcase name="session" classname="auth" time="0.5">
+      <flakyFailure message="timeout"/>
+    </testcase>
+  </testsuite>
+</testsuites>"#;
+
+    #[test]
+    fn test_parse_junit_reads_status_message_duration_and_retries() {
+        let report = TestReport::parse(JUNIT).unwrap();
+        assert_eq!(
+            report.counts(),
+            TestCounts {
+                passed: 2,
+                failed: 1,
+                skipped: 1
+            }
+        );
+        let failed: Vec<_> = report.failures().collect();
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0].id(), "auth::refresh");
+        assert_eq!(failed[0].message.as_deref(), Some("expected 200, got 502"));
+        assert_eq!(report.duration_secs(), 2.25);
+        assert_eq!(report.cases[3].retries, 1);
+        assert_eq!(report.cases[3].status, TestStatus::Passed);
+    }
+
+    #[test]
+    fn test_parse_json_round_trips_and_accepts_bare_arrays() {
+        let report = TestReport::parse(JUNIT).unwrap();
+        let json = serde_json::to_string(&report).unwrap();
+        assert_eq!(TestReport::parse(&json).unwrap(), report);
+
+        let bare = r#"[{"name": "checkout", "status": "failed"}]"#;
+        let parsed = TestReport::parse(bare).unwrap();
+        assert_eq!(parsed.cases[0].id(), "checkout");
+        assert_eq!(parsed.counts().failed, 1);
+
+        assert!(TestReport::parse("   ").is_err());
+        assert!(TestReport::parse("PASS all tests").is_err());
+    }
+
+    #[test]
+    fn test_detect_flaky_counts_flips_across_runs_and_retries() {
+        let case = |name: &str, status, retries| TestCase {
+            suite: "cart".to_string(),
+            name: name.to_string(),
+            status,
+            duration_secs: 0.0,
+            message: None,
+            retries,
+        };
+        let runs = [
+            TestReport {
+                cases: vec![
+                    case("add", TestStatus::Passed, 0),
+                    case("remove", TestStatus::Failed, 0),
+                    case("total", TestStatus::Passed, 0),
+                    case("broken", TestStatus::Failed, 0),
+                ],
+            },
+            TestReport {
+                cases: vec![
+                    case("add", TestStatus::Passed, 0),
+                    case("remove", TestStatus::Passed, 0),
+                    case("total", TestStatus::Passed, 2),
+                    case("broken", TestStatus::Failed, 0),
+                ],
+            },
+        ];
+
+        let flaky = detect_flaky(&runs);
+        assert_eq!(
+            flaky,
+            vec![
+                FlakyTest {
+                    id: "cart::total".to_string(),
+                    passes: 2,
+                    failures: 2
+                },
+                FlakyTest {
+                    id: "cart::remove".to_string(),
+                    passes: 1,
+                    failures: 1
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_runner_output_reads_libtest_binaries_failures_and_time() {
+        let output = "\
+     Running unittests src/lib.rs (target/debug/deps/backend-0123456789abcdef)
+
+running 3 tests
+test auth::tests::login ... ok
+test auth::tests::refresh ... FAILED
+test auth::tests::slow ... ignored
+
+failures:
+
+---- auth::tests::refresh stdout ----
+thread 'auth::tests::refresh' panicked at src/auth.rs:10:5:
+token expired
+
+failures:
+    auth::tests::refresh
+
+test result: FAILED. 1 passed; 1 failed; 1 ignored; 0 measured; 0 filtered out; finished in 0.50s
+
+   Doc-tests backend
+
+running 1 test
+test src/lib.rs - Config (line 12) ... ok
+
+test result: ok. 1 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.10s
+";
+        let report = TestReport::parse_runner_output(output, "unit");
+        let ids: Vec<String> = report.cases.iter().map(|c| c.id()).collect();
+        assert_eq!(
+            ids,
+            vec![
+                "unit/backend::auth::tests::login",
+                "unit/backend::auth::tests::refresh",
+                "unit/backend::auth::tests::slow",
+                "unit/backend-doctests::src/lib.rs - Config (line 12)",
+            ]
+        );
+        assert_eq!(
+            report.cases[1].message.as_deref(),
+            Some("thread 'auth::tests::refresh' panicked at src/auth.rs:10:5:\ntoken expired")
+        );
+        assert_eq!(report.cases[0].duration_secs, 0.25);
+        assert_eq!(report.cases[2].duration_secs, 0.0);
+        assert_eq!(report.cases[3].duration_secs, 0.1);
+    }
+
+    #[test]
+    fn test_interleave_runner_streams_attributes_tests_to_their_binary() {
+        // cargo test on a crate with a lib and an integration test target,
+        // captured as two pipes
+        let stdout = "
+running 1 test
+test tests::unit ... ok
+
+test result: ok. 1 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.20s
+
+
+running 2 tests
+test api ... ok
+test orders ... FAILED
+
+failures:
+
+---- orders stdout ----
+boom
+
+failures:
+    orders
+
+test result: FAILED. 1 passed; 1 failed; 0 ignored; 0 measured; 0 filtered out; finished in 1.00s
+
+
+running 0 tests
+
+test result: ok. 0 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.00s
+
+";
+        let stderr = "\
+   Compiling shop v0.1.0 (/src/shop)
+    Finished `test` profile [unoptimized + debuginfo] target(s) in 2.10s
+     Running unittests src/lib.rs (target/debug/deps/shop-0123456789abcdef)
+     Running tests/http.rs (target/debug/deps/http-fedcba9876543210)
+   Doc-tests shop
+error: test failed, to rerun pass `--test http`
+";
+        let report = TestReport::parse_runner_output(
+            &interleave_runner_streams(stdout, stderr),
+            "integration",
+        );
+        let ids: Vec<String> = report.cases.iter().map(|c| c.id()).collect();
+        assert_eq!(
+            ids,
+            vec![
+                "integration/shop::tests::unit",
+                "integration/http::api",
+                "integration/http::orders",
+            ]
+        );
+        assert_eq!(report.cases[0].duration_secs, 0.2);
+        assert_eq!(report.cases[1].duration_secs, 0.5);
+        assert_eq!(report.cases[2].message.as_deref(), Some("boom"));
+    }
+
+    #[test]
+    fn test_parse_runner_output_reads_nextest_retries_once_per_test() {
+        let output = "\
+    Starting 3 tests across 2 binaries
+        PASS [   0.004s] backend auth::tests::login
+   TRY 1 FAIL [   0.010s] backend auth::tests::refresh
+   TRY 2 PASS [   0.008s] backend auth::tests::refresh
+        FAIL [   1.250s] (3/3) backend::integration_tests orders::create
+------------
+     Summary [   1.300s] 3 tests run: 2 passed (1 flaky), 1 failed, 0 skipped
+       FLAKY 2/2 [   0.008s] backend auth::tests::refresh
+        FAIL [   1.250s] backend::integration_tests orders::create
+";
+        let report = TestReport::parse_runner_output(output, "");
+        assert_eq!(report.cases.len(), 3);
+        assert_eq!(report.cases[1].id(), "backend::auth::tests::refresh");
+        assert_eq!(report.cases[1].status, TestStatus::Passed);
+        assert_eq!(report.cases[1].retries, 1);
+        assert_eq!(report.cases[2].status, TestStatus::Failed);
+        assert_eq!(report.cases[2].duration_secs, 1.25);
+        assert_eq!(report.counts().failed, 1);
+    }
+
+    #[test]
+    fn test_to_junit_round_trips_through_parse_junit() {
+        let report = TestReport::parse(JUNIT).unwrap();
+        let mut report = TestReport {
+            cases: report.cases,
+        };
+        report.record_step("e2e", "checkout <flow> & \"pay\"", false, 3.0, None);
+        report.cases[1].retries = 2;
+
+        let xml = report.to_junit("forge");
+        assert!(xml.contains("checkout &lt;flow&gt; &amp; &quot;pay&quot;"));
+        assert_eq!(TestReport::parse_junit(&xml).unwrap(), report);
+    }
+}
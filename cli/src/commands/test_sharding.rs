@@ -0,0 +1,499 @@
+//! Test Sharding
+//!
+//! `--shard i/n` splits a test run across `n` CI workers. The work is cut
+//! into units — a Rust test binary, a vitest file, a playwright spec — and
+//! every worker computes the same assignment independently, so no
+//! coordinator is needed:
+//!
+//! 1. each unit is weighted by its duration in earlier runs (units with no
+//!    history get the mean of the known ones)
+//! 2. units are taken heaviest first (ties by name) and given to the
+//!    currently lightest shard (ties to the lowest index)
+//! 3. worker `i` runs only the units that landed on shard `i`
+//!
+//! Durations are kept per report suite in a JSON file, by default
+//! `deploy/reports/test-durations.json`. The test commands fold the results
+//! they collect back into that file only when it is named with
+//! `--durations`, so a plain run never dirties the checkout. Pointing
+//! `--durations` at a directory of JUnit XML files instead (e.g. the
+//! previous pipeline's shard artifacts) reads the history from those.
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use super::test_reports::TestReport;
+
+/// Environment variable carrying a shard's files (space-separated, relative
+/// to the suite's working directory) to a configured test command.
+pub const SHARD_FILES_ENV: &str = "FORGE_SHARD_FILES";
+
+/// Weight of a unit when no unit has any history yet
+const DEFAULT_UNIT_SECS: f64 = 1.0;
+
+/// Directories never searched for test files
+const IGNORED_DIRS: &[&str] = &[
+    "node_modules",
+    "dist",
+    "build",
+    "target",
+    "coverage",
+    ".git",
+];
+
+/// One worker's slice of a sharded run (`index` is 1-based).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Shard {
+    pub index: usize,
+    pub total: usize,
+}
+
+impl Shard {
+    /// Parse `i/n`, e.g. `2/4`.
+    pub fn parse(s: &str) -> Result<Self> {
+        let Some((index, total)) = s.split_once('/') else {
+            bail!("Invalid shard '{}': expected i/n (e.g. 2/4)", s);
+        };
+        let index: usize = index
+            .trim()
+            .parse()
+            .with_context(|| format!("Invalid shard index in '{}'", s))?;
+        let total: usize = total
+            .trim()
+            .parse()
+            .with_context(|| format!("Invalid shard count in '{}'", s))?;
+        if total == 0 || index == 0 || index > total {
+            bail!(
+                "Invalid shard '{}': index must be between 1 and {}",
+                s,
+                total
+            );
+        }
+        Ok(Self { index, total })
+    }
+}
+
+impl fmt::Display for Shard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.index, self.total)
+    }
+}
+
+/// Historical duration per report suite, in seconds.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TestDurations {
+    pub suites: BTreeMap<String, f64>,
+}
+
+impl TestDurations {
+    /// Load durations from a JSON file or a directory of JUnit XML files.
+    /// A missing path is an empty history.
+    pub fn load(path: &Path) -> Result<Self> {
+        let mut durations = Self::default();
+        if path.is_dir() {
+            let mut files: Vec<PathBuf> = std::fs::read_dir(path)
+                .with_context(|| format!("Failed to read {}", path.display()))?
+                .filter_map(|entry| entry.ok().map(|e| e.path()))
+                .filter(|p| p.extension().is_some_and(|ext| ext == "xml"))
+                .collect();
+            files.sort();
+            for file in files {
+                let content = std::fs::read_to_string(&file)
+                    .with_context(|| format!("Failed to read {}", file.display()))?;
+                let report = TestReport::parse_junit(&content)
+                    .with_context(|| format!("Failed to parse {}", file.display()))?;
+                durations.record(&report);
+            }
+        } else if path.exists() {
+            let content = std::fs::read_to_string(path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            durations = serde_json::from_str(&content)
+                .with_context(|| format!("Failed to parse {}", path.display()))?;
+        }
+        Ok(durations)
+    }
+
+    /// Save as JSON. A directory of JUnit files is left untouched.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if path.is_dir() {
+            return Ok(());
+        }
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        let json = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, json).with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// Replace the durations of every suite present in `report`.
+    pub fn record(&mut self, report: &TestReport) {
+        let mut totals: BTreeMap<&str, f64> = BTreeMap::new();
+        for case in &report.cases {
+            *totals.entry(&case.suite).or_default() += case.duration_secs;
+        }
+        for (suite, secs) in totals {
+            self.suites.insert(suite.to_string(), secs);
+        }
+    }
+
+    /// Duration of a unit: the suite named exactly `unit` plus every suite
+    /// nested under it (`unit/...`).
+    pub fn unit_secs(&self, unit: &str) -> Option<f64> {
+        let nested = format!("{}/", unit);
+        let mut found = false;
+        let mut secs = 0.0;
+        for (suite, d) in &self.suites {
+            if suite == unit || suite.starts_with(&nested) {
+                found = true;
+                secs += d;
+            }
+        }
+        found.then_some(secs)
+    }
+}
+
+/// Default location of the duration history below a product root.
+pub fn default_durations_path(root: &Path) -> PathBuf {
+    root.join("deploy/reports/test-durations.json")
+}
+
+/// The units assigned to one shard.
+#[derive(Debug, Clone)]
+pub struct ShardPlan {
+    pub shard: Shard,
+    pub units: BTreeSet<String>,
+    pub estimated_secs: f64,
+    /// Number of units across all shards
+    pub total_units: usize,
+}
+
+impl ShardPlan {
+    /// Partition `units` across `shard.total` shards and keep `shard`'s.
+    pub fn new(shard: Shard, units: &[String], durations: &TestDurations) -> Self {
+        let known: Vec<f64> = units
+            .iter()
+            .filter_map(|u| durations.unit_secs(u))
+            .collect();
+        let fallback = if known.is_empty() {
+            DEFAULT_UNIT_SECS
+        } else {
+            known.iter().sum::<f64>() / known.len() as f64
+        };
+
+        let unique: BTreeSet<&String> = units.iter().collect();
+        let mut weighted: Vec<(&String, f64)> = unique
+            .into_iter()
+            .map(|u| (u, durations.unit_secs(u).unwrap_or(fallback)))
+            .collect();
+        weighted.sort_by(|(a, da), (b, db)| db.total_cmp(da).then_with(|| a.cmp(b)));
+
+        let mut loads = vec![0.0_f64; shard.total];
+        let mut assigned = BTreeSet::new();
+        for (unit, secs) in &weighted {
+            let lightest = loads
+                .iter()
+                .enumerate()
+                .min_by(|(i, a), (j, b)| a.total_cmp(b).then_with(|| i.cmp(j)))
+                .map(|(i, _)| i)
+                .unwrap_or(0);
+            loads[lightest] += secs;
+            if lightest + 1 == shard.index {
+                assigned.insert((*unit).clone());
+            }
+        }
+
+        Self {
+            shard,
+            units: assigned,
+            estimated_secs: loads[shard.index - 1],
+            total_units: weighted.len(),
+        }
+    }
+
+    pub fn contains(&self, unit: &str) -> bool {
+        self.units.contains(unit)
+    }
+
+    /// This shard's units nested under `prefix/`, with the prefix removed.
+    pub fn files_under(&self, prefix: &str) -> Vec<String> {
+        let nested = format!("{}/", prefix);
+        self.units
+            .iter()
+            .filter_map(|u| u.strip_prefix(&nested).map(str::to_string))
+            .collect()
+    }
+
+    pub fn print_summary(&self) {
+        println!(
+            "Shard {}: {} of {} test units (~{:.0}s estimated)",
+            self.shard,
+            self.units.len(),
+            self.total_units,
+            self.estimated_secs
+        );
+    }
+}
+
+/// Vitest test files below `dir` (`*.test.*` / `*.spec.*`), relative to
+/// `dir`, skipping dependency/build directories and `e2e` folders.
+pub fn find_vitest_files(dir: &Path) -> Vec<String> {
+    find_test_files(dir, &["e2e"], |name| {
+        name.contains(".test.") || name.contains(".spec.")
+    })
+}
+
+/// Playwright spec files below `dir` (`*.spec.*`), relative to `dir`.
+pub fn find_playwright_specs(dir: &Path) -> Vec<String> {
+    find_test_files(dir, &[], |name| name.contains(".spec."))
+}
+
+/// Cargo integration test targets of a crate: `tests/<name>.rs` and
+/// `tests/<name>/main.rs`.
+pub fn find_rust_test_targets(crate_dir: &Path) -> Vec<String> {
+    let Ok(entries) = std::fs::read_dir(crate_dir.join("tests")) else {
+        return Vec::new();
+    };
+    let mut targets: Vec<String> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter_map(|path| {
+            if path.is_dir() {
+                path.join("main.rs")
+                    .exists()
+                    .then(|| path.file_name()?.to_str().map(str::to_string))
+                    .flatten()
+            } else if path.extension().is_some_and(|ext| ext == "rs") {
+                path.file_stem()?.to_str().map(str::to_string)
+            } else {
+                None
+            }
+        })
+        .collect();
+    targets.sort();
+    targets
+}
+
+fn find_test_files(dir: &Path, skip_dirs: &[&str], is_test: impl Fn(&str) -> bool) -> Vec<String> {
+    const SCRIPT_EXTENSIONS: &[&str] = &["ts", "tsx", "js", "jsx", "mts", "mjs"];
+    let mut files: Vec<String> = walkdir::WalkDir::new(dir)
+        .into_iter()
+        .filter_entry(|entry| {
+            entry.depth() == 0
+                || !entry.file_type().is_dir()
+                || entry
+                    .file_name()
+                    .to_str()
+                    .is_some_and(|name| !IGNORED_DIRS.contains(&name) && !skip_dirs.contains(&name))
+        })
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .filter(|entry| {
+            let path = entry.path();
+            path.extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| SCRIPT_EXTENSIONS.contains(&ext))
+                && path
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(&is_test)
+        })
+        .filter_map(|entry| {
+            entry
+                .path()
+                .strip_prefix(dir)
+                .ok()
+                .map(|p| p.to_string_lossy().replace('\\', "/"))
+        })
+        .collect();
+    files.sort();
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_reports::{TestCase, TestStatus};
+
+    fn case(suite: &str, name: &str, secs: f64) -> TestCase {
+        TestCase {
+            suite: suite.to_string(),
+            name: name.to_string(),
+            status: TestStatus::Passed,
+            duration_secs: secs,
+            message: None,
+            retries: 0,
+        }
+    }
+
+    fn units(names: &[&str]) -> Vec<String> {
+        names.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_shard_parse() {
+        assert_eq!(Shard::parse("2/4").unwrap(), Shard { index: 2, total: 4 });
+        assert_eq!(Shard::parse("1/1").unwrap().to_string(), "1/1");
+        for bad in ["0/4", "5/4", "1/0", "2", "a/b", "1/-2"] {
+            assert!(Shard::parse(bad).is_err(), "{} should be rejected", bad);
+        }
+    }
+
+    #[test]
+    fn test_durations_record_and_unit_secs() {
+        let mut durations = TestDurations::default();
+        durations.record(&TestReport {
+            cases: vec![
+                case("backend-unit/backend", "a", 1.5),
+                case("backend-unit/backend", "b", 0.5),
+                case("frontend-unit/src/a.test.ts", "renders", 3.0),
+            ],
+        });
+        assert_eq!(durations.unit_secs("backend-unit"), Some(2.0));
+        assert_eq!(
+            durations.unit_secs("frontend-unit/src/a.test.ts"),
+            Some(3.0)
+        );
+        assert_eq!(durations.unit_secs("frontend-unit/src/b.test.ts"), None);
+        // `backend` must not match `backend-unit`
+        assert_eq!(durations.unit_secs("backend"), None);
+
+        // A later run replaces the suites it ran and keeps the others
+        durations.record(&TestReport {
+            cases: vec![case("backend-unit/backend", "a", 4.0)],
+        });
+        assert_eq!(durations.unit_secs("backend-unit"), Some(4.0));
+        assert_eq!(durations.unit_secs("frontend-unit"), Some(3.0));
+    }
+
+    #[test]
+    fn test_durations_save_load_roundtrip_and_junit_dir() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let mut durations = TestDurations::default();
+        durations.suites.insert("e2e".to_string(), 42.0);
+        let path = tmp.path().join("reports/test-durations.json");
+        durations.save(&path).unwrap();
+        assert_eq!(TestDurations::load(&path).unwrap(), durations);
+        assert_eq!(
+            TestDurations::load(&tmp.path().join("missing.json")).unwrap(),
+            TestDurations::default()
+        );
+
+        let junit_dir = tmp.path().join("junit");
+        std::fs::create_dir_all(&junit_dir).unwrap();
+        let report = TestReport {
+            cases: vec![case("e2e/e2e_tests", "login", 7.0)],
+        };
+        report
+            .write_junit(&junit_dir.join("shard-1.xml"), "test-pyramid")
+            .unwrap();
+        std::fs::write(junit_dir.join("notes.txt"), "ignored").unwrap();
+        let loaded = TestDurations::load(&junit_dir).unwrap();
+        assert_eq!(loaded.unit_secs("e2e"), Some(7.0));
+    }
+
+    #[test]
+    fn test_shard_plan_balances_by_duration() {
+        let mut durations = TestDurations::default();
+        for (suite, secs) in [("a", 10.0), ("b", 6.0), ("c", 5.0), ("d", 4.0), ("e", 1.0)] {
+            durations.suites.insert(suite.to_string(), secs);
+        }
+        let all = units(&["a", "b", "c", "d", "e"]);
+        let first = ShardPlan::new(Shard::parse("1/2").unwrap(), &all, &durations);
+        let second = ShardPlan::new(Shard::parse("2/2").unwrap(), &all, &durations);
+
+        // Heaviest first onto the lightest shard: a→1, b→2, c→2, d→1, e→2
+        assert_eq!(
+            first.units,
+            ["a", "d"].iter().map(|s| s.to_string()).collect()
+        );
+        assert_eq!(
+            second.units,
+            ["b", "c", "e"].iter().map(|s| s.to_string()).collect()
+        );
+        assert_eq!(first.estimated_secs, 14.0);
+        assert_eq!(second.estimated_secs, 12.0);
+    }
+
+    #[test]
+    fn test_shard_plan_covers_every_unit_once_and_is_order_independent() {
+        let mut durations = TestDurations::default();
+        durations
+            .suites
+            .insert("frontend-unit/src/slow.test.ts".to_string(), 30.0);
+        let all = units(&[
+            "backend-unit",
+            "backend-integration",
+            "e2e",
+            "frontend-unit/src/slow.test.ts",
+            "frontend-unit/src/a.test.ts",
+            "frontend-unit/src/b.test.ts",
+        ]);
+        let mut reversed = all.clone();
+        reversed.reverse();
+
+        let mut seen = BTreeSet::new();
+        for index in 1..=3 {
+            let shard = Shard { index, total: 3 };
+            let plan = ShardPlan::new(shard, &all, &durations);
+            assert_eq!(
+                plan.units,
+                ShardPlan::new(shard, &reversed, &durations).units
+            );
+            for unit in &plan.units {
+                assert!(seen.insert(unit.clone()), "{} assigned twice", unit);
+            }
+        }
+        assert_eq!(seen.len(), all.len());
+    }
+
+    #[test]
+    fn test_files_under_strips_prefix() {
+        let plan = ShardPlan {
+            shard: Shard { index: 1, total: 1 },
+            units: [
+                "backend-unit",
+                "frontend-unit/src/a.test.ts",
+                "frontend-unit-x",
+            ]
+            .iter()
+            .map(|s| s.to_string())
+            .collect(),
+            estimated_secs: 0.0,
+            total_units: 3,
+        };
+        assert_eq!(plan.files_under("frontend-unit"), vec!["src/a.test.ts"]);
+        assert!(plan.contains("backend-unit"));
+    }
+
+    #[test]
+    fn test_find_test_files() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let root = tmp.path();
+        for file in [
+            "src/a.test.ts",
+            "src/lib/b.spec.tsx",
+            "src/lib/util.ts",
+            "e2e/login.spec.ts",
+            "node_modules/pkg/x.test.js",
+            "tests/api.rs",
+            "tests/flows/main.rs",
+            "tests/fixtures/data.json",
+        ] {
+            let path = root.join(file);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, "").unwrap();
+        }
+
+        assert_eq!(
+            find_vitest_files(root),
+            vec!["src/a.test.ts", "src/lib/b.spec.tsx"]
+        );
+        assert_eq!(
+            find_playwright_specs(&root.join("e2e")),
+            vec!["login.spec.ts"]
+        );
+        assert_eq!(find_rust_test_targets(root), vec!["api", "flows"]);
+    }
+}
@@ -0,0 +1,893 @@
+//! Release monitoring dashboard (`forge watch`)
+//!
+//! A full-screen terminal view of one product environment during a
+//! release, replacing the usual juggling of `forge rollout`, `flux get`
+//! and `kubectl logs` in separate terminals:
+//!
+//! - per-service rollout progress (pods ready on the Deployment's image tag)
+//! - pod states from [`k8s::get_pod_statuses`]
+//! - readiness of the product's `{product}-{environment}*` kustomizations
+//! - migration Job status
+//! - a live tail of the first failing pod's logs
+//!
+//! Keybindings: `↑`/`↓` (or `k`/`j`) select a service, `r` reconciles the
+//! product kustomization, `b` rolls the selected service back (confirmed
+//! with `y`), `q` quits.
+
+use anyhow::{bail, Result};
+use kube::Client;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Cell, Paragraph, Row, Table, Wrap};
+use ratatui::Frame;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+use super::fleet_status;
+use super::rollout;
+use super::status::{self, MigrationInfo};
+use crate::config::{self, DeployConfig};
+use crate::flux_get::{self, KustomizationRow};
+use crate::flux_reconcile;
+use crate::k8s::{self, PodStatus};
+
+/// Number of log lines tailed from a failing pod.
+const LOG_TAIL_LINES: i64 = 20;
+
+// ============================================================================
+// Data Structures
+// ============================================================================
+
+/// What the dashboard watches: one product environment.
+#[derive(Debug, Clone)]
+pub struct WatchTarget {
+    pub product: String,
+    pub environment: String,
+    /// Root `{product}-{environment}` kustomization in `flux-system`.
+    pub kustomization: String,
+    pub services: Vec<ServiceTarget>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ServiceTarget {
+    pub name: String,
+    pub namespace: String,
+    pub deployment: String,
+}
+
+/// One refresh worth of cluster state.
+#[derive(Debug, Clone, Default)]
+pub struct Snapshot {
+    pub services: Vec<ServicePanel>,
+    pub flux: Vec<KustomizationRow>,
+    pub flux_error: Option<String>,
+    pub migrations: Vec<(String, MigrationInfo)>,
+    pub logs: Option<LogTail>,
+}
+
+/// Live state of one service.
+#[derive(Debug, Clone, Default)]
+pub struct ServicePanel {
+    pub name: String,
+    pub namespace: String,
+    pub deployment: String,
+    pub desired: i32,
+    /// Tag of the Deployment's pod template — the tag being rolled out.
+    pub expected_tag: Option<String>,
+    pub pods: Vec<PodStatus>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct LogTail {
+    pub pod: String,
+    pub lines: Vec<String>,
+}
+
+impl ServicePanel {
+    /// Pods that are ready and running the expected tag.
+    pub fn updated_ready(&self) -> usize {
+        self.pods
+            .iter()
+            .filter(|p| p.ready && Some(&p.image_tag) == self.expected_tag.as_ref())
+            .count()
+    }
+
+    /// `true` once every desired replica is ready on the expected tag.
+    pub fn is_rolled_out(&self) -> bool {
+        self.error.is_none() && self.updated_ready() >= self.desired.max(0) as usize
+    }
+
+    /// First pod worth tailing: stuck in a bad container state, or not
+    /// ready after at least one restart.
+    pub fn failing_pod(&self) -> Option<&PodStatus> {
+        self.pods.iter().find(|p| {
+            k8s::is_bad_state(p)
+                || (!p.ready
+                    && p.container_state
+                        .as_ref()
+                        .is_some_and(|s| s.restart_count > 0))
+        })
+    }
+}
+
+/// An operator action triggered from the dashboard.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Action {
+    Reconcile {
+        kustomization: String,
+    },
+    Rollback {
+        namespace: String,
+        deployment: String,
+    },
+}
+
+/// Dashboard state and key handling, independent of the terminal.
+#[derive(Debug)]
+pub struct App {
+    pub target: WatchTarget,
+    pub snapshot: Snapshot,
+    pub selected: usize,
+    /// Destructive action awaiting `y`/`n`.
+    pub pending: Option<Action>,
+    /// An action is running in the background.
+    pub busy: bool,
+    pub status_line: String,
+    pub quit: bool,
+}
+
+impl App {
+    pub fn new(target: WatchTarget) -> Self {
+        Self {
+            target,
+            snapshot: Snapshot::default(),
+            selected: 0,
+            pending: None,
+            busy: false,
+            status_line: "loading…".to_string(),
+            quit: false,
+        }
+    }
+
+    /// Apply a key press. Returns an action to run when the key
+    /// triggers one (immediately for reconcile, after confirmation for
+    /// rollback).
+    pub fn handle_key(&mut self, code: KeyCode) -> Option<Action> {
+        if let Some(action) = self.pending.take() {
+            return match code {
+                KeyCode::Char('y') | KeyCode::Char('Y') => Some(action),
+                _ => {
+                    self.status_line = "cancelled".to_string();
+                    None
+                }
+            };
+        }
+
+        match code {
+            KeyCode::Char('q') | KeyCode::Esc => self.quit = true,
+            KeyCode::Down | KeyCode::Char('j')
+                if self.selected + 1 < self.target.services.len() =>
+            {
+                self.selected += 1;
+            }
+            KeyCode::Up | KeyCode::Char('k') => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Char('r') => {
+                return Some(Action::Reconcile {
+                    kustomization: self.target.kustomization.clone(),
+                });
+            }
+            KeyCode::Char('b') => {
+                if let Some(svc) = self.target.services.get(self.selected) {
+                    self.status_line = format!(
+                        "roll back deployment/{} in {}? [y/N]",
+                        svc.deployment, svc.namespace
+                    );
+                    self.pending = Some(Action::Rollback {
+                        namespace: svc.namespace.clone(),
+                        deployment: svc.deployment.clone(),
+                    });
+                }
+            }
+            _ => {}
+        }
+        None
+    }
+
+    /// Mark `action` as running. Returns `false`, leaving the running
+    /// action alone, when one is already in flight.
+    pub fn begin(&mut self, action: &Action) -> bool {
+        if self.busy {
+            self.status_line = "an action is still running".to_string();
+            return false;
+        }
+        self.busy = true;
+        self.status_line = match action {
+            Action::Reconcile { kustomization } => format!("reconciling {kustomization}…"),
+            Action::Rollback { deployment, .. } => {
+                format!("rolling back deployment/{deployment}…")
+            }
+        };
+        true
+    }
+
+    /// Record the outcome of the running action.
+    pub fn finish(&mut self, status: String) {
+        self.busy = false;
+        self.status_line = status;
+    }
+}
+
+// ============================================================================
+// Execute Command
+// ============================================================================
+
+/// Execute `forge watch`
+pub async fn execute(
+    product: &str,
+    repo_root: &str,
+    environment: &str,
+    interval: u64,
+) -> Result<()> {
+    let target = resolve_target(product, repo_root, environment)?;
+    let client = k8s::create_client().await?;
+    let mut app = App::new(target);
+
+    // Crossterm's event read is blocking; a reader thread forwards key
+    // presses so the refresh timer and the keyboard share one select loop.
+    let (key_tx, mut key_rx) = mpsc::unbounded_channel();
+    let stop = Arc::new(AtomicBool::new(false));
+    let reader_stop = stop.clone();
+    let reader = std::thread::spawn(move || {
+        while !reader_stop.load(Ordering::Relaxed) {
+            if !event::poll(Duration::from_millis(100)).unwrap_or(false) {
+                continue;
+            }
+            if let Ok(Event::Key(key)) = event::read() {
+                if key.kind == KeyEventKind::Press && key_tx.send(key.code).is_err() {
+                    break;
+                }
+            }
+        }
+    });
+
+    // Actions run as tasks (a reconcile can wait minutes) and report back
+    // here, so the dashboard keeps redrawing and handling keys meanwhile.
+    let (action_tx, mut action_rx) = mpsc::unbounded_channel();
+
+    let mut terminal = ratatui::init();
+    let mut refresh = tokio::time::interval(Duration::from_secs(interval.max(1)));
+    let result: Result<()> = async {
+        while !app.quit {
+            tokio::select! {
+                _ = refresh.tick() => {
+                    app.snapshot = fetch_snapshot(&client, &app.target, app.selected).await;
+                    if app.pending.is_none() && !app.busy {
+                        app.status_line = format!(
+                            "refreshed {}",
+                            chrono::Local::now().format("%H:%M:%S")
+                        );
+                    }
+                }
+                Some(code) = key_rx.recv() => {
+                    if let Some(action) = app.handle_key(code) {
+                        if app.begin(&action) {
+                            let action_tx = action_tx.clone();
+                            tokio::spawn(async move {
+                                let _ = action_tx.send(run_action(&action).await);
+                            });
+                        }
+                    }
+                }
+                Some(status) = action_rx.recv() => {
+                    app.finish(status);
+                    refresh.reset_immediately();
+                }
+            }
+            terminal.draw(|frame| draw(frame, &app))?;
+        }
+        Ok(())
+    }
+    .await;
+
+    ratatui::restore();
+    stop.store(true, Ordering::Relaxed);
+    let _ = reader.join();
+    result
+}
+
+/// Resolve the product's release services into watch targets for `environment`.
+fn resolve_target(product: &str, repo_root: &str, environment: &str) -> Result<WatchTarget> {
+    let product_config = DeployConfig::load_product_config(product, repo_root)?;
+    let services = product_config
+        .release
+        .as_ref()
+        .map(|r| r.services.clone())
+        .unwrap_or_default();
+    if services.is_empty() {
+        bail!(
+            "No services configured in deploy.yaml release.services section for product '{}'",
+            product
+        );
+    }
+
+    let product_dir = config::resolve_product_dir(Path::new(repo_root), product);
+    Ok(WatchTarget {
+        product: product.to_string(),
+        environment: environment.to_string(),
+        kustomization: format!("{product}-{environment}"),
+        services: services
+            .iter()
+            .map(|svc| ServiceTarget {
+                name: svc.name.clone(),
+                namespace: fleet_status::service_namespace(
+                    &product_config,
+                    &svc.path,
+                    repo_root,
+                    environment,
+                ),
+                deployment: fleet_status::service_deployment_name(&product_dir, svc),
+            })
+            .collect(),
+    })
+}
+
+async fn run_action(action: &Action) -> String {
+    match action {
+        Action::Reconcile { kustomization } => {
+            match flux_reconcile::reconcile_kustomization(kustomization, "flux-system", true).await
+            {
+                Ok(()) => format!("reconciled {kustomization}"),
+                Err(e) => format!("reconcile failed: {e}"),
+            }
+        }
+        Action::Rollback {
+            namespace,
+            deployment,
+        } => match rollout::rollback_deployment(namespace, deployment).await {
+            Ok(()) => format!("rollback of deployment/{deployment} initiated"),
+            Err(e) => format!("rollback failed: {e}"),
+        },
+    }
+}
+
+// ============================================================================
+// Data Fetching
+// ============================================================================
+
+async fn fetch_snapshot(client: &Client, target: &WatchTarget, selected: usize) -> Snapshot {
+    let mut snapshot = Snapshot::default();
+
+    for svc in &target.services {
+        let selector = format!("app={}", svc.deployment);
+        let (replicas, tag, pods, migrations) = tokio::join!(
+            k8s::get_replicas(client, &svc.namespace, &svc.deployment),
+            k8s::get_expected_image_tag(client, &svc.namespace, &svc.deployment),
+            k8s::get_pod_statuses(client, &svc.namespace, &selector),
+            status::fetch_migrations(client, &svc.namespace, &svc.deployment),
+        );
+
+        let mut panel = ServicePanel {
+            name: svc.name.clone(),
+            namespace: svc.namespace.clone(),
+            deployment: svc.deployment.clone(),
+            ..ServicePanel::default()
+        };
+        match (replicas, pods) {
+            (Ok(desired), Ok(pods)) => {
+                panel.desired = desired;
+                panel.pods = pods;
+                panel.expected_tag = tag.ok();
+            }
+            (Err(e), _) | (_, Err(e)) => panel.error = Some(format!("{e:#}")),
+        }
+        if let Ok(jobs) = migrations {
+            snapshot
+                .migrations
+                .extend(jobs.into_iter().map(|job| (svc.name.clone(), job)));
+        }
+        snapshot.services.push(panel);
+    }
+
+    match flux_get::list_kustomizations_in_namespace("flux-system").await {
+        Ok(rows) => snapshot.flux = product_kustomizations(&target.kustomization, rows),
+        Err(e) => snapshot.flux_error = Some(e.to_string()),
+    }
+
+    if let Some((namespace, pod)) = log_candidate(&snapshot.services, selected) {
+        let lines = match k8s::get_pod_logs(client, &namespace, &pod, LOG_TAIL_LINES).await {
+            Ok(logs) => logs.lines().map(str::to_string).collect(),
+            Err(e) => vec![format!("{e:#}")],
+        };
+        snapshot.logs = Some(LogTail { pod, lines });
+    }
+
+    snapshot
+}
+
+/// The root kustomization and its phase siblings (`-init`, `-secrets`, ...).
+pub fn product_kustomizations(root: &str, rows: Vec<KustomizationRow>) -> Vec<KustomizationRow> {
+    let phase_prefix = format!("{root}-");
+    rows.into_iter()
+        .filter(|r| r.name == root || r.name.starts_with(&phase_prefix))
+        .collect()
+}
+
+/// Pod whose logs to tail: the selected service's failing pod, else the
+/// first failing pod of any service. Returns `(namespace, pod)`.
+pub fn log_candidate(services: &[ServicePanel], selected: usize) -> Option<(String, String)> {
+    services
+        .get(selected)
+        .into_iter()
+        .chain(services.iter())
+        .find_map(|svc| {
+            svc.failing_pod()
+                .map(|pod| (svc.namespace.clone(), pod.name.clone()))
+        })
+}
+
+// ============================================================================
+// Rendering
+// ============================================================================
+
+pub fn draw(frame: &mut Frame, app: &App) {
+    let [header, services, pods, bottom, logs, footer] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Length(app.target.services.len() as u16 + 3),
+        Constraint::Min(5),
+        Constraint::Length(8),
+        Constraint::Length(LOG_TAIL_LINES as u16 / 2 + 2),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+    let [flux, migrations] =
+        Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)]).areas(bottom);
+
+    frame.render_widget(
+        Paragraph::new(Line::from(vec![
+            Span::styled(
+                "forge watch ",
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(format!(
+                "{} / {}",
+                app.target.product, app.target.environment
+            )),
+        ])),
+        header,
+    );
+    draw_services(frame, services, app);
+    draw_pods(frame, pods, app);
+    draw_flux(frame, flux, &app.snapshot);
+    draw_migrations(frame, migrations, &app.snapshot);
+    draw_logs(frame, logs, &app.snapshot);
+    frame.render_widget(
+        Paragraph::new(format!(
+            "↑↓ select  r reconcile  b rollback  q quit   {}",
+            app.status_line
+        ))
+        .style(Style::default().fg(Color::DarkGray)),
+        footer,
+    );
+}
+
+fn draw_services(frame: &mut Frame, area: Rect, app: &App) {
+    let rows = app.snapshot.services.iter().enumerate().map(|(i, svc)| {
+        let (progress, color) = match &svc.error {
+            Some(err) => (
+                err.lines().next().unwrap_or_default().to_string(),
+                Color::Red,
+            ),
+            None => (
+                format!("{}/{} updated", svc.updated_ready(), svc.desired),
+                if svc.is_rolled_out() {
+                    Color::Green
+                } else {
+                    Color::Yellow
+                },
+            ),
+        };
+        let style = if i == app.selected {
+            Style::default().add_modifier(Modifier::REVERSED)
+        } else {
+            Style::default()
+        };
+        Row::new(vec![
+            Cell::from(svc.name.clone()),
+            Cell::from(svc.namespace.clone()),
+            Cell::from(svc.expected_tag.clone().unwrap_or_default()),
+            Cell::from(progress).style(Style::default().fg(color)),
+        ])
+        .style(style)
+    });
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Percentage(20),
+            Constraint::Percentage(25),
+            Constraint::Percentage(25),
+            Constraint::Percentage(30),
+        ],
+    )
+    .header(Row::new(vec![
+        "SERVICE",
+        "NAMESPACE",
+        "TARGET TAG",
+        "ROLLOUT",
+    ]))
+    .block(Block::default().borders(Borders::ALL).title(" Services "));
+    frame.render_widget(table, area);
+}
+
+fn draw_pods(frame: &mut Frame, area: Rect, app: &App) {
+    let pods = app
+        .snapshot
+        .services
+        .get(app.selected)
+        .map(|svc| svc.pods.as_slice())
+        .unwrap_or_default();
+    let rows = pods.iter().map(|pod| {
+        let state = pod
+            .container_state
+            .as_ref()
+            .map(|s| s.reason.clone().unwrap_or_else(|| s.state.clone()))
+            .unwrap_or_else(|| pod.phase.clone());
+        let restarts = pod.container_state.as_ref().map_or(0, |s| s.restart_count);
+        let color = if k8s::is_bad_state(pod) {
+            Color::Red
+        } else if pod.ready {
+            Color::Green
+        } else {
+            Color::Yellow
+        };
+        Row::new(vec![
+            pod.name.clone(),
+            pod.image_tag.clone(),
+            state,
+            restarts.to_string(),
+        ])
+        .style(Style::default().fg(color))
+    });
+    let title = app
+        .snapshot
+        .services
+        .get(app.selected)
+        .map(|svc| format!(" Pods: {} ", svc.deployment))
+        .unwrap_or_else(|| " Pods ".to_string());
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Percentage(40),
+            Constraint::Percentage(25),
+            Constraint::Percentage(25),
+            Constraint::Percentage(10),
+        ],
+    )
+    .header(Row::new(vec!["POD", "TAG", "STATE", "RESTARTS"]))
+    .block(Block::default().borders(Borders::ALL).title(title));
+    frame.render_widget(table, area);
+}
+
+fn draw_flux(frame: &mut Frame, area: Rect, snapshot: &Snapshot) {
+    let lines: Vec<Line> = match &snapshot.flux_error {
+        Some(err) => vec![Line::styled(err.clone(), Style::default().fg(Color::Red))],
+        None => snapshot
+            .flux
+            .iter()
+            .map(|row| {
+                let (icon, color) = if row.is_ready() {
+                    ("✓", Color::Green)
+                } else {
+                    ("✗", Color::Red)
+                };
+                Line::styled(
+                    format!("{icon} {} {}", row.name, row.revision),
+                    Style::default().fg(color),
+                )
+            })
+            .collect(),
+    };
+    frame.render_widget(
+        Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title(" Flux ")),
+        area,
+    );
+}
+
+fn draw_migrations(frame: &mut Frame, area: Rect, snapshot: &Snapshot) {
+    let lines: Vec<Line> = snapshot
+        .migrations
+        .iter()
+        .map(|(service, job)| {
+            let color = match job.status.as_str() {
+                "Succeeded" => Color::Green,
+                "Failed" => Color::Red,
+                _ => Color::Yellow,
+            };
+            Line::styled(
+                format!("{service}: {} {}", job.name, job.status),
+                Style::default().fg(color),
+            )
+        })
+        .collect();
+    frame.render_widget(
+        Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title(" Migrations ")),
+        area,
+    );
+}
+
+fn draw_logs(frame: &mut Frame, area: Rect, snapshot: &Snapshot) {
+    let (title, lines) = match &snapshot.logs {
+        Some(tail) => {
+            // Show the newest lines that fit inside the border.
+            let visible = area.height.saturating_sub(2) as usize;
+            let skip = tail.lines.len().saturating_sub(visible);
+            (
+                format!(" Logs: {} ", tail.pod),
+                tail.lines[skip..]
+                    .iter()
+                    .map(|l| Line::raw(l.clone()))
+                    .collect(),
+            )
+        }
+        None => (" Logs ".to_string(), vec![Line::raw("no failing pods")]),
+    };
+    frame.render_widget(
+        Paragraph::new(lines)
+            .wrap(Wrap { trim: false })
+            .block(Block::default().borders(Borders::ALL).title(title)),
+        area,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::k8s::ContainerStateInfo;
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+
+    fn target() -> WatchTarget {
+        WatchTarget {
+            product: "acme".to_string(),
+            environment: "staging".to_string(),
+            kustomization: "acme-staging".to_string(),
+            services: ["api", "web"]
+                .iter()
+                .map(|name| ServiceTarget {
+                    name: name.to_string(),
+                    namespace: "acme-staging".to_string(),
+                    deployment: format!("acme-{name}"),
+                })
+                .collect(),
+        }
+    }
+
+    fn pod(name: &str, tag: &str, ready: bool, reason: Option<&str>, restarts: i32) -> PodStatus {
+        PodStatus {
+            name: name.to_string(),
+            phase: "Running".to_string(),
+            ready,
+            image_tag: tag.to_string(),
+            container_state: Some(ContainerStateInfo {
+                state: if reason.is_some() {
+                    "waiting"
+                } else {
+                    "running"
+                }
+                .to_string(),
+                reason: reason.map(str::to_string),
+                message: None,
+                restart_count: restarts,
+                started: ready,
+            }),
+            creation_time: None,
+        }
+    }
+
+    fn panel(pods: Vec<PodStatus>) -> ServicePanel {
+        ServicePanel {
+            name: "api".to_string(),
+            namespace: "acme-staging".to_string(),
+            deployment: "acme-api".to_string(),
+            desired: 2,
+            expected_tag: Some("amd64-new".to_string()),
+            pods,
+            error: None,
+        }
+    }
+
+    fn kustomization(name: &str) -> KustomizationRow {
+        KustomizationRow {
+            namespace: "flux-system".to_string(),
+            name: name.to_string(),
+            revision: "main@sha1:abc".to_string(),
+            suspended: "False".to_string(),
+            ready: "True".to_string(),
+            message: String::new(),
+        }
+    }
+
+    #[test]
+    fn rollout_progress_counts_only_ready_pods_on_expected_tag() {
+        let svc = panel(vec![
+            pod("a", "amd64-new", true, None, 0),
+            pod("b", "amd64-old", true, None, 0),
+            pod("c", "amd64-new", false, None, 0),
+        ]);
+        assert_eq!(svc.updated_ready(), 1);
+        assert!(!svc.is_rolled_out());
+
+        let done = panel(vec![
+            pod("a", "amd64-new", true, None, 0),
+            pod("b", "amd64-new", true, None, 0),
+        ]);
+        assert!(done.is_rolled_out());
+    }
+
+    #[test]
+    fn failing_pod_prefers_bad_state_or_restarted_unready_pods() {
+        let svc = panel(vec![
+            pod("starting", "amd64-new", false, None, 0),
+            pod("crashing", "amd64-new", false, Some("CrashLoopBackOff"), 4),
+        ]);
+        assert_eq!(svc.failing_pod().map(|p| p.name.as_str()), Some("crashing"));
+
+        let healthy = panel(vec![pod("ok", "amd64-new", true, None, 0)]);
+        assert!(healthy.failing_pod().is_none());
+    }
+
+    #[test]
+    fn log_candidate_prefers_selected_service_then_any_failing() {
+        let mut failing = panel(vec![pod("api-1", "t", false, Some("ErrImagePull"), 0)]);
+        failing.namespace = "ns-api".to_string();
+        let healthy = panel(vec![pod("web-1", "t", true, None, 0)]);
+
+        let services = vec![failing, healthy];
+        assert_eq!(
+            log_candidate(&services, 1),
+            Some(("ns-api".to_string(), "api-1".to_string()))
+        );
+        assert_eq!(log_candidate(&services[1..], 0), None);
+    }
+
+    #[test]
+    fn product_kustomizations_keeps_root_and_phase_siblings() {
+        let rows = vec![
+            kustomization("acme-staging"),
+            kustomization("acme-staging-init"),
+            kustomization("acme-staging-migrations"),
+            kustomization("acme-production"),
+            kustomization("acme-stagingx"),
+        ];
+        let names: Vec<_> = product_kustomizations("acme-staging", rows)
+            .into_iter()
+            .map(|r| r.name)
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                "acme-staging",
+                "acme-staging-init",
+                "acme-staging-migrations"
+            ]
+        );
+    }
+
+    #[test]
+    fn handle_key_moves_selection_within_bounds() {
+        let mut app = App::new(target());
+        app.handle_key(KeyCode::Up);
+        assert_eq!(app.selected, 0);
+        app.handle_key(KeyCode::Down);
+        app.handle_key(KeyCode::Char('j'));
+        assert_eq!(app.selected, 1);
+        app.handle_key(KeyCode::Char('k'));
+        assert_eq!(app.selected, 0);
+    }
+
+    #[test]
+    fn handle_key_reconcile_runs_immediately() {
+        let mut app = App::new(target());
+        assert_eq!(
+            app.handle_key(KeyCode::Char('r')),
+            Some(Action::Reconcile {
+                kustomization: "acme-staging".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn handle_key_rollback_requires_confirmation() {
+        let mut app = App::new(target());
+        app.handle_key(KeyCode::Down);
+
+        assert_eq!(app.handle_key(KeyCode::Char('b')), None);
+        assert!(app.status_line.contains("deployment/acme-web"));
+        assert_eq!(
+            app.handle_key(KeyCode::Char('y')),
+            Some(Action::Rollback {
+                namespace: "acme-staging".to_string(),
+                deployment: "acme-web".to_string(),
+            })
+        );
+
+        app.handle_key(KeyCode::Char('b'));
+        assert_eq!(app.handle_key(KeyCode::Char('n')), None);
+        assert!(app.pending.is_none());
+        assert!(
+            !app.quit,
+            "declining a rollback must not quit the dashboard"
+        );
+    }
+
+    #[test]
+    fn begin_refuses_a_second_action_while_one_runs() {
+        let mut app = App::new(target());
+        let reconcile = Action::Reconcile {
+            kustomization: "acme-staging".to_string(),
+        };
+
+        assert!(app.begin(&reconcile));
+        assert_eq!(app.status_line, "reconciling acme-staging…");
+        assert!(!app.begin(&reconcile));
+        assert!(app.busy);
+
+        app.finish("reconciled acme-staging".to_string());
+        assert!(!app.busy);
+        assert_eq!(app.status_line, "reconciled acme-staging");
+        assert!(app.begin(&reconcile));
+    }
+
+    #[test]
+    fn handle_key_quits_on_q() {
+        let mut app = App::new(target());
+        app.handle_key(KeyCode::Char('q'));
+        assert!(app.quit);
+    }
+
+    #[test]
+    fn draw_renders_services_pods_flux_and_logs() {
+        let mut app = App::new(target());
+        app.snapshot = Snapshot {
+            services: vec![panel(vec![pod(
+                "acme-api-7d9",
+                "amd64-new",
+                false,
+                Some("CrashLoopBackOff"),
+                3,
+            )])],
+            flux: vec![kustomization("acme-staging")],
+            flux_error: None,
+            migrations: Vec::new(),
+            logs: Some(LogTail {
+                pod: "acme-api-7d9".to_string(),
+                lines: vec!["panicked at config".to_string()],
+            }),
+        };
+
+        let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+        terminal.draw(|frame| draw(frame, &app)).unwrap();
+        let screen: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|c| c.symbol())
+            .collect();
+
+        for needle in [
+            "acme / staging",
+            "0/2 updated",
+            "CrashLoopBackOff",
+            "acme-staging main@sha1:abc",
+            "panicked at config",
+        ] {
+            assert!(screen.contains(needle), "missing {needle:?} on screen");
+        }
+    }
+}
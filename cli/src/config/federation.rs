@@ -29,6 +29,60 @@ pub struct FederationConfig {
     /// - External: "https://staging.example.com"
     #[serde(default)]
     pub bff_admin_url: Option<String>,
+
+    /// Supergraph composer (default: rover)
+    ///
+    /// `rover` shells out to `rover-fhs supergraph compose` as before;
+    /// `native` (opt-in) composes in-process and needs no external tooling.
+    #[serde(default)]
+    pub composer: Composer,
+
+    /// Client operations validated against every newly composed supergraph
+    #[serde(default)]
+    pub operations: OperationChecksConfig,
+}
+
+/// Sources of the client operations checked against a new supergraph
+///
+/// ```yaml
+/// federation:
+///   operations:
+///     manifest: web/persisted-query-manifest.json
+///     documents: true
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OperationChecksConfig {
+    /// Persisted-query manifest, relative to the product directory
+    /// (Apollo format, or codegen's `persisted-documents.json`)
+    #[serde(default)]
+    pub manifest: Option<String>,
+
+    /// Collect operations from the GraphQL documents under the product's
+    /// `dirs.web` (default: true)
+    #[serde(default = "default_operation_documents")]
+    pub documents: bool,
+}
+
+fn default_operation_documents() -> bool {
+    true
+}
+
+impl Default for OperationChecksConfig {
+    fn default() -> Self {
+        Self {
+            manifest: None,
+            documents: default_operation_documents(),
+        }
+    }
+}
+
+/// How the supergraph is composed from subgraph schemas
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Composer {
+    Native,
+    #[default]
+    Rover,
 }
 
 fn default_federation_port() -> u16 {
@@ -50,6 +104,8 @@ impl Default for FederationConfig {
             protocol: default_federation_protocol(),
             routing_url_pattern: default_routing_url_pattern(),
             bff_admin_url: None,
+            composer: Composer::default(),
+            operations: OperationChecksConfig::default(),
         }
     }
 }
@@ -104,6 +160,12 @@ pub struct ServiceFederationConfig {
     /// If set, overrides the global federation.bff_admin_url
     #[serde(default)]
     pub bff_admin_url: Option<String>,
+
+    /// Schema coordinates of breaking changes accepted for release
+    /// (e.g. "Product.legacyCode", "Query.products(limit:)", "Status.ARCHIVED")
+    /// Unlisted breaking changes to the subgraph block the federation update
+    #[serde(default)]
+    pub acknowledged_breaking_changes: Vec<String>,
 }
 
 fn default_federation_enabled() -> bool {
@@ -140,6 +202,7 @@ impl Default for ServiceFederationConfig {
             supergraph_router_path: None,
             hive_router_deployment_path: None,
             bff_admin_url: None,
+            acknowledged_breaking_changes: vec![],
         }
     }
 }
@@ -238,6 +301,16 @@ pub struct ServiceFederationTestsConfig {
     /// Example: "amd64-960d3cbb78"
     #[serde(default)]
     pub image_tag: Option<String>,
+
+    /// Number of stored test reports kept per service; flaky-test
+    /// detection looks at this many most recent runs. 0 disables storing.
+    #[serde(default = "default_federation_tests_report_history")]
+    pub report_history: usize,
+
+    /// Directory (relative to the product directory) for stored test
+    /// reports. Default: "target/reports/federation-tests"
+    #[serde(default)]
+    pub report_dir: Option<String>,
 }
 
 fn default_federation_tests_enabled() -> bool {
@@ -260,6 +333,10 @@ fn default_federation_tests_router_url() -> String {
     "http://hive-router:4000/graphql".to_string()
 }
 
+fn default_federation_tests_report_history() -> usize {
+    10
+}
+
 impl Default for ServiceFederationTestsConfig {
     fn default() -> Self {
         Self {
@@ -271,6 +348,8 @@ impl Default for ServiceFederationTestsConfig {
             job_name_pattern: None,
             namespace_pattern: None,
             image_tag: None,
+            report_history: default_federation_tests_report_history(),
+            report_dir: None,
         }
     }
 }
@@ -396,6 +475,9 @@ mod tests {
         assert_eq!(config.protocol, "http");
         assert!(config.routing_url_pattern.contains("{service}"));
         assert!(config.bff_admin_url.is_none());
+        assert_eq!(config.composer, Composer::Rover);
+        assert!(config.operations.manifest.is_none());
+        assert!(config.operations.documents);
     }
 
     #[test]
@@ -406,6 +488,7 @@ mod tests {
         assert_eq!(config.graphql_path, "/graphql");
         assert!(config.required);
         assert_eq!(config.min_schema_size, 100);
+        assert!(config.acknowledged_breaking_changes.is_empty());
     }
 
     #[test]
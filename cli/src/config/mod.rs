@@ -43,15 +43,15 @@ pub use deployment::{
     ProductionStrategy,
 };
 pub use federation::{
-    FederationConfig, FederationTestsConfig, FederationTestsServiceConfig, ServiceFederationConfig,
-    ServiceFederationTestsConfig,
+    Composer, FederationConfig, FederationTestsConfig, FederationTestsServiceConfig,
+    ServiceFederationConfig, ServiceFederationTestsConfig,
 };
 pub use global::GlobalConfig;
 pub use kubernetes::{KubernetesConfig, ManifestPaths, ManifestPathsConfig, PathsConfig};
 pub use migration::{NovaSearchConfig, ServiceMigrationConfig};
 pub use prerelease::{
-    BackendGatesConfig, E2eGatesConfig, FrontendGatesConfig, IntegrationGatesConfig,
-    MigrationGatesConfig, PostDeployGatesConfig, PreReleaseGatesConfig,
+    E2eGatesConfig, FrontendGatesConfig, IntegrationGatesConfig, LintSeverity,
+    MigrationGatesConfig, PreReleaseGatesConfig,
 };
 pub use product::{
     default_cluster, default_environment, DirsConfig, EndpointsConfig, K8sRepoConfig,
@@ -291,7 +291,12 @@ impl DeployConfig {
                  Service-level flakes are no longer supported.",
             )
             .map(PathBuf::from)?;
+        Self::load_for_service_dir(service_name, service_dir)
+    }
 
+    /// [`Self::load_for_service`] for an explicit service directory, for
+    /// callers that walk several services without setting `SERVICE_DIR`.
+    pub fn load_for_service_dir(service_name: &str, service_dir: PathBuf) -> Result<Self> {
         // Find product directory early so we can resolve deploy.yaml from
         // the deploy/ directory (outside the Nix source tree).
         let product_dir_for_resolve = Self::find_product_directory(&service_dir).ok();
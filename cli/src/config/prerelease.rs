@@ -3,6 +3,7 @@
 //! Configures which gates run and how failures are handled during pre-release validation.
 
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 /// Pre-release gate configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -141,6 +142,51 @@ pub struct MigrationGatesConfig {
     /// Verifies every SeaORM migration is assessed in migration-manifest.yaml
     #[serde(default = "default_true")]
     pub data_completeness_check: bool,
+
+    /// Enable the migration lock-safety lint (default: true)
+    /// Flags lock-heavy and destructive DDL in migrations added since the
+    /// previous release; see `commands::migration_lint`
+    #[serde(default = "default_true")]
+    pub lock_safety_check: bool,
+
+    /// Severity overrides per lint rule (e.g., `rename: error`, `create-index-non-concurrent: off`)
+    #[serde(default)]
+    pub lint_severity: BTreeMap<String, LintSeverity>,
+
+    /// Tables small enough that lock-taking DDL on them is acceptable
+    #[serde(default)]
+    pub small_tables: Vec<String>,
+
+    /// Enable the schema backward-compatibility check (default: true)
+    /// Fails when a new migration drops or renames a table or column the
+    /// previous release's SeaORM entities still reference
+    #[serde(default = "default_true")]
+    pub backward_compat_check: bool,
+
+    /// Git ref of the previous release for the backward-compatibility check
+    /// (defaults to the latest vX.Y.Z tag)
+    #[serde(default)]
+    pub compat_base_ref: Option<String>,
+
+    /// Enable Databend migration validation (default: true)
+    /// Idempotency, manifest and rollback checks for the analytics chain
+    #[serde(default = "default_true")]
+    pub databend_check: bool,
+
+    /// Enable Elasticsearch migration validation (default: true)
+    /// Idempotency, manifest, reindex and rollback checks for the search chain
+    #[serde(default = "default_true")]
+    pub elasticsearch_check: bool,
+}
+
+/// Severity of a migration lint finding. `error` fails the gate,
+/// `warning` is reported only, `off` disables the rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LintSeverity {
+    Off,
+    Warning,
+    Error,
 }
 
 impl Default for MigrationGatesConfig {
@@ -154,6 +200,13 @@ impl Default for MigrationGatesConfig {
             check_after: None,
             seaorm_check_after: None,
             data_completeness_check: true,
+            lock_safety_check: true,
+            lint_severity: BTreeMap::new(),
+            small_tables: Vec::new(),
+            backward_compat_check: true,
+            compat_base_ref: None,
+            databend_check: true,
+            elasticsearch_check: true,
         }
     }
 }
@@ -342,6 +395,10 @@ mod tests {
         assert!(config.migrations.soft_delete_check);
         assert!(config.migrations.seaorm_safety_check);
         assert!(config.migrations.data_completeness_check);
+        assert!(config.migrations.lock_safety_check);
+        assert!(config.migrations.backward_compat_check);
+        assert!(config.migrations.databend_check);
+        assert!(config.migrations.elasticsearch_check);
         assert!(config.frontend.codegen_drift);
         assert!(config.frontend.type_check);
         assert!(config.frontend.lint);
@@ -463,6 +520,11 @@ migrations:
   idempotency_check: true
   soft_delete_check: false
   seaorm_safety_check: true
+  lint_severity:
+    rename: error
+    create-index-non-concurrent: off
+  small_tables: [feature_flags]
+  compat_base_ref: v1.4.0
 frontend:
   codegen_drift: false
   type_check: true
@@ -487,6 +549,19 @@ post_deploy:
         assert!(!config.backend.cargo_clippy);
         assert!(!config.backend.cargo_test);
         assert!(!config.migrations.soft_delete_check);
+        assert_eq!(
+            config.migrations.lint_severity.get("rename"),
+            Some(&LintSeverity::Error)
+        );
+        assert_eq!(
+            config
+                .migrations
+                .lint_severity
+                .get("create-index-non-concurrent"),
+            Some(&LintSeverity::Off)
+        );
+        assert_eq!(config.migrations.small_tables, ["feature_flags"]);
+        assert_eq!(config.migrations.compat_base_ref.as_deref(), Some("v1.4.0"));
         assert!(!config.frontend.codegen_drift);
         assert_eq!(config.frontend.linter, "eslint");
         assert!(config.integration.enabled);
@@ -330,6 +330,106 @@ pub enum KubernetesError {
         exit_code: Option<i32>,
         stderr: String,
     },
+
+    #[error("Kubernetes API {op} in namespace {namespace} failed (status {code:?}): {message}")]
+    ApiFailed {
+        op: String,
+        namespace: String,
+        code: Option<u16>,
+        message: String,
+    },
+
+    #[error("Rendering kustomization {path} failed: {message}")]
+    RenderFailed { path: String, message: String },
+
+    #[error(
+        "Kustomization {path} uses fields forge does not render: {}",
+        fields.join(", ")
+    )]
+    RenderUnsupported { path: String, fields: Vec<String> },
+
+    #[error(
+        "Rendered manifests for {path} failed schema validation:\n  {}",
+        violations.join("\n  ")
+    )]
+    SchemaInvalid {
+        path: String,
+        violations: Vec<String>,
+    },
+
+    #[error(
+        "Server-side dry run of {path} was rejected:\n  {}",
+        rejections.join("\n  ")
+    )]
+    DryRunRejected {
+        path: String,
+        rejections: Vec<String>,
+    },
+
+    #[error("Flux {resource} is not ready: {message}")]
+    FluxNotReady { resource: String, message: String },
+
+    #[error("Flux {resource} did not finish reconciling within {timeout_secs}s")]
+    FluxReconcileTimeout { resource: String, timeout_secs: u64 },
+
+    #[error(
+        "Flux {resource} did not apply {expected} within {timeout_secs}s \
+         (applied: {}, source: {}){}",
+        .observed.applied,
+        .observed.source_revision,
+        .observed.last_error.as_ref().map(|e| format!("; last error: {e}")).unwrap_or_default()
+    )]
+    FluxRevisionTimeout {
+        resource: String,
+        expected: String,
+        observed: Box<FluxRevisionObserved>,
+        timeout_secs: u64,
+    },
+
+    #[error("HelmRelease {resource} failed and was remediated ({reason}): {message}")]
+    HelmReleaseRemediated {
+        resource: String,
+        reason: String,
+        message: String,
+    },
+}
+
+/// Where Flux actually was when [`KubernetesError::FluxRevisionTimeout`]
+/// gave up waiting.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FluxRevisionObserved {
+    /// The Kustomization's `lastAppliedRevision`.
+    pub applied: String,
+    /// The revision its source has fetched.
+    pub source_revision: String,
+    /// The `Ready` condition's message when the last attempt failed.
+    pub last_error: Option<String>,
+}
+
+impl KubernetesError {
+    /// Map a failed kube API call into `ApiFailed`.
+    ///
+    /// Lifts the HTTP status out of `kube::Error::Api` into the separate
+    /// `code` field so callers can tell an RBAC denial (403) or a missing
+    /// CRD (404) from a transport failure (`None`) without parsing the
+    /// message — the API-client counterpart of the `(exit_code, stderr)`
+    /// split the CLI-backed variants carry.
+    pub fn api_failed(
+        op: impl Into<String>,
+        namespace: impl Into<String>,
+        err: kube::Error,
+    ) -> Self {
+        let code = match &err {
+            kube::Error::Api(resp) => Some(resp.code),
+            _ => None,
+        };
+        KubernetesError::ApiFailed {
+            op: op.into(),
+            namespace: namespace.into(),
+            code,
+            message: err.to_string(),
+        }
+    }
 }
 
 /// Configuration errors
@@ -1384,6 +1484,15 @@ mod tests {
                 KubernetesError::ExecFailed { .. } => "exec",
                 KubernetesError::FluxReconcileFailed { .. } => "flux",
                 KubernetesError::KustomizationFailed { .. } => "kustomization",
+                KubernetesError::ApiFailed { .. } => "api",
+                KubernetesError::RenderFailed { .. } => "render",
+                KubernetesError::RenderUnsupported { .. } => "render-unsupported",
+                KubernetesError::SchemaInvalid { .. } => "schema",
+                KubernetesError::DryRunRejected { .. } => "dry-run",
+                KubernetesError::FluxNotReady { .. } => "flux-not-ready",
+                KubernetesError::FluxReconcileTimeout { .. } => "flux-timeout",
+                KubernetesError::FluxRevisionTimeout { .. } => "flux-revision",
+                KubernetesError::HelmReleaseRemediated { .. } => "helm-remediated",
             }
         }
         assert_eq!(
@@ -1420,6 +1529,150 @@ mod tests {
             classify(&KubernetesError::RolloutTimeout { timeout_secs: 1 }),
             "timeout"
         );
+        assert_eq!(
+            classify(&KubernetesError::ApiFailed {
+                op: "list pods".into(),
+                namespace: "ns".into(),
+                code: Some(403),
+                message: "forbidden".into(),
+            }),
+            "api"
+        );
+        assert_eq!(
+            classify(&KubernetesError::RenderFailed {
+                path: "overlays/staging".into(),
+                message: "Patch target Deployment/cart matched no resources".into(),
+            }),
+            "render"
+        );
+        assert_eq!(
+            classify(&KubernetesError::RenderUnsupported {
+                path: "overlays/staging".into(),
+                fields: vec!["components".into()],
+            }),
+            "render-unsupported"
+        );
+        assert_eq!(
+            classify(&KubernetesError::SchemaInvalid {
+                path: "overlays/staging".into(),
+                violations: vec!["Deployment/cart: unknown field .spec.replica".into()],
+            }),
+            "schema"
+        );
+        assert_eq!(
+            classify(&KubernetesError::DryRunRejected {
+                path: "overlays/staging".into(),
+                rejections: vec!["[quota] Deployment/cart: exceeded quota".into()],
+            }),
+            "dry-run"
+        );
+        assert_eq!(
+            classify(&KubernetesError::FluxNotReady {
+                resource: "Kustomization/flux-system/cart".into(),
+                message: "kustomize build failed".into(),
+            }),
+            "flux-not-ready"
+        );
+        assert_eq!(
+            classify(&KubernetesError::FluxReconcileTimeout {
+                resource: "Kustomization/flux-system/cart".into(),
+                timeout_secs: 300,
+            }),
+            "flux-timeout"
+        );
+        assert_eq!(
+            classify(&KubernetesError::FluxRevisionTimeout {
+                resource: "Kustomization/flux-system/flux-system".into(),
+                expected: "0123abc".into(),
+                observed: Box::new(FluxRevisionObserved {
+                    applied: "main@sha1:fedcba9".into(),
+                    source_revision: "main@sha1:0123abc".into(),
+                    last_error: Some("kustomize build failed".into()),
+                }),
+                timeout_secs: 600,
+            }),
+            "flux-revision"
+        );
+        assert_eq!(
+            classify(&KubernetesError::HelmReleaseRemediated {
+                resource: "HelmRelease/cart/cart".into(),
+                reason: "RollbackSucceeded".into(),
+                message: "Helm rollback to previous release succeeded".into(),
+            }),
+            "helm-remediated"
+        );
+    }
+
+    /// The revision-timeout diagnostic must show where Flux actually is —
+    /// applied and fetched revisions — and the last reconcile error only
+    /// when there is one.
+    #[test]
+    fn test_kubernetes_error_flux_revision_timeout_reports_observed_state() {
+        let err = |last_error: Option<&str>| KubernetesError::FluxRevisionTimeout {
+            resource: "Kustomization/flux-system/flux-system".into(),
+            expected: "0123abc".into(),
+            observed: Box::new(FluxRevisionObserved {
+                applied: "main@sha1:fedcba9".into(),
+                source_revision: "main@sha1:0123abc".into(),
+                last_error: last_error.map(str::to_string),
+            }),
+            timeout_secs: 600,
+        };
+        assert_eq!(
+            err(None).to_string(),
+            "Flux Kustomization/flux-system/flux-system did not apply 0123abc within 600s \
+             (applied: main@sha1:fedcba9, source: main@sha1:0123abc)"
+        );
+        assert!(err(Some("kustomize build failed"))
+            .to_string()
+            .ends_with("; last error: kustomize build failed"));
+    }
+
+    /// `api_failed` must lift the apiserver's HTTP status into `code` so
+    /// an RBAC denial is distinguishable from a transport failure without
+    /// parsing the message, and must keep `op` / `namespace` verbatim.
+    #[test]
+    fn test_kubernetes_error_api_failed_lifts_status_code() {
+        let err = KubernetesError::api_failed(
+            "list secrets",
+            "myapp-staging",
+            kube::Error::Api(kube::error::ErrorResponse {
+                status: "Failure".into(),
+                message: "secrets is forbidden".into(),
+                reason: "Forbidden".into(),
+                code: 403,
+            }),
+        );
+        match &err {
+            KubernetesError::ApiFailed {
+                op,
+                namespace,
+                code,
+                message,
+            } => {
+                assert_eq!(op, "list secrets");
+                assert_eq!(namespace, "myapp-staging");
+                assert_eq!(*code, Some(403));
+                assert!(message.contains("forbidden"), "message: {message}");
+            }
+            other => panic!("expected ApiFailed, got: {other:?}"),
+        }
+        let msg = err.to_string();
+        assert!(msg.contains("list secrets"), "op must appear: {msg}");
+        assert!(
+            msg.contains("myapp-staging"),
+            "namespace must appear: {msg}"
+        );
+
+        let err = KubernetesError::api_failed(
+            "get deployment/web",
+            "prod",
+            kube::Error::LinesCodecMaxLineLengthExceeded,
+        );
+        assert!(
+            matches!(err, KubernetesError::ApiFailed { code: None, .. }),
+            "non-API kube errors carry no status code: {err:?}"
+        );
     }
 
     #[test]
@@ -0,0 +1,833 @@
+//! Typed access to Flux custom resources through the Kubernetes API
+//!
+//! Reads `Kustomization`, `GitRepository`, `HelmRelease` and
+//! `OCIRepository` objects as [`DynamicObject`]s and decodes their status
+//! into [`FluxStatus`] (conditions, `lastAppliedRevision`, artifact
+//! revision), so nothing depends on the `flux` CLI or its table layout.
+//!
+//! Reconciles are requested the way the Flux CLI does it: by stamping the
+//! `reconcile.fluxcd.io/requestedAt` annotation with a fresh timestamp and
+//! waiting until the controller echoes that token back in
+//! `status.lastHandledReconcileAt`, after which the `Ready` condition is
+//! the outcome.
+
+use chrono::{SecondsFormat, Utc};
+use kube::api::{Api, DynamicObject, ListParams, Patch, PatchParams};
+use kube::core::{ApiResource, GroupVersionKind};
+use kube::Client;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::time::Duration;
+
+use crate::error::{FluxRevisionObserved, KubernetesError};
+
+/// Annotation the Flux controllers watch to trigger an out-of-band reconcile.
+pub const REQUESTED_AT_ANNOTATION: &str = "reconcile.fluxcd.io/requestedAt";
+
+/// How long [`reconcile`] waits for the controller, matching `flux reconcile`.
+pub const RECONCILE_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Default for [`wait_for_revision`]: a source fetch plus a full apply
+/// with health checks.
+pub const REVISION_TIMEOUT: Duration = Duration::from_secs(600);
+
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+// ============================================================================
+// Data Structures
+// ============================================================================
+
+/// Flux custom resource kinds forge reads and reconciles.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FluxKind {
+    Kustomization,
+    GitRepository,
+    HelmRelease,
+    OciRepository,
+}
+
+impl FluxKind {
+    fn gvk(self) -> GroupVersionKind {
+        match self {
+            FluxKind::Kustomization => {
+                GroupVersionKind::gvk("kustomize.toolkit.fluxcd.io", "v1", "Kustomization")
+            }
+            FluxKind::GitRepository => {
+                GroupVersionKind::gvk("source.toolkit.fluxcd.io", "v1", "GitRepository")
+            }
+            FluxKind::HelmRelease => {
+                GroupVersionKind::gvk("helm.toolkit.fluxcd.io", "v2", "HelmRelease")
+            }
+            FluxKind::OciRepository => {
+                GroupVersionKind::gvk("source.toolkit.fluxcd.io", "v1beta2", "OCIRepository")
+            }
+        }
+    }
+
+    pub fn api_resource(self) -> ApiResource {
+        ApiResource::from_gvk(&self.gvk())
+    }
+
+    /// Kind for a `sourceRef.kind`, or `None` for sources forge does not
+    /// model (e.g. `Bucket`).
+    pub fn from_kind(kind: &str) -> Option<Self> {
+        match kind {
+            "Kustomization" => Some(FluxKind::Kustomization),
+            "GitRepository" => Some(FluxKind::GitRepository),
+            "HelmRelease" => Some(FluxKind::HelmRelease),
+            "OCIRepository" => Some(FluxKind::OciRepository),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for FluxKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            FluxKind::Kustomization => "Kustomization",
+            FluxKind::GitRepository => "GitRepository",
+            FluxKind::HelmRelease => "HelmRelease",
+            FluxKind::OciRepository => "OCIRepository",
+        })
+    }
+}
+
+/// A `metav1.Condition` from a Flux object's status.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Condition {
+    #[serde(rename = "type")]
+    pub type_: String,
+    pub status: String,
+    #[serde(default)]
+    pub reason: String,
+    #[serde(default)]
+    pub message: String,
+    pub observed_generation: Option<i64>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct Artifact {
+    pub revision: Option<String>,
+}
+
+/// Status fields shared by the Flux kinds; each kind fills the subset it has.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct FluxStatus {
+    pub observed_generation: Option<i64>,
+    pub conditions: Vec<Condition>,
+    /// Kustomization / HelmRelease: revision of the last successful apply.
+    pub last_applied_revision: Option<String>,
+    pub last_attempted_revision: Option<String>,
+    /// Echo of the last handled `reconcile.fluxcd.io/requestedAt` token.
+    pub last_handled_reconcile_at: Option<String>,
+    /// Sources: the artifact produced by the last fetch.
+    pub artifact: Option<Artifact>,
+}
+
+/// `spec.sourceRef` of a Kustomization.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct SourceRef {
+    pub kind: String,
+    pub name: String,
+    pub namespace: Option<String>,
+}
+
+/// A Flux object with its spec essentials and typed status.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FluxObject {
+    pub kind: FluxKind,
+    pub namespace: String,
+    pub name: String,
+    pub generation: Option<i64>,
+    pub annotations: BTreeMap<String, String>,
+    pub suspended: bool,
+    pub source_ref: Option<SourceRef>,
+    pub status: FluxStatus,
+}
+
+/// Where an object stands relative to a requested reconcile.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReconcileState {
+    /// The controller has not handled the request yet, or is still working.
+    Pending,
+    Ready,
+    Failed(String),
+}
+
+/// Where a HelmRelease stands after a spec change.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HelmReleaseState {
+    /// The controller has not observed the new generation, or is still
+    /// installing, testing or retrying.
+    Pending,
+    Ready,
+    /// The release failed and helm-controller remediated it; `reason` is the
+    /// `Remediated` condition's reason (`RollbackSucceeded`,
+    /// `UninstallSucceeded`).
+    Remediated {
+        reason: String,
+        message: String,
+    },
+    /// Remediation failed or the release is stalled with no retries left.
+    Failed(String),
+}
+
+impl FluxObject {
+    pub fn from_dynamic(kind: FluxKind, object: &DynamicObject) -> Result<Self, KubernetesError> {
+        let namespace = object.metadata.namespace.clone().unwrap_or_default();
+        let name = object.metadata.name.clone().unwrap_or_default();
+        let decode_failed = |e: serde_json::Error| KubernetesError::ApiFailed {
+            op: format!("decode {kind} {name}"),
+            namespace: namespace.clone(),
+            code: None,
+            message: e.to_string(),
+        };
+
+        let status = match object.data.get("status") {
+            Some(status) => FluxStatus::deserialize(status).map_err(decode_failed)?,
+            None => FluxStatus::default(),
+        };
+        let source_ref = match object.data.pointer("/spec/sourceRef") {
+            Some(source_ref) => Some(SourceRef::deserialize(source_ref).map_err(decode_failed)?),
+            None => None,
+        };
+        let suspended = object
+            .data
+            .pointer("/spec/suspend")
+            .and_then(Value::as_bool)
+            .unwrap_or(false);
+
+        Ok(FluxObject {
+            kind,
+            generation: object.metadata.generation,
+            annotations: object.metadata.annotations.clone().unwrap_or_default(),
+            suspended,
+            source_ref,
+            status,
+            namespace,
+            name,
+        })
+    }
+
+    /// `Kind/namespace/name`
+    pub fn display_name(&self) -> String {
+        format!("{}/{}/{}", self.kind, self.namespace, self.name)
+    }
+
+    pub fn condition(&self, type_: &str) -> Option<&Condition> {
+        self.status.conditions.iter().find(|c| c.type_ == type_)
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.condition("Ready").is_some_and(|c| c.status == "True")
+    }
+
+    /// Applied revision for appliers, artifact revision for sources.
+    pub fn revision(&self) -> Option<&str> {
+        self.status.last_applied_revision.as_deref().or(self
+            .status
+            .artifact
+            .as_ref()
+            .and_then(|a| a.revision.as_deref()))
+    }
+
+    /// The object's state with respect to the reconcile requested with
+    /// `token`. The outcome only counts once the controller has echoed the
+    /// token and observed the current generation.
+    pub fn reconcile_state(&self, token: &str) -> ReconcileState {
+        if self.status.last_handled_reconcile_at.as_deref() != Some(token) {
+            return ReconcileState::Pending;
+        }
+        if let (Some(generation), Some(observed)) =
+            (self.generation, self.status.observed_generation)
+        {
+            if observed < generation {
+                return ReconcileState::Pending;
+            }
+        }
+        match self.condition("Ready") {
+            Some(c) if c.status == "True" => ReconcileState::Ready,
+            Some(c) if c.status == "False" => ReconcileState::Failed(c.message.clone()),
+            _ => ReconcileState::Pending,
+        }
+    }
+
+    /// The state of a HelmRelease with respect to its current generation.
+    /// A `Remediated` condition left over from an earlier generation, or
+    /// seen while the upgrade is still in progress, does not count.
+    pub fn helm_release_state(&self) -> HelmReleaseState {
+        let generation = self.generation.unwrap_or(0);
+        if self.status.observed_generation.unwrap_or(-1) < generation {
+            return HelmReleaseState::Pending;
+        }
+        let ready = self.condition("Ready");
+        match ready {
+            Some(c) if c.status == "True" => return HelmReleaseState::Ready,
+            Some(c) if c.status == "Unknown" => return HelmReleaseState::Pending,
+            _ => {}
+        }
+        let current = |c: &&Condition| c.observed_generation.unwrap_or(generation) >= generation;
+        if let Some(c) = self.condition("Remediated").filter(current) {
+            return if c.status == "True" {
+                HelmReleaseState::Remediated {
+                    reason: c.reason.clone(),
+                    message: c.message.clone(),
+                }
+            } else {
+                HelmReleaseState::Failed(c.message.clone())
+            };
+        }
+        if self
+            .condition("Stalled")
+            .filter(current)
+            .is_some_and(|c| c.status == "True")
+        {
+            let message = ready.map(|c| c.message.clone()).unwrap_or_default();
+            return HelmReleaseState::Failed(message);
+        }
+        HelmReleaseState::Pending
+    }
+}
+
+// ============================================================================
+// API Access
+// ============================================================================
+
+/// List objects of `kind` in `namespace`, or across all namespaces.
+pub async fn list(
+    client: &Client,
+    kind: FluxKind,
+    namespace: Option<&str>,
+) -> Result<Vec<FluxObject>, KubernetesError> {
+    let resource = kind.api_resource();
+    let api: Api<DynamicObject> = match namespace {
+        Some(ns) => Api::namespaced_with(client.clone(), ns, &resource),
+        None => Api::all_with(client.clone(), &resource),
+    };
+    let objects = api.list(&ListParams::default()).await.map_err(|e| {
+        KubernetesError::api_failed(format!("list {kind}"), namespace.unwrap_or("*"), e)
+    })?;
+    objects
+        .items
+        .iter()
+        .map(|o| FluxObject::from_dynamic(kind, o))
+        .collect()
+}
+
+/// Fetch one object, `Ok(None)` if it does not exist.
+pub async fn get(
+    client: &Client,
+    kind: FluxKind,
+    name: &str,
+    namespace: &str,
+) -> Result<Option<FluxObject>, KubernetesError> {
+    let api: Api<DynamicObject> =
+        Api::namespaced_with(client.clone(), namespace, &kind.api_resource());
+    match api.get_opt(name).await {
+        Ok(Some(object)) => FluxObject::from_dynamic(kind, &object).map(Some),
+        Ok(None) => Ok(None),
+        Err(e) => Err(KubernetesError::api_failed(
+            format!("get {kind} {name}"),
+            namespace,
+            e,
+        )),
+    }
+}
+
+/// Stamp [`REQUESTED_AT_ANNOTATION`] on an object and return the token to
+/// wait for.
+pub async fn request_reconcile(
+    client: &Client,
+    kind: FluxKind,
+    name: &str,
+    namespace: &str,
+) -> Result<String, KubernetesError> {
+    let token = Utc::now().to_rfc3339_opts(SecondsFormat::Nanos, true);
+    let patch = json!({"metadata": {"annotations": {REQUESTED_AT_ANNOTATION: token}}});
+    let api: Api<DynamicObject> =
+        Api::namespaced_with(client.clone(), namespace, &kind.api_resource());
+    api.patch(name, &PatchParams::default(), &Patch::Merge(&patch))
+        .await
+        .map_err(|e| {
+            KubernetesError::api_failed(format!("annotate {kind} {name}"), namespace, e)
+        })?;
+    Ok(token)
+}
+
+/// Set `spec.suspend` on an object, merging `annotations` into its metadata
+/// in the same patch (a `null` value removes an annotation).
+pub async fn set_suspended(
+    client: &Client,
+    kind: FluxKind,
+    name: &str,
+    namespace: &str,
+    suspend: bool,
+    annotations: Value,
+) -> Result<(), KubernetesError> {
+    let patch = json!({
+        "metadata": {"annotations": annotations},
+        "spec": {"suspend": suspend},
+    });
+    let op = if suspend { "suspend" } else { "resume" };
+    let api: Api<DynamicObject> =
+        Api::namespaced_with(client.clone(), namespace, &kind.api_resource());
+    api.patch(name, &PatchParams::default(), &Patch::Merge(&patch))
+        .await
+        .map_err(|e| KubernetesError::api_failed(format!("{op} {kind} {name}"), namespace, e))?;
+    Ok(())
+}
+
+/// Poll until the controller has handled the request identified by `token`.
+pub async fn wait_for_reconcile(
+    client: &Client,
+    kind: FluxKind,
+    name: &str,
+    namespace: &str,
+    token: &str,
+    timeout: Duration,
+) -> Result<FluxObject, KubernetesError> {
+    let deadline = tokio::time::Instant::now() + timeout;
+    loop {
+        let object = get(client, kind, name, namespace).await?.ok_or_else(|| {
+            KubernetesError::FluxNotReady {
+                resource: format!("{kind}/{namespace}/{name}"),
+                message: "object no longer exists".to_string(),
+            }
+        })?;
+        match object.reconcile_state(token) {
+            ReconcileState::Ready => return Ok(object),
+            ReconcileState::Failed(message) => {
+                return Err(KubernetesError::FluxNotReady {
+                    resource: object.display_name(),
+                    message,
+                })
+            }
+            ReconcileState::Pending if tokio::time::Instant::now() >= deadline => {
+                return Err(KubernetesError::FluxReconcileTimeout {
+                    resource: object.display_name(),
+                    timeout_secs: timeout.as_secs(),
+                })
+            }
+            ReconcileState::Pending => tokio::time::sleep(POLL_INTERVAL).await,
+        }
+    }
+}
+
+/// Wait until HelmRelease `name` has moved past `previous_generation` (the
+/// generation before forge pushed its change, `None` if it did not exist)
+/// and helm-controller has finished with the new spec: `Ready`, rolled back
+/// or uninstalled by remediation, or stalled.
+pub async fn wait_for_helm_release(
+    client: &Client,
+    name: &str,
+    namespace: &str,
+    previous_generation: Option<i64>,
+    timeout: Duration,
+) -> Result<FluxObject, KubernetesError> {
+    let kind = FluxKind::HelmRelease;
+    let deadline = tokio::time::Instant::now() + timeout;
+    loop {
+        let current = get(client, kind, name, namespace)
+            .await?
+            .filter(|o| o.generation > previous_generation);
+        if let Some(object) = current {
+            match object.helm_release_state() {
+                HelmReleaseState::Ready => return Ok(object),
+                HelmReleaseState::Remediated { reason, message } => {
+                    return Err(KubernetesError::HelmReleaseRemediated {
+                        resource: object.display_name(),
+                        reason,
+                        message,
+                    })
+                }
+                HelmReleaseState::Failed(message) => {
+                    return Err(KubernetesError::FluxNotReady {
+                        resource: object.display_name(),
+                        message,
+                    })
+                }
+                HelmReleaseState::Pending => {}
+            }
+        }
+        if tokio::time::Instant::now() >= deadline {
+            return Err(KubernetesError::FluxReconcileTimeout {
+                resource: format!("{kind}/{namespace}/{name}"),
+                timeout_secs: timeout.as_secs(),
+            });
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+}
+
+/// Request a reconcile and wait for its outcome, like `flux reconcile`.
+/// With `with_source`, a Kustomization's source is reconciled first so the
+/// apply picks up the latest commit.
+pub async fn reconcile(
+    client: &Client,
+    kind: FluxKind,
+    name: &str,
+    namespace: &str,
+    with_source: bool,
+) -> Result<FluxObject, KubernetesError> {
+    let object =
+        get(client, kind, name, namespace)
+            .await?
+            .ok_or_else(|| KubernetesError::FluxNotReady {
+                resource: format!("{kind}/{namespace}/{name}"),
+                message: "not found".to_string(),
+            })?;
+    if object.suspended {
+        return Err(KubernetesError::FluxNotReady {
+            resource: object.display_name(),
+            message: "reconciliation is suspended".to_string(),
+        });
+    }
+
+    if with_source {
+        if let Some(source_ref) = &object.source_ref {
+            let source_kind = FluxKind::from_kind(&source_ref.kind).ok_or_else(|| {
+                KubernetesError::FluxNotReady {
+                    resource: object.display_name(),
+                    message: format!("unsupported source kind {}", source_ref.kind),
+                }
+            })?;
+            let source_namespace = source_ref.namespace.as_deref().unwrap_or(namespace);
+            let token =
+                request_reconcile(client, source_kind, &source_ref.name, source_namespace).await?;
+            wait_for_reconcile(
+                client,
+                source_kind,
+                &source_ref.name,
+                source_namespace,
+                &token,
+                RECONCILE_TIMEOUT,
+            )
+            .await?;
+        }
+    }
+
+    let token = request_reconcile(client, kind, name, namespace).await?;
+    wait_for_reconcile(client, kind, name, namespace, &token, RECONCILE_TIMEOUT).await
+}
+
+/// Whether a Flux revision string names commit `sha`. Flux renders
+/// revisions as `main@sha1:<sha>` (v2.x) or `main/<sha>` (older
+/// controllers); `sha` may be abbreviated.
+pub fn revision_matches(revision: &str, sha: &str) -> bool {
+    let commit = revision_commit(revision);
+    !sha.is_empty() && (commit.starts_with(sha) || (sha.starts_with(commit) && commit.len() >= 7))
+}
+
+/// The commit a Flux revision string names, in either format
+/// [`revision_matches`] accepts.
+pub fn revision_commit(revision: &str) -> &str {
+    match revision.rsplit_once(':') {
+        Some((_, commit)) => commit,
+        None => revision.rsplit('/').next().unwrap_or(revision),
+    }
+}
+
+/// Wait until Kustomization `name` has applied commit `sha`, i.e. its
+/// `status.lastAppliedRevision` names that commit, or names a later commit
+/// that `includes` reports as containing it (someone pushed on top before
+/// Flux caught up). `includes` is asked once per newly observed commit.
+/// Reconciles of the Kustomization and its source are requested up front
+/// so Flux does not sit out its interval. On timeout the error reports the revision Flux is
+/// actually on, the revision its source has fetched, and the `Ready`
+/// condition's message when the last attempt failed.
+pub async fn wait_for_revision(
+    client: &Client,
+    name: &str,
+    namespace: &str,
+    sha: &str,
+    includes: impl Fn(&str) -> bool,
+    timeout: Duration,
+) -> Result<FluxObject, KubernetesError> {
+    let kind = FluxKind::Kustomization;
+    let not_found = || KubernetesError::FluxNotReady {
+        resource: format!("{kind}/{namespace}/{name}"),
+        message: "not found".to_string(),
+    };
+    let object = get(client, kind, name, namespace)
+        .await?
+        .ok_or_else(not_found)?;
+    if object.suspended {
+        return Err(KubernetesError::FluxNotReady {
+            resource: object.display_name(),
+            message: "reconciliation is suspended".to_string(),
+        });
+    }
+    let source = source_of(&object);
+    if let Some((source_kind, source_name, source_namespace)) = &source {
+        request_reconcile(client, *source_kind, source_name, source_namespace).await?;
+    }
+    request_reconcile(client, kind, name, namespace).await?;
+
+    let deadline = tokio::time::Instant::now() + timeout;
+    let mut checked: Option<String> = None;
+    loop {
+        let object = get(client, kind, name, namespace)
+            .await?
+            .ok_or_else(not_found)?;
+        if let Some(revision) = object.revision() {
+            if revision_matches(revision, sha) {
+                return Ok(object);
+            }
+            let commit = revision_commit(revision);
+            if checked.as_deref() != Some(commit) {
+                if includes(commit) {
+                    return Ok(object);
+                }
+                checked = Some(commit.to_string());
+            }
+        }
+        if tokio::time::Instant::now() >= deadline {
+            let source_revision = match &source {
+                Some((source_kind, source_name, source_namespace)) => {
+                    get(client, *source_kind, source_name, source_namespace)
+                        .await?
+                        .and_then(|s| s.revision().map(str::to_string))
+                }
+                None => None,
+            };
+            let last_error = object
+                .condition("Ready")
+                .filter(|c| c.status == "False")
+                .map(|c| c.message.clone());
+            return Err(KubernetesError::FluxRevisionTimeout {
+                resource: object.display_name(),
+                expected: sha.to_string(),
+                observed: Box::new(FluxRevisionObserved {
+                    applied: object.revision().unwrap_or("none").to_string(),
+                    source_revision: source_revision.unwrap_or_else(|| "unknown".to_string()),
+                    last_error,
+                }),
+                timeout_secs: timeout.as_secs(),
+            });
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+}
+
+fn source_of(object: &FluxObject) -> Option<(FluxKind, String, String)> {
+    let source_ref = object.source_ref.as_ref()?;
+    let kind = FluxKind::from_kind(&source_ref.kind)?;
+    let namespace = source_ref
+        .namespace
+        .clone()
+        .unwrap_or_else(|| object.namespace.clone());
+    Some((kind, source_ref.name.clone(), namespace))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kustomization(status: Value) -> DynamicObject {
+        serde_json::from_value(json!({
+            "apiVersion": "kustomize.toolkit.fluxcd.io/v1",
+            "kind": "Kustomization",
+            "metadata": {"name": "cart", "namespace": "flux-system", "generation": 3},
+            "spec": {
+                "suspend": true,
+                "sourceRef": {"kind": "GitRepository", "name": "flux-system"}
+            },
+            "status": status
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn from_dynamic_decodes_status_and_spec() {
+        let object = FluxObject::from_dynamic(
+            FluxKind::Kustomization,
+            &kustomization(json!({
+                "observedGeneration": 3,
+                "lastAppliedRevision": "main@sha1:abc",
+                "lastAttemptedRevision": "main@sha1:def",
+                "conditions": [{
+                    "type": "Ready",
+                    "status": "False",
+                    "reason": "BuildFailed",
+                    "message": "kustomize build failed",
+                    "lastTransitionTime": "2026-01-01T00:00:00Z"
+                }]
+            })),
+        )
+        .unwrap();
+
+        assert_eq!(object.display_name(), "Kustomization/flux-system/cart");
+        assert!(object.suspended);
+        assert!(!object.is_ready());
+        assert_eq!(object.revision(), Some("main@sha1:abc"));
+        assert_eq!(
+            object.status.last_attempted_revision.as_deref(),
+            Some("main@sha1:def")
+        );
+        assert_eq!(object.condition("Ready").unwrap().reason, "BuildFailed");
+        assert_eq!(
+            object.source_ref,
+            Some(SourceRef {
+                kind: "GitRepository".into(),
+                name: "flux-system".into(),
+                namespace: None,
+            })
+        );
+    }
+
+    #[test]
+    fn sources_report_their_artifact_revision() {
+        let mut source = kustomization(json!({"artifact": {"revision": "main@sha1:abc"}}));
+        source.data["spec"] = json!({});
+        let object = FluxObject::from_dynamic(FluxKind::GitRepository, &source).unwrap();
+        assert_eq!(object.revision(), Some("main@sha1:abc"));
+        assert!(!object.suspended);
+        assert_eq!(object.source_ref, None);
+    }
+
+    #[test]
+    fn reconcile_state_waits_for_token_and_generation() {
+        let state = |status: Value| {
+            FluxObject::from_dynamic(FluxKind::Kustomization, &kustomization(status))
+                .unwrap()
+                .reconcile_state("t2")
+        };
+        let ready = json!([{"type": "Ready", "status": "True"}]);
+
+        assert_eq!(
+            state(
+                json!({"lastHandledReconcileAt": "t1", "observedGeneration": 3, "conditions": ready})
+            ),
+            ReconcileState::Pending
+        );
+        assert_eq!(
+            state(
+                json!({"lastHandledReconcileAt": "t2", "observedGeneration": 2, "conditions": ready})
+            ),
+            ReconcileState::Pending
+        );
+        assert_eq!(
+            state(
+                json!({"lastHandledReconcileAt": "t2", "observedGeneration": 3, "conditions": ready})
+            ),
+            ReconcileState::Ready
+        );
+        assert_eq!(
+            state(json!({
+                "lastHandledReconcileAt": "t2",
+                "observedGeneration": 3,
+                "conditions": [{"type": "Ready", "status": "Unknown", "message": "Reconciliation in progress"}]
+            })),
+            ReconcileState::Pending
+        );
+        assert_eq!(
+            state(json!({
+                "lastHandledReconcileAt": "t2",
+                "observedGeneration": 3,
+                "conditions": [{"type": "Ready", "status": "False", "message": "health check failed"}]
+            })),
+            ReconcileState::Failed("health check failed".into())
+        );
+    }
+
+    #[test]
+    fn helm_release_state_ignores_stale_and_in_progress_remediation() {
+        let state = |status: Value| {
+            let mut object = kustomization(status);
+            object.data["spec"] = json!({});
+            FluxObject::from_dynamic(FluxKind::HelmRelease, &object)
+                .unwrap()
+                .helm_release_state()
+        };
+        let remediated = |generation: i64| {
+            json!({
+                "type": "Remediated",
+                "status": "True",
+                "reason": "RollbackSucceeded",
+                "message": "Helm rollback to previous release succeeded",
+                "observedGeneration": generation
+            })
+        };
+        let upgrade_failed = json!({
+            "type": "Ready",
+            "status": "False",
+            "reason": "UpgradeFailed",
+            "message": "context deadline exceeded"
+        });
+
+        assert_eq!(
+            state(
+                json!({"observedGeneration": 2, "conditions": [{"type": "Ready", "status": "True"}]})
+            ),
+            HelmReleaseState::Pending
+        );
+        assert_eq!(
+            state(
+                json!({"observedGeneration": 3, "conditions": [{"type": "Ready", "status": "True"}]})
+            ),
+            HelmReleaseState::Ready
+        );
+        assert_eq!(
+            state(json!({
+                "observedGeneration": 3,
+                "conditions": [{"type": "Ready", "status": "Unknown"}, remediated(3)]
+            })),
+            HelmReleaseState::Pending
+        );
+        assert_eq!(
+            state(json!({"observedGeneration": 3, "conditions": [upgrade_failed, remediated(2)]})),
+            HelmReleaseState::Pending
+        );
+        assert_eq!(
+            state(json!({"observedGeneration": 3, "conditions": [upgrade_failed, remediated(3)]})),
+            HelmReleaseState::Remediated {
+                reason: "RollbackSucceeded".into(),
+                message: "Helm rollback to previous release succeeded".into(),
+            }
+        );
+        assert_eq!(
+            state(json!({
+                "observedGeneration": 3,
+                "conditions": [upgrade_failed, {"type": "Stalled", "status": "True"}]
+            })),
+            HelmReleaseState::Failed("context deadline exceeded".into())
+        );
+    }
+
+    #[test]
+    fn revision_matches_both_flux_revision_formats() {
+        let sha = "0123abc4567def89012345678901234567890abc";
+        assert!(revision_matches(&format!("main@sha1:{sha}"), sha));
+        assert!(revision_matches(&format!("main@sha1:{sha}"), "0123abc"));
+        assert!(revision_matches(&format!("main/{sha}"), sha));
+        assert!(revision_matches("main/0123abc", sha));
+        assert!(!revision_matches("main@sha1:fedcba9876543210", sha));
+        assert!(!revision_matches("main/0123", sha));
+        assert!(!revision_matches(&format!("main@sha1:{sha}"), ""));
+    }
+
+    #[test]
+    fn revision_commit_strips_the_branch() {
+        assert_eq!(revision_commit("main@sha1:0123abc"), "0123abc");
+        assert_eq!(revision_commit("main/0123abc"), "0123abc");
+        assert_eq!(revision_commit("0123abc"), "0123abc");
+    }
+
+    #[test]
+    fn api_resources_use_served_flux_versions() {
+        assert_eq!(
+            FluxKind::Kustomization.api_resource().api_version,
+            "kustomize.toolkit.fluxcd.io/v1"
+        );
+        assert_eq!(FluxKind::HelmRelease.api_resource().plural, "helmreleases");
+        assert_eq!(
+            FluxKind::OciRepository.api_resource().api_version,
+            "source.toolkit.fluxcd.io/v1beta2"
+        );
+        assert_eq!(FluxKind::from_kind("Bucket"), None);
+    }
+}
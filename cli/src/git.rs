@@ -13,9 +13,7 @@ use crate::tools::{get_tool_path, tools};
 /// `GIT_BIN` env override the tools-registry idiom names as the
 /// hermetic-runner contract for [`tools::GIT`], then delegates the
 /// spawn-plus-classify to [`git_capture_with_bin`]. Same production /
-/// test split as `flux_reconcile::reconcile_kustomization` +
-/// `reconcile_kustomization_with_bin`,
-/// `graphql_schema::extract_graphql_schema` +
+/// test split as `graphql_schema::extract_graphql_schema` +
 /// `extract_graphql_schema_with_bin`, and `nix::run_nix_build_typed` +
 /// `run_nix_build_typed_with_bin` — production reads through
 /// `get_tool_path`, tests point at an absolute-path shim without
@@ -43,8 +41,7 @@ fn git_capture(args: &[&str], workdir: Option<&Path>, op: &str) -> Result<Vec<u8
 /// Test-injection sibling of [`git_capture`]: accepts the resolved
 /// `bin` as an explicit argument so unit tests can point at a
 /// hermetic shim without mutating the process-wide `GIT_BIN` env var
-/// — same discipline as `flux_reconcile::reconcile_kustomization_with_bin`,
-/// `graphql_schema::extract_graphql_schema_with_bin`, and
+/// — same discipline as `graphql_schema::extract_graphql_schema_with_bin` and
 /// `AtticClient::with_attic_bin`. Splitting the resolution from the
 /// execution keeps the test surface hermetic AND parallel-safe:
 /// `#[test]` on this module can run concurrent tests without racing on
@@ -450,6 +447,14 @@ pub async fn update_manifest(manifest_path: &Path, _old_tag: &str, new_tag: &str
     Ok(())
 }
 
+/// Path of the service ConfigMap next to a kustomization.yaml
+/// (e.g. .../services/email/kustomization.yaml -> .../services/email/email-config.yaml)
+pub fn configmap_path(manifest_path: &Path) -> Option<PathBuf> {
+    let dir = manifest_path.parent()?;
+    let service_name = dir.file_name()?.to_str()?;
+    Some(dir.join(format!("{}-config.yaml", service_name)))
+}
+
 /// Update service ConfigMap with GIT_SHA
 /// This function updates the `data.GIT_SHA` field in a service's ConfigMap
 /// For web service, it also replaces GIT_SHA_PLACEHOLDER in env.js
@@ -469,10 +474,8 @@ pub async fn update_configmap_git_sha(manifest_path: &Path, git_sha: &str) -> Re
         .ok_or_else(|| anyhow::anyhow!("Could not extract service name from manifest path"))?;
 
     // Construct ConfigMap file path (e.g., email-config.yaml or web-config.yaml)
-    let config_map_path = manifest_path
-        .parent()
-        .ok_or_else(|| anyhow::anyhow!("Could not get parent directory"))?
-        .join(format!("{}-config.yaml", service_name));
+    let config_map_path = configmap_path(manifest_path)
+        .ok_or_else(|| anyhow::anyhow!("Could not get parent directory"))?;
 
     // Check if ConfigMap exists
     if !config_map_path.exists() {
@@ -759,6 +762,15 @@ mod tests {
     /// offending op label — never a stringly anyhow
     /// `Failed to execute git`. Pins the typed split so telemetry can
     /// distinguish "git missing" from "git said no".
+    #[test]
+    fn test_configmap_path_is_named_after_the_service_dir() {
+        assert_eq!(
+            configmap_path(Path::new("deploy/services/email/kustomization.yaml")),
+            Some(PathBuf::from("deploy/services/email/email-config.yaml"))
+        );
+        assert_eq!(configmap_path(Path::new("kustomization.yaml")), None);
+    }
+
     #[test]
     fn test_git_capture_exec_failed_carries_op() {
         let result = git_capture_with_bin(
@@ -82,7 +82,9 @@ use crate::tools::{get_tool_path, tools};
 ///   argv shape past the primitive's constructor call — covered
 ///   by a single whole-module include_str! shield that follows
 ///   the `commands/supergraph_verification.rs` (65283fb)
-///   discipline.
+///   discipline. Since ported off kubectl entirely onto typed
+///   `kube::Api` queries through `k8s::create_client`; no longer a
+///   consumer of this primitive.
 /// - `commands/flux.rs` — eight sites across the two async
 ///   diagnostic helpers (`get_pod_status_full`'s pod-image /
 ///   phase / readiness / waiting-reason probe, and
@@ -3,9 +3,12 @@ use k8s_openapi::api::apps::v1::{Deployment, StatefulSet};
 use k8s_openapi::api::core::v1::{Event, Pod};
 use k8s_openapi::chrono::{DateTime, Utc};
 use kube::{
-    api::{Api, ListParams},
+    api::{Api, DynamicObject, GroupVersionKind, ListParams},
+    config::KubeConfigOptions,
+    discovery::{self, ApiCapabilities, ApiResource, Scope},
     Client, Config,
 };
+use std::collections::HashMap;
 use tracing::debug;
 
 #[derive(Debug, Clone)]
@@ -36,6 +39,23 @@ pub async fn create_client() -> Result<Client> {
     Client::try_from(config).context("Failed to create Kubernetes client")
 }
 
+/// Create a Kubernetes client for kubeconfig context `context`, or the
+/// current context when `None`
+pub async fn create_client_for_context(context: Option<&str>) -> Result<Client> {
+    let Some(context) = context else {
+        return create_client().await;
+    };
+    let options = KubeConfigOptions {
+        context: Some(context.to_string()),
+        ..Default::default()
+    };
+    let config = Config::from_kubeconfig(&options)
+        .await
+        .with_context(|| format!("Failed to load kubeconfig context '{}'", context))?;
+
+    Client::try_from(config).context("Failed to create Kubernetes client")
+}
+
 /// Get replica count from Deployment or StatefulSet (auto-detects resource type)
 pub async fn get_replicas(client: &Client, namespace: &str, name: &str) -> Result<i32> {
     // Try Deployment first
@@ -132,105 +152,7 @@ pub async fn get_pod_statuses(
 
     let pod_list = pods.list(&lp).await.context("Failed to list pods")?;
 
-    let mut statuses = Vec::new();
-
-    for pod in pod_list {
-        let name = pod.metadata.name.unwrap_or_else(|| "unknown".to_string());
-
-        let phase = pod
-            .status
-            .as_ref()
-            .and_then(|s| s.phase.clone())
-            .unwrap_or_else(|| "Unknown".to_string());
-
-        let creation_time = pod.metadata.creation_timestamp.as_ref().map(|t| t.0);
-
-        let container_statuses = pod
-            .status
-            .as_ref()
-            .and_then(|s| s.container_statuses.as_ref());
-
-        let ready = container_statuses
-            .and_then(|cs| cs.first())
-            .map(|c| c.ready)
-            .unwrap_or(false);
-
-        let image = pod
-            .spec
-            .as_ref()
-            .and_then(|s| s.containers.first())
-            .and_then(|c| c.image.as_ref())
-            .unwrap_or(&"unknown".to_string())
-            .clone();
-
-        // Extract tag from image via the typed primitive at
-        // `crate::oci_manifest::image_tag_display` — the display sibling
-        // of `image_tag` that returns the shared `IMAGE_TAG_UNKNOWN`
-        // sentinel on a bare reference, a digest-form reference, or any
-        // input the naïve `.split(':').last()` predecessor confused with
-        // a legitimate tag. Centralising the "unknown" literal at the
-        // OCI-adjacent parse module prevents the five k8s / flux
-        // display sites from drifting to five different fallback
-        // strings.
-        let image_tag = crate::oci_manifest::image_tag_display(&image).to_string();
-
-        // Extract detailed container state
-        let container_state = container_statuses.and_then(|cs| cs.first()).map(|c| {
-            let restart_count = c.restart_count;
-            let started = c.started.unwrap_or(false);
-
-            // Check state
-            if let Some(waiting) = &c.state.as_ref().and_then(|s| s.waiting.as_ref()) {
-                ContainerStateInfo {
-                    state: "waiting".to_string(),
-                    reason: waiting.reason.clone(),
-                    message: waiting.message.clone(),
-                    restart_count,
-                    started,
-                }
-            } else if let Some(running) = &c.state.as_ref().and_then(|s| s.running.as_ref()) {
-                ContainerStateInfo {
-                    state: "running".to_string(),
-                    reason: None,
-                    message: Some(format!(
-                        "Started at {}",
-                        running
-                            .started_at
-                            .as_ref()
-                            .map(|t| t.0.to_rfc3339())
-                            .unwrap_or_default()
-                    )),
-                    restart_count,
-                    started,
-                }
-            } else if let Some(terminated) = &c.state.as_ref().and_then(|s| s.terminated.as_ref()) {
-                ContainerStateInfo {
-                    state: "terminated".to_string(),
-                    reason: terminated.reason.clone(),
-                    message: terminated.message.clone(),
-                    restart_count,
-                    started,
-                }
-            } else {
-                ContainerStateInfo {
-                    state: "unknown".to_string(),
-                    reason: None,
-                    message: None,
-                    restart_count,
-                    started,
-                }
-            }
-        });
-
-        statuses.push(PodStatus {
-            name,
-            phase,
-            ready,
-            image_tag,
-            container_state,
-            creation_time,
-        });
-    }
+    let mut statuses: Vec<PodStatus> = pod_list.iter().map(pod_status).collect();
 
     // Sort by name
     statuses.sort_by(|a, b| a.name.cmp(&b.name));
@@ -238,6 +160,110 @@ pub async fn get_pod_statuses(
     Ok(statuses)
 }
 
+/// Summarize one typed [`Pod`] into the [`PodStatus`] the rollout
+/// monitors consume (first container's readiness, image tag and state).
+pub fn pod_status(pod: &Pod) -> PodStatus {
+    let name = pod
+        .metadata
+        .name
+        .clone()
+        .unwrap_or_else(|| "unknown".to_string());
+
+    let phase = pod
+        .status
+        .as_ref()
+        .and_then(|s| s.phase.clone())
+        .unwrap_or_else(|| "Unknown".to_string());
+
+    let creation_time = pod.metadata.creation_timestamp.as_ref().map(|t| t.0);
+
+    let container_statuses = pod
+        .status
+        .as_ref()
+        .and_then(|s| s.container_statuses.as_ref());
+
+    let ready = container_statuses
+        .and_then(|cs| cs.first())
+        .map(|c| c.ready)
+        .unwrap_or(false);
+
+    let image = pod
+        .spec
+        .as_ref()
+        .and_then(|s| s.containers.first())
+        .and_then(|c| c.image.as_ref())
+        .unwrap_or(&"unknown".to_string())
+        .clone();
+
+    // Extract tag from image via the typed primitive at
+    // `crate::oci_manifest::image_tag_display` — the display sibling
+    // of `image_tag` that returns the shared `IMAGE_TAG_UNKNOWN`
+    // sentinel on a bare reference, a digest-form reference, or any
+    // input the naïve `.split(':').last()` predecessor confused with
+    // a legitimate tag. Centralising the "unknown" literal at the
+    // OCI-adjacent parse module prevents the five k8s / flux
+    // display sites from drifting to five different fallback
+    // strings.
+    let image_tag = crate::oci_manifest::image_tag_display(&image).to_string();
+
+    // Extract detailed container state
+    let container_state = container_statuses.and_then(|cs| cs.first()).map(|c| {
+        let restart_count = c.restart_count;
+        let started = c.started.unwrap_or(false);
+
+        // Check state
+        if let Some(waiting) = &c.state.as_ref().and_then(|s| s.waiting.as_ref()) {
+            ContainerStateInfo {
+                state: "waiting".to_string(),
+                reason: waiting.reason.clone(),
+                message: waiting.message.clone(),
+                restart_count,
+                started,
+            }
+        } else if let Some(running) = &c.state.as_ref().and_then(|s| s.running.as_ref()) {
+            ContainerStateInfo {
+                state: "running".to_string(),
+                reason: None,
+                message: Some(format!(
+                    "Started at {}",
+                    running
+                        .started_at
+                        .as_ref()
+                        .map(|t| t.0.to_rfc3339())
+                        .unwrap_or_default()
+                )),
+                restart_count,
+                started,
+            }
+        } else if let Some(terminated) = &c.state.as_ref().and_then(|s| s.terminated.as_ref()) {
+            ContainerStateInfo {
+                state: "terminated".to_string(),
+                reason: terminated.reason.clone(),
+                message: terminated.message.clone(),
+                restart_count,
+                started,
+            }
+        } else {
+            ContainerStateInfo {
+                state: "unknown".to_string(),
+                reason: None,
+                message: None,
+                restart_count,
+                started,
+            }
+        }
+    });
+
+    PodStatus {
+        name,
+        phase,
+        ready,
+        image_tag,
+        container_state,
+        creation_time,
+    }
+}
+
 /// Check if a pod is in a bad state
 pub fn is_bad_state(pod: &PodStatus) -> bool {
     if let Some(state) = &pod.container_state {
@@ -321,3 +347,66 @@ pub async fn get_pod_events(
 
     Ok(event_messages)
 }
+
+/// Discovery results keyed by `(apiVersion, kind)`, so each kind of a
+/// rendered manifest set is resolved against the apiserver once per run.
+#[derive(Default)]
+pub struct KindCache {
+    kinds: HashMap<(String, String), (ApiResource, ApiCapabilities)>,
+}
+
+impl KindCache {
+    pub async fn resolve(
+        &mut self,
+        client: &Client,
+        api_version: &str,
+        kind: &str,
+    ) -> Result<(ApiResource, ApiCapabilities)> {
+        let key = (api_version.to_string(), kind.to_string());
+        if let Some(found) = self.kinds.get(&key) {
+            return Ok(found.clone());
+        }
+        let (group, version) = match api_version.split_once('/') {
+            Some((group, version)) => (group, version),
+            None => ("", api_version),
+        };
+        let gvk = GroupVersionKind::gvk(group, version, kind);
+        let found = discovery::pinned_kind(client, &gvk)
+            .await
+            .with_context(|| format!("Failed to discover {api_version} {kind}"))?;
+        self.kinds.insert(key, found.clone());
+        Ok(found)
+    }
+}
+
+/// Dynamic API handle for a manifest object. Namespaced kinds are
+/// addressed in the object's `metadata.namespace`, else in
+/// `default_namespace`; the namespace used is returned alongside
+/// (`None` for cluster-scoped kinds).
+pub async fn object_api(
+    client: &Client,
+    kinds: &mut KindCache,
+    object: &serde_json::Value,
+    default_namespace: &str,
+) -> Result<(Api<DynamicObject>, Option<String>)> {
+    let field = |path: &str| {
+        object
+            .pointer(path)
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or("")
+    };
+    let (resource, caps) = kinds
+        .resolve(client, field("/apiVersion"), field("/kind"))
+        .await?;
+    if caps.scope != Scope::Namespaced {
+        return Ok((Api::all_with(client.clone(), &resource), None));
+    }
+    let namespace = match field("/metadata/namespace") {
+        "" => default_namespace,
+        ns => ns,
+    };
+    Ok((
+        Api::namespaced_with(client.clone(), namespace, &resource),
+        Some(namespace.to_string()),
+    ))
+}
@@ -0,0 +1,1554 @@
+//! In-process kustomize rendering
+//!
+//! Builds a kustomization directory the way `kustomize build` (and Flux's
+//! kustomize-controller) would, for the feature set forge's overlays use:
+//!
+//! - `resources` / `bases`: manifest files and nested kustomization
+//!   directories, rendered recursively
+//! - `configMapGenerator` / `secretGenerator` (literals, files, env files,
+//!   `create` / `merge` / `replace` behaviors) with kustomize's content-hash
+//!   name suffix, and references to generated objects rewritten in pod
+//!   specs
+//! - `patches`, `patchesStrategicMerge`, `patchesJson6902`: strategic merge
+//!   patches (named-list merging, `$patch: delete|replace`, `null` removal)
+//!   and RFC 6902 JSON patches, with `target` selectors
+//! - `namespace`, `namePrefix`, `nameSuffix`, `commonLabels`, `labels`,
+//!   `commonAnnotations`, `images`, `replicas`
+//!
+//! Any other kustomization field (`components`, `crds`, `helmCharts`,
+//! `replacements`, ...) is never silently ignored: the render stops with
+//! [`KubernetesError::RenderUnsupported`] so callers can skip an overlay
+//! they cannot reproduce instead of checking the wrong output. Remote
+//! resources and all other failures surface as
+//! [`KubernetesError::RenderFailed`].
+
+use anyhow::{anyhow, bail, Context, Result};
+use base64::Engine;
+use regex::Regex;
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::error::KubernetesError;
+
+/// File names kustomize accepts for a kustomization, in lookup order.
+const KUSTOMIZATION_FILE_NAMES: &[&str] =
+    &["kustomization.yaml", "kustomization.yml", "Kustomization"];
+
+/// Nested kustomizations deeper than this are treated as a cycle.
+const MAX_DEPTH: usize = 32;
+
+/// Internal annotation marking generated objects that still need their
+/// content-hash suffix; stripped before rendering finishes.
+const NEEDS_HASH_ANNOTATION: &str = "internal.forge/needs-hash";
+
+/// Kinds that are never namespaced, so the namespace transformer skips them.
+const CLUSTER_SCOPED_KINDS: &[&str] = &[
+    "APIService",
+    "ClusterIssuer",
+    "ClusterRole",
+    "ClusterRoleBinding",
+    "ClusterSecretStore",
+    "CustomResourceDefinition",
+    "IngressClass",
+    "MutatingWebhookConfiguration",
+    "Namespace",
+    "PersistentVolume",
+    "PriorityClass",
+    "RuntimeClass",
+    "StorageClass",
+    "ValidatingWebhookConfiguration",
+];
+
+/// Keys that identify entries of a list for strategic merge, in preference
+/// order (Kubernetes merges container ports by `containerPort`, Service
+/// ports by `port`).
+const MERGE_KEYS: &[&str] = &[
+    "containerPort",
+    "port",
+    "mountPath",
+    "devicePath",
+    "ip",
+    "name",
+];
+
+// ============================================================================
+// Kustomization File
+// ============================================================================
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct Kustomization {
+    // Accepted so real kustomization files parse; they do not affect output.
+    #[allow(dead_code)]
+    api_version: Option<String>,
+    #[allow(dead_code)]
+    kind: Option<String>,
+    #[allow(dead_code)]
+    metadata: Option<Value>,
+    #[allow(dead_code)]
+    build_metadata: Vec<String>,
+    #[allow(dead_code)]
+    sort_options: Option<Value>,
+    resources: Vec<String>,
+    bases: Vec<String>,
+    namespace: Option<String>,
+    name_prefix: Option<String>,
+    name_suffix: Option<String>,
+    common_labels: BTreeMap<String, String>,
+    labels: Vec<LabelSet>,
+    common_annotations: BTreeMap<String, String>,
+    images: Vec<ImageOverride>,
+    replicas: Vec<ReplicaOverride>,
+    patches: Vec<PatchEntry>,
+    patches_strategic_merge: Vec<String>,
+    patches_json6902: Vec<PatchEntry>,
+    config_map_generator: Vec<GeneratorArgs>,
+    secret_generator: Vec<GeneratorArgs>,
+    generator_options: Option<GeneratorOptions>,
+    /// Fields outside the supported feature set.
+    #[serde(flatten)]
+    unsupported: BTreeMap<String, Value>,
+}
+
+/// A kustomization using fields this renderer does not implement.
+#[derive(Debug)]
+struct Unsupported {
+    file: PathBuf,
+    fields: Vec<String>,
+}
+
+impl std::fmt::Display for Unsupported {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} uses unsupported fields: {}",
+            self.file.display(),
+            self.fields.join(", ")
+        )
+    }
+}
+
+impl std::error::Error for Unsupported {}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase", deny_unknown_fields)]
+struct LabelSet {
+    pairs: BTreeMap<String, String>,
+    include_selectors: bool,
+    include_templates: bool,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase", deny_unknown_fields)]
+struct ImageOverride {
+    name: String,
+    new_name: Option<String>,
+    new_tag: Option<String>,
+    digest: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ReplicaOverride {
+    name: String,
+    count: i64,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct PatchEntry {
+    path: Option<String>,
+    patch: Option<String>,
+    target: Option<PatchTarget>,
+    #[allow(dead_code)]
+    options: Option<Value>,
+}
+
+/// Selector for the objects a patch applies to.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase", deny_unknown_fields)]
+struct PatchTarget {
+    group: Option<String>,
+    version: Option<String>,
+    kind: Option<String>,
+    name: Option<String>,
+    namespace: Option<String>,
+    label_selector: Option<String>,
+    annotation_selector: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase", deny_unknown_fields)]
+struct GeneratorArgs {
+    name: String,
+    namespace: Option<String>,
+    behavior: Option<String>,
+    literals: Vec<String>,
+    files: Vec<String>,
+    envs: Vec<String>,
+    env: Option<String>,
+    #[serde(rename = "type")]
+    secret_type: Option<String>,
+    options: Option<GeneratorOptions>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default, rename_all = "camelCase", deny_unknown_fields)]
+struct GeneratorOptions {
+    labels: BTreeMap<String, String>,
+    annotations: BTreeMap<String, String>,
+    disable_name_suffix_hash: Option<bool>,
+    immutable: Option<bool>,
+}
+
+// ============================================================================
+// Entry Points
+// ============================================================================
+
+/// Render the kustomization at `path` (its directory or its
+/// `kustomization.yaml`) into the list of objects it produces.
+pub fn render(path: &Path) -> Result<Vec<Value>, KubernetesError> {
+    let dir = kustomization_dir(path);
+    build(&dir, &mut Vec::new()).map_err(|e| match e.downcast_ref::<Unsupported>() {
+        Some(unsupported) => KubernetesError::RenderUnsupported {
+            path: unsupported.file.display().to_string(),
+            fields: unsupported.fields.clone(),
+        },
+        None => KubernetesError::RenderFailed {
+            path: dir.display().to_string(),
+            message: format!("{e:#}"),
+        },
+    })
+}
+
+/// Directory holding the kustomization `path` names: `path` itself when it
+/// is a directory, else its parent.
+pub fn kustomization_dir(path: &Path) -> PathBuf {
+    if path.is_dir() {
+        path.to_path_buf()
+    } else {
+        path.parent().unwrap_or(path).to_path_buf()
+    }
+}
+
+/// The kustomization file inside `dir`, if there is one.
+pub fn find_kustomization_file(dir: &Path) -> Option<PathBuf> {
+    KUSTOMIZATION_FILE_NAMES
+        .iter()
+        .map(|name| dir.join(name))
+        .find(|p| p.is_file())
+}
+
+/// Split a multi-document YAML stream into objects, dropping empty
+/// documents and expanding `kind: List` wrappers.
+pub fn parse_documents(yaml: &str) -> Result<Vec<Value>> {
+    let mut objects = Vec::new();
+    for document in serde_yaml::Deserializer::from_str(yaml) {
+        let value = Value::deserialize(document).context("Failed to parse YAML document")?;
+        match value {
+            Value::Null => {}
+            Value::Object(ref map) if map.get("kind").and_then(Value::as_str) == Some("List") => {
+                if let Some(Value::Array(items)) = map.get("items") {
+                    objects.extend(items.iter().cloned());
+                }
+            }
+            other => objects.push(other),
+        }
+    }
+    Ok(objects)
+}
+
+// ============================================================================
+// Build
+// ============================================================================
+
+fn build(dir: &Path, stack: &mut Vec<PathBuf>) -> Result<Vec<Value>> {
+    let canonical = dir
+        .canonicalize()
+        .with_context(|| format!("Kustomization directory {} does not exist", dir.display()))?;
+    if stack.contains(&canonical) || stack.len() >= MAX_DEPTH {
+        bail!("Kustomization cycle through {}", dir.display());
+    }
+
+    let file = find_kustomization_file(dir)
+        .ok_or_else(|| anyhow!("No kustomization.yaml in {}", dir.display()))?;
+    let content = std::fs::read_to_string(&file)
+        .with_context(|| format!("Failed to read {}", file.display()))?;
+    let kustomization: Kustomization = serde_yaml::from_str(&content)
+        .with_context(|| format!("Unsupported or invalid kustomization {}", file.display()))?;
+    if !kustomization.unsupported.is_empty() {
+        return Err(Unsupported {
+            file,
+            fields: kustomization.unsupported.into_keys().collect(),
+        }
+        .into());
+    }
+
+    stack.push(canonical);
+    let mut objects = Vec::new();
+    for resource in kustomization.bases.iter().chain(&kustomization.resources) {
+        objects.extend(load_resource(dir, resource, stack)?);
+    }
+    stack.pop();
+
+    let global_options = kustomization.generator_options.clone().unwrap_or_default();
+    for args in &kustomization.config_map_generator {
+        let generated = generate(dir, args, &global_options, "ConfigMap")?;
+        add_generated(&mut objects, generated, args)?;
+    }
+    for args in &kustomization.secret_generator {
+        let generated = generate(dir, args, &global_options, "Secret")?;
+        add_generated(&mut objects, generated, args)?;
+    }
+
+    apply_patches(dir, &kustomization, &mut objects)?;
+
+    let original_names: Vec<(String, String)> = objects.iter().map(kind_and_name).collect();
+    apply_transformers(&kustomization, &mut objects);
+    for object in &mut objects {
+        apply_hash_suffix(object)?;
+    }
+
+    let renames: Vec<(String, String, String)> = original_names
+        .into_iter()
+        .zip(objects.iter().map(kind_and_name))
+        .filter(|((_, before), (_, after))| before != after)
+        .map(|((kind, before), (_, after))| (kind, before, after))
+        .collect();
+    for object in &mut objects {
+        rewrite_references(object, &renames);
+    }
+
+    Ok(objects)
+}
+
+/// Load one `resources` entry: a nested kustomization directory or a
+/// manifest file.
+fn load_resource(dir: &Path, resource: &str, stack: &mut Vec<PathBuf>) -> Result<Vec<Value>> {
+    if resource.contains("://") || resource.starts_with("github.com/") {
+        bail!("Remote resource {resource} is not supported; vendor it into the repository");
+    }
+    let path = dir.join(resource);
+    if path.is_dir() {
+        return build(&path, stack);
+    }
+    let content = std::fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read resource {}", path.display()))?;
+    parse_documents(&content).with_context(|| format!("Failed to parse {}", path.display()))
+}
+
+fn kind_and_name(object: &Value) -> (String, String) {
+    (
+        str_at(object, "/kind").to_string(),
+        str_at(object, "/metadata/name").to_string(),
+    )
+}
+
+fn str_at<'a>(object: &'a Value, pointer: &str) -> &'a str {
+    object
+        .pointer(pointer)
+        .and_then(Value::as_str)
+        .unwrap_or("")
+}
+
+/// Mutable `metadata.{field}` map of `object`, created when absent.
+fn metadata_map<'a>(object: &'a mut Value, field: &str) -> Option<&'a mut Map<String, Value>> {
+    let metadata = object
+        .as_object_mut()?
+        .entry("metadata")
+        .or_insert_with(|| Value::Object(Map::new()));
+    let map = metadata
+        .as_object_mut()?
+        .entry(field)
+        .or_insert_with(|| Value::Object(Map::new()));
+    if map.is_null() {
+        *map = Value::Object(Map::new());
+    }
+    map.as_object_mut()
+}
+
+// ============================================================================
+// Generators
+// ============================================================================
+
+fn generate(
+    dir: &Path,
+    args: &GeneratorArgs,
+    global: &GeneratorOptions,
+    kind: &str,
+) -> Result<Value> {
+    if args.name.is_empty() {
+        bail!("{kind} generator entry is missing `name`");
+    }
+
+    let mut entries: BTreeMap<String, Vec<u8>> = BTreeMap::new();
+    let mut insert = |key: String, value: Vec<u8>| -> Result<()> {
+        if entries.insert(key.clone(), value).is_some() {
+            bail!("{} {} has duplicate key {}", kind, args.name, key);
+        }
+        Ok(())
+    };
+
+    for literal in &args.literals {
+        let (key, value) = literal
+            .split_once('=')
+            .ok_or_else(|| anyhow!("Literal {literal:?} in {} is not KEY=VALUE", args.name))?;
+        insert(key.to_string(), unquote(value).as_bytes().to_vec())?;
+    }
+    for env_file in args.envs.iter().chain(&args.env) {
+        let path = dir.join(env_file);
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read env file {}", path.display()))?;
+        for line in content.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| anyhow!("Line {line:?} in {} is not KEY=VALUE", path.display()))?;
+            insert(key.trim().to_string(), value.as_bytes().to_vec())?;
+        }
+    }
+    for file in &args.files {
+        let (key, file_path) = match file.split_once('=') {
+            Some((key, file_path)) => (key.to_string(), file_path),
+            None => {
+                let key = Path::new(file)
+                    .file_name()
+                    .and_then(|n| n.to_str())
+                    .unwrap_or(file)
+                    .to_string();
+                (key, file.as_str())
+            }
+        };
+        let path = dir.join(file_path);
+        let content =
+            std::fs::read(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+        insert(key, content)?;
+    }
+
+    let mut object = serde_json::json!({
+        "apiVersion": "v1",
+        "kind": kind,
+        "metadata": {"name": args.name},
+    });
+    if let Some(namespace) = &args.namespace {
+        object["metadata"]["namespace"] = Value::String(namespace.clone());
+    }
+
+    let engine = base64::engine::general_purpose::STANDARD;
+    if kind == "Secret" {
+        object["type"] = Value::String(
+            args.secret_type
+                .clone()
+                .unwrap_or_else(|| "Opaque".to_string()),
+        );
+        let data: Map<String, Value> = entries
+            .into_iter()
+            .map(|(k, v)| (k, Value::String(engine.encode(v))))
+            .collect();
+        object["data"] = Value::Object(data);
+    } else {
+        let mut data = Map::new();
+        let mut binary = Map::new();
+        for (key, value) in entries {
+            match String::from_utf8(value) {
+                Ok(text) => data.insert(key, Value::String(text)),
+                Err(e) => binary.insert(key, Value::String(engine.encode(e.into_bytes()))),
+            };
+        }
+        object["data"] = Value::Object(data);
+        if !binary.is_empty() {
+            object["binaryData"] = Value::Object(binary);
+        }
+    }
+
+    let local = args.options.clone().unwrap_or_default();
+    if local.immutable.or(global.immutable).unwrap_or(false) {
+        object["immutable"] = Value::Bool(true);
+    }
+    for (field, pairs) in [
+        ("labels", global.labels.iter().chain(&local.labels)),
+        (
+            "annotations",
+            global.annotations.iter().chain(&local.annotations),
+        ),
+    ] {
+        let mut pairs = pairs.peekable();
+        if pairs.peek().is_none() {
+            continue;
+        }
+        if let Some(map) = metadata_map(&mut object, field) {
+            for (k, v) in pairs {
+                map.insert(k.clone(), Value::String(v.clone()));
+            }
+        }
+    }
+    let disable_hash = local
+        .disable_name_suffix_hash
+        .or(global.disable_name_suffix_hash)
+        .unwrap_or(false);
+    if !disable_hash {
+        if let Some(map) = metadata_map(&mut object, "annotations") {
+            map.insert(
+                NEEDS_HASH_ANNOTATION.to_string(),
+                Value::String("true".into()),
+            );
+        }
+    }
+    Ok(object)
+}
+
+/// Strip one level of matching quotes, as kustomize does for literals.
+fn unquote(value: &str) -> &str {
+    for quote in ['"', '\''] {
+        if let Some(inner) = value
+            .strip_prefix(quote)
+            .and_then(|v| v.strip_suffix(quote))
+        {
+            return inner;
+        }
+    }
+    value
+}
+
+/// Add a generated object according to its generator's `behavior`.
+fn add_generated(objects: &mut Vec<Value>, generated: Value, args: &GeneratorArgs) -> Result<()> {
+    let (kind, name) = kind_and_name(&generated);
+    let existing = objects
+        .iter_mut()
+        .find(|o| kind_and_name(o) == (kind.clone(), name.clone()));
+
+    match (args.behavior.as_deref().unwrap_or("create"), existing) {
+        ("create", None) => objects.push(generated),
+        ("create", Some(_)) => {
+            bail!("{kind} {name} already exists; use behavior: merge or replace")
+        }
+        ("merge", Some(existing)) => {
+            for field in ["data", "binaryData"] {
+                if let Some(Value::Object(new)) = generated.get(field) {
+                    let target = existing
+                        .as_object_mut()
+                        .map(|o| o.entry(field).or_insert_with(|| Value::Object(Map::new())));
+                    if let Some(Value::Object(target)) = target {
+                        target.extend(new.clone());
+                    }
+                }
+            }
+            merge_metadata(existing, &generated);
+        }
+        ("replace", Some(existing)) => {
+            for field in ["data", "binaryData"] {
+                if let Some(map) = existing.as_object_mut() {
+                    map.remove(field);
+                    if let Some(value) = generated.get(field) {
+                        map.insert(field.to_string(), value.clone());
+                    }
+                }
+            }
+            merge_metadata(existing, &generated);
+        }
+        (behavior @ ("merge" | "replace"), None) => {
+            bail!("{kind} {name} has behavior {behavior} but no base object to apply it to")
+        }
+        (behavior, _) => bail!("Unknown generator behavior {behavior:?} for {kind} {name}"),
+    }
+    Ok(())
+}
+
+fn merge_metadata(existing: &mut Value, generated: &Value) {
+    for field in ["labels", "annotations"] {
+        if let Some(Value::Object(new)) = generated.pointer(&format!("/metadata/{field}")) {
+            if let Some(map) = metadata_map(existing, field) {
+                map.extend(new.clone());
+            }
+        }
+    }
+}
+
+/// Append kustomize's content hash to a generated object's name.
+fn apply_hash_suffix(object: &mut Value) -> Result<()> {
+    let marked = object
+        .pointer_mut("/metadata/annotations")
+        .and_then(Value::as_object_mut)
+        .map(|annotations| {
+            let marked = annotations.remove(NEEDS_HASH_ANNOTATION).is_some();
+            (marked, annotations.is_empty())
+        });
+    let Some((true, annotations_empty)) = marked else {
+        return Ok(());
+    };
+    if annotations_empty {
+        if let Some(metadata) = object.get_mut("metadata").and_then(Value::as_object_mut) {
+            metadata.remove("annotations");
+        }
+    }
+
+    let hash = content_hash(object)?;
+    let name = format!("{}-{}", str_at(object, "/metadata/name"), hash);
+    object["metadata"]["name"] = Value::String(name);
+    Ok(())
+}
+
+/// kustomize's name-suffix hash for a ConfigMap or Secret: the SHA-256 of
+/// the Go `json.Marshal` encoding of `{kind, name, data, ...}`, first ten
+/// hex digits, with vowel-like digits swapped out so the suffix never
+/// spells words.
+pub fn content_hash(object: &Value) -> Result<String> {
+    let mut encoded = BTreeMap::new();
+    let kind = str_at(object, "/kind");
+    encoded.insert("kind", Value::String(kind.to_string()));
+    encoded.insert(
+        "name",
+        Value::String(str_at(object, "/metadata/name").to_string()),
+    );
+    encoded.insert(
+        "data",
+        object
+            .get("data")
+            .cloned()
+            .unwrap_or(Value::Object(Map::new())),
+    );
+    match kind {
+        "ConfigMap" => {
+            if let Some(Value::Object(binary)) = object.get("binaryData") {
+                if !binary.is_empty() {
+                    encoded.insert("binaryData", Value::Object(binary.clone()));
+                }
+            }
+        }
+        "Secret" => {
+            encoded.insert("type", object.get("type").cloned().unwrap_or(Value::Null));
+        }
+        other => bail!("Cannot compute a name hash for kind {other}"),
+    }
+    if let Some(immutable) = object.get("immutable") {
+        encoded.insert("immutable", immutable.clone());
+    }
+
+    let json = go_json_escape(&serde_json::to_string(&encoded)?);
+    let digest = format!("{:x}", Sha256::digest(json.as_bytes()));
+    Ok(digest[..10]
+        .chars()
+        .map(|c| match c {
+            '0' => 'g',
+            '1' => 'h',
+            '3' => 'k',
+            'a' => 'm',
+            'e' => 't',
+            other => other,
+        })
+        .collect())
+}
+
+/// Apply the HTML-safe escaping Go's `encoding/json` performs and
+/// serde_json does not. These characters only occur inside strings.
+fn go_json_escape(json: &str) -> String {
+    json.replace('<', "\\u003c")
+        .replace('>', "\\u003e")
+        .replace('&', "\\u0026")
+        .replace('\u{2028}', "\\u2028")
+        .replace('\u{2029}', "\\u2029")
+}
+
+// ============================================================================
+// Patches
+// ============================================================================
+
+fn apply_patches(
+    dir: &Path,
+    kustomization: &Kustomization,
+    objects: &mut Vec<Value>,
+) -> Result<()> {
+    for entry in &kustomization.patches_strategic_merge {
+        let content = if entry.contains('\n') {
+            entry.clone()
+        } else {
+            let path = dir.join(entry);
+            std::fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read patch {}", path.display()))?
+        };
+        for patch in parse_documents(&content)? {
+            apply_strategic_patch(objects, &patch, None)?;
+        }
+    }
+
+    for entry in kustomization
+        .patches
+        .iter()
+        .chain(&kustomization.patches_json6902)
+    {
+        let content = match (&entry.patch, &entry.path) {
+            (Some(inline), _) => inline.clone(),
+            (None, Some(path)) => {
+                let path = dir.join(path);
+                std::fs::read_to_string(&path)
+                    .with_context(|| format!("Failed to read patch {}", path.display()))?
+            }
+            (None, None) => bail!("Patch entry has neither `patch` nor `path`"),
+        };
+        let documents = parse_documents(&content)?;
+        match documents.as_slice() {
+            [Value::Array(operations)] => {
+                let target = entry
+                    .target
+                    .as_ref()
+                    .ok_or_else(|| anyhow!("JSON6902 patch requires a `target`"))?;
+                apply_json_patch(objects, operations, target)?;
+            }
+            _ => {
+                for patch in &documents {
+                    apply_strategic_patch(objects, patch, entry.target.as_ref())?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn apply_json_patch(
+    objects: &mut [Value],
+    operations: &[Value],
+    target: &PatchTarget,
+) -> Result<()> {
+    let patch: json_patch::Patch = serde_json::from_value(Value::Array(operations.to_vec()))
+        .context("Invalid JSON6902 patch")?;
+    let mut matched = false;
+    for object in objects.iter_mut() {
+        if target_matches(target, object)? {
+            matched = true;
+            let (kind, name) = kind_and_name(object);
+            json_patch::patch(object, &patch)
+                .with_context(|| format!("JSON6902 patch failed on {kind}/{name}"))?;
+        }
+    }
+    if !matched {
+        bail!(
+            "JSON6902 patch target {} matched no resources",
+            describe_target(target)
+        );
+    }
+    Ok(())
+}
+
+/// Apply one strategic merge patch, to the objects `target` selects or,
+/// without a target, to the object with the patch's own kind and name.
+fn apply_strategic_patch(
+    objects: &mut Vec<Value>,
+    patch: &Value,
+    target: Option<&PatchTarget>,
+) -> Result<()> {
+    let (kind, name) = kind_and_name(patch);
+    let namespace = str_at(patch, "/metadata/namespace");
+    let deletes = patch.get("$patch").and_then(Value::as_str) == Some("delete");
+
+    let mut matched = Vec::new();
+    for (index, object) in objects.iter().enumerate() {
+        let selected = match target {
+            Some(target) => target_matches(target, object)?,
+            None => {
+                kind_and_name(object) == (kind.clone(), name.clone())
+                    && (namespace.is_empty() || str_at(object, "/metadata/namespace") == namespace)
+            }
+        };
+        if selected {
+            matched.push(index);
+        }
+    }
+    if matched.is_empty() {
+        let wanted = match target {
+            Some(target) => describe_target(target),
+            None => format!("{kind}/{name}"),
+        };
+        bail!("Patch target {wanted} matched no resources");
+    }
+
+    if deletes {
+        for index in matched.into_iter().rev() {
+            objects.remove(index);
+        }
+        return Ok(());
+    }
+
+    let mut patch = patch.clone();
+    if target.is_some() {
+        // A targeted patch's own identity is only a placeholder.
+        if let Some(map) = patch.as_object_mut() {
+            map.remove("apiVersion");
+            map.remove("kind");
+            if let Some(Value::Object(metadata)) = map.get_mut("metadata") {
+                metadata.remove("name");
+                metadata.remove("namespace");
+            }
+        }
+    }
+    for index in matched {
+        strategic_merge(&mut objects[index], &patch);
+    }
+    Ok(())
+}
+
+/// Strategic merge `patch` into `target`.
+pub fn strategic_merge(target: &mut Value, patch: &Value) {
+    match (target.as_object_mut(), patch.as_object()) {
+        (Some(target), Some(patch)) => {
+            for (key, value) in patch {
+                if key.starts_with('$') {
+                    continue;
+                }
+                if value.is_null() {
+                    target.remove(key);
+                    continue;
+                }
+                match target.get_mut(key) {
+                    Some(existing) => merge_field(existing, value),
+                    None => {
+                        target.insert(key.clone(), strip_directives(value));
+                    }
+                }
+            }
+        }
+        _ => *target = strip_directives(patch),
+    }
+}
+
+fn merge_field(existing: &mut Value, patch: &Value) {
+    if patch.get("$patch").and_then(Value::as_str) == Some("replace") {
+        *existing = strip_directives(patch);
+        return;
+    }
+    match (existing, patch) {
+        (existing @ Value::Object(_), Value::Object(_)) => strategic_merge(existing, patch),
+        (Value::Array(existing), Value::Array(patch)) => merge_list(existing, patch),
+        (existing, patch) => *existing = strip_directives(patch),
+    }
+}
+
+fn merge_list(existing: &mut Vec<Value>, patch: &[Value]) {
+    let is_replace = |v: &Value| v.get("$patch").and_then(Value::as_str) == Some("replace");
+    let replace_all = patch.iter().any(is_replace);
+    let entries: Vec<&Value> = patch.iter().filter(|v| !is_replace(v)).collect();
+
+    let key = list_merge_key(&entries);
+    let Some(key) = key.filter(|_| !replace_all) else {
+        *existing = entries.into_iter().map(strip_directives).collect();
+        return;
+    };
+
+    for entry in entries {
+        let id = entry.get(key);
+        let position = existing.iter().position(|e| e.get(key) == id);
+        let delete = entry.get("$patch").and_then(Value::as_str) == Some("delete");
+        match (position, delete) {
+            (Some(index), true) => {
+                existing.remove(index);
+            }
+            (None, true) => {}
+            (Some(index), false) => strategic_merge(&mut existing[index], entry),
+            (None, false) => existing.push(strip_directives(entry)),
+        }
+    }
+}
+
+/// The merge key shared by every entry of a patch list, if any.
+fn list_merge_key(entries: &[&Value]) -> Option<&'static str> {
+    if entries.is_empty() || !entries.iter().all(|e| e.is_object()) {
+        return None;
+    }
+    MERGE_KEYS
+        .iter()
+        .copied()
+        .find(|key| entries.iter().all(|e| e.get(*key).is_some()))
+}
+
+/// Copy of `value` without `$patch`-style directive keys.
+fn strip_directives(value: &Value) -> Value {
+    match value {
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .filter(|(k, _)| !k.starts_with('$'))
+                .map(|(k, v)| (k.clone(), strip_directives(v)))
+                .collect(),
+        ),
+        Value::Array(items) => Value::Array(items.iter().map(strip_directives).collect()),
+        other => other.clone(),
+    }
+}
+
+fn target_matches(target: &PatchTarget, object: &Value) -> Result<bool> {
+    let api_version = str_at(object, "/apiVersion");
+    let (group, version) = api_version.split_once('/').unwrap_or(("", api_version));
+    if target.group.as_deref().is_some_and(|g| g != group)
+        || target.version.as_deref().is_some_and(|v| v != version)
+        || target
+            .kind
+            .as_deref()
+            .is_some_and(|k| k != str_at(object, "/kind"))
+        || target
+            .namespace
+            .as_deref()
+            .is_some_and(|ns| ns != str_at(object, "/metadata/namespace"))
+    {
+        return Ok(false);
+    }
+    if let Some(name) = &target.name {
+        let pattern = Regex::new(&format!("^(?:{name})$"))
+            .with_context(|| format!("Invalid patch target name {name:?}"))?;
+        if !pattern.is_match(str_at(object, "/metadata/name")) {
+            return Ok(false);
+        }
+    }
+    Ok(selector_matches(
+        target.label_selector.as_deref(),
+        object.pointer("/metadata/labels"),
+    ) && selector_matches(
+        target.annotation_selector.as_deref(),
+        object.pointer("/metadata/annotations"),
+    ))
+}
+
+/// Equality-based selector (`a=b,c!=d,e`) against a label/annotation map.
+fn selector_matches(selector: Option<&str>, map: Option<&Value>) -> bool {
+    let Some(selector) = selector else {
+        return true;
+    };
+    let get = |key: &str| map.and_then(|m| m.get(key)).and_then(Value::as_str);
+    selector
+        .split(',')
+        .map(str::trim)
+        .filter(|term| !term.is_empty())
+        .all(|term| {
+            if let Some((key, value)) = term.split_once("!=") {
+                get(key.trim()) != Some(value.trim())
+            } else if let Some((key, value)) = term.split_once('=') {
+                let value = value.trim_start_matches('=');
+                get(key.trim()) == Some(value.trim())
+            } else {
+                get(term).is_some()
+            }
+        })
+}
+
+fn describe_target(target: &PatchTarget) -> String {
+    [
+        ("group", &target.group),
+        ("version", &target.version),
+        ("kind", &target.kind),
+        ("name", &target.name),
+        ("namespace", &target.namespace),
+        ("labelSelector", &target.label_selector),
+        ("annotationSelector", &target.annotation_selector),
+    ]
+    .iter()
+    .filter_map(|(field, value)| value.as_ref().map(|v| format!("{field}={v}")))
+    .collect::<Vec<_>>()
+    .join(",")
+}
+
+// ============================================================================
+// Transformers
+// ============================================================================
+
+fn apply_transformers(kustomization: &Kustomization, objects: &mut [Value]) {
+    let mut selector_labels = kustomization.common_labels.clone();
+    let mut template_labels = kustomization.common_labels.clone();
+    let mut metadata_labels = kustomization.common_labels.clone();
+    for set in &kustomization.labels {
+        metadata_labels.extend(set.pairs.clone());
+        if set.include_selectors {
+            selector_labels.extend(set.pairs.clone());
+            template_labels.extend(set.pairs.clone());
+        } else if set.include_templates {
+            template_labels.extend(set.pairs.clone());
+        }
+    }
+
+    for object in objects.iter_mut() {
+        let kind = str_at(object, "/kind").to_string();
+
+        if let Some(namespace) = &kustomization.namespace {
+            if !CLUSTER_SCOPED_KINDS.contains(&kind.as_str()) {
+                object["metadata"]["namespace"] = Value::String(namespace.clone());
+            }
+            set_subject_namespaces(object, namespace);
+        }
+
+        if let Some(replicas) = kustomization
+            .replicas
+            .iter()
+            .find(|r| r.name == str_at(object, "/metadata/name"))
+        {
+            if matches!(kind.as_str(), "Deployment" | "StatefulSet" | "ReplicaSet") {
+                if let Some(spec) = object.get_mut("spec").and_then(Value::as_object_mut) {
+                    spec.insert("replicas".to_string(), Value::from(replicas.count));
+                }
+            }
+        }
+
+        if kind != "Namespace" && kind != "CustomResourceDefinition" {
+            let prefix = kustomization.name_prefix.as_deref().unwrap_or("");
+            let suffix = kustomization.name_suffix.as_deref().unwrap_or("");
+            if !prefix.is_empty() || !suffix.is_empty() {
+                let name = format!("{prefix}{}{suffix}", str_at(object, "/metadata/name"));
+                object["metadata"]["name"] = Value::String(name);
+            }
+        }
+
+        add_pairs(object, "/metadata", "labels", &metadata_labels);
+        add_pairs(
+            object,
+            "/metadata",
+            "annotations",
+            &kustomization.common_annotations,
+        );
+        add_selector_labels(object, &kind, &selector_labels);
+        for template in template_paths(&kind) {
+            add_pairs(
+                object,
+                &format!("{template}/metadata"),
+                "labels",
+                &template_labels,
+            );
+            add_pairs(
+                object,
+                &format!("{template}/metadata"),
+                "annotations",
+                &kustomization.common_annotations,
+            );
+        }
+
+        apply_images(object, &kustomization.images);
+    }
+}
+
+/// JSON pointers to the pod templates of a workload kind.
+fn template_paths(kind: &str) -> &'static [&'static str] {
+    match kind {
+        "Deployment" | "StatefulSet" | "DaemonSet" | "ReplicaSet" | "Job" => &["/spec/template"],
+        "CronJob" => &["/spec/jobTemplate/spec/template"],
+        _ => &[],
+    }
+}
+
+/// Merge `pairs` into the `{parent}.{field}` map, creating it as needed.
+/// Does nothing when the parent object itself is absent.
+fn add_pairs(object: &mut Value, parent: &str, field: &str, pairs: &BTreeMap<String, String>) {
+    if pairs.is_empty() {
+        return;
+    }
+    let Some(parent) = object.pointer_mut(parent).and_then(Value::as_object_mut) else {
+        return;
+    };
+    let map = parent
+        .entry(field)
+        .or_insert_with(|| Value::Object(Map::new()));
+    if map.is_null() {
+        *map = Value::Object(Map::new());
+    }
+    if let Some(map) = map.as_object_mut() {
+        for (k, v) in pairs {
+            map.insert(k.clone(), Value::String(v.clone()));
+        }
+    }
+}
+
+fn add_selector_labels(object: &mut Value, kind: &str, labels: &BTreeMap<String, String>) {
+    if labels.is_empty() {
+        return;
+    }
+    match kind {
+        "Service" => add_pairs(object, "/spec", "selector", labels),
+        "Deployment" | "StatefulSet" | "DaemonSet" | "ReplicaSet" | "PodDisruptionBudget" => {
+            if let Some(spec) = object.get_mut("spec").and_then(Value::as_object_mut) {
+                spec.entry("selector")
+                    .or_insert_with(|| Value::Object(Map::new()));
+            }
+            add_pairs(object, "/spec/selector", "matchLabels", labels);
+        }
+        "NetworkPolicy" => add_pairs(object, "/spec/podSelector", "matchLabels", labels),
+        _ => {}
+    }
+}
+
+/// Point ServiceAccount subjects of (Cluster)RoleBindings that name no
+/// namespace at the kustomization's namespace.
+fn set_subject_namespaces(object: &mut Value, namespace: &str) {
+    if !matches!(
+        str_at(object, "/kind"),
+        "RoleBinding" | "ClusterRoleBinding"
+    ) {
+        return;
+    }
+    if let Some(Value::Array(subjects)) = object.get_mut("subjects") {
+        for subject in subjects {
+            if str_at(subject, "/kind") == "ServiceAccount" && subject.get("namespace").is_none() {
+                subject["namespace"] = Value::String(namespace.to_string());
+            }
+        }
+    }
+}
+
+/// Rewrite every container image matching an `images` entry.
+fn apply_images(value: &mut Value, images: &[ImageOverride]) {
+    if images.is_empty() {
+        return;
+    }
+    match value {
+        Value::Object(map) => {
+            for (key, child) in map.iter_mut() {
+                let is_container_list = matches!(
+                    key.as_str(),
+                    "containers" | "initContainers" | "ephemeralContainers"
+                );
+                if let (true, Value::Array(containers)) = (is_container_list, &mut *child) {
+                    for container in containers.iter_mut() {
+                        if let Some(Value::String(image)) = container.get_mut("image") {
+                            if let Some(rewritten) = rewrite_image(image, images) {
+                                *image = rewritten;
+                            }
+                        }
+                    }
+                }
+                apply_images(child, images);
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(|v| apply_images(v, images)),
+        _ => {}
+    }
+}
+
+/// Apply the first matching `images` entry to an image reference.
+fn rewrite_image(image: &str, images: &[ImageOverride]) -> Option<String> {
+    let (without_digest, digest) = match image.split_once('@') {
+        Some((name, digest)) => (name, Some(digest)),
+        None => (image, None),
+    };
+    let (name, tag) = match without_digest.rsplit_once(':') {
+        Some((name, tag)) if !tag.contains('/') => (name, Some(tag)),
+        _ => (without_digest, None),
+    };
+
+    let overlay = images.iter().find(|i| i.name == name)?;
+    let name = overlay.new_name.as_deref().unwrap_or(name);
+    Some(match (&overlay.digest, &overlay.new_tag) {
+        (Some(new_digest), _) => format!("{name}@{new_digest}"),
+        (None, Some(new_tag)) => format!("{name}:{new_tag}"),
+        (None, None) => match (tag, digest) {
+            (_, Some(digest)) => format!("{name}@{digest}"),
+            (Some(tag), None) => format!("{name}:{tag}"),
+            (None, None) => name.to_string(),
+        },
+    })
+}
+
+// ============================================================================
+// Name References
+// ============================================================================
+
+/// Point references to renamed ConfigMaps, Secrets and ServiceAccounts at
+/// their new names. `renames` holds `(kind, old name, new name)`.
+fn rewrite_references(object: &mut Value, renames: &[(String, String, String)]) {
+    if renames.is_empty() {
+        return;
+    }
+    let rename = |value: &mut Value, kind: &str| {
+        if let Value::String(name) = value {
+            if let Some((_, _, new)) = renames.iter().find(|(k, old, _)| k == kind && old == name) {
+                *name = new.clone();
+            }
+        }
+    };
+
+    let kind = str_at(object, "/kind").to_string();
+    let pod_spec = match kind.as_str() {
+        "Pod" => Some("/spec"),
+        "CronJob" => Some("/spec/jobTemplate/spec/template/spec"),
+        k if !template_paths(k).is_empty() => Some("/spec/template/spec"),
+        _ => None,
+    };
+
+    if let Some(spec) = pod_spec.and_then(|p| object.pointer_mut(p)) {
+        if let Some(name) = spec.get_mut("serviceAccountName") {
+            rename(name, "ServiceAccount");
+        }
+        for secret in array_items(spec, "imagePullSecrets") {
+            if let Some(name) = secret.get_mut("name") {
+                rename(name, "Secret");
+            }
+        }
+        for volume in array_items(spec, "volumes") {
+            if let Some(name) = volume.pointer_mut("/configMap/name") {
+                rename(name, "ConfigMap");
+            }
+            if let Some(name) = volume.pointer_mut("/secret/secretName") {
+                rename(name, "Secret");
+            }
+            if let Some(Value::Array(sources)) = volume.pointer_mut("/projected/sources") {
+                for source in sources {
+                    if let Some(name) = source.pointer_mut("/configMap/name") {
+                        rename(name, "ConfigMap");
+                    }
+                    if let Some(name) = source.pointer_mut("/secret/name") {
+                        rename(name, "Secret");
+                    }
+                }
+            }
+        }
+        for list in ["containers", "initContainers"] {
+            for container in array_items(spec, list) {
+                for source in array_items(container, "envFrom") {
+                    if let Some(name) = source.pointer_mut("/configMapRef/name") {
+                        rename(name, "ConfigMap");
+                    }
+                    if let Some(name) = source.pointer_mut("/secretRef/name") {
+                        rename(name, "Secret");
+                    }
+                }
+                for env in array_items(container, "env") {
+                    if let Some(name) = env.pointer_mut("/valueFrom/configMapKeyRef/name") {
+                        rename(name, "ConfigMap");
+                    }
+                    if let Some(name) = env.pointer_mut("/valueFrom/secretKeyRef/name") {
+                        rename(name, "Secret");
+                    }
+                }
+            }
+        }
+    }
+
+    match kind.as_str() {
+        "RoleBinding" | "ClusterRoleBinding" => {
+            for subject in array_items(object, "subjects") {
+                if str_at(subject, "/kind") == "ServiceAccount" {
+                    if let Some(name) = subject.get_mut("name") {
+                        rename(name, "ServiceAccount");
+                    }
+                }
+            }
+        }
+        "Ingress" => {
+            if let Some(spec) = object.get_mut("spec") {
+                for tls in array_items(spec, "tls") {
+                    if let Some(name) = tls.get_mut("secretName") {
+                        rename(name, "Secret");
+                    }
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn array_items<'a>(value: &'a mut Value, field: &str) -> impl Iterator<Item = &'a mut Value> {
+    value
+        .get_mut(field)
+        .and_then(Value::as_array_mut)
+        .into_iter()
+        .flatten()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::fs;
+
+    fn write(dir: &Path, name: &str, content: &str) {
+        let path = dir.join(name);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    const DEPLOYMENT: &str = "\
+apiVersion: apps/v1
+kind: Deployment
+metadata:
+  name: cart
+spec:
+  replicas: 1
+  selector:
+    matchLabels:
+      app: cart
+  template:
+    metadata:
+      labels:
+        app: cart
+    spec:
+      containers:
+        - name: cart
+          image: ghcr.io/acme/cart:old
+          envFrom:
+            - configMapRef:
+                name: cart-config
+          env:
+            - name: RUST_LOG
+              value: info
+";
+
+    fn overlay_fixture() -> tempfile::TempDir {
+        let root = tempfile::tempdir().unwrap();
+        write(
+            root.path(),
+            "base/kustomization.yaml",
+            "resources:\n  - deployment.yaml\n",
+        );
+        write(root.path(), "base/deployment.yaml", DEPLOYMENT);
+        write(
+            root.path(),
+            "staging/kustomization.yaml",
+            "\
+resources:
+  - ../base
+namespace: shop-staging
+namePrefix: shop-
+commonLabels:
+  env: staging
+images:
+  - name: ghcr.io/acme/cart
+    newTag: amd64-abc123
+replicas:
+  - name: cart
+    count: 3
+configMapGenerator:
+  - name: cart-config
+    literals:
+      - GIT_SHA=abc123
+patches:
+  - patch: |
+      apiVersion: apps/v1
+      kind: Deployment
+      metadata:
+        name: cart
+      spec:
+        template:
+          spec:
+            containers:
+              - name: cart
+                env:
+                  - name: RUST_LOG
+                    value: debug
+                  - name: FEATURE_X
+                    value: \"on\"
+  - target:
+      kind: Deployment
+      name: cart
+    patch: |
+      - op: add
+        path: /spec/template/metadata/annotations
+        value:
+          checked: \"true\"
+",
+        );
+        root
+    }
+
+    #[test]
+    fn parse_documents_splits_documents_and_expands_lists() {
+        let yaml = "\
+apiVersion: v1
+kind: Service
+metadata:
+  name: cart
+---
+---
+apiVersion: v1
+kind: List
+items:
+  - apiVersion: v1
+    kind: ConfigMap
+    metadata:
+      name: a
+  - apiVersion: v1
+    kind: ConfigMap
+    metadata:
+      name: b
+";
+        let objects = parse_documents(yaml).unwrap();
+        let names: Vec<&str> = objects
+            .iter()
+            .map(|o| o["metadata"]["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(names, vec!["cart", "a", "b"]);
+    }
+
+    #[test]
+    fn render_applies_overlay_transformers_patches_and_generators() {
+        let root = overlay_fixture();
+        let objects = render(&root.path().join("staging/kustomization.yaml")).unwrap();
+        assert_eq!(objects.len(), 2);
+
+        let deployment = &objects[0];
+        assert_eq!(deployment["metadata"]["name"], "shop-cart");
+        assert_eq!(deployment["metadata"]["namespace"], "shop-staging");
+        assert_eq!(deployment["metadata"]["labels"]["env"], "staging");
+        assert_eq!(deployment["spec"]["replicas"], 3);
+        assert_eq!(
+            deployment["spec"]["selector"]["matchLabels"]["env"],
+            "staging"
+        );
+        assert_eq!(
+            deployment["spec"]["template"]["metadata"]["annotations"]["checked"],
+            "true"
+        );
+
+        let container = &deployment["spec"]["template"]["spec"]["containers"][0];
+        assert_eq!(container["image"], "ghcr.io/acme/cart:amd64-abc123");
+        assert_eq!(
+            container["env"],
+            json!([
+                {"name": "RUST_LOG", "value": "debug"},
+                {"name": "FEATURE_X", "value": "on"}
+            ])
+        );
+
+        let config_map = &objects[1];
+        let name = config_map["metadata"]["name"].as_str().unwrap();
+        assert!(name.starts_with("shop-cart-config-"), "{name}");
+        assert_eq!(name.len(), "shop-cart-config-".len() + 10);
+        assert!(config_map["metadata"].get("annotations").is_none());
+        assert_eq!(container["envFrom"][0]["configMapRef"]["name"], name);
+    }
+
+    #[test]
+    fn render_rejects_unsupported_fields_and_unmatched_patches() {
+        let root = tempfile::tempdir().unwrap();
+        write(
+            root.path(),
+            "base/kustomization.yaml",
+            "helmCharts: []\ncrds: []\n",
+        );
+        write(
+            root.path(),
+            "kustomization.yaml",
+            "resources:\n  - base\ncomponents:\n  - ../c\n",
+        );
+        match render(root.path()) {
+            Err(KubernetesError::RenderUnsupported { path, fields }) => {
+                assert!(path.ends_with("kustomization.yaml"), "{path}");
+                assert_eq!(fields, vec!["components"]);
+            }
+            other => panic!("expected RenderUnsupported, got {other:?}"),
+        }
+        write(root.path(), "kustomization.yaml", "resources:\n  - base\n");
+        match render(root.path()) {
+            Err(KubernetesError::RenderUnsupported { path, fields }) => {
+                assert!(path.contains("base"), "{path}");
+                assert_eq!(fields, vec!["crds", "helmCharts"]);
+            }
+            other => panic!("expected RenderUnsupported, got {other:?}"),
+        }
+
+        write(
+            root.path(),
+            "kustomization.yaml",
+            "resources:\n  - d.yaml\npatches:\n  - patch: |\n      kind: Deployment\n      metadata:\n        name: missing\n",
+        );
+        write(root.path(), "d.yaml", DEPLOYMENT);
+        let err = render(root.path()).unwrap_err().to_string();
+        assert!(
+            err.contains("Deployment/missing matched no resources"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn render_detects_kustomization_cycles() {
+        let root = tempfile::tempdir().unwrap();
+        write(
+            root.path(),
+            "a/kustomization.yaml",
+            "resources:\n  - ../b\n",
+        );
+        write(
+            root.path(),
+            "b/kustomization.yaml",
+            "resources:\n  - ../a\n",
+        );
+        let err = render(&root.path().join("a")).unwrap_err().to_string();
+        assert!(err.contains("cycle"), "{err}");
+    }
+
+    #[test]
+    fn strategic_merge_handles_named_lists_deletes_and_replace() {
+        let mut target = json!({
+            "spec": {
+                "containers": [
+                    {"name": "app", "image": "a", "ports": [{"containerPort": 80}]},
+                    {"name": "sidecar", "image": "s"}
+                ],
+                "args": ["--a"],
+                "nodeSelector": {"zone": "a", "disk": "ssd"}
+            }
+        });
+        let patch = json!({
+            "spec": {
+                "containers": [
+                    {"name": "app", "ports": [{"containerPort": 80, "name": "http"}]},
+                    {"name": "sidecar", "$patch": "delete"}
+                ],
+                "args": ["--b"],
+                "nodeSelector": {"$patch": "replace", "zone": "b"}
+            }
+        });
+        strategic_merge(&mut target, &patch);
+        assert_eq!(
+            target,
+            json!({
+                "spec": {
+                    "containers": [
+                        {"name": "app", "image": "a", "ports": [{"containerPort": 80, "name": "http"}]}
+                    ],
+                    "args": ["--b"],
+                    "nodeSelector": {"zone": "b"}
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn rewrite_image_handles_registry_ports_digests_and_new_names() {
+        let images = vec![
+            ImageOverride {
+                name: "registry:5000/app".into(),
+                new_tag: Some("v2".into()),
+                ..Default::default()
+            },
+            ImageOverride {
+                name: "nginx".into(),
+                new_name: Some("ghcr.io/acme/nginx".into()),
+                digest: Some("sha256:abc".into()),
+                ..Default::default()
+            },
+        ];
+        assert_eq!(
+            rewrite_image("registry:5000/app:v1", &images).as_deref(),
+            Some("registry:5000/app:v2")
+        );
+        assert_eq!(
+            rewrite_image("nginx:1.27", &images).as_deref(),
+            Some("ghcr.io/acme/nginx@sha256:abc")
+        );
+        assert_eq!(rewrite_image("redis:7", &images), None);
+    }
+
+    #[test]
+    fn content_hash_is_stable_and_uses_kustomize_alphabet() {
+        let config_map = json!({
+            "apiVersion": "v1",
+            "kind": "ConfigMap",
+            "metadata": {"name": "cart-config"},
+            "data": {"GIT_SHA": "abc123"}
+        });
+        let hash = content_hash(&config_map).unwrap();
+        assert_eq!(hash.len(), 10);
+        assert!(!hash.contains(['0', '1', '3', 'a', 'e']), "{hash}");
+        assert_eq!(hash, content_hash(&config_map).unwrap());
+
+        let mut changed = config_map.clone();
+        changed["data"]["GIT_SHA"] = json!("def456");
+        assert_ne!(hash, content_hash(&changed).unwrap());
+    }
+}
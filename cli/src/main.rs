@@ -26,6 +26,7 @@ mod cosign;
 #[cfg(feature = "attestation")]
 mod deployment_manifest;
 mod duration;
+mod flux_api;
 mod flux_get;
 mod flux_reconcile;
 #[cfg(feature = "attestation")]
@@ -40,6 +41,8 @@ mod helm_provenance;
 mod helm_release_signature;
 #[cfg(feature = "attestation")]
 mod kensa_policy;
+mod kustomize;
+mod manifest_schema;
 #[cfg(feature = "attestation")]
 mod network_policy_admission;
 #[cfg(feature = "attestation")]
@@ -54,6 +57,7 @@ mod pod_listing;
 mod probe_outcome;
 #[cfg(feature = "attestation")]
 mod security_scan;
+mod server_dry_run;
 mod store_path;
 mod tree_listing;
 mod version;
@@ -82,13 +86,15 @@ mod ui;
 mod test_support;
 
 use cli::{
-    BootstrapCommands, Cli, Commands, CrossplaneCommands, GemCommands, HelmCommands, InfraCommands,
-    LocalCommands, PangeaCommands, PangeaInfraCommands, ToolCommands, TypescriptCommands,
+    BootstrapCommands, Cli, Commands, CrossplaneCommands, FluxCommands, GemCommands, HelmCommands,
+    InfraCommands, LocalCommands, MigrationsCommands, PangeaCommands, PangeaInfraCommands,
+    SearchCommands, ToolCommands, TypescriptCommands,
 };
 use commands::{
-    bootstrap, build, comprehensive_release, deploy, federation, github_runner_ci,
-    integration_tests, kenshi, kenshi_agent, migrations, nix_builder, pangea, pangea_infra, push,
-    rollout, rust_service, service_config, status, test, web_build_verify, workspace_deps,
+    bootstrap, build, comprehensive_release, deploy, drift, federation, fleet_status, flux_suspend,
+    github_runner_ci, integration_tests, kenshi, kenshi_agent, migrations, nix_builder, pangea,
+    pangea_infra, push, rollout, rust_service, service_config, status, test, watch,
+    web_build_verify, workspace_deps,
 };
 
 /// Setup environment for root flake pattern
@@ -200,10 +206,20 @@ async fn main() -> Result<()> {
             skip_build,
             cache_url,
             cache_name,
+            server_dry_run,
         } => {
             deploy::execute(
-                manifest, registry, tag, namespace, name, watch, timeout, skip_build, cache_url,
+                manifest,
+                registry,
+                tag,
+                namespace,
+                name,
+                watch,
+                timeout,
+                skip_build,
+                cache_url,
                 cache_name,
+                server_dry_run,
             )
             .await?;
         }
@@ -382,6 +398,36 @@ async fn main() -> Result<()> {
             };
             commands::rollback::execute(product, repo_root, env, skip_health_check, force).await?;
         }
+        Commands::Watch {
+            product,
+            repo_root,
+            env,
+            interval,
+        } => {
+            let product = match product {
+                Some(p) => p,
+                None => config::auto_discover_product(&repo_root)?,
+            };
+            watch::execute(&product, &repo_root, &env, interval).await?;
+        }
+        Commands::Drift {
+            product,
+            repo_root,
+            env,
+            format,
+        } => {
+            let product = match product {
+                Some(p) => p,
+                None => config::auto_discover_product(&repo_root)?,
+            };
+            drift::execute(
+                &product,
+                &repo_root,
+                &env,
+                status::OutputFormat::from_str(&format),
+            )
+            .await?;
+        }
         Commands::ProductRelease {
             product,
             repo_root,
@@ -389,6 +435,7 @@ async fn main() -> Result<()> {
             skip_gates,
             skip_dashboards,
             build_only,
+            server_dry_run,
         } => {
             let product = match product {
                 Some(p) => p,
@@ -401,6 +448,7 @@ async fn main() -> Result<()> {
                 skip_gates,
                 skip_dashboards,
                 build_only,
+                server_dry_run,
             )
             .await?;
         }
@@ -428,6 +476,32 @@ async fn main() -> Result<()> {
         Commands::FluxReconcile { namespace } => {
             commands::flux::reconcile(namespace).await?;
         }
+        Commands::Flux { command } => match command {
+            FluxCommands::Suspend {
+                product,
+                repo_root,
+                env,
+                reason,
+                by,
+            } => {
+                let product = match product {
+                    Some(p) => p,
+                    None => config::auto_discover_product(&repo_root)?,
+                };
+                flux_suspend::suspend(&product, &repo_root, &env, &reason, by).await?;
+            }
+            FluxCommands::Resume {
+                product,
+                repo_root,
+                env,
+            } => {
+                let product = match product {
+                    Some(p) => p,
+                    None => config::auto_discover_product(&repo_root)?,
+                };
+                flux_suspend::resume(&product, &repo_root, &env).await?;
+            }
+        },
         Commands::RunMigrations {
             service,
             namespace,
@@ -445,6 +519,58 @@ async fn main() -> Result<()> {
             let deploy_config = config::DeployConfig::load_for_service(&service)?;
             federation::update_federation(service, namespace, &deploy_config).await?;
         }
+        Commands::SubgraphCheck {
+            service,
+            base_ref,
+            configmap,
+            namespace,
+            allow_breaking,
+        } => {
+            let deploy_config = config::DeployConfig::load_for_service(&service)?;
+            let baseline = match (configmap, namespace, base_ref) {
+                (Some(name), Some(namespace), _) => {
+                    commands::subgraph_changes::Baseline::ConfigMap { namespace, name }
+                }
+                (_, _, Some(base_ref)) => commands::subgraph_changes::Baseline::Git { base_ref },
+                _ => commands::subgraph_changes::Baseline::Supergraph {
+                    path: deploy_config.supergraph_router_path()?,
+                },
+            };
+            commands::subgraph_changes::check_subgraph_changes(
+                &deploy_config,
+                &baseline,
+                allow_breaking,
+            )
+            .await?;
+        }
+        Commands::OperationCheck {
+            service,
+            supergraph,
+            manifest,
+            documents,
+            output,
+            allow_broken,
+        } => {
+            use commands::operation_checks::{check_client_operations, OperationSources};
+            use std::path::PathBuf;
+
+            let deploy_config = config::DeployConfig::load_for_service(&service)?;
+            let mut sources = OperationSources::from_config(&deploy_config)?;
+            if let Some(manifest) = manifest {
+                sources.manifest = Some(PathBuf::from(manifest));
+            }
+            if let Some(documents) = documents {
+                sources.documents = Some(PathBuf::from(documents));
+            }
+            let supergraph = match supergraph {
+                Some(path) => PathBuf::from(path),
+                None => deploy_config
+                    .federation_directory()?
+                    .join("supergraph.graphql"),
+            };
+            let output = output.map(PathBuf::from);
+            check_client_operations(&sources, &supergraph, output.as_deref(), allow_broken).await?;
+        }
         Commands::WebBuildVerify {
             dist_dir,
             template_path,
@@ -479,6 +605,8 @@ async fn main() -> Result<()> {
             secondary_builder_pool,
             retries,
             token,
+            wait_for_flux,
+            flux_targets,
         } => {
             nix_builder::release(
                 image_path,
@@ -490,6 +618,7 @@ async fn main() -> Result<()> {
                 secondary_builder_pool,
                 retries,
                 token,
+                wait_for_flux.then_some(flux_targets.as_slice()),
             )
             .await?;
         }
@@ -500,6 +629,8 @@ async fn main() -> Result<()> {
             secondary_kustomization,
             retries,
             token,
+            wait_for_flux,
+            flux_targets,
         } => {
             kenshi::release(
                 image_path,
@@ -508,6 +639,7 @@ async fn main() -> Result<()> {
                 secondary_kustomization,
                 retries,
                 token,
+                wait_for_flux.then_some(flux_targets.as_slice()),
             )
             .await?;
         }
@@ -520,6 +652,8 @@ async fn main() -> Result<()> {
             secondary_builder_pool,
             retries,
             token,
+            wait_for_flux,
+            flux_targets,
         } => {
             kenshi_agent::release(
                 image_path,
@@ -530,6 +664,7 @@ async fn main() -> Result<()> {
                 secondary_builder_pool,
                 retries,
                 token,
+                wait_for_flux.then_some(flux_targets.as_slice()),
             )
             .await?;
         }
@@ -554,17 +689,27 @@ async fn main() -> Result<()> {
             service_dir,
             repo_root,
             suite,
+            junit,
         } => {
-            integration_tests::execute_manual(&service, &service_dir, &repo_root, suite).await?;
+            integration_tests::execute_manual(&service, &service_dir, &repo_root, suite, junit)
+                .await?;
         }
         Commands::Status {
             service,
             service_dir,
             repo_root,
             format,
+            all,
         } => {
             let output_format = status::OutputFormat::from_str(&format);
-            status::execute(&service, &service_dir, &repo_root, output_format).await?;
+            if all {
+                fleet_status::execute(&repo_root, output_format).await?;
+            } else {
+                let (Some(service), Some(service_dir)) = (service, service_dir) else {
+                    anyhow::bail!("--service and --service-dir are required unless --all is set");
+                };
+                status::execute(&service, &service_dir, &repo_root, output_format).await?;
+            }
         }
         Commands::Test {
             service,
@@ -572,6 +717,9 @@ async fn main() -> Result<()> {
             repo_root,
             service_type,
             test_type,
+            junit,
+            shard,
+            durations,
         } => {
             test::execute(
                 &service,
@@ -579,6 +727,9 @@ async fn main() -> Result<()> {
                 &repo_root,
                 &service_type,
                 &test_type,
+                junit.as_deref(),
+                shard.as_deref(),
+                durations.as_deref(),
             )
             .await?;
         }
@@ -711,15 +862,95 @@ async fn main() -> Result<()> {
             skip_backend,
             skip_frontend,
             skip_migrations,
+            junit,
         } => {
             commands::prerelease::execute(
                 working_dir,
                 skip_backend,
                 skip_frontend,
                 skip_migrations,
+                junit,
             )
             .await?;
         }
+        Commands::Migrations { command } => match command {
+            MigrationsCommands::Lint { working_dir } => {
+                commands::migration_lint::execute(&working_dir).await?;
+            }
+            MigrationsCommands::Rehearse {
+                working_dir,
+                from_ref,
+                schema_dump,
+                image,
+                databend_image,
+                elasticsearch_image,
+                keep,
+            } => {
+                let options = commands::migration_rehearsal::RehearseOptions {
+                    from_ref,
+                    schema_dump: schema_dump.map(std::path::PathBuf::from),
+                    image,
+                    databend_image,
+                    elasticsearch_image,
+                    keep,
+                };
+                commands::migration_rehearsal::execute(&working_dir, &options).await?;
+            }
+            MigrationsCommands::Status {
+                product,
+                repo_root,
+                env,
+            } => {
+                let product = match product {
+                    Some(p) => p,
+                    None => config::auto_discover_product(&repo_root)?,
+                };
+                commands::migrations::show_status(&product, &repo_root, &env).await?;
+            }
+            MigrationsCommands::Backfill {
+                product,
+                repo_root,
+                env,
+                migration,
+                batch_size,
+                sleep,
+                max_duration,
+                restart,
+            } => {
+                let product = match product {
+                    Some(p) => p,
+                    None => config::auto_discover_product(&repo_root)?,
+                };
+                let options = commands::migration_backfill::BackfillOptions {
+                    batch_size,
+                    sleep,
+                    max_duration,
+                    restart,
+                };
+                commands::migration_backfill::execute(
+                    &product, &repo_root, &env, &migration, &options,
+                )
+                .await?;
+            }
+        },
+        Commands::Search { command } => match command {
+            SearchCommands::Reindex {
+                alias,
+                url,
+                template,
+                retain,
+                timeout,
+            } => {
+                let options = commands::search_reindex::ReindexOptions {
+                    url,
+                    alias,
+                    template,
+                    retain,
+                    timeout,
+                };
+                commands::search_reindex::execute(&options).await?;
+            }
+        },
         Commands::MigrationNew {
             working_dir,
             name,
@@ -828,7 +1059,14 @@ async fn main() -> Result<()> {
             headless,
             filter,
         } => {
-            commands::e2e::run_e2e_tests(repo_root, headless, filter)?;
+            commands::e2e::run_e2e_tests(
+                repo_root,
+                headless,
+                filter,
+                None,
+                &commands::test_quarantine::Quarantine::default(),
+                None,
+            )?;
         }
         Commands::TestPyramid {
             repo_root,
@@ -839,6 +1077,9 @@ async fn main() -> Result<()> {
             fail_fast,
             report,
             report_path,
+            junit,
+            shard,
+            durations,
         } => {
             commands::e2e::run_test_pyramid(
                 repo_root,
@@ -849,6 +1090,9 @@ async fn main() -> Result<()> {
                 fail_fast,
                 report,
                 report_path,
+                junit,
+                shard,
+                durations,
             )?;
         }
         Commands::TestUnit {
@@ -857,11 +1101,23 @@ async fn main() -> Result<()> {
             skip_frontend,
             report,
             report_path,
+            junit,
         } => {
-            commands::e2e::run_unit_tests(repo_root, filter, skip_frontend, report, report_path)?;
+            commands::e2e::run_unit_tests(
+                repo_root,
+                filter,
+                skip_frontend,
+                report,
+                report_path,
+                junit,
+            )?;
         }
-        Commands::TestIntegration { repo_root, filter } => {
-            commands::e2e::run_integration_tests(repo_root, filter)?;
+        Commands::TestIntegration {
+            repo_root,
+            filter,
+            junit,
+        } => {
+            commands::e2e::run_integration_tests(repo_root, filter, junit)?;
         }
         Commands::E2eCleanup => {
             commands::e2e::cleanup_all()?;
@@ -871,8 +1127,9 @@ async fn main() -> Result<()> {
             headless,
             filter,
             force_rebuild,
+            junit,
         } => {
-            commands::e2e::run_e2e_tests_smart(repo_root, headless, filter, force_rebuild)?;
+            commands::e2e::run_e2e_tests_smart(repo_root, headless, filter, force_rebuild, junit)?;
         }
         Commands::Seed {
             working_dir,
@@ -946,15 +1203,27 @@ async fn main() -> Result<()> {
                 environment,
                 commit,
                 watch,
+                diff,
+                chart_dir,
+                registry,
+                timeout,
             } => {
+                let options = commands::helm_release::UpgradeOptions {
+                    diff,
+                    watch,
+                    chart_dir,
+                    registry,
+                    timeout: duration::parse_duration(&timeout)?,
+                };
                 commands::helm::deploy(
                     &service,
                     &image_tag,
                     &k8s_repo,
                     &environment,
                     commit,
-                    watch,
-                )?;
+                    &options,
+                )
+                .await?;
             }
             HelmCommands::Release {
                 chart_dir,
@@ -1085,8 +1354,9 @@ async fn main() -> Result<()> {
         Commands::TestCi {
             working_dir,
             threads,
+            junit,
         } => {
-            commands::test_ci::execute(&working_dir, threads)?;
+            commands::test_ci::execute(&working_dir, threads, junit.as_deref())?;
         }
         Commands::TestCoverage {
             working_dir,
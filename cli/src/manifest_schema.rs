@@ -0,0 +1,564 @@
+//! Schema validation of rendered manifests against the Kubernetes OpenAPI
+//! types bundled in `k8s-openapi` (the cluster version forge builds
+//! against), so a broken overlay fails before its GitOps commit instead of
+//! in Flux.
+//!
+//! Each built-in object is deserialized into its typed `k8s-openapi`
+//! struct, which catches values of the wrong type (`replicas: "two"`).
+//! The typed value is then serialized back and compared with the input:
+//! any field that does not survive the round trip is unknown to the
+//! schema (a typo such as `spec.replica`, or a field from a newer API).
+//! A built-in kind under an apiVersion the cluster no longer serves
+//! (`apps/v1beta1` Deployment) is reported too. Custom resources have no
+//! bundled schema and are skipped, as are SOPS-encrypted objects (a
+//! top-level `sops` key), which only become valid once Flux decrypts them.
+//!
+//! Manifests are normalised to what the apiserver would accept first:
+//! numeric quantities (`cpu: 1`) become strings, and values still holding
+//! a Flux `${VAR}` substitution are dropped when they keep an object from
+//! deserializing. Overlays using kustomize features the in-process
+//! renderer does not implement are skipped with a warning, and
+//! `FORGE_SCHEMA_VALIDATION=warn|off` relaxes the check for a deploy.
+
+use k8s_openapi::api::{apps, autoscaling, batch, core, networking, policy, rbac};
+use k8s_openapi::Resource;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use std::path::Path;
+
+use tracing::warn;
+
+use crate::error::KubernetesError;
+use crate::kustomize;
+
+/// Environment variable selecting the [`Mode`]: `warn` reports violations
+/// without failing, `off` skips validation. Read from the environment so
+/// nested forge invocations (`product-release` deploys) inherit it.
+pub const ENV_VAR: &str = "FORGE_SCHEMA_VALIDATION";
+
+/// How [`validate_overlay`] treats violations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// Violations fail with [`KubernetesError::SchemaInvalid`].
+    Enforce,
+    /// Violations are logged and the overlay is accepted.
+    Warn,
+    /// Nothing is rendered or validated.
+    Off,
+}
+
+/// The [`Mode`] [`ENV_VAR`] asks for; `Enforce` when unset.
+pub fn mode() -> Mode {
+    match std::env::var(ENV_VAR)
+        .unwrap_or_default()
+        .to_lowercase()
+        .as_str()
+    {
+        "warn" => Mode::Warn,
+        "off" | "0" | "false" | "no" => Mode::Off,
+        _ => Mode::Enforce,
+    }
+}
+
+/// Outcome of validating one rendered overlay.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OverlayValidation {
+    /// Objects the overlay rendered.
+    pub objects: usize,
+    /// Objects checked against a bundled schema (the rest are custom resources).
+    pub validated: usize,
+}
+
+/// Render the kustomization containing `manifest` and validate every
+/// object it produces, in the [`Mode`] [`ENV_VAR`] selects. Returns
+/// `Ok(None)` when nothing was validated: the manifest's directory is not
+/// a kustomization (e.g. a bare HelmRelease file), it uses kustomize
+/// features forge does not render, or validation is off.
+pub fn validate_overlay(manifest: &Path) -> Result<Option<OverlayValidation>, KubernetesError> {
+    validate_overlay_in(manifest, mode())
+}
+
+fn validate_overlay_in(
+    manifest: &Path,
+    mode: Mode,
+) -> Result<Option<OverlayValidation>, KubernetesError> {
+    if mode == Mode::Off {
+        warn!("⚠️  Schema validation disabled ({ENV_VAR}=off)");
+        return Ok(None);
+    }
+    let dir = kustomize::kustomization_dir(manifest);
+    if kustomize::find_kustomization_file(&dir).is_none() {
+        return Ok(None);
+    }
+
+    let objects = match kustomize::render(&dir) {
+        Err(KubernetesError::RenderUnsupported { path, fields }) => {
+            warn!(
+                "⚠️  {path} uses {} which forge does not render; schema validation skipped",
+                fields.join(", ")
+            );
+            return Ok(None);
+        }
+        result => result?,
+    };
+    let mut validated = 0;
+    let mut violations = Vec::new();
+    for object in &objects {
+        if let Some(problems) = validate_object(object) {
+            validated += 1;
+            let name = object_name(object);
+            violations.extend(problems.into_iter().map(|p| format!("{name}: {p}")));
+        }
+    }
+
+    if !violations.is_empty() && mode == Mode::Warn {
+        warn!(
+            "⚠️  Rendered manifests for {} failed schema validation ({ENV_VAR}=warn):",
+            dir.display()
+        );
+        for violation in &violations {
+            warn!("   {violation}");
+        }
+    } else if !violations.is_empty() {
+        return Err(KubernetesError::SchemaInvalid {
+            path: dir.display().to_string(),
+            violations,
+        });
+    }
+    Ok(Some(OverlayValidation {
+        objects: objects.len(),
+        validated,
+    }))
+}
+
+/// Problems with one object, or `None` when no bundled schema covers its
+/// kind (custom resources) or the object is SOPS-encrypted.
+pub fn validate_object(object: &Value) -> Option<Vec<String>> {
+    if object.get("sops").is_some() {
+        return None;
+    }
+    let api_version = object
+        .get("apiVersion")
+        .and_then(Value::as_str)
+        .unwrap_or("");
+    let kind = object.get("kind").and_then(Value::as_str).unwrap_or("");
+
+    macro_rules! built_in {
+        ($($ty:ty),+ $(,)?) => {{
+            $(
+                if api_version == <$ty as Resource>::API_VERSION
+                    && kind == <$ty as Resource>::KIND
+                {
+                    return Some(check::<$ty>(object));
+                }
+            )+
+            $(
+                if kind == <$ty as Resource>::KIND
+                    && group_of(api_version) == <$ty as Resource>::GROUP
+                {
+                    return Some(vec![format!(
+                        "apiVersion {api_version} is not served for {kind}; use {}",
+                        <$ty as Resource>::API_VERSION
+                    )]);
+                }
+            )+
+        }};
+    }
+
+    built_in!(
+        apps::v1::DaemonSet,
+        apps::v1::Deployment,
+        apps::v1::StatefulSet,
+        autoscaling::v2::HorizontalPodAutoscaler,
+        batch::v1::CronJob,
+        batch::v1::Job,
+        core::v1::ConfigMap,
+        core::v1::Namespace,
+        core::v1::PersistentVolumeClaim,
+        core::v1::Pod,
+        core::v1::Secret,
+        core::v1::Service,
+        core::v1::ServiceAccount,
+        networking::v1::Ingress,
+        networking::v1::NetworkPolicy,
+        policy::v1::PodDisruptionBudget,
+        rbac::v1::ClusterRole,
+        rbac::v1::ClusterRoleBinding,
+        rbac::v1::Role,
+        rbac::v1::RoleBinding,
+    );
+    None
+}
+
+fn group_of(api_version: &str) -> &str {
+    api_version
+        .split_once('/')
+        .map(|(group, _)| group)
+        .unwrap_or("")
+}
+
+fn object_name(object: &Value) -> String {
+    let field = |p: &str| object.pointer(p).and_then(Value::as_str).unwrap_or("?");
+    format!("{}/{}", field("/kind"), field("/metadata/name"))
+}
+
+/// Deserialize into `K`, then report fields lost in the round trip.
+fn check<K: Serialize + DeserializeOwned>(object: &Value) -> Vec<String> {
+    let mut input = object.clone();
+    if let Some(map) = input.as_object_mut() {
+        map.remove("status");
+    }
+    quantities_as_strings(&mut input);
+    let typed: K = match serde_json::from_value(input.clone()) {
+        Ok(typed) => typed,
+        Err(e) => {
+            // A `${VAR}` Flux substitutes after the render may stand in for
+            // a number or bool; retry without those values, and give up on
+            // the object when one of them was required.
+            let mut pruned = Vec::new();
+            prune_substitutions(&mut input, &mut pruned);
+            if pruned.is_empty() {
+                return vec![e.to_string()];
+            }
+            match serde_json::from_value(input.clone()) {
+                Ok(typed) => typed,
+                Err(e) if missing_field(&e).is_some_and(|f| pruned.contains(&f)) => {
+                    return Vec::new()
+                }
+                Err(e) => return vec![e.to_string()],
+            }
+        }
+    };
+    let round_trip = match serde_json::to_value(&typed) {
+        Ok(value) => value,
+        Err(e) => return vec![e.to_string()],
+    };
+
+    let mut problems = Vec::new();
+    unknown_fields("", &input, &round_trip, &mut problems);
+    problems
+}
+
+/// Turn numeric `resource.Quantity` values (`cpu: 1`, `sizeLimit: 1024`)
+/// into the strings `k8s-openapi` expects; the apiserver accepts both.
+/// `IntOrString` fields already take either form.
+fn quantities_as_strings(value: &mut Value) {
+    fn stringify(value: &mut Value) {
+        if let Value::Number(n) = value {
+            *value = Value::String(n.to_string());
+        }
+    }
+    match value {
+        Value::Object(map) => {
+            for (key, child) in map.iter_mut() {
+                match (key.as_str(), &mut *child) {
+                    ("requests" | "limits" | "hard" | "overhead", Value::Object(quantities)) => {
+                        quantities.values_mut().for_each(stringify)
+                    }
+                    ("sizeLimit", child) => stringify(child),
+                    // HorizontalPodAutoscaler metric targets.
+                    ("target", Value::Object(target)) => {
+                        for field in ["value", "averageValue"] {
+                            if let Some(quantity) = target.get_mut(field) {
+                                stringify(quantity);
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+                quantities_as_strings(child);
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(quantities_as_strings),
+        _ => {}
+    }
+}
+
+/// Remove every field whose value is a string still holding a `${...}`
+/// substitution, collecting the removed field names.
+fn prune_substitutions(value: &mut Value, pruned: &mut Vec<String>) {
+    match value {
+        Value::Object(map) => {
+            map.retain(|key, child| {
+                let unresolved = child.as_str().is_some_and(|s| s.contains("${"));
+                if unresolved {
+                    pruned.push(key.clone());
+                }
+                !unresolved
+            });
+            map.values_mut()
+                .for_each(|child| prune_substitutions(child, pruned));
+        }
+        Value::Array(items) => items
+            .iter_mut()
+            .for_each(|item| prune_substitutions(item, pruned)),
+        _ => {}
+    }
+}
+
+/// The field a serde "missing field `x`" error names.
+fn missing_field(error: &serde_json::Error) -> Option<String> {
+    let message = error.to_string();
+    let rest = message.strip_prefix("missing field `")?;
+    rest.split_once('`').map(|(field, _)| field.to_string())
+}
+
+fn unknown_fields(path: &str, input: &Value, typed: &Value, problems: &mut Vec<String>) {
+    match (input, typed) {
+        (Value::Object(input), Value::Object(typed)) => {
+            for (key, value) in input {
+                let child = format!("{path}.{key}");
+                match typed.get(key) {
+                    Some(typed_value) => unknown_fields(&child, value, typed_value, problems),
+                    None if value.is_null() => {}
+                    None => problems.push(format!("unknown field {child}")),
+                }
+            }
+        }
+        (Value::Array(input), Value::Array(typed)) => {
+            for (index, (i, t)) in input.iter().zip(typed).enumerate() {
+                unknown_fields(&format!("{path}[{index}]"), i, t, problems);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn deployment() -> Value {
+        json!({
+            "apiVersion": "apps/v1",
+            "kind": "Deployment",
+            "metadata": {"name": "cart", "creationTimestamp": null},
+            "spec": {
+                "replicas": 2,
+                "selector": {"matchLabels": {"app": "cart"}},
+                "template": {
+                    "metadata": {"labels": {"app": "cart"}},
+                    "spec": {"containers": [{
+                        "name": "cart",
+                        "image": "ghcr.io/acme/cart:abc",
+                        "resources": {"limits": {"cpu": "500m", "memory": "1Gi"}},
+                        "ports": [{"containerPort": 8080, "name": "http"}]
+                    }]}
+                }
+            },
+            "status": {}
+        })
+    }
+
+    #[test]
+    fn valid_deployment_has_no_violations() {
+        assert_eq!(validate_object(&deployment()), Some(vec![]));
+    }
+
+    #[test]
+    fn unknown_fields_are_reported_with_their_path() {
+        let mut object = deployment();
+        object["spec"]["replica"] = json!(3);
+        object["spec"]["template"]["spec"]["containers"][0]["imagePullPolcy"] = json!("Always");
+        assert_eq!(
+            validate_object(&object),
+            Some(vec![
+                "unknown field .spec.replica".to_string(),
+                "unknown field .spec.template.spec.containers[0].imagePullPolcy".to_string(),
+            ])
+        );
+    }
+
+    #[test]
+    fn type_errors_and_stale_api_versions_are_reported() {
+        let mut object = deployment();
+        object["spec"]["replicas"] = json!("two");
+        let problems = validate_object(&object).unwrap();
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].contains("invalid type"), "{problems:?}");
+
+        let mut object = deployment();
+        object["apiVersion"] = json!("apps/v1beta1");
+        assert_eq!(
+            validate_object(&object),
+            Some(vec![
+                "apiVersion apps/v1beta1 is not served for Deployment; use apps/v1".to_string()
+            ])
+        );
+    }
+
+    #[test]
+    fn numeric_quantities_and_int_or_string_values_are_accepted() {
+        let mut object = deployment();
+        object["spec"]["strategy"] = json!({
+            "type": "RollingUpdate",
+            "rollingUpdate": {"maxSurge": 1, "maxUnavailable": "25%"}
+        });
+        let container = &mut object["spec"]["template"]["spec"]["containers"][0];
+        container["resources"] =
+            json!({"requests": {"cpu": 1, "memory": "512Mi"}, "limits": {"cpu": 1.5}});
+        container["readinessProbe"] = json!({"httpGet": {"path": "/health", "port": 8080}});
+        assert_eq!(validate_object(&object), Some(vec![]));
+
+        let service = json!({
+            "apiVersion": "v1",
+            "kind": "Service",
+            "metadata": {"name": "cart"},
+            "spec": {"ports": [{"port": 80, "targetPort": 8080}]}
+        });
+        assert_eq!(validate_object(&service), Some(vec![]));
+    }
+
+    #[test]
+    fn unresolved_flux_substitutions_are_skipped() {
+        let mut object = deployment();
+        object["spec"]["replicas"] = json!("${REPLICAS}");
+        object["spec"]["replica"] = json!(3);
+        assert_eq!(
+            validate_object(&object),
+            Some(vec!["unknown field .spec.replica".to_string()])
+        );
+
+        // A required field left unresolved leaves nothing to check.
+        let service = json!({
+            "apiVersion": "v1",
+            "kind": "Service",
+            "metadata": {"name": "cart"},
+            "spec": {"ports": [{"port": "${PORT}"}]}
+        });
+        assert_eq!(validate_object(&service), Some(vec![]));
+    }
+
+    #[test]
+    fn custom_resources_are_skipped() {
+        let object = json!({
+            "apiVersion": "helm.toolkit.fluxcd.io/v2",
+            "kind": "HelmRelease",
+            "metadata": {"name": "cart"},
+            "spec": {"anything": true}
+        });
+        assert_eq!(validate_object(&object), None);
+    }
+
+    #[test]
+    fn sops_encrypted_secrets_are_skipped() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("kustomization.yaml"),
+            "resources:\n  - secret.enc.yaml\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("secret.enc.yaml"),
+            r#"apiVersion: v1
+kind: Secret
+metadata:
+  name: cart-secrets
+type: Opaque
+data:
+  DATABASE_URL: ENC[AES256_GCM,data:3q2+7w==,iv:AAAA,tag:BBBB,type:str]
+sops:
+  age:
+    - recipient: age1qyqszqgpqyqszqgpqyqszqgpqyqszqgpqyqszqgpqyqszqgpqyqs3290gq
+      enc: |
+        -----BEGIN AGE ENCRYPTED FILE-----
+        -----END AGE ENCRYPTED FILE-----
+  lastmodified: "2026-01-01T00:00:00Z"
+  mac: ENC[AES256_GCM,data:abc,iv:def,tag:ghi,type:str]
+  version: 3.8.1
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            validate_overlay(&dir.path().join("kustomization.yaml")).unwrap(),
+            Some(OverlayValidation {
+                objects: 1,
+                validated: 0
+            })
+        );
+    }
+
+    #[test]
+    fn validate_overlay_renders_and_collects_violations() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("kustomization.yaml"),
+            "resources:\n  - deployment.yaml\n",
+        )
+        .unwrap();
+        let mut object = deployment();
+        std::fs::write(
+            dir.path().join("deployment.yaml"),
+            serde_yaml::to_string(&object).unwrap(),
+        )
+        .unwrap();
+
+        let manifest = dir.path().join("kustomization.yaml");
+        assert_eq!(
+            validate_overlay(&manifest).unwrap(),
+            Some(OverlayValidation {
+                objects: 1,
+                validated: 1
+            })
+        );
+
+        object["spec"]["replica"] = json!(3);
+        std::fs::write(
+            dir.path().join("deployment.yaml"),
+            serde_yaml::to_string(&object).unwrap(),
+        )
+        .unwrap();
+        match validate_overlay(&manifest) {
+            Err(KubernetesError::SchemaInvalid { violations, .. }) => {
+                assert_eq!(
+                    violations,
+                    vec!["Deployment/cart: unknown field .spec.replica"]
+                );
+            }
+            other => panic!("expected SchemaInvalid, got {other:?}"),
+        }
+
+        let bare = tempfile::tempdir().unwrap();
+        assert_eq!(
+            validate_overlay(&bare.path().join("helmrelease.yaml")).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn warn_and_off_modes_and_unsupported_overlays_do_not_fail() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("kustomization.yaml"),
+            "resources:\n  - deployment.yaml\n",
+        )
+        .unwrap();
+        let mut object = deployment();
+        object["spec"]["replica"] = json!(3);
+        std::fs::write(
+            dir.path().join("deployment.yaml"),
+            serde_yaml::to_string(&object).unwrap(),
+        )
+        .unwrap();
+        let manifest = dir.path().join("kustomization.yaml");
+
+        assert!(validate_overlay_in(&manifest, Mode::Enforce).is_err());
+        assert_eq!(
+            validate_overlay_in(&manifest, Mode::Warn).unwrap(),
+            Some(OverlayValidation {
+                objects: 1,
+                validated: 1
+            })
+        );
+        assert_eq!(validate_overlay_in(&manifest, Mode::Off).unwrap(), None);
+
+        std::fs::write(
+            &manifest,
+            "resources:\n  - deployment.yaml\ncomponents:\n  - ../shared\n",
+        )
+        .unwrap();
+        assert_eq!(validate_overlay_in(&manifest, Mode::Enforce).unwrap(), None);
+    }
+}
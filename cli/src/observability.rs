@@ -58,6 +58,12 @@ pub enum ReleaseEvent {
     ShinkaMigrationCompleted(ShinkaMigrationCompletedEvent),
     /// Shinka migration gating failed
     ShinkaMigrationFailed(ShinkaMigrationFailedEvent),
+    /// Backfill batch committed
+    BackfillProgress(BackfillProgressEvent),
+    /// Backfill run finished or stopped at its time budget
+    BackfillCompleted(BackfillCompletedEvent),
+    /// Backfill batch failed
+    BackfillFailed(BackfillFailedEvent),
 }
 
 /// Common fields for all events
@@ -300,6 +306,55 @@ pub struct ShinkaMigrationFailedEvent {
     pub last_phase: Option<String>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackfillProgressEvent {
+    #[serde(flatten)]
+    pub metadata: EventMetadata,
+    /// Data migration being backfilled
+    pub backfill: String,
+    /// Batch number within this run
+    pub batch: u64,
+    /// Rows walked by this batch
+    pub batch_rows: u64,
+    /// Rows walked across all runs
+    pub rows_processed: u64,
+    /// Key of the last row walked
+    pub cursor: String,
+    /// Run duration so far in seconds
+    pub duration_secs: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackfillCompletedEvent {
+    #[serde(flatten)]
+    pub metadata: EventMetadata,
+    /// Data migration being backfilled
+    pub backfill: String,
+    /// Batches committed by this run
+    pub batches: u64,
+    /// Rows walked across all runs
+    pub rows_processed: u64,
+    /// Run duration in seconds
+    pub duration_secs: f64,
+    /// False when the run stopped at --max-duration and will resume
+    pub finished: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackfillFailedEvent {
+    #[serde(flatten)]
+    pub metadata: EventMetadata,
+    /// Data migration being backfilled
+    pub backfill: String,
+    /// Key the failed batch started after (resume point)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+    /// Run duration until failure in seconds
+    pub duration_secs: f64,
+    /// Error message
+    pub error: String,
+}
+
 /// Emits a structured event as JSON to stdout
 ///
 /// Events are prefixed with `FORGE_EVENT:` for Vector to parse.
@@ -727,6 +782,32 @@ mod tests {
         assert!(json.contains("backend"));
     }
 
+    #[test]
+    fn test_backfill_event_serialization() {
+        let metadata = EventMetadata::new(
+            "abc123",
+            "testapp",
+            "migration",
+            "staging",
+            "testapp-staging",
+        );
+
+        let event = ReleaseEvent::BackfillProgress(BackfillProgressEvent {
+            metadata,
+            backfill: "m20260301_000002_display_name_data".into(),
+            batch: 3,
+            batch_rows: 1000,
+            rows_processed: 3000,
+            cursor: "3000".into(),
+            duration_secs: 1.5,
+        });
+
+        let json = serde_json::to_string(&event).unwrap();
+        assert!(json.contains(r#""event_type":"BackfillProgress""#));
+        assert!(json.contains(r#""rows_processed":3000"#));
+        assert!(json.contains(r#""cursor":"3000""#));
+    }
+
     #[test]
     fn test_step_timer() {
         let timer = StepTimer::new("test_step");
@@ -0,0 +1,317 @@
+//! Server-side dry run of rendered manifests (`dryRun=All`)
+//!
+//! Schema validation ([`crate::manifest_schema`]) cannot see what only the
+//! apiserver knows: admission webhooks, CRD validation, immutable fields
+//! of objects that already exist, and namespace quotas. This module
+//! renders a kustomization in-process and submits every object as a
+//! server-side apply with `dryRun=All`, under the same field manager Flux's
+//! kustomize-controller uses, so the apiserver evaluates exactly the
+//! change Flux would make without persisting anything.
+//!
+//! The step is opt-in (`--server-dry-run` on `deploy` and
+//! `product-release`, or `FORGE_SERVER_DRY_RUN=true`) because it needs
+//! cluster credentials at commit time. Rejections are collected for the
+//! whole overlay and surface as [`KubernetesError::DryRunRejected`].
+
+use anyhow::Result;
+use kube::api::{Patch, PatchParams};
+use kube::Client;
+use serde_json::Value;
+use std::fmt;
+use std::path::Path;
+use tracing::warn;
+
+use crate::error::KubernetesError;
+use crate::k8s::{self, KindCache};
+use crate::kustomize;
+
+/// Environment variable enabling the dry run in nested forge invocations
+/// (`product-release` runs each service deploy as a subprocess).
+pub const ENV_VAR: &str = "FORGE_SERVER_DRY_RUN";
+
+/// Field manager Flux's kustomize-controller applies with; reusing it makes
+/// ownership conflicts and field removals match the real reconcile.
+const FIELD_MANAGER: &str = "kustomize-controller";
+
+/// Whether [`ENV_VAR`] asks for a server-side dry run.
+pub fn enabled() -> bool {
+    std::env::var(ENV_VAR)
+        .map(|v| matches!(v.to_lowercase().as_str(), "1" | "true" | "yes"))
+        .unwrap_or(false)
+}
+
+// ============================================================================
+// Data Structures
+// ============================================================================
+
+/// Why the apiserver refused an object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RejectionKind {
+    /// A validating or mutating admission webhook denied the request.
+    Admission,
+    /// The change touches a field that cannot be updated in place
+    /// (e.g. a Deployment's `spec.selector`).
+    ImmutableField,
+    /// A ResourceQuota or LimitRange in the namespace was violated.
+    Quota,
+    /// Schema or CRD validation failed.
+    Invalid,
+    Other,
+}
+
+impl fmt::Display for RejectionKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            RejectionKind::Admission => "admission",
+            RejectionKind::ImmutableField => "immutable field",
+            RejectionKind::Quota => "quota",
+            RejectionKind::Invalid => "invalid",
+            RejectionKind::Other => "rejected",
+        })
+    }
+}
+
+/// One object the apiserver refused.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rejection {
+    /// `Kind/name`
+    pub object: String,
+    pub kind: RejectionKind,
+    pub code: Option<u16>,
+    pub message: String,
+}
+
+impl fmt::Display for Rejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] {}: {}", self.kind, self.object, self.message)
+    }
+}
+
+/// Outcome of a dry run that the apiserver accepted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DryRunOutcome {
+    /// Objects submitted to the apiserver.
+    pub submitted: usize,
+    /// SOPS-encrypted objects, which only Flux can decrypt.
+    pub skipped: usize,
+}
+
+// ============================================================================
+// Dry Run
+// ============================================================================
+
+/// Render the kustomization containing `manifest` and dry-run every object
+/// against the current kubeconfig context. Objects without a namespace are
+/// submitted to `default_namespace`. Returns `Ok(None)` when the
+/// manifest's directory is not a kustomization or uses kustomize features
+/// forge does not render.
+pub async fn dry_run_overlay(
+    manifest: &Path,
+    default_namespace: &str,
+) -> Result<Option<DryRunOutcome>> {
+    let dir = kustomize::kustomization_dir(manifest);
+    if kustomize::find_kustomization_file(&dir).is_none() {
+        return Ok(None);
+    }
+    let objects = match kustomize::render(&dir) {
+        Err(KubernetesError::RenderUnsupported { path, fields }) => {
+            warn!(
+                "⚠️  {path} uses {} which forge does not render; server-side dry run skipped",
+                fields.join(", ")
+            );
+            return Ok(None);
+        }
+        result => result?,
+    };
+
+    // Namespaces created by this same overlay do not exist yet, so objects
+    // inside them legitimately come back NotFound.
+    let rendered_namespaces: Vec<&str> = objects
+        .iter()
+        .filter(|o| str_at(o, "/kind") == "Namespace")
+        .map(|o| str_at(o, "/metadata/name"))
+        .collect();
+
+    let client = k8s::create_client().await?;
+    let mut kinds = KindCache::default();
+    let mut outcome = DryRunOutcome {
+        submitted: 0,
+        skipped: 0,
+    };
+    let mut rejections = Vec::new();
+
+    for object in &objects {
+        if object.get("sops").is_some() {
+            outcome.skipped += 1;
+            continue;
+        }
+        outcome.submitted += 1;
+        if let Some(rejection) = dry_run_object(
+            &client,
+            &mut kinds,
+            object,
+            default_namespace,
+            &rendered_namespaces,
+        )
+        .await
+        {
+            rejections.push(rejection.to_string());
+        }
+    }
+
+    if !rejections.is_empty() {
+        return Err(KubernetesError::DryRunRejected {
+            path: dir.display().to_string(),
+            rejections,
+        }
+        .into());
+    }
+    Ok(Some(outcome))
+}
+
+async fn dry_run_object(
+    client: &Client,
+    kinds: &mut KindCache,
+    object: &Value,
+    default_namespace: &str,
+    rendered_namespaces: &[&str],
+) -> Option<Rejection> {
+    let name = str_at(object, "/metadata/name");
+    let label = format!("{}/{}", str_at(object, "/kind"), name);
+
+    let (api, namespace) = match k8s::object_api(client, kinds, object, default_namespace).await {
+        Ok(found) => found,
+        Err(e) => {
+            return Some(Rejection {
+                object: label,
+                kind: RejectionKind::Other,
+                code: None,
+                message: format!("{e:#}"),
+            })
+        }
+    };
+
+    let mut body = object.clone();
+    if let Some(map) = body.as_object_mut() {
+        map.remove("status");
+    }
+    if let Some(ns) = &namespace {
+        body["metadata"]["namespace"] = Value::String(ns.clone());
+    }
+
+    let mut params = PatchParams::apply(FIELD_MANAGER).force();
+    params.dry_run = true;
+    match api.patch(name, &params, &Patch::Apply(&body)).await {
+        Ok(_) => None,
+        Err(kube::Error::Api(response)) => {
+            let in_rendered_namespace = namespace
+                .as_deref()
+                .is_some_and(|ns| rendered_namespaces.contains(&ns));
+            if response.code == 404 && in_rendered_namespace {
+                return None;
+            }
+            Some(Rejection {
+                object: label,
+                kind: classify(response.code, &response.reason, &response.message),
+                code: Some(response.code),
+                message: response.message,
+            })
+        }
+        Err(e) => Some(Rejection {
+            object: label,
+            kind: RejectionKind::Other,
+            code: None,
+            message: e.to_string(),
+        }),
+    }
+}
+
+/// Classify an apiserver refusal from its status code, reason and message.
+pub fn classify(code: u16, reason: &str, message: &str) -> RejectionKind {
+    let message = message.to_lowercase();
+    if message.contains("field is immutable") || message.contains("immutable field") {
+        RejectionKind::ImmutableField
+    } else if message.contains("exceeded quota")
+        || message.contains("forbidden: maximum")
+        || message.contains("forbidden: minimum")
+        || message.contains("must specify limits")
+        || message.contains("must specify requests")
+    {
+        RejectionKind::Quota
+    } else if message.contains("admission webhook") || message.contains("denied the request") {
+        RejectionKind::Admission
+    } else if code == 422 || reason == "Invalid" || reason == "BadRequest" {
+        RejectionKind::Invalid
+    } else {
+        RejectionKind::Other
+    }
+}
+
+fn str_at<'a>(object: &'a Value, pointer: &str) -> &'a str {
+    object
+        .pointer(pointer)
+        .and_then(Value::as_str)
+        .unwrap_or("")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classify_recognises_immutable_quota_admission_and_invalid() {
+        assert_eq!(
+            classify(
+                422,
+                "Invalid",
+                "Deployment.apps \"cart\" is invalid: spec.selector: Invalid value: \
+                 v1.LabelSelector{...}: field is immutable"
+            ),
+            RejectionKind::ImmutableField
+        );
+        assert_eq!(
+            classify(
+                403,
+                "Forbidden",
+                "pods \"cart\" is forbidden: exceeded quota: compute, requested: \
+                 limits.cpu=2, used: limits.cpu=7, limited: limits.cpu=8"
+            ),
+            RejectionKind::Quota
+        );
+        assert_eq!(
+            classify(
+                400,
+                "BadRequest",
+                "admission webhook \"validate.kyverno.svc\" denied the request: \
+                 image tag latest is not allowed"
+            ),
+            RejectionKind::Admission
+        );
+        assert_eq!(
+            classify(
+                422,
+                "Invalid",
+                "HelmRelease.helm.toolkit.fluxcd.io \"cart\" is invalid: spec.interval: Required value"
+            ),
+            RejectionKind::Invalid
+        );
+        assert_eq!(
+            classify(409, "Conflict", "Operation cannot be fulfilled"),
+            RejectionKind::Other
+        );
+    }
+
+    #[test]
+    fn rejection_display_names_kind_object_and_message() {
+        let rejection = Rejection {
+            object: "Deployment/cart".into(),
+            kind: RejectionKind::ImmutableField,
+            code: Some(422),
+            message: "spec.selector: field is immutable".into(),
+        };
+        assert_eq!(
+            rejection.to_string(),
+            "[immutable field] Deployment/cart: spec.selector: field is immutable"
+        );
+    }
+}